@@ -28,6 +28,21 @@ impl<'a> BinArray<'a> {
         self.len() == 0
     }
 
+    /// Returns the [`type`] of the [`values`] in the [`array`] (as determined by its first [`value`]),
+    /// or `None` if the [`array`] is empty.
+    ///
+    /// NOTE: numeric values (`i64`, `u64` and `f64`) are compatible,
+    /// so a numeric [`array`] may contain values of other numeric [`types`].
+    ///
+    /// [`type`]: enum.ValueType.html
+    /// [`types`]: enum.ValueType.html
+    /// [`values`]: type.BinConfigValue.html
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    pub fn element_type(&self) -> Option<ValueType> {
+        self.get_val(0).ok().map(|value| value.get_type())
+    }
+
     /// Tries to get a reference to a [`value`] in the [`array`] at `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds.
@@ -176,6 +191,44 @@ impl<'a> BinArray<'a> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`u64`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not a [`u64`] / [`i64`] / [`f64`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`error`]: enum.BinArrayError.html
+    pub fn get_u64(&self, index: u32) -> Result<u64, BinArrayError> {
+        self.get(index)
+    }
+
+    /// Tries to get a [`u64`] [`value`] in the [`array`] at `path`.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`] or an [`array`](enum.Value.html#variant.Array) value.
+    /// The last key must correspond to a [`u64`] / [`i64`] / [`f64`] [`value`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.BinConfigValue.html
+    /// [`array`]: struct.BinArray.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`table`]: enum.Value.html#variant.Table
+    pub fn get_u64_path<'k, K, P>(&self, path: P) -> Result<u64, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_path(path)
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`array`] at `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds or if value is not an [`f64`] / [`i64`].
@@ -313,9 +366,12 @@ impl<'a> BinArray<'a> {
 
     /// Returns an in-order iterator over [`values`] in the [`array`].
     ///
+    /// [`values`] are read directly from the data blob on demand, see [`BinTable::iter`].
+    ///
     /// [`values`]: type.BinConfigValue.html
     /// [`array`]: struct.BinArray.html
-    pub fn iter(&self) -> impl Iterator<Item = BinConfigValue<'a>> {
+    /// [`BinTable::iter`]: struct.BinTable.html#method.iter
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = BinConfigValue<'a>> {
         BinArrayIter::new(BinArray(self.0.clone()))
     }
 
@@ -336,6 +392,7 @@ impl<'a> BinArray<'a> {
             let value = match unsafe { self.0.value(index) } {
                 Bool(val) => Value::Bool(val),
                 I64(val) => Value::I64(val),
+                U64(val) => Value::U64(val),
                 F64(val) => Value::F64(val),
                 BinConfigUnpackedValue::String { offset, len } => {
                     Value::String(unsafe { self.0.string(offset, len) })
@@ -358,28 +415,36 @@ impl<'a> BinArray<'a> {
         }
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        writeln!(w, "{{")?;
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        <Self as DisplayLua>::write_lua_open(w, options)?;
+
+        let len = self.len();
 
         // Iterate the array.
         for (index, value) in self.iter().enumerate() {
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+            <Self as DisplayLua>::write_lua_element_start(w, indent, options)?;
 
-            value.fmt_lua(w, indent + 1)?;
+            value
+                .fmt_lua(w, indent + 1, options)
+                .map_err(|err| err.push_key(index as u32))?;
 
-            write!(w, ",")?;
+            <Self as DisplayLua>::write_lua_comma(w, index as u32 + 1 == len, options)?;
 
             let is_array_or_table = matches!(value.get_type(), ValueType::Array | ValueType::Table);
 
-            if is_array_or_table {
+            if is_array_or_table && options.write_key_comments() {
                 write!(w, " -- [{}]", index)?;
             }
 
-            writeln!(w)?;
+            <Self as DisplayLua>::write_lua_element_end(w, options)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
-        write!(w, "}}")?;
+        <Self as DisplayLua>::write_lua_close(w, indent, len == 0, options)?;
 
         Ok(())
     }
@@ -418,17 +483,38 @@ impl<'a> Iterator for BinArrayIter<'a> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.array.len() - self.index) as usize;
+        (len, Some(len))
+    }
 }
 
+impl<'a> ExactSizeIterator for BinArrayIter<'a> {}
+
 impl<'a> DisplayLua for BinArray<'a> {
-    fn fmt_lua<W: Write>(&self, f: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(f, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        f: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_impl(f, indent, options)
     }
 }
 
 impl<'a> Display for BinArray<'a> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
+    }
+}
+
+/// Arrays are compared by their contents (values, in order),
+/// regardless of their layout in the binary config data blobs.
+impl<'a> PartialEq for BinArray<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.len() == other.len()) && self.iter().zip(other.iter()).all(|(l, r)| l == r)
     }
 }
 
@@ -6,6 +6,7 @@ use {
     },
     crate::{util::DisplayLua, *},
     std::{
+        collections::HashSet,
         fmt::{Display, Formatter, Write},
         mem::size_of,
         slice::from_raw_parts,
@@ -17,7 +18,10 @@ use std::ops::DerefMut;
 
 /// Represents an immutable config with a root [`table`].
 ///
+/// See [`BinConfigRef`] for a config which borrows its data blob instead.
+///
 /// [`table`]: struct.BinTable.html
+/// [`BinConfigRef`]: struct.BinConfigRef.html
 pub struct BinConfig(Box<[u8]>);
 
 impl BinConfig {
@@ -27,12 +31,29 @@ impl BinConfig {
     /// if the `data` is not a valid binary config data blob,
     /// e.g. returned by the binary config [`writer`].
     ///
+    /// The `data` may contain arbitrary bytes following the binary config data,
+    /// which are ignored and may be accessed via [`trailing_bytes`].
+    ///
     /// [`config`]: struct.BinConfig.html
     /// [`error`]: enum.BinConfigError.html
     /// [`writer`]: struct.BinConfigWriter.html
+    /// [`trailing_bytes`]: #method.trailing_bytes
     pub fn new(data: Box<[u8]>) -> Result<Self, BinConfigError> {
+        Self::new_opts(data, Default::default())
+    }
+
+    /// Tries to create a new [`config`] from the `data` binary blob, like [`new`],
+    /// additionally validating the `data` against the limits specified by the [`options`].
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`new`]: #method.new
+    /// [`options`]: struct.BinConfigReadOptions.html
+    pub fn new_opts(
+        data: Box<[u8]>,
+        options: BinConfigReadOptions,
+    ) -> Result<Self, BinConfigError> {
         // Try to validate the data.
-        Self::validate_data(&data)?;
+        Self::validate_data(&data, options)?;
         // Seems to be fine?
 
         Ok(Self(data))
@@ -42,11 +63,30 @@ impl BinConfig {
     /// if the `data` is not a valid binary config data blob,
     /// e.g. returned by the binary config [`writer`].
     ///
+    /// Any bytes following the binary config data (see [`trailing_bytes`]) are ignored.
+    ///
     /// [`config`]: struct.BinConfig.html
     /// [`error`]: enum.BinConfigError.html
     /// [`writer`]: struct.BinConfigWriter.html
+    /// [`trailing_bytes`]: #method.trailing_bytes
     pub fn validate(data: &Box<[u8]>) -> Result<(), BinConfigError> {
-        Self::validate_data(&data)
+        Self::validate_data(&data, Default::default())
+    }
+
+    /// Attempts to validate the binary config data blobs `l` and `r` and returns an [`error`]
+    /// if either of them is not a valid binary config data blob,
+    /// e.g. returned by the binary config [`writer`].
+    ///
+    /// Otherwise returns `true` if the binary configs contain the same data,
+    /// regardless of their layout in the data blobs (e.g. the order of table keys, string interning, checksums or trailing bytes),
+    /// without deserializing them.
+    ///
+    /// Values are compared strictly by type - i.e. integer `7` is not equal to floating point `7.0`.
+    ///
+    /// [`error`]: enum.BinConfigError.html
+    /// [`writer`]: struct.BinConfigWriter.html
+    pub fn structurally_eq(l: &[u8], r: &[u8]) -> Result<bool, BinConfigError> {
+        Ok(BinConfigRef::new(l)? == BinConfigRef::new(r)?)
     }
 
     /// Like [`new`], but does not validate the `data` at all.
@@ -62,6 +102,21 @@ impl BinConfig {
         Self(data)
     }
 
+    /// Returns the (possibly empty) trailing bytes of the data blob the [`config`] was created from,
+    /// which follow the binary config data (as determined by its total size recorded in the header).
+    ///
+    /// Allows colocating the binary config and arbitrary application-specific data in one blob.
+    ///
+    /// Always empty for data blobs in the original layout, which do not record the total size.
+    ///
+    /// [`config`]: struct.BinConfig.html
+    pub fn trailing_bytes(&self) -> &[u8] {
+        // We ensured the data is validated.
+        let offset = unsafe { Self::header(&self.0).trailing_bytes_offset(&self.0) };
+
+        &self.0[offset as usize..]
+    }
+
     /// Returns the immutable reference to the root [`table`] of the [`config`].
     ///
     /// [`table`]: struct.BinTable.html
@@ -71,28 +126,55 @@ impl BinConfig {
         unsafe { Self::root_impl(&self.0) }
     }
 
-    /// Tries to serialize this [`config`] to a Lua script string.
+    /// Tries to serialize this [`config`] to a Lua script string using default [`options`].
     ///
     /// NOTE: you may also call `to_string` via the [`config`]'s `Display` implementation.
     ///
     /// [`config`]: struct.BinConfig.html
-    pub fn to_lua_string(&self) -> Result<String, std::fmt::Error> {
+    /// [`options`]: struct.LuaDisplayOptions.html
+    pub fn to_lua_string(&self) -> Result<String, ToLuaStringError> {
+        self.to_lua_string_opts(Default::default())
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string to the writer `w` using default [`options`].
+    ///
+    /// NOTE: you may also use the [`config`]'s `Display` implementation.
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`options`]: struct.LuaDisplayOptions.html
+    pub fn fmt_lua<W: Write>(&self, w: &mut W) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string using provided [`options`].
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`options`]: struct.LuaDisplayOptions.html
+    pub fn to_lua_string_opts(
+        &self,
+        options: LuaDisplayOptions,
+    ) -> Result<String, ToLuaStringError> {
         let mut result = String::new();
 
-        self.fmt_lua(&mut result)?;
+        self.fmt_lua_opts(options, &mut result)?;
 
         result.shrink_to_fit();
 
         Ok(result)
     }
 
-    /// Tries to serialize this [`config`] to a Lua script string to the writer `w`.
-    ///
-    /// NOTE: you may also use the [`config`]'s `Display` implementation.
+    /// Tries to serialize this [`config`] to a Lua script string to the writer `w` using provided [`options`].
     ///
     /// [`config`]: struct.BinConfig.html
-    pub fn fmt_lua<W: Write>(&self, w: &mut W) -> Result<(), std::fmt::Error> {
-        self.root().fmt_lua(w, 0)
+    /// [`options`]: struct.LuaDisplayOptions.html
+    pub fn fmt_lua_opts<W: Write>(
+        &self,
+        options: LuaDisplayOptions,
+        w: &mut W,
+    ) -> Result<(), ToLuaStringError> {
+        self.root()
+            .fmt_lua(w, 0, options)
+            .map_err(ToLuaStringError::reverse)
     }
 
     /// Tries to serialize this [`config`] to an `.ini` string using default [`options`].
@@ -159,6 +241,22 @@ impl BinConfig {
         result
     }
 
+    /// Tries to deserialize a value of type `T` (e.g. a `#[derive(Deserialize)]` struct) from this [`config`],
+    /// starting at the root [`table`].
+    ///
+    /// Values are read directly from the data blob, without deserializing it to a [`dynamic config`];
+    /// strings may be borrowed from the data blob.
+    ///
+    /// [`config`]: struct.BinConfig.html
+    /// [`table`]: struct.BinTable.html
+    /// [`dynamic config`]: struct.DynConfig.html
+    #[cfg(feature = "serde")]
+    pub fn deserialize_into<'de, T: serde::Deserialize<'de>>(
+        &'de self,
+    ) -> Result<T, DeserializeError> {
+        T::deserialize(Value::Table(self.root()))
+    }
+
     /// The caller ensures `key_table_offset` and `key_table_len` are valid and point to
     /// the actual key table in the `data` blob.
     unsafe fn key_table(
@@ -172,13 +270,13 @@ impl BinConfig {
         )
     }
 
-    /// The caller ensures the data is at least large enough for the header.
-    unsafe fn header(data: &[u8]) -> &BinConfigHeader {
+    /// The caller ensures the data is at least large enough for the header (not including the total size following it).
+    pub(super) unsafe fn header(data: &[u8]) -> &BinConfigHeader {
         &*(data.as_ptr() as *const _)
     }
 
     /// The caller ensures the data is at least large enough for the header.
-    unsafe fn root_impl(data: &[u8]) -> BinTable<'_> {
+    pub(super) unsafe fn root_impl(data: &[u8]) -> BinTable<'_> {
         BinTable::new(Self::root_raw_impl(data))
     }
 
@@ -190,14 +288,14 @@ impl BinConfig {
         BinArrayOrTable::new(
             data.as_ptr(), // Base address of the binary config.
             Self::key_table(data, header.key_table_offset(), header.key_table_len()),
-            size_of::<BinConfigHeader>() as u32, // Offset to the first value of the root table is the size of the header.
-            header.len(), // Config root table length as read from the header.
+            header.header_size(), // Offset to the first value of the root table is the size of the header.
+            header.len(),         // Config root table length as read from the header.
         )
     }
 
-    /// Header, one value, one key table entry and the shortest possible key.
-    const fn min_size() -> usize {
-        size_of::<BinConfigHeader>()
+    /// Header (of `header_size` bytes), one value, one key table entry and the shortest possible key.
+    const fn min_size(header_size: usize) -> usize {
+        header_size
             + size_of::<BinConfigPackedValue>()
             + size_of::<InternedString>()
             + Self::min_string_section_size()
@@ -207,9 +305,9 @@ impl BinConfig {
         u32::MAX as _
     }
 
-    /// Key table comes after the header and at least one value.
-    const fn min_key_table_offset() -> usize {
-        size_of::<BinConfigHeader>() + size_of::<BinConfigPackedValue>()
+    /// Key table comes after the header (of `header_size` bytes) and at least one value.
+    const fn min_key_table_offset(header_size: usize) -> usize {
+        header_size + size_of::<BinConfigPackedValue>()
     }
 
     /// 1 byte + terminating null char.
@@ -217,16 +315,19 @@ impl BinConfig {
         2
     }
 
-    fn validate_data(data: &[u8]) -> Result<(), BinConfigError> {
+    pub(super) fn validate_data(
+        data: &[u8],
+        options: BinConfigReadOptions,
+    ) -> Result<(), BinConfigError> {
         use BinConfigError::*;
 
-        // Make sure the data is large enough to contain at least the header, one value, one key table entry and the shortest possible key.
-        if data.len() < Self::min_size() {
-            return Err(InvalidBinaryConfigData);
+        // Empty root table - the data is just the header, with no values, key table or string section.
+        if Self::validate_empty_data(data) {
+            return Ok(());
         }
 
-        // Make sure the data is not too large.
-        if data.len() > Self::max_size() {
+        // Make sure the data is large enough to contain at least the header.
+        if data.len() < size_of::<BinConfigHeader>() {
             return Err(InvalidBinaryConfigData);
         }
 
@@ -238,17 +339,58 @@ impl BinConfig {
             return Err(InvalidBinaryConfigData);
         }
 
+        let header_size = header.header_size() as usize;
+
+        // Make sure the data is large enough to contain at least the header, one value, one key table entry and the shortest possible key.
+        if data.len() < Self::min_size(header_size) {
+            return Err(InvalidBinaryConfigData);
+        }
+
+        // Make sure the binary config data, as determined by the total size following the header,
+        // is large enough and lies within the data blob.
+        // Ignore the trailing bytes, if any.
+        let size = unsafe { header.size(data) } as usize;
+
+        if size < Self::min_size(header_size) || size > data.len() {
+            return Err(InvalidBinaryConfigData);
+        }
+
+        // Make sure the binary config data is not larger than allowed by the `options`.
+        if size > options.max_size as usize {
+            return Err(LimitExceeded);
+        }
+
+        // Make sure the checksum, if any, which follows the binary config data, lies within the data blob
+        // and matches everything following the header.
+        if header.has_checksum() {
+            let checksum = data
+                .get(size..size + size_of::<u32>())
+                .ok_or_else(|| InvalidBinaryConfigData)?;
+            let checksum = u32::from_ne_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]);
+
+            if u32_from_bin(checksum) != crc32(&data[header_size..size]) {
+                return Err(ChecksumMismatch);
+            }
+        }
+
+        let data = &data[..size];
+
+        // Make sure the data is not too large.
+        if data.len() > Self::max_size() {
+            return Err(InvalidBinaryConfigData);
+        }
+
         // Check the key table - must contain at least one table key, as we don't allow empty root tables.
         if header.key_table_len == 0 {
             return Err(InvalidBinaryConfigData);
         }
 
-        // |---------- header (16b) --------|-------- root table (16b) ------|- key table 0 (8b) -|2b|
+        // |------ header (16b / 20b) ------|-------- root table (16b) ------|- key table 0 (8b) -|2b|
 
         // Make sure the key table lies within the config data blob.
         Self::validate_range(
             // Minus shortest string section length - one byte and a null terminator.
-            Self::min_key_table_offset() as u32
+            Self::min_key_table_offset(header_size) as u32
                 ..data.len() as u32 - Self::min_string_section_size() as u32,
             header.key_table_range(),
         )?;
@@ -260,14 +402,19 @@ impl BinConfig {
             // Make sure the root table values lie within the config data blob.
             // Offset to the first value of the root table is the size of the header.
             // Last value of the root table may be just before the key table and the shortest string section.
-            let valid_range = size_of::<BinConfigHeader>() as u32
+            let valid_range = header_size as u32
                 ..data.len() as u32
                     - Self::min_string_section_size() as u32
                     - header.key_table_size();
 
             Self::validate_range(valid_range.clone(), root.offset_range())?;
 
-            Self::validate_table(data, header.key_table_offset, &root)
+            Self::validate_table(
+                data,
+                header.key_table_offset,
+                &root,
+                &mut ReadLimits::new(options),
+            )
 
         // Empty binary config root tables are not supported.
         } else {
@@ -275,10 +422,35 @@ impl BinConfig {
         }
     }
 
+    /// Returns `true` if the `data` is a valid binary config data blob with an empty root table,
+    /// e.g. written by the [`dynamic config`](struct.DynConfig.html#method.to_bin_config) for an empty config.
+    /// Any bytes following the header are ignored.
+    fn validate_empty_data(data: &[u8]) -> bool {
+        if data.len() < size_of::<BinConfigHeader>() {
+            return false;
+        }
+
+        let header = unsafe { BinConfig::header(&data) };
+
+        if !header.check_magic() {
+            return false;
+        }
+
+        let header_size = header.header_size();
+
+        data.len() >= header_size as usize
+            && !header.has_checksum()
+            && header.len() == 0
+            && header.key_table_offset() == header_size
+            && header.key_table_len() == 0
+            && unsafe { header.size(data) } == header_size
+    }
+
     fn validate_table(
         data: &[u8],
         key_table_offset: u32,
         table: &BinArrayOrTable<'_>,
+        limits: &mut ReadLimits,
     ) -> Result<(), BinConfigError> {
         use BinConfigError::*;
 
@@ -287,6 +459,8 @@ impl BinConfig {
             return Err(InvalidBinaryConfigData);
         }
 
+        limits.add_entries(table.len)?;
+
         let key_table = unsafe { table.key_table() };
         let key_table_size = table.key_table_size();
 
@@ -300,6 +474,9 @@ impl BinConfig {
             // Valid offset range for strings.
             let valid_string_range = key_table_offset + key_table_size..data.len() as u32;
 
+            // Keys already encountered in the table.
+            let mut keys = HashSet::with_capacity(table.len as usize);
+
             // For each table element.
             for index in 0..table.len {
                 let value = unsafe { table.packed_value(index) };
@@ -345,6 +522,11 @@ impl BinConfig {
                 if string_hash_fnv1a(key_string) != key.hash {
                     return Err(InvalidBinaryConfigData);
                 }
+
+                // Make sure the key is unique in the table.
+                if !keys.insert(key_string) {
+                    return Err(InvalidBinaryConfigData);
+                }
                 //----------------------------------------------------------------------------------
                 // The key seems to be OK.
 
@@ -357,6 +539,7 @@ impl BinConfig {
                     valid_string_range.clone(),
                     table,
                     value,
+                    limits,
                 )?;
                 // The value seems to be OK.
             }
@@ -370,6 +553,7 @@ impl BinConfig {
         key_table_offset: u32,
         valid_range_end: u32,
         array: &BinArrayOrTable<'_>,
+        limits: &mut ReadLimits,
     ) -> Result<(), BinConfigError> {
         use BinConfigError::*;
 
@@ -378,6 +562,8 @@ impl BinConfig {
             return Err(InvalidBinaryConfigData);
         }
 
+        limits.add_entries(array.len)?;
+
         let key_table = unsafe { array.key_table() };
         let key_table_size = array.key_table_size();
 
@@ -397,12 +583,13 @@ impl BinConfig {
 
             let value_type = value.value_type();
 
+            // Make sure the array is not mixed.
             if let Some(current_array_type) = array_type {
                 if !current_array_type.is_compatible(value_type) {
                     return Err(InvalidBinaryConfigData);
-                } else {
-                    array_type.replace(value_type);
                 }
+            } else {
+                array_type.replace(value_type);
             }
 
             // All values in the array must have no keys.
@@ -421,6 +608,7 @@ impl BinConfig {
                 valid_string_range.clone(),
                 array,
                 value,
+                limits,
             )?;
             // The value seems to be OK.
         }
@@ -453,6 +641,7 @@ impl BinConfig {
         valid_string_range: std::ops::Range<u32>, // Valid range of offsets within the binary data blob for strings.
         array_or_table: &BinArrayOrTable<'_>,     // Validated value's parent array/table.
         value: &BinConfigPackedValue,
+        limits: &mut ReadLimits,
     ) -> Result<(), BinConfigError> {
         use BinConfigError::*;
 
@@ -466,7 +655,7 @@ impl BinConfig {
             ValueType::Bool => {
                 value.try_bool().ok_or_else(|| InvalidBinaryConfigData)?;
             }
-            ValueType::I64 | ValueType::F64 => {}
+            ValueType::I64 | ValueType::U64 | ValueType::F64 => {}
             ValueType::String => {
                 // Non-empty strings have a positive offset to data.
                 if value.len() > 0 {
@@ -503,6 +692,8 @@ impl BinConfig {
                     // Make sure the array/table slice lies within the config data blob.
                     Self::validate_range(valid_range.clone(), array_or_table.offset_range())?;
 
+                    limits.enter()?;
+
                     // Validate the array/table values.
                     match value_type {
                         ValueType::Array => {
@@ -511,14 +702,17 @@ impl BinConfig {
                                 key_table_offset,
                                 valid_range.end,
                                 &array_or_table,
+                                limits,
                             )?;
                         }
                         ValueType::Table => {
-                            Self::validate_table(data, key_table_offset, &array_or_table)?;
+                            Self::validate_table(data, key_table_offset, &array_or_table, limits)?;
                         }
                         _ => debug_unreachable!("value must be an array or table if we got here"),
                     }
 
+                    limits.leave();
+
                     valid_range.end += size_of::<BinConfigPackedValue>() as u32;
 
                 // Empty arrays/tables must have no offset.
@@ -559,15 +753,16 @@ impl BinConfig {
         let already_existed = match value {
             Bool(value) => dyn_table.set(key, value),
             I64(value) => dyn_table.set(key, value),
+            U64(value) => dyn_table.set(key, DynConfigValue::U64(value)),
             F64(value) => dyn_table.set(key, value),
             String(value) => dyn_table.set(key, value),
             Array(value) => {
-                let mut array = DynArray::new();
+                let mut array = DynArray::with_capacity(value.len());
                 Self::array_to_dyn_array(value, &mut array);
                 dyn_table.set(key, array)
             }
             Table(value) => {
-                let mut table = DynTable::new();
+                let mut table = DynTable::with_capacity(value.len());
                 Self::table_to_dyn_table(value, &mut table);
                 dyn_table.set(key, table)
             }
@@ -588,15 +783,16 @@ impl BinConfig {
         if let Err(_) = match value {
             Bool(value) => dyn_array.push(Bool(value)),
             I64(value) => dyn_array.push(I64(value)),
+            U64(value) => dyn_array.push(U64(value)),
             F64(value) => dyn_array.push(F64(value)),
             String(value) => dyn_array.push(String(value.to_owned())),
             Array(value) => {
-                let mut array = DynArray::new();
+                let mut array = DynArray::with_capacity(value.len());
                 Self::array_to_dyn_array(value, &mut array);
                 dyn_array.push(Array(array))
             }
             Table(value) => {
-                let mut table = DynTable::new();
+                let mut table = DynTable::with_capacity(value.len());
                 Self::table_to_dyn_table(value, &mut table);
                 dyn_array.push(Table(table))
             }
@@ -608,18 +804,38 @@ impl BinConfig {
 
 impl Display for BinConfig {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.root().fmt_lua(f, 0)
+        self.root()
+            .fmt_lua(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
+    }
+}
+
+/// Configs are compared by the contents of their root [`tables`](struct.BinTable.html#impl-PartialEq<BinTable<'t>>).
+/// Trailing bytes and checksums, if any, are ignored.
+impl PartialEq for BinConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.root() == other.root()
     }
 }
 
-const BIN_CONFIG_HEADER_MAGIC: u32 = 0x67666362; // `bcfg`, little endian.
+/// Original binary config data blob layout, only supported for reading.
+/// The header is not followed by the total size of the binary config data, which spans the entire data blob;
+/// no checksum and no trailing bytes.
+const BIN_CONFIG_HEADER_MAGIC_V1: u32 = 0x67666362; // `bcfg`, little endian.
+/// Current binary config data blob layout - the header is followed by the total size of the binary config data.
+const BIN_CONFIG_HEADER_MAGIC: u32 = 0x32666362; // `bcf2`, little endian.
+/// Used instead of `BIN_CONFIG_HEADER_MAGIC` if the binary config data is followed by its checksum.
+const BIN_CONFIG_HEADER_MAGIC_CHECKSUM: u32 = 0x63666362; // `bcfc`, little endian.
 
-/// Binary config data blob header.
+/// Binary config data blob header, common to all layouts.
+///
+/// In the current layout, it is followed by the `u32` total size in bytes of the binary config data
+/// (header, values, key table and string section; not including the checksum and the trailing bytes, if any).
 ///
 /// Fields are in whatever endianness we use; see `super::util::__to_bin_bytes(), _from_bin()`.
 #[repr(C, packed)]
 pub(super) struct BinConfigHeader {
-    /// Arbitrary magic value for a quick sanity check.
+    /// Arbitrary magic value for a quick sanity check; also determines the data blob layout.
     magic: u32,
     /// Followed by the root table length.
     len: u32,
@@ -633,7 +849,62 @@ pub(super) struct BinConfigHeader {
 
 impl BinConfigHeader {
     fn check_magic(&self) -> bool {
-        u32_from_bin(self.magic) == BIN_CONFIG_HEADER_MAGIC
+        let magic = u32_from_bin(self.magic);
+        magic == BIN_CONFIG_HEADER_MAGIC
+            || magic == BIN_CONFIG_HEADER_MAGIC_CHECKSUM
+            || magic == BIN_CONFIG_HEADER_MAGIC_V1
+    }
+
+    /// Returns `true` if the binary config data uses the original layout,
+    /// with no total size following the header.
+    fn is_v1(&self) -> bool {
+        u32_from_bin(self.magic) == BIN_CONFIG_HEADER_MAGIC_V1
+    }
+
+    /// Returns the size in bytes of the header, including the total size of the binary config data
+    /// following it, if any - i.e. the offset to the first value of the root table.
+    pub(super) fn header_size(&self) -> u32 {
+        if self.is_v1() {
+            size_of::<Self>() as u32
+        } else {
+            BIN_CONFIG_HEADER_SIZE as u32
+        }
+    }
+
+    /// Returns `true` if the binary config data is followed by
+    /// the CRC-32 checksum of everything following the header.
+    pub(super) fn has_checksum(&self) -> bool {
+        u32_from_bin(self.magic) == BIN_CONFIG_HEADER_MAGIC_CHECKSUM
+    }
+
+    /// Returns the total size in bytes of the binary config data in the `data` blob with this header
+    /// (header, values, key table and string section; not including the checksum and the trailing bytes, if any).
+    ///
+    /// The caller ensures the `data` blob starts with this header and is at least [`header_size`] bytes large.
+    ///
+    /// [`header_size`]: #method.header_size
+    pub(super) unsafe fn size(&self, data: &[u8]) -> u32 {
+        if self.is_v1() {
+            data.len() as u32
+        } else {
+            let size = data.get_unchecked(size_of::<Self>()..BIN_CONFIG_HEADER_SIZE);
+            u32_from_bin(u32::from_ne_bytes([size[0], size[1], size[2], size[3]]))
+        }
+    }
+
+    /// Returns the offset in bytes to the trailing bytes, if any,
+    /// which follow the binary config data and its checksum, if any.
+    ///
+    /// The caller ensures the `data` blob starts with this header and is at least [`header_size`] bytes large.
+    ///
+    /// [`header_size`]: #method.header_size
+    pub(super) unsafe fn trailing_bytes_offset(&self, data: &[u8]) -> u32 {
+        self.size(data)
+            + if self.has_checksum() {
+                size_of::<u32>() as u32
+            } else {
+                0
+            }
     }
 
     pub(super) fn len(&self) -> u32 {
@@ -664,13 +935,22 @@ impl BinConfigHeader {
         len: u32,
         key_table_offset: u32,
         key_table_len: u32,
+        size: u32,
+        checksum: bool,
     ) -> Result<u32, BinConfigWriterError> {
-        debug_assert!(len > 0);
+        // Always written in the current layout.
+
+        // Only empty root tables have no keys.
+        debug_assert!(len > 0 || key_table_len == 0);
 
         let mut written = 0;
 
         // Magic.
-        written += writer.write(&u32_to_bin_bytes(BIN_CONFIG_HEADER_MAGIC))?;
+        written += writer.write(&u32_to_bin_bytes(if checksum {
+            BIN_CONFIG_HEADER_MAGIC_CHECKSUM
+        } else {
+            BIN_CONFIG_HEADER_MAGIC
+        }))?;
 
         // Root table length.
         written += writer.write(&u32_to_bin_bytes(len))?;
@@ -681,10 +961,61 @@ impl BinConfigHeader {
         // Key table length.
         written += writer.write(&u32_to_bin_bytes(key_table_len))?;
 
+        // Total size.
+        written += writer.write(&u32_to_bin_bytes(size))?;
+
         Ok(written as _)
     }
 }
 
+/// Tracks the number of elements and the depth of nested arrays / tables
+/// during the binary config data blob validation against the limits specified by the read `options`.
+struct ReadLimits {
+    options: BinConfigReadOptions,
+    /// Total number of array / table elements encountered so far.
+    num_entries: u32,
+    /// Depth of the currently validated array / table; the root table is at depth `0`.
+    depth: u32,
+}
+
+impl ReadLimits {
+    fn new(options: BinConfigReadOptions) -> Self {
+        Self {
+            options,
+            num_entries: 0,
+            depth: 0,
+        }
+    }
+
+    /// Called before validating the `len` elements of an array / table.
+    fn add_entries(&mut self, len: u32) -> Result<(), BinConfigError> {
+        self.num_entries = self.num_entries.saturating_add(len);
+
+        if self.num_entries > self.options.max_entries {
+            Err(BinConfigError::LimitExceeded)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Called before validating a nested array / table.
+    fn enter(&mut self) -> Result<(), BinConfigError> {
+        self.depth += 1;
+
+        if self.depth > self.options.max_depth {
+            Err(BinConfigError::LimitExceeded)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Called after validating a nested array / table.
+    fn leave(&mut self) {
+        debug_assert!(self.depth > 0);
+        self.depth -= 1;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(non_snake_case)]
@@ -1086,4 +1417,258 @@ string = "bar""#;
 
         assert_eq!(string, ini);
     }
+
+    #[test]
+    fn trailing_bytes() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+        let data = writer.finish().unwrap();
+
+        // No trailing bytes.
+        let config = BinConfig::new(data.clone()).unwrap();
+
+        assert!(config.trailing_bytes().is_empty());
+
+        // Trailing bytes are ignored and exposed.
+        let trailing_bytes = b"\0application-specific data";
+
+        let mut data_with_trailing_bytes = data.to_vec();
+        data_with_trailing_bytes.extend_from_slice(trailing_bytes);
+
+        let config = BinConfig::new(data_with_trailing_bytes.into_boxed_slice()).unwrap();
+
+        assert_eq!(config.trailing_bytes(), &trailing_bytes[..]);
+        assert_eq!(config.root().len(), 2);
+        assert_eq!(config.root().get_i64("int".into()).unwrap(), 7);
+        assert_eq!(config.root().get_string("string".into()).unwrap(), "foo");
+
+        // Truncated data is still invalid.
+        for len in 0..data.len() {
+            assert_eq!(
+                BinConfig::new(data[..len].to_vec().into_boxed_slice())
+                    .err()
+                    .unwrap(),
+                BinConfigError::InvalidBinaryConfigData
+            );
+        }
+    }
+
+    #[test]
+    fn v1_layout() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.bool(nestr!("bool"), true).unwrap();
+        let data = writer.finish().unwrap();
+
+        let u32_at = |data: &[u8], offset: usize| {
+            u32::from_le_bytes([
+                data[offset],
+                data[offset + 1],
+                data[offset + 2],
+                data[offset + 3],
+            ])
+        };
+
+        // Same data in the original layout: `bcfg` magic, no total size following the header,
+        // key table and key string offsets moved back by the size of the omitted total size.
+        let shift = BIN_CONFIG_HEADER_SIZE - std::mem::size_of::<super::BinConfigHeader>();
+        let key_table_offset = u32_at(&data, 8) as usize - shift;
+        let key_table_len = u32_at(&data, 12) as usize;
+
+        let mut v1 = Vec::new();
+        v1.extend_from_slice(b"bcfg");
+        v1.extend_from_slice(&data[4..8]);
+        v1.extend_from_slice(&(key_table_offset as u32).to_le_bytes());
+        v1.extend_from_slice(&data[12..16]);
+        v1.extend_from_slice(&data[BIN_CONFIG_HEADER_SIZE..]);
+
+        for key_index in 0..key_table_len {
+            let offset = key_table_offset + key_index * 8;
+            let string_offset = u32_at(&v1, offset) - shift as u32;
+            v1[offset..offset + 4].copy_from_slice(&string_offset.to_le_bytes());
+        }
+
+        assert_eq!(v1.len(), data.len() - shift);
+
+        let config = BinConfig::new(v1.into_boxed_slice()).unwrap();
+
+        assert!(config.trailing_bytes().is_empty());
+        assert_eq!(config.root().len(), 2);
+        assert_eq!(config.root().get_i64("int".into()).unwrap(), 7);
+        assert!(config.root().get_bool("bool".into()).unwrap());
+        assert!(config == BinConfig::new(data).unwrap());
+    }
+
+    #[test]
+    fn read_options() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.table(nestr!("table"), 1).unwrap();
+        writer.array(nestr!("array"), 2).unwrap();
+        writer.i64(None, 1).unwrap();
+        writer.i64(None, 2).unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+        let data = writer.finish().unwrap();
+
+        // 2 root table elements, 1 nested table element, 2 array elements; max depth is `2`.
+        let options = BinConfigReadOptions {
+            max_size: data.len() as u32,
+            max_entries: 5,
+            max_depth: 2,
+        };
+
+        assert!(BinConfig::new_opts(data.clone(), options).is_ok());
+        assert!(BinConfigRef::new_opts(&data, options).is_ok());
+        #[cfg(feature = "dyn")]
+        assert!(DynConfig::from_bin_config_opts(&data, options).is_ok());
+
+        for options in [
+            BinConfigReadOptions {
+                max_size: data.len() as u32 - 1,
+                ..options
+            },
+            BinConfigReadOptions {
+                max_entries: 4,
+                ..options
+            },
+            BinConfigReadOptions {
+                max_depth: 1,
+                ..options
+            },
+        ]
+        .iter()
+        {
+            assert_eq!(
+                BinConfig::new_opts(data.clone(), *options).err().unwrap(),
+                BinConfigError::LimitExceeded
+            );
+            assert_eq!(
+                BinConfigRef::new_opts(&data, *options).err().unwrap(),
+                BinConfigError::LimitExceeded
+            );
+            #[cfg(feature = "dyn")]
+            assert_eq!(
+                DynConfig::from_bin_config_opts(&data, *options)
+                    .err()
+                    .unwrap(),
+                BinConfigError::LimitExceeded
+            );
+        }
+    }
+
+    #[test]
+    fn checksum() {
+        let write = |checksum: bool| {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap())
+                .unwrap()
+                .with_checksum(checksum);
+            writer.i64(nestr!("int"), 7).unwrap();
+            writer.string(nestr!("string"), "foo").unwrap();
+            writer.finish().unwrap()
+        };
+
+        let data = write(false);
+        let data_with_checksum = write(true);
+
+        assert_eq!(data_with_checksum.len(), data.len() + 4);
+
+        // Checksum is verified and is not a part of the trailing bytes.
+        let trailing_bytes = b"\0application-specific data";
+
+        let mut data_with_trailing_bytes = data_with_checksum.to_vec();
+        data_with_trailing_bytes.extend_from_slice(trailing_bytes);
+
+        let config = BinConfig::new(data_with_trailing_bytes.into_boxed_slice()).unwrap();
+
+        assert_eq!(config.trailing_bytes(), &trailing_bytes[..]);
+        assert_eq!(config.root().get_i64("int".into()).unwrap(), 7);
+        assert_eq!(config.root().get_string("string".into()).unwrap(), "foo");
+
+        // Any corruption following the header is detected.
+        for idx in BIN_CONFIG_HEADER_SIZE..data_with_checksum.len() {
+            let mut corrupted = data_with_checksum.to_vec();
+            corrupted[idx] = !corrupted[idx];
+
+            assert_eq!(
+                BinConfig::new(corrupted.into_boxed_slice()).err().unwrap(),
+                BinConfigError::ChecksumMismatch
+            );
+        }
+
+        // Missing checksum.
+        assert_eq!(
+            BinConfig::new(data_with_checksum[..data.len()].to_vec().into_boxed_slice())
+                .err()
+                .unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+    }
+
+    #[test]
+    fn structurally_eq() {
+        // Same contents, different key order / string interning.
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+        writer.array(nestr!("array"), 2).unwrap();
+        writer.f64(None, 3.5).unwrap();
+        writer.f64(None, 42.0).unwrap();
+        writer.end().unwrap();
+        let l = writer.finish().unwrap();
+
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap())
+            .unwrap()
+            .with_checksum(true);
+        writer.array(nestr!("array"), 2).unwrap();
+        writer.f64(None, 3.5).unwrap();
+        writer.f64(None, 42.0).unwrap();
+        writer.end().unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        let r = writer.finish().unwrap();
+
+        assert_ne!(l, r);
+        assert!(BinConfig::structurally_eq(&l, &r).unwrap());
+        assert!(BinConfig::new(l.clone()).unwrap() == BinConfig::new(r).unwrap());
+
+        // Different array element order.
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+        writer.array(nestr!("array"), 2).unwrap();
+        writer.f64(None, 42.0).unwrap();
+        writer.f64(None, 3.5).unwrap();
+        writer.end().unwrap();
+        let r = writer.finish().unwrap();
+
+        assert!(!BinConfig::structurally_eq(&l, &r).unwrap());
+
+        // Different value type.
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+        writer.f64(nestr!("int"), 7.0).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+        writer.array(nestr!("array"), 2).unwrap();
+        writer.f64(None, 3.5).unwrap();
+        writer.f64(None, 42.0).unwrap();
+        writer.end().unwrap();
+        let r = writer.finish().unwrap();
+
+        assert!(!BinConfig::structurally_eq(&l, &r).unwrap());
+
+        // Different key set.
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+        let r = writer.finish().unwrap();
+
+        assert!(!BinConfig::structurally_eq(&l, &r).unwrap());
+
+        // Invalid data.
+        assert_eq!(
+            BinConfig::structurally_eq(&l, &[0u8; 4]).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+    }
 }
@@ -0,0 +1,294 @@
+use {
+    crate::{util::DisplayLua, *},
+    std::fmt::{Display, Formatter, Write},
+};
+
+/// Represents an immutable config with a root [`table`],
+/// borrowing (rather than owning) its binary config data blob,
+/// e.g. a memory-mapped file.
+///
+/// Like for the owning [`BinConfig`], the data is validated once on construction,
+/// and [`table`] / [`array`] lookups navigate the data blob on access.
+/// Strings returned by the [`table`] / [`array`] accessors borrow the data blob directly
+/// and may outlive the [`config`] itself.
+///
+/// [`table`]: struct.BinTable.html
+/// [`array`]: struct.BinArray.html
+/// [`BinConfig`]: struct.BinConfig.html
+/// [`config`]: struct.BinConfigRef.html
+#[derive(Clone, Copy)]
+pub struct BinConfigRef<'a>(&'a [u8]);
+
+impl<'a> BinConfigRef<'a> {
+    /// Tries to create a new [`config`] from the borrowed `data` binary blob.
+    ///
+    /// Attempts to validate the binary config `data` blob and returns an [`error`]
+    /// if the `data` is not a valid binary config data blob,
+    /// e.g. returned by the binary config [`writer`].
+    ///
+    /// The `data` may contain arbitrary bytes following the binary config data,
+    /// which are ignored and may be accessed via [`trailing_bytes`].
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    /// [`error`]: enum.BinConfigError.html
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`trailing_bytes`]: #method.trailing_bytes
+    pub fn new(data: &'a [u8]) -> Result<Self, BinConfigError> {
+        Self::new_opts(data, Default::default())
+    }
+
+    /// Tries to create a new [`config`] from the borrowed `data` binary blob, like [`new`],
+    /// additionally validating the `data` against the limits specified by the [`options`].
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    /// [`new`]: #method.new
+    /// [`options`]: struct.BinConfigReadOptions.html
+    pub fn new_opts(data: &'a [u8], options: BinConfigReadOptions) -> Result<Self, BinConfigError> {
+        BinConfig::validate_data(data, options)?;
+
+        Ok(Self(data))
+    }
+
+    /// Like [`new`], but does not validate the `data` at all.
+    ///
+    /// # Safety
+    ///
+    /// It's up to the user to ensure that `data` is a valid binary config data blob,
+    /// e.g. returned by the binary config [`writer`].
+    ///
+    /// [`new`]: #method.new
+    /// [`writer`]: struct.BinConfigWriter.html
+    pub unsafe fn new_unchecked(data: &'a [u8]) -> Self {
+        Self(data)
+    }
+
+    /// Returns the (possibly empty) trailing bytes of the data blob the [`config`] was created from,
+    /// which follow the binary config data (as determined by its total size recorded in the header).
+    ///
+    /// Always empty for data blobs in the original layout, which do not record the total size.
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    pub fn trailing_bytes(&self) -> &'a [u8] {
+        // We ensured the data is validated.
+        let offset = unsafe { BinConfig::header(self.0).trailing_bytes_offset(self.0) };
+
+        &self.0[offset as usize..]
+    }
+
+    /// Returns the immutable reference to the root [`table`] of the [`config`].
+    ///
+    /// [`table`]: struct.BinTable.html
+    /// [`config`]: struct.BinConfigRef.html
+    pub fn root(&self) -> BinTable<'a> {
+        // We ensured the data is validated.
+        unsafe { BinConfig::root_impl(self.0) }
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string using default [`options`].
+    ///
+    /// NOTE: you may also call `to_string` via the [`config`]'s `Display` implementation.
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    /// [`options`]: struct.LuaDisplayOptions.html
+    pub fn to_lua_string(&self) -> Result<String, ToLuaStringError> {
+        self.to_lua_string_opts(Default::default())
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string to the writer `w` using default [`options`].
+    ///
+    /// NOTE: you may also use the [`config`]'s `Display` implementation.
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    /// [`options`]: struct.LuaDisplayOptions.html
+    pub fn fmt_lua<W: Write>(&self, w: &mut W) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string using provided [`options`].
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    /// [`options`]: struct.LuaDisplayOptions.html
+    pub fn to_lua_string_opts(
+        &self,
+        options: LuaDisplayOptions,
+    ) -> Result<String, ToLuaStringError> {
+        let mut result = String::new();
+
+        self.fmt_lua_opts(options, &mut result)?;
+
+        result.shrink_to_fit();
+
+        Ok(result)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string to the writer `w` using provided [`options`].
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    /// [`options`]: struct.LuaDisplayOptions.html
+    pub fn fmt_lua_opts<W: Write>(
+        &self,
+        options: LuaDisplayOptions,
+        w: &mut W,
+    ) -> Result<(), ToLuaStringError> {
+        self.root()
+            .fmt_lua(w, 0, options)
+            .map_err(ToLuaStringError::reverse)
+    }
+
+    /// Tries to serialize this [`config`] to an `.ini` string using default [`options`].
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    #[cfg(feature = "ini")]
+    pub fn to_ini_string(&self) -> Result<String, ToIniStringError> {
+        self.to_ini_string_opts(Default::default())
+    }
+
+    /// Tries to serialize this [`config`] to an `.ini` string to the writer `w` using default [`options`].
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    #[cfg(feature = "ini")]
+    pub fn fmt_ini<W: Write>(&self, w: &mut W) -> Result<(), ToIniStringError> {
+        self.fmt_ini_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to an `.ini` string using provided [`options`].
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    #[cfg(feature = "ini")]
+    pub fn to_ini_string_opts(
+        &self,
+        options: ToIniStringOptions,
+    ) -> Result<String, ToIniStringError> {
+        let mut result = String::new();
+
+        self.fmt_ini_opts(options, &mut result)?;
+
+        result.shrink_to_fit();
+
+        Ok(result)
+    }
+
+    /// Tries to serialize this [`config`] to an `.ini` string to the writer `w` using provided [`options`].
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    #[cfg(feature = "ini")]
+    pub fn fmt_ini_opts<W: std::fmt::Write>(
+        &self,
+        options: ToIniStringOptions,
+        w: &mut W,
+    ) -> Result<(), ToIniStringError> {
+        let mut path = IniPath::new();
+
+        self.root().fmt_ini(w, 0, false, &mut path, options)
+    }
+
+    /// Copies the borrowed data blob to create an owning [`config`].
+    ///
+    /// [`config`]: struct.BinConfig.html
+    pub fn to_bin_config(&self) -> BinConfig {
+        // We ensured the data is validated.
+        unsafe { BinConfig::new_unchecked(self.0.into()) }
+    }
+
+    /// Tries to deserialize a value of type `T` (e.g. a `#[derive(Deserialize)]` struct) from this [`config`],
+    /// starting at the root [`table`].
+    ///
+    /// See [`BinConfig::deserialize_into`]; strings may outlive the [`config`] and borrow the data blob.
+    ///
+    /// [`config`]: struct.BinConfigRef.html
+    /// [`table`]: struct.BinTable.html
+    /// [`BinConfig::deserialize_into`]: struct.BinConfig.html#method.deserialize_into
+    #[cfg(feature = "serde")]
+    pub fn deserialize_into<T: serde::Deserialize<'a>>(&self) -> Result<T, DeserializeError> {
+        T::deserialize(Value::Table(self.root()))
+    }
+}
+
+impl<'a> Display for BinConfigRef<'a> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.root()
+            .fmt_lua(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
+    }
+}
+
+/// Configs are compared by the contents of their root [`tables`](struct.BinTable.html#impl-PartialEq<BinTable<'t>>).
+/// Trailing bytes and checksums, if any, are ignored.
+impl<'a> PartialEq for BinConfigRef<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.root() == other.root()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {crate::*, ministr_macro::nestr, std::num::NonZeroU32};
+
+    #[test]
+    fn bin_config_ref() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+        let data = writer.finish().unwrap();
+
+        let mut data_with_trailing_bytes = data.to_vec();
+        data_with_trailing_bytes.extend_from_slice(b"\0trailing");
+
+        // Strings borrow the data blob and outlive the config.
+        let string = {
+            let config = BinConfigRef::new(&data_with_trailing_bytes).unwrap();
+
+            assert_eq!(config.root().len(), 2);
+            assert_eq!(config.root().get_i64("int".into()).unwrap(), 7);
+            assert_eq!(config.trailing_bytes(), b"\0trailing");
+            assert_eq!(config.to_bin_config().root().len(), 2);
+
+            config.root().get_string("string".into()).unwrap()
+        };
+
+        assert_eq!(string, "foo");
+        assert!(data_with_trailing_bytes
+            .as_ptr_range()
+            .contains(&string.as_ptr()));
+
+        // Truncated data is invalid.
+        for len in 0..data.len() {
+            assert_eq!(
+                BinConfigRef::new(&data[..len]).err().unwrap(),
+                BinConfigError::InvalidBinaryConfigData
+            );
+        }
+    }
+
+    #[test]
+    fn to_string() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.string(nestr!("string"), "foo").unwrap();
+        writer.i64(nestr!("int"), 7).unwrap();
+        let data = writer.finish().unwrap();
+
+        let config = BinConfigRef::new(&data).unwrap();
+
+        assert_eq!(
+            config.to_lua_string().unwrap(),
+            "{\n\tint = 7,\n\tstring = \"foo\",\n}"
+        );
+        assert_eq!(
+            config
+                .to_lua_string_opts(LuaDisplayOptions {
+                    key_comments: false,
+                    sort_keys: false,
+                    ..Default::default()
+                })
+                .unwrap(),
+            "{\n\tstring = \"foo\",\n\tint = 7,\n}"
+        );
+
+        #[cfg(feature = "ini")]
+        assert_eq!(config.to_ini_string().unwrap(), "int = 7\nstring = \"foo\"");
+    }
+}
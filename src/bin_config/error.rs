@@ -10,6 +10,14 @@ use {
 pub enum BinConfigError {
     /// Binary config data blob is invalid.
     InvalidBinaryConfigData,
+    /// Binary config data blob checksum does not match its contents.
+    ///
+    /// See [`with_checksum`](struct.BinConfigWriter.html#method.with_checksum).
+    ChecksumMismatch,
+    /// Binary config data blob exceeds the size / number of elements / depth limits.
+    ///
+    /// See [`BinConfigReadOptions`](struct.BinConfigReadOptions.html).
+    LimitExceeded,
 }
 
 impl Error for BinConfigError {}
@@ -20,6 +28,8 @@ impl Display for BinConfigError {
 
         match self {
             InvalidBinaryConfigData => "binary config data blob is invalid".fmt(f),
+            ChecksumMismatch => "binary config data blob checksum mismatch".fmt(f),
+            LimitExceeded => "binary config data blob exceeds the read limits".fmt(f),
         }
     }
 }
@@ -54,7 +64,7 @@ impl Display for BinArrayError {
             IncorrectValueType(invalid_type) => {
                 write!(
                     f,
-                    "array value is of incorrect and incompatible type (expected {})",
+                    "array value is of incorrect and incompatible type (found {})",
                     invalid_type
                 )
             }
@@ -69,7 +79,10 @@ impl Display for BinArrayError {
 pub enum BinConfigWriterError {
     /// Empty [`binary config`] root tables are not supported.
     ///
+    /// NOTE: no longer returned - empty [`config`]s are serialized to a minimal [`binary config`] data blob.
+    ///
     /// [`binary config`]: struct.BinConfig.html
+    /// [`config`]: struct.DynConfig.html
     EmptyRootTable,
     /// A non-empty string key is required for a [`table`] element.
     ///
@@ -120,6 +133,14 @@ pub enum BinConfigWriterError {
     /// [`tables`]: struct.BinTable.html
     /// [`finish`]: struct.BinConfigWriter.html#method.finish
     UnfinishedArraysOrTables(u32),
+    /// Nested [`arrays`]/[`tables`] exceed the maximum supported depth.
+    /// Contains the maximum supported depth.
+    ///
+    /// See [`with_max_depth`](struct.BinConfigWriter.html#method.with_max_depth).
+    ///
+    /// [`arrays`]: struct.BinArray.html
+    /// [`tables`]: struct.BinTable.html
+    DepthExceeded(u32),
     /// General write error.
     WriteError,
 }
@@ -151,6 +172,7 @@ impl Display for BinConfigWriterError {
             ),
             EndCallMismatch => "mismatched call to `end` (expected a previous call to `array`/`table`)".fmt(f),
             UnfinishedArraysOrTables(num) => write!(f, "{} unfinished array(s)/table(s) remain in the call to `finish`", num),
+            DepthExceeded(max_depth) => write!(f, "nested arrays/tables exceed the maximum supported depth ({})", max_depth),
             WriteError => "general write error".fmt(f),
         }
     }
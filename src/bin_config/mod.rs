@@ -1,12 +1,17 @@
 mod array;
 mod array_or_table;
 mod config;
+mod config_ref;
 mod error;
+mod options;
 mod table;
 mod util;
 mod value;
 mod writer;
 
-pub(crate) use util::string_hash_fnv1a;
+pub(crate) use {util::string_hash_fnv1a, writer::BIN_CONFIG_HEADER_SIZE};
 
-pub use {array::*, config::*, error::*, table::*, value::*, writer::*};
+#[cfg(feature = "dyn")]
+pub(crate) use writer::{BIN_CONFIG_KEY_SIZE, BIN_CONFIG_VALUE_SIZE};
+
+pub use {array::*, config::*, config_ref::*, error::*, options::*, table::*, value::*, writer::*};
@@ -0,0 +1,45 @@
+/// Configuration options for reading (validating) a [`binary config`] data blob.
+///
+/// Limit the resources used to validate and access binary config data blobs from untrusted sources.
+/// Data blobs which exceed the limits cause a [`LimitExceeded`] error.
+///
+/// [`binary config`]: struct.BinConfig.html
+/// [`LimitExceeded`]: enum.BinConfigError.html#variant.LimitExceeded
+#[derive(Clone, Copy, Debug)]
+pub struct BinConfigReadOptions {
+    /// Maximum size in bytes of the binary config data, as recorded in its header
+    /// (not including the checksum and the trailing bytes, if any).
+    ///
+    /// Default: `u32::MAX` (no limit).
+    pub max_size: u32,
+    /// Maximum total number of elements in all [`arrays`] / [`tables`] in the config, including the root [`table`].
+    ///
+    /// Default: `u32::MAX` (no limit).
+    ///
+    /// [`arrays`]: struct.BinArray.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`table`]: struct.BinTable.html
+    pub max_entries: u32,
+    /// Maximum supported depth of nested [`arrays`] / [`tables`].
+    /// Values of the root [`table`] are at depth `1`.
+    ///
+    /// Protects against stack overflow when validating the data blob.
+    ///
+    /// Default: `128` (same as the [`writer`]).
+    ///
+    /// [`arrays`]: struct.BinArray.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`table`]: struct.BinTable.html
+    /// [`writer`]: struct.BinConfigWriter.html#method.with_max_depth
+    pub max_depth: u32,
+}
+
+impl Default for BinConfigReadOptions {
+    fn default() -> Self {
+        Self {
+            max_size: u32::MAX,
+            max_entries: u32::MAX,
+            max_depth: 128,
+        }
+    }
+}
@@ -186,6 +186,44 @@ impl<'t> BinTable<'t> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`u64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`u64`] / [`i64`] / [`f64`].
+    ///
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn get_u64(&self, key: TableKey<'_>) -> Result<u64, TableError> {
+        self.get(key)
+    }
+
+    /// Tries to get a [`u64`] [`value`] in the [`table`] at `path`.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`](enum.Value.html#variant.Table) or an [`array`] value.
+    /// The last key must correspond to a [`u64`] / [`i64`] / [`f64`] [`value`].
+    ///
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`array`]: struct.BinArray.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn get_u64_path<'k, K, P>(&self, path: P) -> Result<u64, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_path(path)
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`].
@@ -230,7 +268,7 @@ impl<'t> BinTable<'t> {
     /// [`value`]: type.BinConfigValue.html
     /// [`table`]: struct.BinTable.html
     /// [`error`]: enum.TableError.html
-    pub fn get_string(&self, key: TableKey<'_>) -> Result<&str, TableError> {
+    pub fn get_string(&self, key: TableKey<'_>) -> Result<&'t str, TableError> {
         self.get(key)
     }
 
@@ -248,7 +286,7 @@ impl<'t> BinTable<'t> {
     /// [`table keys`]: enum.ConfigKey.html#variant.Table
     /// [`array indices`]: enum.ConfigKey.html#variant.Array
     /// [`array`]: enum.Value.html#variant.Array
-    pub fn get_string_path<'k, K, P>(&self, path: P) -> Result<&str, GetPathError>
+    pub fn get_string_path<'k, K, P>(&self, path: P) -> Result<&'t str, GetPathError>
     where
         K: Borrow<ConfigKey<'k>>,
         P: IntoIterator<Item = K>,
@@ -321,12 +359,39 @@ impl<'t> BinTable<'t> {
         self.get_path(path)
     }
 
-    /// Returns an iterator over (`key`, [`value`]) pairs of the [`table`], in unspecified order.
+    /// Returns an iterator over (`key`, [`value`]) pairs of the [`table`],
+    /// in the order they are stored in the binary config data blob, i.e. the order they were written by the [`writer`]
+    /// (alphabetical for configs serialized via `to_bin_config`).
+    ///
+    /// Keys and [`value`]s are read directly from the data blob on demand; nested [`tables`] / [`arrays`]
+    /// are returned as views into the data blob and are not deserialized.
+    /// The data blob was validated when the [`config`] was created, so iteration never fails.
     ///
     /// [`value`]: type.BinConfigValue.html
     /// [`table`]: struct.BinTable.html
-    pub fn iter<'i>(&'i self) -> impl Iterator<Item = (&'t NonEmptyStr, BinConfigValue<'t>)> + 'i {
-        BinTableIter::new(self)
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`arrays`]: struct.BinArray.html
+    /// [`config`]: struct.BinConfig.html
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&'t NonEmptyStr, BinConfigValue<'t>)> {
+        BinTableIter::new(BinTable(self.0.clone()))
+    }
+
+    /// Returns an iterator over the keys of the [`table`], in the order they were written by the [`writer`].
+    ///
+    /// [`table`]: struct.BinTable.html
+    /// [`writer`]: struct.BinConfigWriter.html
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &'t NonEmptyStr> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the [`values`] of the [`table`], in the order they were written by the [`writer`].
+    ///
+    /// [`values`]: type.BinConfigValue.html
+    /// [`table`]: struct.BinTable.html
+    /// [`writer`]: struct.BinConfigWriter.html
+    pub fn values(&self) -> impl ExactSizeIterator<Item = BinConfigValue<'t>> {
+        self.iter().map(|(_, value)| value)
     }
 
     pub(super) fn new(table: BinArrayOrTable<'t>) -> Self {
@@ -363,6 +428,7 @@ impl<'t> BinTable<'t> {
         match value {
             Bool(val) => Value::Bool(val),
             I64(val) => Value::I64(val),
+            U64(val) => Value::U64(val),
             F64(val) => Value::F64(val),
             BinConfigUnpackedValue::String { offset, len } => {
                 // Safe to call - the string was validated.
@@ -383,18 +449,27 @@ impl<'t> BinTable<'t> {
         }
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        writeln!(w, "{{")?;
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        <Self as DisplayLua>::write_lua_open(w, options)?;
 
         // Gather the keys.
-        let mut keys: Vec<_> = self.iter().map(|(key, _)| key).collect();
+        let mut keys: Vec<_> = self.keys().collect();
+
+        // Sort the keys in canonical order, if required.
+        if options.sort_keys {
+            sort_by_table_key(&mut keys, |key| key.as_str());
+        }
 
-        // Sort the keys in alphabetical order.
-        keys.sort();
+        let len = keys.len();
 
-        // Iterate the table using the sorted keys.
-        for key in keys.into_iter() {
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+        // Iterate the table using the (sorted) keys.
+        for (index, key) in keys.into_iter().enumerate() {
+            <Self as DisplayLua>::write_lua_element_start(w, indent, options)?;
 
             write_lua_key(w, key)?;
             write!(w, " = ")?;
@@ -407,19 +482,20 @@ impl<'t> BinTable<'t> {
 
             let is_array_or_table = matches!(value.get_type(), ValueType::Array | ValueType::Table);
 
-            value.fmt_lua(w, indent + 1)?;
+            value
+                .fmt_lua(w, indent + 1, options)
+                .map_err(|err| err.push_key(key))?;
 
-            write!(w, ",")?;
+            <Self as DisplayLua>::write_lua_comma(w, index + 1 == len, options)?;
 
-            if is_array_or_table {
+            if is_array_or_table && options.write_key_comments() {
                 write!(w, " -- {}", key)?;
             }
 
-            writeln!(w)?;
+            <Self as DisplayLua>::write_lua_element_end(w, options)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
-        write!(w, "}}")?;
+        <Self as DisplayLua>::write_lua_close(w, indent, len == 0, options)?;
 
         Ok(())
     }
@@ -436,9 +512,9 @@ impl<'t> BinTable<'t> {
         debug_assert!(options.nested_sections() || level < 2);
 
         // Gather the keys.
-        let mut keys: Vec<_> = self.iter().map(|(key, _)| key).collect();
+        let mut keys: Vec<_> = self.keys().collect();
 
-        // Sort the keys in alphabetical order, non-tables first.
+        // Sort the keys in canonical order, non-tables first.
         keys.sort_by(|&l, &r| {
             // Must succeed - all keys are valid.
             let l_val = unwrap_unchecked(
@@ -450,16 +526,12 @@ impl<'t> BinTable<'t> {
                 "failed to get a value from a bin config table with a valid key",
             );
 
-            let l_is_a_table = l_val.get_type() == ValueType::Table;
-            let r_is_a_table = r_val.get_type() == ValueType::Table;
-
-            if !l_is_a_table && r_is_a_table {
-                std::cmp::Ordering::Less
-            } else if l_is_a_table && !r_is_a_table {
-                std::cmp::Ordering::Greater
-            } else {
-                l.cmp(r)
-            }
+            cmp_ini_keys(
+                l.as_str(),
+                l_val.get_type() == ValueType::Table,
+                r.as_str(),
+                r_val.get_type() == ValueType::Table,
+            )
         });
 
         let len = self.len() as usize;
@@ -479,6 +551,8 @@ impl<'t> BinTable<'t> {
                     write_ini_array(
                         w,
                         key,
+                        None,
+                        None,
                         value.iter(),
                         value.len() as usize,
                         last,
@@ -495,6 +569,7 @@ impl<'t> BinTable<'t> {
                     write_ini_table(
                         w,
                         key,
+                        None,
                         key_index as u32,
                         &value,
                         value.len(),
@@ -506,7 +581,9 @@ impl<'t> BinTable<'t> {
                     )?;
                 }
                 value => {
-                    write_ini_value(w, key, &value, last, level, array, path, options)?;
+                    write_ini_value(
+                        w, key, None, None, &value, last, level, array, path, options,
+                    )?;
                 }
             }
         }
@@ -519,18 +596,18 @@ impl<'t> BinTable<'t> {
 ///
 /// [`value`]: type.BinConfigValue.html
 /// [`table`]: struct.BinTable.html
-struct BinTableIter<'i, 't> {
-    table: &'i BinTable<'t>,
+struct BinTableIter<'t> {
+    table: BinTable<'t>,
     index: u32,
 }
 
-impl<'i, 't> BinTableIter<'i, 't> {
-    fn new(table: &'i BinTable<'t>) -> Self {
+impl<'t> BinTableIter<'t> {
+    fn new(table: BinTable<'t>) -> Self {
         Self { table, index: 0 }
     }
 }
 
-impl<'i, 't> Iterator for BinTableIter<'i, 't> {
+impl<'t> Iterator for BinTableIter<'t> {
     type Item = (&'t NonEmptyStr, BinConfigValue<'t>);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -558,11 +635,23 @@ impl<'i, 't> Iterator for BinTableIter<'i, 't> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.table.len() - self.index) as usize;
+        (len, Some(len))
+    }
 }
 
+impl<'t> ExactSizeIterator for BinTableIter<'t> {}
+
 impl<'t> DisplayLua for BinTable<'t> {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
@@ -582,7 +671,20 @@ impl<'t> DisplayIni for BinTable<'t> {
 
 impl<'t> Display for BinTable<'t> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
+    }
+}
+
+/// Tables are compared by their contents (key / value pairs),
+/// regardless of their layout in the binary config data blobs (e.g. the order of the keys).
+impl<'t> PartialEq for BinTable<'t> {
+    fn eq(&self, other: &Self) -> bool {
+        // Keys are unique, so equal length and all keys present in the `other` table mean the same key sets.
+        (self.len() == other.len())
+            && self
+                .iter()
+                .all(|(key, value)| other.get_val(key.into()) == Some(value))
     }
 }
 
@@ -805,4 +907,25 @@ mod tests {
         let string: String = root.get("string".into()).unwrap();
         assert_eq!(string, "foo");
     }
+
+    #[test]
+    fn iter() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+        writer.i64(nestr!("b"), 7).unwrap();
+        writer.table(nestr!("a"), 1).unwrap();
+        writer.bool(nestr!("bool"), true).unwrap();
+        writer.end().unwrap();
+        writer.string(nestr!("c"), "foo").unwrap();
+        let data = writer.finish().unwrap();
+        let config = BinConfig::new(data).unwrap();
+
+        // Written order.
+        let keys: Vec<&str> = config.root().iter().map(|(key, _)| key.as_ref()).collect();
+        assert_eq!(keys, vec!["b", "a", "c"]);
+
+        let (_, value) = config.root().iter().nth(1).unwrap();
+        let table = value.table().unwrap();
+        assert_eq!(table.len(), 1);
+        assert!(table.get_bool("bool".into()).unwrap());
+    }
 }
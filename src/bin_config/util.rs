@@ -13,6 +13,27 @@ pub(crate) fn string_hash_fnv1a(string: &str) -> StringHash {
     hash
 }
 
+/// CRC-32 (IEEE 802.3) checksum of the `data`.
+pub(super) fn crc32(data: &[u8]) -> u32 {
+    const CRC32_POLYNOMIAL: u32 = 0xEDB8_8320; // Reversed.
+
+    let mut crc = !0u32;
+
+    for byte in data {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ CRC32_POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
 pub(super) fn u32_to_bin(val: u32) -> u32 {
     u32::to_le(val)
     //u32::to_be(val)
@@ -59,4 +80,10 @@ mod tests {
 
         assert_ne!(string_hash_fnv1a("foo"), string_hash_fnv1a("bar"),);
     }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
 }
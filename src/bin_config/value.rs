@@ -70,9 +70,9 @@ pub(super) struct BinConfigPackedValue {
     /// For table elements - key string hash (for quick lookups). Otherwise `0`.
     key_hash: u32,
 
-    /// `Bool`, `I64`, `F64` values are stored here directly, using 8 bytes.
-    /// |---- bool / i64 / f64 ----|
-    /// |----      64 bits     ----|
+    /// `Bool`, `I64`, `U64`, `F64` values are stored here directly, using 8 bytes.
+    /// |---- bool / i64 / u64 / f64 ----|
+    /// |----         64 bits        ----|
     /// `String`, `Array` and `Table` values of `u32` length are stored separately at a `u32` offset.
     /// |--   offset   --|--   length   --|
     /// |--   32 bits  --|--   32 bits  --|
@@ -104,6 +104,18 @@ impl BinConfigPackedValue {
         result
     }
 
+    /// Create a new packed value representing a `u64`.
+    pub(super) fn new_u64(key: BinTableKey, value: u64) -> Self {
+        let mut result = Self::default();
+
+        result.set_value_type_and_key_index(ValueType::U64, key.index);
+        result.key_hash = u32_to_bin(key.hash);
+
+        result.set_value_or_offset_and_len(value);
+
+        result
+    }
+
     /// Create a new packed value representing an `f64`.
     pub(super) fn new_f64(key: BinTableKey, value: f64) -> Self {
         let mut result = Self::default();
@@ -179,6 +191,7 @@ impl BinConfigPackedValue {
         match self.value_type() {
             ValueType::Bool => Bool(self.bool()),
             ValueType::I64 => I64(self.i64()),
+            ValueType::U64 => U64(self.u64()),
             ValueType::F64 => F64(self.f64()),
             ValueType::String => BinConfigUnpackedValue::String {
                 offset: self.offset(),
@@ -240,6 +253,12 @@ impl BinConfigPackedValue {
         unsafe { std::mem::transmute(self.value_or_offset_and_len()) }
     }
 
+    /// Unpacks and interprets this value as a `u64`.
+    /// NOTE - the caller ensures the value is actually a `u64`.
+    fn u64(&self) -> u64 {
+        self.value_or_offset_and_len()
+    }
+
     /// Unpacks and interprets this value as an `f64`.
     /// NOTE - the caller ensures the value is actually an `f64`.
     fn f64(&self) -> f64 {
@@ -348,6 +367,7 @@ impl BinTableKey {
 pub(super) enum BinConfigUnpackedValue {
     Bool(bool),
     I64(i64),
+    U64(u64),
     F64(f64),
     String { offset: u32, len: u32 },
     Array { offset: u32, len: u32 },
@@ -417,7 +437,8 @@ impl<'at> BinConfigValue<'at> {
 
 impl<'a> Display for BinConfigValue<'a> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua(f, 0)
+        self.fmt_lua(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
     }
 }
 
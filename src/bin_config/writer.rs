@@ -22,8 +22,23 @@ struct BinConfigWriterString {
     index: Option<StringIndex>,
 }
 
+/// Size in bytes of the binary config data blob header, including the total size of the binary config data following it.
+pub(crate) const BIN_CONFIG_HEADER_SIZE: usize = size_of::<BinConfigHeader>() + size_of::<u32>();
+
+/// Size in bytes of a packed value - one per each array / table element (including the root table).
+#[cfg(feature = "dyn")]
+pub(crate) const BIN_CONFIG_VALUE_SIZE: usize = size_of::<BinConfigPackedValue>();
+
+/// Size in bytes of a key table entry - one per each unique table key string.
+#[cfg(feature = "dyn")]
+pub(crate) const BIN_CONFIG_KEY_SIZE: usize = size_of::<InternedString>();
+
 /// Provides an interface for recording of [`binary configs`].
 ///
+/// All strings (both table keys and string values) are interned -
+/// each unique string is written to the binary config data blob only once,
+/// no matter how many times it is used as a key or a value.
+///
 /// [`binary configs`]: struct.BinConfig.html
 pub struct BinConfigWriter {
     /// Offset in bytes to the string section of the binary config data blob.
@@ -42,6 +57,10 @@ pub struct BinConfigWriter {
     string_writer: Vec<u8>,
     /// LIFO stack which contains the root table and any nested arrays/tables during recording.
     stack: Vec<BinConfigArrayOrTable>,
+    /// Whether the checksum is appended to the binary config data.
+    checksum: bool,
+    /// Maximum supported depth of nested arrays/tables.
+    max_depth: u32,
 }
 
 impl BinConfigWriter {
@@ -58,6 +77,8 @@ impl BinConfigWriter {
             key_table: Vec::new(),
             string_writer: Vec::new(),
             stack: Vec::new(),
+            checksum: false,
+            max_depth: 128,
         };
 
         // Write the config header / root table length, prepare to receive root table elements.
@@ -66,6 +87,55 @@ impl BinConfigWriter {
         Ok(writer)
     }
 
+    /// Sets whether the CRC-32 checksum of the [`binary config`] data (except the header)
+    /// is written following the data by [`finish`], to be verified when the [`binary config`] is created.
+    ///
+    /// [`Binary configs`] without the checksum are still valid.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`binary config`]: struct.BinConfig.html
+    /// [`finish`]: #method.finish
+    /// [`Binary configs`]: struct.BinConfig.html
+    pub fn with_checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Sets the maximum supported depth of nested [`arrays`] / [`tables`].
+    /// Values of the root [`table`] are at depth `1`.
+    /// Recording deeper nested [`arrays`] / [`tables`] returns a [`DepthExceeded`] error.
+    ///
+    /// Bounds the recursion depth when recording deeply nested configs.
+    ///
+    /// Default: `128`.
+    ///
+    /// [`arrays`]: struct.BinArray.html
+    /// [`tables`]: struct.BinTable.html
+    /// [`table`]: struct.BinTable.html
+    /// [`DepthExceeded`]: enum.BinConfigWriterError.html#variant.DepthExceeded
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns the data blob of a [`binary config`] with an empty root [`table`] -
+    /// just the header, with no values, key table or string section.
+    ///
+    /// [`binary config`]: struct.BinConfig.html
+    /// [`table`]: struct.BinTable.html
+    #[cfg(any(feature = "dyn", feature = "lua"))]
+    pub(crate) fn empty() -> Result<Box<[u8]>, BinConfigWriterError> {
+        let size = BIN_CONFIG_HEADER_SIZE as u32;
+
+        let mut data = Vec::with_capacity(size as _);
+
+        // The key table offset points past the header, where the (empty) key table would be.
+        BinConfigHeader::write(&mut data, 0, size, 0, size, false)?;
+
+        Ok(data.into_boxed_slice())
+    }
+
     /// Writes a `bool` value to the current [`array`] / [`table`] (including the root [`table`]).
     ///
     /// NOTE - a non-empty UTF-8 string `key` is required for a [`table`] element (including the root [`table`]).
@@ -116,6 +186,31 @@ impl BinConfigWriter {
         Ok(())
     }
 
+    /// Writes a `u64` value to the current [`array`] / [`table`] (including the root [`table`]).
+    ///
+    /// NOTE - a non-empty string `key` is required for a [`table`] element (including the root [`table`]).
+    ///
+    /// [`array`]: struct.BinArray.html
+    /// [`table`]: struct.BinTable.html
+    pub fn u64<'k, K: Into<Option<&'k NonEmptyStr>>>(
+        &mut self,
+        key: K,
+        value: u64,
+    ) -> Result<(), BinConfigWriterError> {
+        // Value's key and its offset in bytes.
+        let (key, value_offset) = self.key_and_value_offset(key.into(), ValueType::U64)?;
+
+        // Write the packed value.
+        Self::write_value(
+            &mut self.config_writer,
+            &mut self.stack,
+            BinConfigPackedValue::new_u64(key, value),
+            value_offset,
+        )?;
+
+        Ok(())
+    }
+
     /// Writes an `f64` value to the current [`array`] / [`table`] (including the root [`table`]).
     ///
     /// NOTE - a non-empty string `key` is required for a [`table`] element (including the root [`table`]).
@@ -143,6 +238,8 @@ impl BinConfigWriter {
 
     /// Writes a string value to the current [`array`] / [`table`] (including the root [`table`]).
     ///
+    /// The `value` is interned - repeated string values (and keys) share the same string section entry.
+    ///
     /// NOTE - a non-empty string `key` is required for a [`table`] element (including the root [`table`]).
     ///
     /// [`array`]: struct.BinArray.html
@@ -273,9 +370,12 @@ impl BinConfigWriter {
             });
         };
 
-        // Fixup the header with correct key table offset and length.
+        // Fixup the header with correct key table offset and length, and the total size.
         let key_table_offset = self.data_offset;
         let key_table_len = self.key_table.len() as u32;
+        let key_table_size = key_table_len * size_of::<InternedString>() as u32;
+
+        let size = self.data_offset + key_table_size + self.string_writer.len() as u32;
 
         self.config_writer.seek(SeekFrom::Start(0))?;
 
@@ -284,10 +384,11 @@ impl BinConfigWriter {
             root.len,
             key_table_offset,
             key_table_len,
+            size,
+            self.checksum,
         )?;
 
         // Fixup the key table offsets.
-        let key_table_size = key_table_len * size_of::<InternedString>() as u32;
 
         let string_offset = self.data_offset + key_table_size as u32;
 
@@ -307,16 +408,19 @@ impl BinConfigWriter {
 
         // Append the strings to the end of the buffer.
         config_writer.append(&mut self.string_writer);
-        config_writer.shrink_to_fit();
         std::mem::drop(self.string_writer);
 
         // Fixup the string offsets in all entries using them
         // via incrementing them by the now-known data offset.
-        let mut data = config_writer.into_boxed_slice();
+        Self::fixup_string_offsets(&mut config_writer, string_offset);
 
-        Self::fixup_string_offsets(&mut data, string_offset);
+        // Append the checksum of everything following the header, if required.
+        if self.checksum {
+            let checksum = crc32(&config_writer[BIN_CONFIG_HEADER_SIZE..]);
+            config_writer.extend_from_slice(&u32_to_bin_bytes(checksum));
+        }
 
-        Ok(data)
+        Ok(config_writer.into_boxed_slice())
     }
 
     /// Called once on construction.
@@ -329,8 +433,10 @@ impl BinConfigWriter {
         self.data_offset += BinConfigHeader::write(
             &mut self.config_writer,
             len.get(),
-            0, // NOTE - fixed up when the recording is finished.
-            0, // NOTE - fixed up when the recording is finished.
+            0,     // NOTE - fixed up when the recording is finished.
+            0,     // NOTE - fixed up when the recording is finished.
+            0,     // NOTE - fixed up when the recording is finished.
+            false, // NOTE - fixed up when the recording is finished.
         )?;
 
         // Push the root table on the stack.
@@ -352,6 +458,11 @@ impl BinConfigWriter {
         len: u32,
         table: bool,
     ) -> Result<(), BinConfigWriterError> {
+        // The stack contains the root table, so its length is the depth of the new array/table.
+        if self.stack.len() as u32 > self.max_depth {
+            return Err(BinConfigWriterError::DepthExceeded(self.max_depth));
+        }
+
         // Offset to the array's/table's packed value is the parent array's/table's value offset.
         let (key, value_offset) = self.key_and_value_offset(
             key,
@@ -653,7 +764,7 @@ impl BinConfigWriter {
 
         let base = data.as_mut_ptr() as *mut u8;
 
-        let begin = unsafe { base.add(size_of::<BinConfigHeader>()) as *mut BinConfigPackedValue };
+        let begin = unsafe { base.add(BIN_CONFIG_HEADER_SIZE) as *mut BinConfigPackedValue };
         let values = unsafe { std::slice::from_raw_parts_mut(begin, len as _) };
 
         Self::fixup_string_offsets_impl(base, values, string_offset);
@@ -1118,4 +1229,97 @@ mod tests {
             assert!(!table_value.contains(key!("bob")));
         }
     }
+
+    #[test]
+    fn u64() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(3).unwrap()).unwrap();
+
+        writer.u64(nestr!("u64"), u64::MAX).unwrap();
+        writer.i64(nestr!("i64"), 7).unwrap();
+
+        // Integer values may be mixed in arrays.
+        writer.array(nestr!("array"), 2).unwrap();
+        writer.i64(None, 7).unwrap();
+        writer.u64(None, 9).unwrap();
+        writer.end().unwrap();
+
+        let data = writer.finish().unwrap();
+
+        let config = BinConfig::new(data).unwrap();
+
+        assert_eq!(config.root().get_u64("u64".into()).unwrap(), u64::MAX);
+        assert_eq!(
+            config.root().get_val("u64".into()).unwrap().get_type(),
+            ValueType::U64
+        );
+        assert_eq!(config.root().get_u64("i64".into()).unwrap(), 7);
+
+        let array = config.root().get_array("array".into()).unwrap();
+
+        assert_eq!(array.get_u64(0).unwrap(), 7);
+        assert_eq!(array.get_u64(1).unwrap(), 9);
+        assert_eq!(array.get_val(1).unwrap().get_type(), ValueType::U64);
+    }
+
+    #[test]
+    fn DepthExceeded() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap())
+            .unwrap()
+            .with_max_depth(2);
+        writer.table(nestr!("table"), 1).unwrap();
+        writer.array(nestr!("array"), 1).unwrap();
+        assert_eq!(
+            writer.table(None, 1).err().unwrap(),
+            BinConfigWriterError::DepthExceeded(2)
+        );
+
+        // But this works.
+
+        writer.bool(None, true).unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn string_interning() {
+        let num_strings = 5000;
+
+        let write_array = |value: &dyn Fn(u32) -> String| {
+            let mut writer = BinConfigWriter::new(NonZeroU32::new(1).unwrap()).unwrap();
+            writer.array(nestr!("array"), num_strings).unwrap();
+            for idx in 0..num_strings {
+                writer.string(None, &value(idx)).unwrap();
+            }
+            writer.end().unwrap();
+            writer.finish().unwrap()
+        };
+
+        let unique = write_array(&|idx| format!("enabled{}", idx));
+        let repeated = write_array(&|_| "enabled".to_owned());
+
+        // Only one copy of the repeated string is written.
+        assert!(repeated.len() + (num_strings as usize - 1) * "enabled".len() < unique.len());
+
+        let config = BinConfig::new(repeated).unwrap();
+        let array = config.root().get_array("array".into()).unwrap();
+        assert_eq!(array.len(), num_strings);
+
+        for value in array.iter() {
+            assert_eq!(value.string().unwrap(), "enabled");
+        }
+
+        // Keys and string values share the interned strings.
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.string(nestr!("enabled"), "enabled").unwrap();
+        writer.string(nestr!("other"), "enabled").unwrap();
+        let shared = writer.finish().unwrap();
+
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.string(nestr!("enabled"), "disabled").unwrap();
+        writer.string(nestr!("other"), "enabled").unwrap();
+        let not_shared = writer.finish().unwrap();
+
+        assert_eq!(shared.len() + "disabled".len() + 1, not_shared.len());
+    }
 }
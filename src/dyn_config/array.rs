@@ -3,14 +3,18 @@ use {
     std::{
         borrow::Borrow,
         fmt::{Display, Formatter, Write},
+        iter::FromIterator,
+        ops::{Deref, Index},
         slice::Iter as VecIter,
     },
 };
 
 /// Represents a mutable array of [`Value`]'s with integer 0-based indices.
 ///
+/// Arrays are equal if they have the same length and their values are equal at each index.
+///
 /// [`Value`]: struct.Value.html
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct DynArray(Vec<DynConfigValue>);
 
 impl DynArray {
@@ -21,6 +25,14 @@ impl DynArray {
         Self(Vec::new())
     }
 
+    /// Creates a new empty [`array`] with space for at least `capacity` values
+    /// without reallocating.
+    ///
+    /// [`array`]: struct.DynArray.html
+    pub fn with_capacity(capacity: u32) -> Self {
+        Self(Vec::with_capacity(capacity as _))
+    }
+
     /// Returns the length of the [`array`].
     ///
     /// [`array`]: struct.DynArray.html
@@ -35,6 +47,21 @@ impl DynArray {
         self.len() == 0
     }
 
+    /// Returns the [`type`] of the [`values`] in the [`array`] (as determined by its first [`value`]),
+    /// or `None` if the [`array`] is empty.
+    ///
+    /// NOTE: numeric values (`i64`, `u64` and `f64`) are compatible,
+    /// so a numeric [`array`] may contain values of other numeric [`types`].
+    ///
+    /// [`type`]: enum.ValueType.html
+    /// [`types`]: enum.ValueType.html
+    /// [`values`]: type.DynConfigValue.html
+    /// [`value`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    pub fn element_type(&self) -> Option<ValueType> {
+        self.0.first().map(Value::get_type)
+    }
+
     /// Clears the [`array`].
     ///
     /// [`array`]: struct.DynArray.html
@@ -42,6 +69,18 @@ impl DynArray {
         self.0.clear();
     }
 
+    /// Shortens the [`array`], keeping the first `len` [`values`] and dropping the rest.
+    ///
+    /// Has no effect if `len` is greater than or equal to the [`array`]'s current length.
+    /// Like [`clear`], does not free the [`array`]'s memory, so it may be reused.
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`values`]: type.DynConfigValue.html
+    /// [`clear`]: #method.clear
+    pub fn truncate(&mut self, len: u32) {
+        self.0.truncate(len as usize);
+    }
+
     /// Tries to get an immutable reference to a [`value`] in the [`array`] at `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds.
@@ -190,6 +229,44 @@ impl DynArray {
         self.get_path(path)
     }
 
+    /// Tries to get a [`u64`] [`value`] in the [`array`] at `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not a [`u64`] / [`i64`] / [`f64`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html
+    pub fn get_u64(&self, index: u32) -> Result<u64, ArrayError> {
+        self.get(index)
+    }
+
+    /// Tries to get a [`u64`] [`value`] in the [`array`] at `path`.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`] or an [`array`](enum.Value.html#variant.Array) value.
+    /// The last key must correspond to a [`u64`] / [`i64`] / [`f64`] [`value`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`table`]: enum.Value.html#variant.Table
+    pub fn get_u64_path<'k, K, P>(&self, path: P) -> Result<u64, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_path(path)
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`array`] at `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds or if value is not an [`f64`] / [`i64`].
@@ -329,10 +406,108 @@ impl DynArray {
     ///
     /// [`values`]: enum.Value.html
     /// [`array`]: struct.DynArray.html
-    pub fn iter(&self) -> impl Iterator<Item = DynConfigValueRef<'_>> {
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = DynConfigValueRef<'_>> {
         DynArrayIter(self.0.iter())
     }
 
+    /// Returns an in-order iterator over mutable [`value handles`] in the [`array`].
+    ///
+    /// The [`value handles`] preserve the [`array`]'s value type homogeneity the same way [`set`] does.
+    /// NOTE: numeric values (`i64`, `u64` and `f64`) are compatible,
+    /// so an `i64` value may be changed to an `f64` value (and vice versa) in a numeric [`array`].
+    ///
+    /// [`value handles`]: struct.DynArrayValueMut.html
+    /// [`array`]: struct.DynArray.html
+    /// [`set`]: #method.set
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = DynArrayValueMut<'_>> {
+        // A single element array will have its only value replaced, so its type doesn't matter.
+        let typed = self.0.len() > 1;
+
+        self.0
+            .iter_mut()
+            .map(move |value| DynArrayValueMut { value, typed })
+    }
+
+    /// Tries to convert all [`values`] in the [`array`] to the user-requested type
+    /// [`convertible`](TryFromValue) from a [`value`], and collect them to a `Vec`.
+    ///
+    /// Returns an [`error`] if the [`array`]'s value type is incorrect and incompatible.
+    ///
+    /// [`values`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+    pub fn to_vec<'a, V: TryFromValue<&'a str, &'a DynArray, &'a DynTable>>(
+        &'a self,
+    ) -> Result<Vec<V>, ArrayError> {
+        self.iter()
+            .map(|value| V::try_from(value).map_err(ArrayError::IncorrectValueType))
+            .collect()
+    }
+
+    /// Tries to collect all [`bool`] [`values`] in the [`array`] to a `Vec`.
+    ///
+    /// Returns an [`error`] if the [`array`]'s value type is not [`bool`].
+    ///
+    /// [`bool`]: enum.Value.html#variant.Bool
+    /// [`values`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+    pub fn as_bool_vec(&self) -> Result<Vec<bool>, ArrayError> {
+        self.to_vec()
+    }
+
+    /// Tries to collect all [`i64`] [`values`] in the [`array`] to a `Vec`.
+    ///
+    /// Returns an [`error`] if the [`array`]'s value type is not [`i64`] / [`f64`].
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`values`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn as_i64_vec(&self) -> Result<Vec<i64>, ArrayError> {
+        self.to_vec()
+    }
+
+    /// Tries to collect all [`f64`] [`values`] in the [`array`] to a `Vec`.
+    ///
+    /// Returns an [`error`] if the [`array`]'s value type is not [`f64`] / [`i64`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`values`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+    /// [`i64`]: enum.Value.html#variant.I64
+    pub fn as_f64_vec(&self) -> Result<Vec<f64>, ArrayError> {
+        self.to_vec()
+    }
+
+    /// Tries to collect all [`string`] [`values`] in the [`array`] to a `Vec` of owned `String`'s.
+    ///
+    /// Returns an [`error`] if the [`array`]'s value type is not [`string`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`values`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+    pub fn as_string_vec(&self) -> Result<Vec<String>, ArrayError> {
+        self.to_vec()
+    }
+
+    /// Returns a [`view`] of the [`array`] statically guaranteed to be non-empty,
+    /// or `None` if the [`array`] is empty.
+    ///
+    /// [`view`]: struct.NonEmptyDynArray.html
+    /// [`array`]: struct.DynArray.html
+    pub fn non_empty(&self) -> Option<NonEmptyDynArray<'_>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(NonEmptyDynArray(self))
+        }
+    }
+
     /// Tries to get a mutable reference to a [`value`] in the [`array`] at `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds.
@@ -572,6 +747,68 @@ impl DynArray {
         self.pop_impl()
     }
 
+    /// Pushes all [`values`] yielded by `iter` to the back of the [`array`], in order.
+    ///
+    /// Returns an [`error`] if any of the [`values`] is of invalid type
+    /// (i.e. incompatible with the [`array`]'s [`element type`], or with the first pushed [`value`] if the [`array`] was empty).
+    /// The operation is transactional - on error the [`array`] is restored to its original length,
+    /// and the [`values`] already consumed from `iter` are dropped.
+    ///
+    /// [`values`]: type.DynConfigValue.html
+    /// [`value`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html
+    /// [`element type`]: #method.element_type
+    pub fn extend_from<V, I>(&mut self, iter: I) -> Result<(), ArrayError>
+    where
+        V: Into<DynConfigValue>,
+        I: IntoIterator<Item = V>,
+    {
+        let len = self.0.len();
+
+        for value in iter.into_iter() {
+            if let Err(err) = self.push_impl(value.into()) {
+                self.0.truncate(len);
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Moves all [`values`] of the `other` [`array`] to the back of this [`array`], leaving `other` empty.
+    ///
+    /// Returns an [`error`] if the [`values`] of the `other` [`array`] are of invalid type
+    /// (i.e. incompatible with this [`array`]'s [`element type`]).
+    /// Neither [`array`] is modified on error.
+    ///
+    /// [`values`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    /// [`error`]: enum.ArrayError.html
+    /// [`element type`]: #method.element_type
+    pub fn append(&mut self, other: &mut DynArray) -> Result<(), ArrayError> {
+        // All values in the `other` array are compatible with its first value.
+        if let Some(value) = other.0.first() {
+            self.validate_value_type(value)?;
+        }
+
+        self.0.append(&mut other.0);
+
+        Ok(())
+    }
+
+    /// Retains only the [`values`] for which `f` returns `true`,
+    /// removing all other [`values`] from the [`array`] and preserving the order of the retained [`values`].
+    ///
+    /// `f` is called once for each [`value`] in the [`array`], in order.
+    ///
+    /// [`values`]: type.DynConfigValueRef.html
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    pub fn retain<F: FnMut(DynConfigValueRef<'_>) -> bool>(&mut self, mut f: F) {
+        self.0.retain(|value| f(value.into()));
+    }
+
     fn len_impl(&self) -> u32 {
         self.0.len() as u32
     }
@@ -588,6 +825,7 @@ impl DynArray {
             let value = match unsafe { self.0.get_unchecked(index as usize) } {
                 Value::Bool(value) => Value::Bool(*value),
                 Value::I64(value) => Value::I64(*value),
+                Value::U64(value) => Value::U64(*value),
                 Value::F64(value) => Value::F64(*value),
                 Value::String(value) => Value::String(value.as_str()),
                 Value::Array(value) => Value::Array(value),
@@ -610,6 +848,7 @@ impl DynArray {
             let value = match unsafe { self.0.get_unchecked_mut(index as usize) } {
                 Value::Bool(value) => Value::Bool(*value),
                 Value::I64(value) => Value::I64(*value),
+                Value::U64(value) => Value::U64(*value),
                 Value::F64(value) => Value::F64(*value),
                 Value::String(value) => Value::String(value.as_str()),
                 Value::Array(value) => Value::Array(value),
@@ -620,6 +859,22 @@ impl DynArray {
         }
     }
 
+    /// Returns the [`array`]'s value type, if it is not empty.
+    ///
+    /// [`array`]: struct.DynArray.html
+    pub(crate) fn value_type(&self) -> Option<ValueType> {
+        self.0.first().map(Value::get_type)
+    }
+
+    /// Appends the (cloned) values of the `other` [`array`].
+    /// The caller guarantees the value types of both [`arrays`] are compatible.
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`arrays`]: struct.DynArray.html
+    pub(crate) fn append_impl(&mut self, other: &DynArray) {
+        self.0.extend(other.0.iter().cloned());
+    }
+
     fn validate_value_type<S: Into<String>>(
         &self,
         value: &Value<S, DynArray, DynTable>,
@@ -666,6 +921,7 @@ impl DynArray {
         match value {
             Value::Bool(value) => *dst = Value::Bool(value),
             Value::I64(value) => *dst = Value::I64(value),
+            Value::U64(value) => *dst = Value::U64(value),
             Value::F64(value) => *dst = Value::F64(value),
             Value::String(value) => *dst = Value::String(value),
             Value::Array(value) => *dst = Value::Array(value),
@@ -688,31 +944,122 @@ impl DynArray {
         self.0.pop().ok_or_else(|| ArrayError::ArrayEmpty)
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        writeln!(w, "{{ ")?;
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        <Self as DisplayLua>::write_lua_open(w, options)?;
+
+        let len = self.len();
 
         // Iterate the array.
         for (index, value) in self.iter().enumerate() {
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+            <Self as DisplayLua>::write_lua_element_start(w, indent, options)?;
 
-            value.fmt_lua(w, indent + 1)?;
+            value
+                .fmt_lua(w, indent + 1, options)
+                .map_err(|err| err.push_key(index as u32))?;
 
-            write!(w, ",")?;
+            <Self as DisplayLua>::write_lua_comma(w, index as u32 + 1 == len, options)?;
 
             let is_array_or_table = matches!(value.get_type(), ValueType::Array | ValueType::Table);
 
-            if is_array_or_table {
+            if is_array_or_table && options.write_key_comments() {
                 write!(w, " -- [{}]", index)?;
             }
 
-            writeln!(w)?;
+            <Self as DisplayLua>::write_lua_element_end(w, options)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
-        write!(w, "}}")?;
+        <Self as DisplayLua>::write_lua_close(w, indent, len == 0, options)?;
 
         Ok(())
     }
+
+    #[cfg(feature = "json")]
+    fn fmt_json_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToJSONStringOptions,
+    ) -> Result<(), ToJSONStringError> {
+        if self.is_empty() {
+            write!(w, "[]")?;
+            return Ok(());
+        }
+
+        writeln!(w, "[")?;
+
+        let len = self.len();
+
+        // Iterate the array.
+        for (index, value) in self.iter().enumerate() {
+            let index = index as u32;
+
+            write_json_array_value(w, index, &value, index == len - 1, indent + 1, options)?;
+        }
+
+        <Self as DisplayJSON>::do_indent(w, indent)?;
+        write!(w, "]")?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "yaml")]
+    fn fmt_yaml_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(w, "[]");
+        }
+
+        // Iterate the array.
+        for (index, value) in self.iter().enumerate() {
+            write_yaml_array_value(w, &value, index == 0, indent)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ron")]
+    fn fmt_ron_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToRONStringOptions,
+    ) -> Result<(), ToRONStringError> {
+        if self.is_empty() {
+            write!(w, "[]")?;
+            return Ok(());
+        }
+
+        writeln!(w, "[")?;
+
+        // Iterate the array.
+        for (index, value) in self.iter().enumerate() {
+            write_ron_array_value(w, index as u32, &value, indent + 1, options)?;
+        }
+
+        <Self as DisplayRON>::do_indent(w, indent)?;
+        write!(w, "]")?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "toml")]
+    fn to_toml_value_impl(&self) -> Result<toml_rs::Value, ToTOMLStringError> {
+        let mut array = toml_rs::value::Array::with_capacity(self.len() as usize);
+
+        for (index, value) in self.iter().enumerate() {
+            array.push(
+                value
+                    .to_toml_value()
+                    .map_err(|err| err.push_key(index as u32))?,
+            );
+        }
+
+        Ok(toml_rs::Value::Array(array))
+    }
 }
 
 /// In-order iterator over [`values`] in the [`array`].
@@ -729,6 +1076,7 @@ impl<'a> Iterator for DynArrayIter<'a> {
             let value = match value {
                 Value::Bool(value) => Value::Bool(*value),
                 Value::I64(value) => Value::I64(*value),
+                Value::U64(value) => Value::U64(*value),
                 Value::F64(value) => Value::F64(*value),
                 Value::String(value) => Value::String(value.as_str()),
                 Value::Array(value) => Value::Array(value),
@@ -740,29 +1088,290 @@ impl<'a> Iterator for DynArrayIter<'a> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for DynArrayIter<'a> {}
+
+/// An immutable view of a [`dynamic array`] statically guaranteed to contain at least one [`value`].
+///
+/// Returned by [`DynArray::non_empty`].
+/// Dereferences to the [`dynamic array`].
+///
+/// [`dynamic array`]: struct.DynArray.html
+/// [`value`]: type.DynConfigValueRef.html
+/// [`DynArray::non_empty`]: struct.DynArray.html#method.non_empty
+#[derive(Clone, Copy)]
+pub struct NonEmptyDynArray<'a>(&'a DynArray);
+
+impl<'a> NonEmptyDynArray<'a> {
+    /// Returns the first [`value`] in the [`array`].
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    pub fn first(&self) -> DynConfigValueRef<'a> {
+        // Must succeed - the array is not empty.
+        unwrap_unchecked(self.0.get_impl(0), "empty non-empty array")
+    }
+
+    /// Returns the last [`value`] in the [`array`].
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`array`]: struct.DynArray.html
+    pub fn last(&self) -> DynConfigValueRef<'a> {
+        // Must succeed - the array is not empty.
+        unwrap_unchecked(self.0.get_impl(self.0.len() - 1), "empty non-empty array")
+    }
+
+    /// Returns the viewed [`array`].
+    ///
+    /// [`array`]: struct.DynArray.html
+    pub fn array(&self) -> &'a DynArray {
+        self.0
+    }
+}
+
+impl<'a> Deref for NonEmptyDynArray<'a> {
+    type Target = DynArray;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
 }
 
 impl DisplayLua for DynArray {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
 impl<'a> DisplayLua for &'a DynArray {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
 impl<'a> DisplayLua for &'a mut DynArray {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_impl(w, indent, options)
+    }
+}
+
+#[cfg(feature = "json")]
+impl DisplayJSON for DynArray {
+    fn fmt_json<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToJSONStringOptions,
+    ) -> Result<(), ToJSONStringError> {
+        self.fmt_json_impl(w, indent, options)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'a> DisplayJSON for &'a DynArray {
+    fn fmt_json<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToJSONStringOptions,
+    ) -> Result<(), ToJSONStringError> {
+        self.fmt_json_impl(w, indent, options)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl DisplayYAML for DynArray {
+    fn fmt_yaml<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+        self.fmt_yaml_impl(w, indent)
+    }
+
+    fn is_block(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+#[cfg(feature = "ron")]
+impl DisplayRON for DynArray {
+    fn fmt_ron<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToRONStringOptions,
+    ) -> Result<(), ToRONStringError> {
+        self.fmt_ron_impl(w, indent, options)
+    }
+}
+
+#[cfg(feature = "ron")]
+impl<'a> DisplayRON for &'a DynArray {
+    fn fmt_ron<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToRONStringOptions,
+    ) -> Result<(), ToRONStringError> {
+        self.fmt_ron_impl(w, indent, options)
+    }
+}
+
+#[cfg(feature = "toml")]
+impl DisplayTOML for DynArray {
+    fn to_toml_value(&self) -> Result<toml_rs::Value, ToTOMLStringError> {
+        self.to_toml_value_impl()
+    }
+}
+
+#[cfg(feature = "toml")]
+impl<'a> DisplayTOML for &'a DynArray {
+    fn to_toml_value(&self) -> Result<toml_rs::Value, ToTOMLStringError> {
+        self.to_toml_value_impl()
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<'a> DisplayYAML for &'a DynArray {
+    fn fmt_yaml<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+        self.fmt_yaml_impl(w, indent)
+    }
+
+    fn is_block(&self) -> bool {
+        !self.is_empty()
     }
 }
 
 impl Display for DynArray {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
+    }
+}
+
+/// Returns an immutable reference to the [`value`] in the [`array`] at `index`.
+///
+/// NOTE: `IndexMut` is not implemented, as it would allow to change the [`value`] to one of incompatible type -
+/// use [`get_val_mut`] / [`iter_mut`] instead.
+///
+/// # Panics
+///
+/// Panics if `index` is out of bounds - use [`get_val`] to handle the [`error`] instead.
+///
+/// [`value`]: type.DynConfigValue.html
+/// [`array`]: struct.DynArray.html
+/// [`get_val_mut`]: struct.DynArray.html#method.get_val_mut
+/// [`iter_mut`]: struct.DynArray.html#method.iter_mut
+/// [`get_val`]: struct.DynArray.html#method.get_val
+/// [`error`]: enum.ArrayError.html#variant.IndexOutOfBounds
+impl Index<u32> for DynArray {
+    type Output = DynConfigValue;
+
+    fn index(&self, index: u32) -> &Self::Output {
+        match self.0.get(index as usize) {
+            Some(value) => value,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            ),
+        }
+    }
+}
+
+/// Creates an [`array`] from the [`values`], as if by [`push`].
+///
+/// # Panics
+///
+/// Panics if the [`values`] are not all of the same (or compatible) [`type`].
+/// Use [`push`] to handle the [`error`] instead.
+///
+/// [`array`]: struct.DynArray.html
+/// [`values`]: type.DynConfigValue.html
+/// [`push`]: struct.DynArray.html#method.push
+/// [`type`]: enum.ValueType.html
+/// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+impl<V: Into<DynConfigValue>> FromIterator<V> for DynArray {
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut array = Self::with_capacity(iter.size_hint().0 as _);
+
+        for value in iter {
+            if let Err(err) = array.push(value.into()) {
+                panic!("failed to collect a dyn config array: {}", err);
+            }
+        }
+
+        array
+    }
+}
+
+/// A mutable handle to a [`value`] in the [`array`], returned by [`iter_mut`].
+///
+/// [`value`]: type.DynConfigValue.html
+/// [`array`]: struct.DynArray.html
+/// [`iter_mut`]: struct.DynArray.html#method.iter_mut
+pub struct DynArrayValueMut<'a> {
+    value: &'a mut DynConfigValue,
+    // Whether the array has a value type which must be preserved (i.e. has more than one element).
+    typed: bool,
+}
+
+impl<'a> DynArrayValueMut<'a> {
+    /// Returns an immutable reference to the [`value`].
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    pub fn get(&self) -> DynConfigValueRef<'_> {
+        (&*self.value).into()
+    }
+
+    /// Returns a mutable reference to the [`value`].
+    ///
+    /// NOTE: mutable reference extends to [`arrays`] and [`tables`], not other value types.
+    /// Use [`set`] to mutate other value types.
+    ///
+    /// [`value`]: type.DynConfigValueMut.html
+    /// [`arrays`]: enum.Value.html#variant.Array
+    /// [`tables`]: enum.Value.html#variant.Table
+    /// [`set`]: #method.set
+    pub fn get_mut(&mut self) -> DynConfigValueMut<'_> {
+        (&mut *self.value).into()
+    }
+
+    /// Changes the [`value`] to `value`.
+    ///
+    /// Returns an [`error`] if `value` is of invalid type for the [`array`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`error`]: enum.ArrayError.html
+    /// [`array`]: struct.DynArray.html
+    pub fn set(&mut self, value: DynConfigValue) -> Result<(), ArrayError> {
+        let value_type = self.value.get_type();
+
+        if self.typed && !value_type.is_compatible(value.get_type()) {
+            return Err(ArrayError::IncorrectValueType(value_type));
+        }
+
+        *self.value = value;
+
+        Ok(())
     }
 }
 
@@ -770,7 +1379,7 @@ impl Display for DynArray {
 mod tests {
     #![allow(non_snake_case)]
 
-    use crate::*;
+    use {crate::*, ministr_macro::nestr};
 
     #[test]
     fn len_empty_clear() {
@@ -795,6 +1404,333 @@ mod tests {
         assert!(array.is_empty());
     }
 
+    #[test]
+    fn element_type() {
+        let mut array = DynArray::new();
+
+        assert_eq!(array.element_type(), None);
+
+        array.push(Value::I64(7)).unwrap();
+        assert_eq!(array.element_type(), Some(ValueType::I64));
+
+        // Numeric values are compatible.
+        array.push(Value::F64(3.5)).unwrap();
+        assert_eq!(array.element_type(), Some(ValueType::I64));
+
+        array.clear();
+        assert_eq!(array.element_type(), None);
+
+        array.push(DynTable::new().into()).unwrap();
+        assert_eq!(array.element_type(), Some(ValueType::Table));
+    }
+
+    #[test]
+    fn truncate() {
+        let mut array: DynArray = (0..3).map(|value| value as i64).collect();
+
+        array.truncate(7);
+        assert_eq!(array.len(), 3);
+
+        array.truncate(1);
+        assert_eq!(array.len(), 1);
+        assert_eq!(array.get_i64(0).unwrap(), 0);
+
+        array.truncate(0);
+        assert!(array.is_empty());
+        assert_eq!(array.element_type(), None);
+    }
+
+    #[test]
+    fn extend_from() {
+        let mut array = DynArray::new();
+
+        array.extend_from(vec![1i64, 2]).unwrap();
+        array.extend_from(vec![Value::F64(3.5)]).unwrap();
+
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.get_i64(1).unwrap(), 2);
+        assert!(cmp_f64(array.get_f64(2).unwrap(), 3.5));
+
+        // Incompatible value - the array is restored.
+        assert_eq!(
+            array
+                .extend_from(vec![Value::I64(4), Value::Bool(true)])
+                .err()
+                .unwrap(),
+            ArrayError::IncorrectValueType(ValueType::I64)
+        );
+        assert_eq!(array.len(), 3);
+
+        // Values of an empty array must be compatible with the first value.
+        let mut array = DynArray::new();
+
+        array.extend_from(vec!["foo", "bar"]).unwrap();
+        assert_eq!(
+            array
+                .extend_from(vec![Value::String("baz".into()), Value::I64(7)])
+                .err()
+                .unwrap(),
+            ArrayError::IncorrectValueType(ValueType::String)
+        );
+        assert_eq!(array.len(), 2);
+
+        let mut array = DynArray::new();
+
+        assert_eq!(
+            array
+                .extend_from(vec![Value::Bool(true), Value::I64(7)])
+                .err()
+                .unwrap(),
+            ArrayError::IncorrectValueType(ValueType::Bool)
+        );
+        assert!(array.is_empty());
+    }
+
+    #[test]
+    fn append() {
+        let mut array: DynArray = (0..2).map(|value| value as i64).collect();
+        let mut other: DynArray = vec![2.5, 3.5].into_iter().collect();
+
+        array.append(&mut other).unwrap();
+
+        assert_eq!(array.len(), 4);
+        assert!(other.is_empty());
+        assert!(cmp_f64(array.get_f64(3).unwrap(), 3.5));
+
+        // Appending an empty array is a no-op.
+        array.append(&mut other).unwrap();
+        assert_eq!(array.len(), 4);
+
+        // Appending to an empty array.
+        let mut empty = DynArray::new();
+        empty.append(&mut array).unwrap();
+        assert_eq!(empty.len(), 4);
+        assert!(array.is_empty());
+
+        // Incompatible types - neither array is modified.
+        let mut strings: DynArray = vec!["foo"].into_iter().collect();
+
+        assert_eq!(
+            empty.append(&mut strings).err().unwrap(),
+            ArrayError::IncorrectValueType(ValueType::I64)
+        );
+        assert_eq!(empty.len(), 4);
+        assert_eq!(strings.len(), 1);
+    }
+
+    #[test]
+    fn from_iter() {
+        let array: DynArray = (0..3).map(|value| value as i64).collect();
+
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.get_i64(2).unwrap(), 2);
+
+        // Compatible numeric types may be mixed.
+        let array: DynArray = vec![Value::I64(1), Value::F64(2.5)].into_iter().collect();
+        assert_eq!(array.len(), 2);
+
+        let array: DynArray = vec!["foo", "bar"].into_iter().collect();
+        assert_eq!(array.get_string(1).unwrap(), "bar");
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_iter_mixed_types() {
+        let _: DynArray = vec![Value::I64(1), Value::Bool(true)].into_iter().collect();
+    }
+
+    #[test]
+    fn index() {
+        let array: DynArray = vec!["foo", "bar"].into_iter().collect();
+
+        assert!(matches!(&array[0], Value::String(string) if string == "foo"));
+        assert!(matches!(&array[1], Value::String(string) if string == "bar"));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+    fn index_out_of_bounds() {
+        let array: DynArray = vec![1, 2].into_iter().map(i64::from).collect();
+
+        let _ = &array[2];
+    }
+
+    #[test]
+    fn to_vec() {
+        let array: DynArray = vec![1, 2, 3].into_iter().map(i64::from).collect();
+
+        assert_eq!(array.as_i64_vec().unwrap(), vec![1, 2, 3]);
+        assert_eq!(array.as_f64_vec().unwrap(), vec![1.0, 2.0, 3.0]);
+        assert_eq!(array.to_vec::<u64>().unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            array.as_bool_vec().err().unwrap(),
+            ArrayError::IncorrectValueType(ValueType::I64)
+        );
+
+        let array: DynArray = vec!["foo", "bar"].into_iter().collect();
+
+        assert_eq!(array.as_string_vec().unwrap(), vec!["foo", "bar"]);
+        assert_eq!(array.to_vec::<&str>().unwrap(), vec!["foo", "bar"]);
+        assert_eq!(
+            array.as_i64_vec().err().unwrap(),
+            ArrayError::IncorrectValueType(ValueType::String)
+        );
+
+        let array: DynArray = vec![true, false].into_iter().collect();
+        assert_eq!(array.as_bool_vec().unwrap(), vec![true, false]);
+
+        assert!(DynArray::new().as_string_vec().unwrap().is_empty());
+    }
+
+    #[test]
+    fn retain() {
+        let mut array = DynArray::new();
+
+        for value in 0..5 {
+            array.push(Value::I64(value)).unwrap();
+        }
+
+        array.retain(|value| value.i64().unwrap() % 2 == 0);
+
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.get_i64(0).unwrap(), 0);
+        assert_eq!(array.get_i64(1).unwrap(), 2);
+        assert_eq!(array.get_i64(2).unwrap(), 4);
+
+        array.retain(|_| false);
+
+        assert!(array.is_empty());
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut array = DynArray::new();
+
+        array.push(Value::I64(1)).unwrap();
+        array.push(Value::F64(2.5)).unwrap();
+
+        // Multiply every numeric value by a factor.
+        for mut value in array.iter_mut() {
+            let new_value = match value.get() {
+                Value::I64(value) => Value::I64(value * 2),
+                Value::F64(value) => Value::F64(value * 2.0),
+                _ => unreachable!(),
+            };
+
+            value.set(new_value).unwrap();
+        }
+
+        assert_eq!(array.get_i64(0).unwrap(), 2);
+        assert!(cmp_f64(array.get_f64(1).unwrap(), 5.0));
+
+        // Numeric values are compatible.
+        array
+            .iter_mut()
+            .next()
+            .unwrap()
+            .set(Value::F64(3.5))
+            .unwrap();
+        assert!(cmp_f64(array.get_f64(0).unwrap(), 3.5));
+
+        // Incompatible values are rejected.
+        assert_eq!(
+            array
+                .iter_mut()
+                .next()
+                .unwrap()
+                .set(true.into())
+                .err()
+                .unwrap(),
+            ArrayError::IncorrectValueType(ValueType::F64)
+        );
+
+        // Single element array values may be changed to any type.
+        let mut array = DynArray::new();
+        array.push(Value::I64(7)).unwrap();
+        array.iter_mut().next().unwrap().set("foo".into()).unwrap();
+        assert_eq!(array.get_string(0).unwrap(), "foo");
+
+        // Nested arrays / tables are mutable in place.
+        let mut array = DynArray::new();
+        array.push(DynTable::new().into()).unwrap();
+
+        for mut value in array.iter_mut() {
+            assert!(!value.get_mut().table().unwrap().set(nestr!("foo"), 7));
+        }
+
+        assert_eq!(array.get_table(0).unwrap().get_i64("foo").unwrap(), 7);
+    }
+
+    #[test]
+    fn partial_eq() {
+        let mut l = DynArray::new();
+        let mut r = DynArray::new();
+
+        assert!(l == r);
+
+        l.push(7.into()).unwrap();
+        l.push(9.into()).unwrap();
+
+        assert!(l != r);
+
+        r.push(9.into()).unwrap();
+        r.push(7.into()).unwrap();
+
+        // Array equality is positional.
+        assert!(l != r);
+
+        r.clear();
+        r.push(7.into()).unwrap();
+        r.push(9.into()).unwrap();
+
+        assert!(l == r);
+
+        // `I64` and `F64` values are never equal.
+        r.set(1, 9.0.into()).unwrap();
+
+        assert!(l != r);
+
+        let mut l = DynArray::new();
+        let mut r = DynArray::new();
+
+        // Floats are compared bit-exactly.
+        l.push(f64::NAN.into()).unwrap();
+        r.push(f64::NAN.into()).unwrap();
+
+        assert!(l == r);
+
+        l.push(0.0.into()).unwrap();
+        r.push((-0.0).into()).unwrap();
+
+        assert!(l != r);
+    }
+
+    #[test]
+    fn non_empty() {
+        let mut array = DynArray::new();
+
+        assert!(array.non_empty().is_none());
+
+        array.push(7.into()).unwrap();
+
+        let non_empty = array.non_empty().unwrap();
+
+        assert_eq!(non_empty.len(), 1);
+        assert_eq!(non_empty.first().i64().unwrap(), 7);
+        assert_eq!(non_empty.last().i64().unwrap(), 7);
+
+        array.push(9.into()).unwrap();
+        array.push(11.into()).unwrap();
+
+        let non_empty = array.non_empty().unwrap();
+
+        assert_eq!(non_empty.len(), 3);
+        assert_eq!(non_empty.first().i64().unwrap(), 7);
+        assert_eq!(non_empty.last().i64().unwrap(), 11);
+        assert_eq!(non_empty.get_i64(1).unwrap(), 9);
+        assert_eq!(non_empty.array().len(), 3);
+    }
+
     #[test]
     fn DynArrayError_IndexOutOfBounds() {
         let mut array = DynArray::new();
@@ -0,0 +1,423 @@
+use crate::*;
+
+/// A fluent builder for [`configs`], e.g. for hand-written configs in tests or default configs.
+///
+/// Nested [`tables`] / [`arrays`] are built by closures which receive the [`table`] / [`array`] builders.
+///
+/// E.g. `DynConfigBuilder::new().table(nestr!("server"), |t| t.i64(nestr!("port"), 8080)).build()`.
+///
+/// [`configs`]: struct.DynConfig.html
+/// [`tables`]: struct.DynTable.html
+/// [`arrays`]: struct.DynArray.html
+/// [`table`]: struct.DynTableBuilder.html
+/// [`array`]: struct.DynArrayBuilder.html
+pub struct DynConfigBuilder(DynTableBuilder);
+
+impl Default for DynConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DynConfigBuilder {
+    /// Creates a new [`config`] builder with an empty root [`table`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`table`]: struct.DynTable.html
+    pub fn new() -> Self {
+        Self(DynTableBuilder::new())
+    }
+
+    /// Sets the [`value`] at `key` in the root [`table`], see [`DynTableBuilder::set`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`DynTableBuilder::set`]: struct.DynTableBuilder.html#method.set
+    pub fn set<K, V>(self, key: K, value: V) -> Self
+    where
+        K: AsRef<NonEmptyStr>,
+        V: Into<DynConfigValue>,
+    {
+        Self(self.0.set(key, value))
+    }
+
+    /// Sets the `bool` value at `key` in the root [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn bool<K: AsRef<NonEmptyStr>>(self, key: K, value: bool) -> Self {
+        Self(self.0.bool(key, value))
+    }
+
+    /// Sets the `i64` value at `key` in the root [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn i64<K: AsRef<NonEmptyStr>>(self, key: K, value: i64) -> Self {
+        Self(self.0.i64(key, value))
+    }
+
+    /// Sets the `u64` value at `key` in the root [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn u64<K: AsRef<NonEmptyStr>>(self, key: K, value: u64) -> Self {
+        Self(self.0.u64(key, value))
+    }
+
+    /// Sets the `f64` value at `key` in the root [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn f64<K: AsRef<NonEmptyStr>>(self, key: K, value: f64) -> Self {
+        Self(self.0.f64(key, value))
+    }
+
+    /// Sets the string value at `key` in the root [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn string<K: AsRef<NonEmptyStr>, S: Into<String>>(self, key: K, value: S) -> Self {
+        Self(self.0.string(key, value))
+    }
+
+    /// Sets the [`array`] built by `f` at `key` in the root [`table`].
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`table`]: struct.DynTable.html
+    pub fn array<K, F>(self, key: K, f: F) -> Self
+    where
+        K: AsRef<NonEmptyStr>,
+        F: FnOnce(DynArrayBuilder) -> DynArrayBuilder,
+    {
+        Self(self.0.array(key, f))
+    }
+
+    /// Sets the [`table`] built by `f` at `key` in the root [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn table<K, F>(self, key: K, f: F) -> Self
+    where
+        K: AsRef<NonEmptyStr>,
+        F: FnOnce(DynTableBuilder) -> DynTableBuilder,
+    {
+        Self(self.0.table(key, f))
+    }
+
+    /// Returns the built [`config`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    pub fn build(self) -> DynConfig {
+        let mut config = DynConfig::new();
+        *config.root_mut() = self.0.build();
+        config
+    }
+}
+
+/// A fluent builder for [`tables`], used by the [`config builder`].
+///
+/// Setting a value at a `key` which was already set replaces the previous value.
+///
+/// [`tables`]: struct.DynTable.html
+/// [`config builder`]: struct.DynConfigBuilder.html
+pub struct DynTableBuilder(DynTable);
+
+impl Default for DynTableBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DynTableBuilder {
+    /// Creates a new builder for an empty [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn new() -> Self {
+        Self(DynTable::new())
+    }
+
+    /// Sets the [`value`] at `key` in the [`table`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    pub fn set<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: AsRef<NonEmptyStr>,
+        V: Into<DynConfigValue>,
+    {
+        self.0.set(key, value);
+        self
+    }
+
+    /// Sets the `bool` value at `key` in the [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn bool<K: AsRef<NonEmptyStr>>(self, key: K, value: bool) -> Self {
+        self.set(key, Value::Bool(value))
+    }
+
+    /// Sets the `i64` value at `key` in the [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn i64<K: AsRef<NonEmptyStr>>(self, key: K, value: i64) -> Self {
+        self.set(key, Value::I64(value))
+    }
+
+    /// Sets the `u64` value at `key` in the [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn u64<K: AsRef<NonEmptyStr>>(self, key: K, value: u64) -> Self {
+        self.set(key, Value::U64(value))
+    }
+
+    /// Sets the `f64` value at `key` in the [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn f64<K: AsRef<NonEmptyStr>>(self, key: K, value: f64) -> Self {
+        self.set(key, Value::F64(value))
+    }
+
+    /// Sets the string value at `key` in the [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn string<K: AsRef<NonEmptyStr>, S: Into<String>>(self, key: K, value: S) -> Self {
+        self.set(key, Value::String(value.into()))
+    }
+
+    /// Sets the [`array`] built by `f` at `key` in the [`table`].
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`table`]: struct.DynTable.html
+    pub fn array<K, F>(self, key: K, f: F) -> Self
+    where
+        K: AsRef<NonEmptyStr>,
+        F: FnOnce(DynArrayBuilder) -> DynArrayBuilder,
+    {
+        self.set(key, Value::Array(f(DynArrayBuilder::new()).build()))
+    }
+
+    /// Sets the [`table`] built by `f` at `key` in the [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn table<K, F>(self, key: K, f: F) -> Self
+    where
+        K: AsRef<NonEmptyStr>,
+        F: FnOnce(DynTableBuilder) -> DynTableBuilder,
+    {
+        self.set(key, Value::Table(f(DynTableBuilder::new()).build()))
+    }
+
+    /// Returns the built [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn build(self) -> DynTable {
+        self.0
+    }
+}
+
+/// A fluent builder for [`arrays`], used by the [`config builder`].
+///
+/// # Panics
+///
+/// Pushing a [`value`] of a [`type`] incompatible with the [`array`]'s [`element type`] panics,
+/// like collecting an [`array`] from an iterator.
+/// Use [`DynArray::push`] to handle the [`error`] instead.
+///
+/// [`arrays`]: struct.DynArray.html
+/// [`array`]: struct.DynArray.html
+/// [`config builder`]: struct.DynConfigBuilder.html
+/// [`value`]: type.DynConfigValue.html
+/// [`type`]: enum.ValueType.html
+/// [`element type`]: struct.DynArray.html#method.element_type
+/// [`DynArray::push`]: struct.DynArray.html#method.push
+/// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+pub struct DynArrayBuilder(DynArray);
+
+impl Default for DynArrayBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DynArrayBuilder {
+    /// Creates a new builder for an empty [`array`].
+    ///
+    /// [`array`]: struct.DynArray.html
+    pub fn new() -> Self {
+        Self(DynArray::new())
+    }
+
+    /// Pushes the [`value`] to the back of the [`array`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`value`] is of invalid type.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`array`]: struct.DynArray.html
+    pub fn push<V: Into<DynConfigValue>>(mut self, value: V) -> Self {
+        if let Err(err) = self.0.push(value.into()) {
+            panic!(
+                "failed to push a value to a dyn config array at index {}: {}",
+                self.0.len(),
+                err
+            );
+        }
+
+        self
+    }
+
+    /// Pushes the `bool` value to the back of the [`array`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`array`] is not empty and is not a `bool` array.
+    ///
+    /// [`array`]: struct.DynArray.html
+    pub fn push_bool(self, value: bool) -> Self {
+        self.push(Value::Bool(value))
+    }
+
+    /// Pushes the `i64` value to the back of the [`array`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`array`] is not empty and is not a numeric array.
+    ///
+    /// [`array`]: struct.DynArray.html
+    pub fn push_i64(self, value: i64) -> Self {
+        self.push(Value::I64(value))
+    }
+
+    /// Pushes the `u64` value to the back of the [`array`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`array`] is not empty and is not a numeric array.
+    ///
+    /// [`array`]: struct.DynArray.html
+    pub fn push_u64(self, value: u64) -> Self {
+        self.push(Value::U64(value))
+    }
+
+    /// Pushes the `f64` value to the back of the [`array`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`array`] is not empty and is not a numeric array.
+    ///
+    /// [`array`]: struct.DynArray.html
+    pub fn push_f64(self, value: f64) -> Self {
+        self.push(Value::F64(value))
+    }
+
+    /// Pushes the string value to the back of the [`array`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`array`] is not empty and is not a string array.
+    ///
+    /// [`array`]: struct.DynArray.html
+    pub fn push_string<S: Into<String>>(self, value: S) -> Self {
+        self.push(Value::String(value.into()))
+    }
+
+    /// Pushes the [`array`] built by `f` to the back of the [`array`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`array`] is not empty and is not an array of [`arrays`].
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`arrays`]: struct.DynArray.html
+    pub fn push_array<F: FnOnce(DynArrayBuilder) -> DynArrayBuilder>(self, f: F) -> Self {
+        self.push(Value::Array(f(DynArrayBuilder::new()).build()))
+    }
+
+    /// Pushes the [`table`] built by `f` to the back of the [`array`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`array`] is not empty and is not an array of [`tables`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    pub fn push_table<F: FnOnce(DynTableBuilder) -> DynTableBuilder>(self, f: F) -> Self {
+        self.push(Value::Table(f(DynTableBuilder::new()).build()))
+    }
+
+    /// Returns the built [`array`].
+    ///
+    /// [`array`]: struct.DynArray.html
+    pub fn build(self) -> DynArray {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn builder() {
+        let config = DynConfigBuilder::new()
+            .bool(nestr!("debug"), true)
+            .table(nestr!("server"), |t| {
+                t.i64(nestr!("port"), 8080)
+                    .string(nestr!("host"), "localhost")
+                    .table(nestr!("limits"), |t| t.f64(nestr!("ratio"), 0.5))
+            })
+            .array(nestr!("tags"), |a| a.push_string("x").push_string("y"))
+            .array(nestr!("servers"), |a| {
+                a.push_table(|t| t.u64(nestr!("port"), 80))
+                    .push_table(|t| t.u64(nestr!("port"), 443))
+            })
+            .set(nestr!("name"), "foo")
+            .build();
+
+        let mut expected = DynConfig::new();
+        let root = expected.root_mut();
+
+        root.set(nestr!("debug"), true);
+        root.set_dotted_path("server.port", Some(Value::I64(8080)))
+            .unwrap();
+        root.set_dotted_path("server.host", Some(Value::String("localhost".into())))
+            .unwrap();
+        root.set_dotted_path("server.limits.ratio", Some(Value::F64(0.5)))
+            .unwrap();
+        root.set(
+            nestr!("tags"),
+            vec!["x", "y"].into_iter().collect::<DynArray>(),
+        );
+
+        let mut servers = DynArray::new();
+
+        for port in [80, 443].iter() {
+            let mut server = DynTable::new();
+            server.set(nestr!("port"), Value::U64(*port));
+            servers.push(Value::Table(server)).unwrap();
+        }
+
+        root.set(nestr!("servers"), servers);
+        root.set(nestr!("name"), "foo");
+
+        assert!(config == expected);
+
+        // Latest value wins.
+        let config = DynConfigBuilder::new()
+            .i64(nestr!("a"), 7)
+            .string(nestr!("a"), "foo")
+            .build();
+
+        assert_eq!(config.root().get_string("a").unwrap(), "foo");
+
+        // Compatible numeric types may be mixed.
+        let array = DynArrayBuilder::new().push_i64(7).push_f64(3.5).build();
+
+        assert_eq!(array.len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn builder_mixed_array() {
+        let _ = DynConfigBuilder::new()
+            .array(nestr!("tags"), |a| a.push_string("x").push_bool(true))
+            .build();
+    }
+}
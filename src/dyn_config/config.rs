@@ -1,4 +1,8 @@
 use {
+    super::{
+        table::{push_dotted_path_key, split_dotted_path_escaped},
+        visitor::visit_table,
+    },
     crate::{util::DisplayLua, *},
     std::{
         fmt::{Display, Formatter, Write},
@@ -6,9 +10,17 @@ use {
     },
 };
 
+#[cfg(any(feature = "bin", feature = "ini"))]
+use std::path::Path;
+
 /// Represents a mutable config with a root hashmap [`table`].
 ///
+/// Cloning the [`config`] deep-copies the whole tree,
+/// so the clone may be used e.g. as a snapshot to roll back mutations of the original.
+///
 /// [`table`]: struct.DynTable.html
+/// [`config`]: struct.DynConfig.html
+#[derive(Clone, PartialEq)]
 pub struct DynConfig(DynTable);
 
 impl DynConfig {
@@ -37,38 +49,388 @@ impl DynConfig {
         &mut self.0
     }
 
-    /// Tries to serialize this [`config`] to a Lua script string.
+    /// Returns the immutable reference to the nested [`table`] in the [`config`]
+    /// at the dot-separated `prefix` (e.g. `"components.renderer"`), without copying it.
+    ///
+    /// Returns `None` if the `prefix` is empty, or if the [`config`] does not contain a [`table`] at the `prefix`.
+    ///
+    /// Dots (`'.'`) and backslashes in keys must be escaped as in [`get_val_dotted_path`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`get_val_dotted_path`]: struct.DynTable.html#method.get_val_dotted_path
+    pub fn subtree(&self, prefix: &str) -> Option<&DynTable> {
+        self.root().get_val_dotted_path(prefix).ok()?.table()
+    }
+
+    /// Deep-copies the nested [`table`] in the [`config`] at the dot-separated `prefix`
+    /// (e.g. `"components.renderer"`) to a new standalone [`config`].
+    ///
+    /// Returns `None` if the `prefix` is empty, or if the [`config`] does not contain a [`table`] at the `prefix`.
+    ///
+    /// See [`subtree`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`subtree`]: #method.subtree
+    pub fn extract(&self, prefix: &str) -> Option<DynConfig> {
+        self.subtree(prefix).cloned().map(Self)
+    }
+
+    /// Traverses this [`config`] and the `other` [`config`] in lockstep,
+    /// calling the `visitor` for each element present in either of them
+    /// with its full path and the [`values`] at that path in this (left) and the `other` (right) [`config`], if any.
+    ///
+    /// Elements are visited depth-first: a [`table`] / [`array`] element is visited before its nested elements.
+    /// [`table`] elements are visited in alphabetical key order, [`array`] elements - in index order.
+    /// Nested elements of a [`table`] / [`array`] are visited even if the other [`config`]
+    /// does not contain a [`table`] / [`array`] at that path.
+    /// The root [`table`] itself is not visited.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`values`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    pub fn walk_paired<'c, F>(&'c self, other: &'c DynConfig, mut visitor: F)
+    where
+        F: FnMut(&ConfigPath, Option<DynConfigValueRef<'c>>, Option<DynConfigValueRef<'c>>),
+    {
+        let mut path = ConfigPath::new();
+
+        walk_paired_tables(
+            Some(self.root()),
+            Some(other.root()),
+            &mut path,
+            &mut visitor,
+        );
+    }
+
+    /// Traverses this [`config`] depth-first, calling the `visitor` for each element with its full path.
+    ///
+    /// [`table`]s are entered before and left after their elements are visited, starting with the root [`table`] (with an empty path);
+    /// same for [`array`]s. Other values are visited via [`visit_value`].
+    /// [`table`] elements are visited in alphabetical key order, [`array`] elements - in index order.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`table`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    /// [`visit_value`]: trait.DynConfigVisitor.html#method.visit_value
+    pub fn visit<'c, V: DynConfigVisitor<'c>>(&'c self, visitor: &mut V) {
+        visit_table(self.root(), &mut ConfigPath::new(), visitor);
+    }
+
+    /// Recursively merges the labeled `sources` (in order) into a new [`config`],
+    /// recording which source each leaf (non-[`table`]) value of the resulting [`config`] came from.
+    ///
+    /// Each source is merged into the result of merging the previous sources as by [`merge_opts`].
+    /// The source of a leaf value is the last source which contains a value at its path
+    /// (e.g., with the [`Append`] [`array`] strategy, the last source appended to the [`array`]).
+    ///
+    /// Returns the merged [`config`] and the [`provenance`] map from leaf value paths to source labels,
+    /// or the first encountered merge [`error`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`table`]: struct.DynTable.html
+    /// [`merge_opts`]: struct.DynTable.html#method.merge_opts
+    /// [`Append`]: enum.MergeArrayStrategy.html#variant.Append
+    /// [`array`]: struct.DynArray.html
+    /// [`provenance`]: struct.MergeProvenance.html
+    /// [`error`]: enum.MergeError.html
+    pub fn merge_with_provenance<'c, L, I>(
+        sources: I,
+        options: MergeOptions,
+    ) -> Result<(Self, MergeProvenance), MergeError>
+    where
+        L: Into<String>,
+        I: IntoIterator<Item = (L, &'c DynConfig)>,
+    {
+        let mut config = DynConfig::new();
+        let mut provenance = MergeProvenance::new();
+
+        for (label, source) in sources {
+            config.root_mut().merge_opts(source.root(), options)?;
+            provenance.add_source(label.into(), source.root());
+        }
+
+        Ok((config, provenance))
+    }
+
+    /// Expands environment variable references in all string values of this [`config`],
+    /// using default [`options`].
+    ///
+    /// See [`expand_env_opts`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.EnvExpandOptions.html
+    /// [`expand_env_opts`]: #method.expand_env_opts
+    pub fn expand_env(&mut self) -> Result<(), EnvExpandError> {
+        self.expand_env_opts(Default::default())
+    }
+
+    /// Expands environment variable references in all string values of this [`config`],
+    /// in nested [`tables`] and [`arrays`].
+    ///
+    /// `"${NAME}"` and `"$NAME"` are replaced with the value of the environment variable `NAME`.
+    /// Variable names must consist of ASCII letters, digits and underscores, and must not start with a digit.
+    /// `"$$"` is replaced with a literal `'$'` (so `"$${NAME}"` results in a literal `"${NAME}"`);
+    /// `'$'` not followed by a variable name is kept as is.
+    ///
+    /// References to unset variables are handled according to the `options`.
+    /// This [`config`] is not modified if an [`error`] is returned.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`error`]: enum.EnvExpandError.html
+    pub fn expand_env_opts(&mut self, options: EnvExpandOptions) -> Result<(), EnvExpandError> {
+        // Expand a copy so that the config is not modified on error.
+        let mut root = self.0.clone();
+
+        table_expand_env(&mut root, &mut ConfigPath::new(), options)?;
+
+        self.0 = root;
+
+        Ok(())
+    }
+
+    /// Parses the `raw` string to a [`value`] and sets it at the dot-separated `path`
+    /// (e.g. `"server.network.port"`), e.g. to apply a `--set server.network.port=8080` command line override.
+    ///
+    /// `raw` values are interpreted like unquoted `.ini` values with default [`.ini parser`] options:
+    /// `true` / `false` (but not e.g. `True` or `yes`) are booleans; decimal, hexadecimal (`0x`) and octal (`0o`)
+    /// integers with an optional sign which fit in an `i64` are integers; other valid floats (e.g. `3.14`, `1e3`, `inf`)
+    /// are floats; everything else, including the empty string, is a string.
+    /// Enclose the `raw` value in string quotes (`'"'` / `'\''`) to always treat it as a string, e.g. `"\"true\""`
+    /// is the string `true`; the quotes are removed, escape sequences are not processed.
+    ///
+    /// Missing intermediate [`tables`] are created and an existing [`value`] at the `path` is overwritten,
+    /// as per [`set_dotted_path`].
+    ///
+    /// Returns an [`error`] if the `raw` value is not properly quoted, or if the [`value`] could not be set at the `path`.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`set_dotted_path`]: struct.DynTable.html#method.set_dotted_path
+    /// [`error`]: enum.OverrideError.html
+    pub fn apply_override(&mut self, path: &str, raw: &str) -> Result<(), OverrideError> {
+        let value = parse_override_value(raw)?;
+
+        self.root_mut()
+            .set_dotted_path(path, Some(value))
+            .map_err(OverrideError::Path)
+    }
+
+    /// Flattens this [`config`] to a list of environment-variable-style (`name`, `value`) pairs,
+    /// e.g. `("PREFIX_DB_HOST", "localhost")`.
+    ///
+    /// Variable names are built by joining the `prefix` (if not empty) and the uppercased
+    /// nested [`table`] keys / [`array`] indices on the path to each value with the `separator`.
+    /// Boolean, number and string values are converted to strings.
+    /// Empty [`tables`] / [`arrays`] produce no variables.
+    ///
+    /// Variables are returned in alphabetical key order / array index order, depth-first.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`table`]: struct.DynTable.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    /// [`arrays`]: struct.DynArray.html
+    pub fn to_env_vars(&self, prefix: &str, separator: &str) -> Vec<(String, String)> {
+        let mut result = Vec::new();
+        let mut name = prefix.to_uppercase();
+
+        table_to_env_vars(self.root(), &mut name, separator, &mut result);
+
+        result
+    }
+
+    /// Flattens this [`config`] to a list of (`path`, `value`) pairs for each non-[`table`] / [`array`] value,
+    /// e.g. `("server.ports.0", 8080)`.
+    ///
+    /// Paths are built by joining the nested [`table`] keys / [`array`] indices on the path to each value with dots (`'.'`).
+    /// Dots (`'.'`) and backslashes (`'\\'`) in keys are escaped with a backslash (`"\."`, `"\\"`),
+    /// as expected by [`get_val_dotted_path`].
+    /// Numeric [`table`] keys (i.e. valid `u32` numbers) have their first digit escaped (`"\0"`)
+    /// to tell them from [`array`] indices in [`from_flat`].
+    /// Empty [`tables`] / [`arrays`] produce no values.
+    ///
+    /// Values are returned in alphabetical key order / array index order, depth-first.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`table`]: struct.DynTable.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`get_val_dotted_path`]: struct.DynTable.html#method.get_val_dotted_path
+    /// [`from_flat`]: #method.from_flat
+    pub fn flatten(&self) -> Vec<(String, DynConfigValueRef<'_>)> {
+        let mut result = Vec::new();
+        let mut path = String::new();
+
+        table_to_flat(self.root(), &mut path, &mut result);
+
+        result
+    }
+
+    /// Tries to create a new [`config`] from the list of (`path`, `value`) pairs,
+    /// e.g. returned by [`flatten`], rebuilding the nested [`tables`] / [`arrays`].
+    ///
+    /// Paths are split at dots (`'.'`); dots and backslashes in keys must be escaped as in [`get_val_dotted_path`].
+    /// Keys which are valid `u32` numbers (other than those in the root [`table`]) are [`array`] indices,
+    /// unless their first digit is escaped (`"\0"`, as done by [`flatten`]); all other keys are [`table`] keys.
+    /// Missing [`tables`] / [`arrays`] are created.
+    /// Pairs are applied in [`array`] index order, so [`array`] elements may be listed in any order,
+    /// but may not be missing (i.e. [`array`] indices must be contiguous and start at `0`).
+    /// Later values at the same path overwrite the earlier ones.
+    ///
+    /// Returns an [`error`] if a key is empty, if the same path is used for values of different types
+    /// (e.g. both as a [`table`] and an [`array`]), if an [`array`] index is out of bounds,
+    /// or if [`array`] values are of incompatible types.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`flatten`]: #method.flatten
+    /// [`table`]: struct.DynTable.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`get_val_dotted_path`]: struct.DynTable.html#method.get_val_dotted_path
+    /// [`error`]: enum.UnflattenError.html
+    pub fn from_flat<I, P, V>(pairs: I) -> Result<Self, UnflattenError>
+    where
+        I: IntoIterator<Item = (P, V)>,
+        P: AsRef<str>,
+        V: Into<DynConfigValue>,
+    {
+        let mut pairs = pairs
+            .into_iter()
+            .map(|(path, value)| (split_flat_path(path.as_ref()), value.into()))
+            .collect::<Vec<(Vec<FlatKey>, DynConfigValue)>>();
+
+        // Apply the pairs in array index order, so that the arrays are filled in order.
+        // The sort is stable, so later values at the same path still overwrite the earlier ones.
+        pairs.sort_by_cached_key(|(keys, _)| {
+            keys.iter().filter_map(|key| key.index).collect::<Vec<_>>()
+        });
+
+        let mut config = DynConfig::new();
+
+        for (keys, value) in pairs {
+            flat_to_table(config.root_mut(), &keys, value, &mut ConfigPath::new())?;
+        }
+
+        Ok(config)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string using default [`options`].
     ///
     /// NOTE: you may also call `to_string` via the [`config`]'s `Display` implementation.
     ///
+    /// NOTE: both empty [`table`]s and empty [`array`]s are serialized as `{}`,
+    /// which is read back as an empty [`table`] by [`from_lua`].
+    /// Use [`to_lua_config`] or the [`binary config`] to preserve the distinction.
+    ///
     /// [`config`]: struct.DynConfig.html
-    pub fn to_lua_string(&self) -> Result<String, std::fmt::Error> {
+    /// [`options`]: struct.LuaDisplayOptions.html
+    /// [`table`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    /// [`from_lua`]: #method.from_lua
+    /// [`to_lua_config`]: #method.to_lua_config
+    /// [`binary config`]: #method.to_bin_config
+    pub fn to_lua_string(&self) -> Result<String, ToLuaStringError> {
+        self.to_lua_string_opts(Default::default())
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string to the writer `w` using default [`options`].
+    ///
+    /// NOTE: you may also use the [`config`]'s `Display` implementation.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.LuaDisplayOptions.html
+    pub fn fmt_lua<W: Write>(&self, w: &mut W) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string using provided [`options`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.LuaDisplayOptions.html
+    pub fn to_lua_string_opts(
+        &self,
+        options: LuaDisplayOptions,
+    ) -> Result<String, ToLuaStringError> {
         let mut result = String::new();
 
-        self.fmt_lua(&mut result)?;
+        self.fmt_lua_opts(options, &mut result)?;
 
         result.shrink_to_fit();
 
         Ok(result)
     }
 
-    /// Tries to serialize this [`config`] to a Lua script string to the writer `w`.
+    /// Tries to serialize this [`config`] to a Lua script string to the writer `w` using provided [`options`].
     ///
-    /// NOTE: you may also use the [`config`]'s `Display` implementation.
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.LuaDisplayOptions.html
+    pub fn fmt_lua_opts<W: Write>(
+        &self,
+        options: LuaDisplayOptions,
+        w: &mut W,
+    ) -> Result<(), ToLuaStringError> {
+        self.root()
+            .fmt_lua(w, 0, options)
+            .map_err(ToLuaStringError::reverse)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string and stream it to the I/O writer `w`
+    /// (e.g. a buffered file) using default [`options`], without buffering the whole script in memory.
+    ///
+    /// Serialization errors are returned as I/O errors of kind [`InvalidData`],
+    /// wrapping the original [`ToLuaStringError`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.LuaDisplayOptions.html
+    /// [`InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+    /// [`ToLuaStringError`]: enum.ToLuaStringError.html
+    pub fn write_lua<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        self.write_lua_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to a Lua script string and stream it to the I/O writer `w`
+    /// (e.g. a buffered file) using provided [`options`], without buffering the whole script in memory.
+    ///
+    /// See [`write_lua`].
     ///
     /// [`config`]: struct.DynConfig.html
-    pub fn fmt_lua<W: Write>(&self, w: &mut W) -> Result<(), std::fmt::Error> {
-        self.root().fmt_lua(w, 0)
+    /// [`options`]: struct.LuaDisplayOptions.html
+    /// [`write_lua`]: #method.write_lua
+    pub fn write_lua_opts<W: std::io::Write>(
+        &self,
+        options: LuaDisplayOptions,
+        w: &mut W,
+    ) -> std::io::Result<()> {
+        let mut w = IoWriter::new(w);
+
+        self.fmt_lua_opts(options, &mut w).map_err(|err| match err {
+            ToLuaStringError::WriteError => io_writer_error(&mut w),
+            err => std::io::Error::new(std::io::ErrorKind::InvalidData, err),
+        })
     }
 
     /// Tries to serialize this [`config`] to a [`binary config`].
     ///
+    /// An empty [`config`] is serialized to a minimal [`binary config`] data blob
+    /// which is read back as an empty [`config`].
+    /// Empty tables and empty arrays are read back as such.
+    ///
+    /// Serialization is deterministic - table keys are written in alphabetical order,
+    /// so equal [`config`]s are serialized to identical data blobs.
+    /// See also [`BinConfig::structurally_eq`](struct.BinConfig.html#method.structurally_eq).
+    ///
     /// [`config`]: struct.DynConfig.html
     /// [`binary config`]: struct.BinConfig.html
     #[cfg(feature = "bin")]
     pub fn to_bin_config(&self) -> Result<Box<[u8]>, BinConfigWriterError> {
-        use BinConfigWriterError::*;
-
         let root = self.root();
 
         if let Some(root_len) = NonZeroU32::new(root.len()) {
@@ -78,26 +440,255 @@ impl DynConfig {
 
             writer.finish()
 
-        // The root table is empty - nothing to do.
+        // The root table is empty - write just the header.
+        } else {
+            BinConfigWriter::empty()
+        }
+    }
+
+    /// Tries to serialize this [`config`] to a [`binary config`] and write it to the writer `w`.
+    ///
+    /// NOTE: the [`binary config`] data blob is not streamed section by section -
+    /// the [`writer`] must back-patch the string offsets once the string section size is known,
+    /// so the whole data blob is built in memory (as by [`to_bin_config`]) and then written to `w` in one call.
+    /// Peak memory use is thus the same as for [`to_bin_config`], but no data blob is returned to the caller.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`binary config`]: struct.BinConfig.html
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`to_bin_config`]: #method.to_bin_config
+    #[cfg(feature = "bin")]
+    pub fn write_bin_config<W: std::io::Write>(
+        &self,
+        w: &mut W,
+    ) -> Result<(), BinConfigWriterError> {
+        let data = self.to_bin_config()?;
+
+        w.write_all(&data)?;
+
+        Ok(())
+    }
+
+    /// Returns the total number of values in the [`config`], including the root [`table`]
+    /// and all nested [`arrays`] / [`tables`] and their elements.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`table`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    /// [`tables`]: struct.DynTable.html
+    pub fn node_count(&self) -> usize {
+        1 + table_node_count(self.root())
+    }
+
+    /// Returns the estimated size in bytes of the [`binary config`] data blob
+    /// which would be produced by [`to_bin_config`], without actually serializing the [`config`].
+    ///
+    /// The estimate is an upper bound - it does not account for the deduplication of repeated strings
+    /// (table keys and string values), and is exact if all strings in the [`config`] are unique.
+    ///
+    /// [`binary config`]: struct.BinConfig.html
+    /// [`to_bin_config`]: #method.to_bin_config
+    /// [`config`]: struct.DynConfig.html
+    #[cfg(feature = "bin")]
+    pub fn estimated_bin_size(&self) -> usize {
+        let root = self.root();
+
+        BIN_CONFIG_HEADER_SIZE + root.len() as usize * BIN_CONFIG_VALUE_SIZE + table_bin_size(root)
+    }
+
+    /// Checks that all float values in the [`config`] are finite (i.e. not NaN or infinite).
+    ///
+    /// Non-finite floats are accepted when setting / pushing values (and are supported by the `.lua` and YAML
+    /// serializers, and by JSON depending on the [`options`]), but are not representable in some formats / consumers;
+    /// this may be used to reject them up front.
+    ///
+    /// Returns the full paths of all non-finite float values, if any,
+    /// with [`table`] elements in alphabetical key order and [`array`] elements in index order.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: enum.JSONNonFiniteFloats.html
+    /// [`table`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    pub fn validate_finite(&self) -> Result<(), Vec<ConfigPath>> {
+        struct NonFiniteFloats(Vec<ConfigPath>);
+
+        impl<'c> DynConfigVisitor<'c> for NonFiniteFloats {
+            fn visit_value(&mut self, path: &ConfigPath, value: DynConfigValueRef<'c>) {
+                if let Value::F64(value) = value {
+                    if !value.is_finite() {
+                        self.0.push(path.clone());
+                    }
+                }
+            }
+        }
+
+        let mut visitor = NonFiniteFloats(Vec::new());
+        self.visit(&mut visitor);
+
+        if visitor.0.is_empty() {
+            Ok(())
         } else {
-            Err(EmptyRootTable)
+            Err(visitor.0)
         }
     }
 
+    /// Tries to create a new [`config`] from the `data` binary blob,
+    /// e.g. returned by [`to_bin_config`] or the binary config [`writer`].
+    ///
+    /// Validates the binary config `data` blob and returns an [`error`]
+    /// if the `data` is not a valid binary config data blob.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`to_bin_config`]: #method.to_bin_config
+    /// [`writer`]: struct.BinConfigWriter.html
+    /// [`error`]: enum.BinConfigError.html
+    #[cfg(feature = "bin")]
+    pub fn from_bin_config(data: &[u8]) -> Result<Self, BinConfigError> {
+        Ok(BinConfig::new(data.into())?.to_dyn_config())
+    }
+
+    /// Tries to create a new [`config`] from the `data` binary blob, like [`from_bin_config`],
+    /// additionally validating the `data` against the limits specified by the [`options`]
+    /// before copying it.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`from_bin_config`]: #method.from_bin_config
+    /// [`options`]: struct.BinConfigReadOptions.html
+    #[cfg(feature = "bin")]
+    pub fn from_bin_config_opts(
+        data: &[u8],
+        options: BinConfigReadOptions,
+    ) -> Result<Self, BinConfigError> {
+        BinConfigRef::new_opts(data, options)?;
+
+        // Safe to call - the data was validated.
+        Ok(unsafe { BinConfig::new_unchecked(data.into()) }.to_dyn_config())
+    }
+
+    /// Tries to create a new [`config`] from the binary config file at `path`.
+    ///
+    /// Reads the file and validates its contents as per [`from_bin_config`].
+    /// The returned [`error`] contains the `path`.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`from_bin_config`]: #method.from_bin_config
+    /// [`error`]: struct.FileError.html
+    #[cfg(feature = "bin")]
+    pub fn from_bin_file<P: AsRef<Path>>(path: P) -> Result<Self, FileError<BinConfigError>> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(|err| FileError::io(path, err))?;
+
+        Ok(BinConfig::new(data.into_boxed_slice())
+            .map_err(|err| FileError::config(path, err))?
+            .to_dyn_config())
+    }
+
+    /// Tries to create a new [`config`] from the Lua config `source` script using default [`options`].
+    ///
+    /// The script is executed in a new temporary [`Lua state`] - see [`LuaConfig::from_script`],
+    /// then the resulting [`Lua config`] is deep-copied to the [`config`].
+    ///
+    /// NOTE: Lua does not distinguish between empty tables and empty arrays,
+    /// so empty Lua tables (`{}`) are always read as empty [`table`]s.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.LuaConfigOptions.html
+    /// [`table`]: struct.DynTable.html
+    /// [`Lua state`]: https://docs.rs/rlua/*/rlua/struct.Lua.html
+    /// [`LuaConfig::from_script`]: struct.LuaConfig.html#method.from_script
+    /// [`Lua config`]: struct.LuaConfig.html
+    #[cfg(feature = "lua")]
+    pub fn from_lua(source: &str) -> Result<Self, LuaConfigError> {
+        Self::from_lua_opts(source, Default::default())
+    }
+
+    /// Tries to create a new [`config`] from the Lua config `source` script using provided [`options`].
+    ///
+    /// See [`from_lua`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.LuaConfigOptions.html
+    /// [`from_lua`]: #method.from_lua
+    #[cfg(feature = "lua")]
+    pub fn from_lua_opts(source: &str, options: LuaConfigOptions) -> Result<Self, LuaConfigError> {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            LuaConfig::from_script_opts(lua, source, options).map(|config| config.to_dyn_config())
+        })
+    }
+
+    /// Creates a new [`Lua config`] within the [`Lua context`]
+    /// by deep-copying this [`config`] to a new root [`Lua table`].
+    ///
+    /// [`Lua config`]: struct.LuaConfig.html
+    /// [`Lua context`]: https://docs.rs/rlua/*/rlua/struct.Context.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`Lua table`]: struct.LuaTable.html
+    #[cfg(feature = "lua")]
+    pub fn to_lua_config<'lua>(&self, lua: rlua::Context<'lua>) -> LuaConfig<'lua> {
+        let config = LuaConfig::new(lua);
+
+        table_to_lua_table(lua, self.root(), &mut config.root());
+
+        config
+    }
+
     /// Creates a new [`config`] from the [`.ini parser`].
     ///
+    /// Use the [`.ini builder`] directly to validate / transform the values during parsing.
+    ///
     /// [`config`]: struct.DynConfig.html
     /// [`.ini parser`]: struct.IniParser.html
+    /// [`.ini builder`]: struct.DynIniBuilder.html
     #[cfg(feature = "ini")]
     pub fn from_ini(parser: IniParser) -> Result<Self, IniError> {
-        let mut config = DynConfigIniConfig::new();
+        let mut config = DynIniBuilder::new();
         parser.parse(&mut config)?;
         Ok(config.into_inner())
     }
 
+    /// Creates a new [`config`] from the `.ini` file at `path`,
+    /// parsed by the [`.ini parser`] with default options.
+    ///
+    /// The returned [`error`] contains the `path`.
+    /// Read the file yourself and use [`from_ini`] to configure the [`.ini parser`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`error`]: struct.FileError.html
+    /// [`from_ini`]: #method.from_ini
+    #[cfg(feature = "ini")]
+    pub fn from_ini_file<P: AsRef<Path>>(path: P) -> Result<Self, FileError<IniError>> {
+        let path = path.as_ref();
+        let string = std::fs::read_to_string(path).map_err(|err| FileError::io(path, err))?;
+
+        Self::from_ini(IniParser::new(&string)).map_err(|err| FileError::config(path, err))
+    }
+
+    /// Creates a new [`lazy config`] from the [`.ini parser`].
+    ///
+    /// Only the root keys and the section headers are parsed immediately;
+    /// the contents of each (top-level) section are parsed when the section is first accessed
+    /// via [`LazyDynConfig::section`], so errors in the section contents are only reported then.
+    ///
+    /// [`lazy config`]: struct.LazyDynConfig.html
+    /// [`.ini parser`]: struct.IniParser.html
+    /// [`LazyDynConfig::section`]: struct.LazyDynConfig.html#method.section
+    #[cfg(feature = "ini")]
+    pub fn from_ini_lazy<'s>(parser: IniParser<'s>) -> Result<LazyDynConfig<'s>, IniError> {
+        LazyDynConfig::new(parser)
+    }
+
     /// Tries to serialize this [`config`] to an `.ini` string.
     ///
+    /// Empty tables are serialized as empty sections and empty arrays (if [`allowed`]) as `key = []`,
+    /// so both are read back as such by [`from_ini`] (with arrays enabled in the [`.ini parser`]).
+    ///
     /// [`config`]: struct.DynConfig.html
+    /// [`allowed`]: struct.ToIniStringOptions.html#structfield.arrays
+    /// [`from_ini`]: #method.from_ini
+    /// [`.ini parser`]: struct.IniParser.html
     #[cfg(feature = "ini")]
     pub fn to_ini_string(&self) -> Result<String, ToIniStringError> {
         self.to_ini_string_opts(Default::default())
@@ -144,550 +735,3841 @@ impl DynConfig {
 
         self.root().fmt_ini(w, 0, false, &mut path, options)
     }
-}
 
-impl Display for DynConfig {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.root().fmt_lua(f, 0)
+    /// Tries to serialize this [`config`] to an `.ini` string and stream it to the I/O writer `w`
+    /// (e.g. a buffered file) using default [`options`], without buffering the whole string in memory.
+    ///
+    /// Serialization errors are returned as I/O errors of kind [`InvalidData`],
+    /// wrapping the original [`ToIniStringError`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    /// [`InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData
+    /// [`ToIniStringError`]: enum.ToIniStringError.html
+    #[cfg(feature = "ini")]
+    pub fn write_ini<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        self.write_ini_opts(Default::default(), w)
     }
-}
 
-/// Implements the `IniConfig` `.ini` parser event handler for the `DynConfig`.
-#[cfg(feature = "ini")]
-pub(crate) struct DynConfigIniConfig {
-    root: DynTable,
-    current_section: Option<DynTable>,
-    // Never allocates if we don't support nested sections.
-    section_stack: Vec<DynTable>,
-    // Always `None` if we don't support arrays.
-    current_array: Option<DynArray>,
-}
+    /// Tries to serialize this [`config`] to an `.ini` string and stream it to the I/O writer `w`
+    /// (e.g. a buffered file) using provided [`options`], without buffering the whole string in memory.
+    ///
+    /// See [`write_ini`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    /// [`write_ini`]: #method.write_ini
+    #[cfg(feature = "ini")]
+    pub fn write_ini_opts<W: std::io::Write>(
+        &self,
+        options: ToIniStringOptions,
+        w: &mut W,
+    ) -> std::io::Result<()> {
+        let mut w = IoWriter::new(w);
 
-#[cfg(feature = "ini")]
-impl DynConfigIniConfig {
-    pub fn new() -> Self {
-        Self {
-            root: DynTable::new(),
-            current_section: None,
-            section_stack: Vec::new(),
-            current_array: None,
-        }
+        self.fmt_ini_opts(options, &mut w).map_err(|err| match err {
+            ToIniStringError::WriteError => io_writer_error(&mut w),
+            err => std::io::Error::new(std::io::ErrorKind::InvalidData, err),
+        })
     }
 
-    pub fn into_inner(self) -> DynConfig {
-        debug_assert!(
-            self.current_section.is_none(),
-            "missing `end_section()` call"
-        );
-        debug_assert!(
-            self.section_stack.is_empty(),
-            "missing `end_section()` call"
-        );
-        debug_assert!(self.current_array.is_none(), "missing `end_array()` call");
-
-        DynConfig(self.root)
+    /// Tries to serialize this [`config`] to a canonical `.ini` string.
+    ///
+    /// The canonical form does not depend on the way the [`config`] was created or modified,
+    /// so two equal [`configs`] always produce byte-identical canonical `.ini` strings,
+    /// which makes them suitable for storing in version control.
+    ///
+    /// Root keys are written first, followed by the sections;
+    /// keys and sections are sorted in alphabetical order;
+    /// key-value pairs are written as `key = value`; lines are separated by `\n`; no comments are written.
+    ///
+    /// Uses the following [`options`]: escape sequences and arrays are allowed,
+    /// nested sections of unlimited depth are allowed and parent sections are always written explicitly.
+    /// Use the same options when parsing the canonical `.ini` string.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`configs`]: struct.DynConfig.html
+    /// [`options`]: struct.ToIniStringOptions.html
+    #[cfg(feature = "ini")]
+    pub fn to_canonical_ini_string(&self) -> Result<String, ToIniStringError> {
+        self.to_ini_string_opts(ToIniStringOptions {
+            escape: true,
+            arrays: true,
+            nested_section_depth: u32::MAX,
+            implicit_parent_sections: false,
+            key_value_separator: IniKeyValueSeparator::Equals,
+            string_quotes: IniStringQuote::Double,
+            comments: IniCommentDelimiter::None,
+            bool_tokens: IniBoolTokens::TrueFalse,
+        })
     }
-}
 
-#[cfg(feature = "ini")]
-impl<'s> IniConfig<'s> for DynConfigIniConfig {
-    fn contains_key(&self, key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
-        let table = self.current_section.as_ref().unwrap_or(&self.root);
-        table
-            .get_impl(key.as_ne_str())
-            .map(|val| val.table().is_some())
-    }
+    /// Tries to create a new [`config`] from the JSON `string`.
+    ///
+    /// The root JSON value must be an object.
+    /// JSON `null` values are not supported (but `null` object members may be skipped - see [`JSONOptions`]);
+    /// arrays must contain values of the same type.
+    ///
+    /// Numbers without a fractional part and an exponent (e.g. `7`, `-7`) are parsed as `I64` values
+    /// (or as `U64` values if they only fit into a `u64`, or as `F64` values if they don't fit into either),
+    /// all other numbers (e.g. `7.0`, `7e3`) are parsed as `F64` values.
+    /// [`to_json_string`] always serializes `F64` values with a fractional part or an exponent,
+    /// so value types survive the round trip.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`JSONOptions`]: struct.JSONOptions.html
+    /// [`to_json_string`]: #method.to_json_string
+    #[cfg(feature = "json")]
+    pub fn from_json(string: &str) -> Result<Self, JSONError> {
+        Self::from_json_opts(string, Default::default())
+    }
+
+    /// Creates a new [`config`] from the JSON `string` using provided [`options`].
+    ///
+    /// See [`from_json`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.JSONOptions.html
+    /// [`from_json`]: #method.from_json
+    #[cfg(feature = "json")]
+    pub fn from_json_opts(string: &str, options: JSONOptions) -> Result<Self, JSONError> {
+        JSONParser::new(string, options).parse()
+    }
+
+    /// Tries to serialize this [`config`] to a JSON string using default [`options`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToJSONStringOptions.html
+    #[cfg(feature = "json")]
+    pub fn to_json_string(&self) -> Result<String, ToJSONStringError> {
+        self.to_json_string_opts(Default::default())
+    }
+
+    /// Tries to serialize this [`config`] to a JSON string to the writer `w` using default [`options`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToJSONStringOptions.html
+    #[cfg(feature = "json")]
+    pub fn fmt_json<W: Write>(&self, w: &mut W) -> Result<(), ToJSONStringError> {
+        self.fmt_json_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to a JSON string using provided [`options`].
+    ///
+    /// `I64` values are serialized as JSON integers, `F64` values always contain a fractional part
+    /// or an exponent, so they may be told apart when parsed by [`from_json`].
+    ///
+    /// NOTE: by default non-finite (NaN / infinite) float values are not allowed
+    /// and cause a [`NonFiniteFloat`] error with the path to the offending value - see [`options`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToJSONStringOptions.html
+    /// [`NonFiniteFloat`]: enum.ToJSONStringError.html#variant.NonFiniteFloat
+    /// [`from_json`]: #method.from_json
+    #[cfg(feature = "json")]
+    pub fn to_json_string_opts(
+        &self,
+        options: ToJSONStringOptions,
+    ) -> Result<String, ToJSONStringError> {
+        let mut result = String::new();
+
+        self.fmt_json_opts(options, &mut result)?;
+
+        result.shrink_to_fit();
+
+        Ok(result)
+    }
+
+    /// Tries to serialize this [`config`] to a JSON string to the writer `w` using provided [`options`].
+    ///
+    /// NOTE: by default non-finite (NaN / infinite) float values are not allowed
+    /// and cause a [`NonFiniteFloat`] error with the path to the offending value - see [`options`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToJSONStringOptions.html
+    /// [`NonFiniteFloat`]: enum.ToJSONStringError.html#variant.NonFiniteFloat
+    #[cfg(feature = "json")]
+    pub fn fmt_json_opts<W: Write>(
+        &self,
+        options: ToJSONStringOptions,
+        w: &mut W,
+    ) -> Result<(), ToJSONStringError> {
+        self.root()
+            .fmt_json(w, 0, options)
+            .map_err(ToJSONStringError::reverse)
+    }
+
+    /// Tries to create a new [`config`] from the YAML `string`.
+    ///
+    /// The string must contain exactly one YAML document with a mapping at the root.
+    /// Mapping keys must be non-empty strings; YAML `null` values and aliases are not supported;
+    /// sequences must contain values of the same type.
+    ///
+    /// Integers are parsed as `I64` values (or as `U64` values if they only fit into a `u64`),
+    /// reals (including `.nan` / `.inf` / `-.inf`) are parsed as `F64` values.
+    /// [`to_yaml_string`] always serializes `F64` values with a fractional part or an exponent
+    /// and quotes strings which would otherwise be parsed as booleans / numbers,
+    /// so value types survive the round trip.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`to_yaml_string`]: #method.to_yaml_string
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(string: &str) -> Result<Self, YAMLError> {
+        Self::from_yaml_opts(string, Default::default())
+    }
+
+    /// Creates a new [`config`] from the YAML `string` using provided [`options`].
+    ///
+    /// See [`from_yaml`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.YAMLOptions.html
+    /// [`from_yaml`]: #method.from_yaml
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_opts(string: &str, options: YAMLOptions) -> Result<Self, YAMLError> {
+        parse_yaml(string, options)
+    }
+
+    /// Tries to serialize this [`config`] to a YAML string.
+    ///
+    /// Tables are serialized as block mappings with keys in alphabetical order,
+    /// arrays are serialized as block sequences, empty tables / arrays are serialized as `{}` / `[]`.
+    /// Nested blocks are indented with two spaces.
+    ///
+    /// `I64` values are serialized as YAML integers, `F64` values always contain a fractional part
+    /// or an exponent (non-finite values are serialized as `.nan` / `.inf` / `-.inf`),
+    /// strings are double-quoted unless they may be unambiguously parsed back as strings by [`from_yaml`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`from_yaml`]: #method.from_yaml
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml_string(&self) -> Result<String, std::fmt::Error> {
+        let mut result = String::new();
+
+        self.fmt_yaml(&mut result)?;
+
+        result.shrink_to_fit();
+
+        Ok(result)
+    }
+
+    /// Tries to serialize this [`config`] to a YAML string to the writer `w`.
+    ///
+    /// See [`to_yaml_string`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`to_yaml_string`]: #method.to_yaml_string
+    #[cfg(feature = "yaml")]
+    pub fn fmt_yaml<W: Write>(&self, w: &mut W) -> Result<(), std::fmt::Error> {
+        let root = self.root();
+
+        root.fmt_yaml(w, 0)?;
+
+        // Empty root table is serialized as `{}` with no trailing new line.
+        if !root.is_block() {
+            writeln!(w)?;
+        }
+
+        Ok(())
+    }
+
+    /// Tries to create a new [`config`] from the TOML `string` using default [`options`].
+    ///
+    /// TOML tables (including inline tables and arrays of tables) are parsed as [`tables`],
+    /// arrays are parsed as [`arrays`] and must contain values of the same type,
+    /// integers, floats, booleans and strings are parsed as `I64`, `F64`, `Bool` and `String` values respectively.
+    ///
+    /// NOTE: the config has no datetime value type, so by default TOML datetime values
+    /// are parsed as strings in RFC 3339 format - see [`options`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.TOMLOptions.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    #[cfg(feature = "toml")]
+    pub fn from_toml(string: &str) -> Result<Self, TOMLError> {
+        Self::from_toml_opts(string, Default::default())
+    }
+
+    /// Creates a new [`config`] from the TOML `string` using provided [`options`].
+    ///
+    /// See [`from_toml`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.TOMLOptions.html
+    /// [`from_toml`]: #method.from_toml
+    #[cfg(feature = "toml")]
+    pub fn from_toml_opts(string: &str, options: TOMLOptions) -> Result<Self, TOMLError> {
+        TOMLParser::new(string, options).parse()
+    }
+
+    /// Tries to serialize this [`config`] to a TOML string.
+    ///
+    /// Keys are sorted in alphabetical order; root / table values are written before the nested tables.
+    ///
+    /// NOTE: `U64` values which do not fit into a TOML (64-bit signed) integer
+    /// cause an [`IntegerOutOfRange`] error with the path to the offending value.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`IntegerOutOfRange`]: enum.ToTOMLStringError.html#variant.IntegerOutOfRange
+    #[cfg(feature = "toml")]
+    pub fn to_toml_string(&self) -> Result<String, ToTOMLStringError> {
+        let root = self
+            .root()
+            .to_toml_value()
+            .map_err(ToTOMLStringError::reverse)?;
+
+        toml_rs::to_string(&root)
+            .map_err(|err| ToTOMLStringError::SerializationError(err.to_string()))
+    }
+
+    /// Tries to serialize this [`config`] to a TOML string to the writer `w`.
+    ///
+    /// See [`to_toml_string`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`to_toml_string`]: #method.to_toml_string
+    #[cfg(feature = "toml")]
+    pub fn fmt_toml<W: Write>(&self, w: &mut W) -> Result<(), ToTOMLStringError> {
+        w.write_str(&self.to_toml_string()?)?;
+
+        Ok(())
+    }
+
+    /// Tries to create a new [`config`] from the RON `string`.
+    ///
+    /// The root value must be a RON map with non-empty string keys.
+    /// Maps are parsed as [`tables`], lists are parsed as [`arrays`] and must contain values of the same type,
+    /// integers, floats, booleans and strings are parsed as `I64`, `F64`, `Bool` and `String` values respectively;
+    /// chars are parsed as strings and `Some(..)` values are unwrapped.
+    /// Unit (`()`) and `None` values are not supported.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`arrays`]: struct.DynArray.html
+    #[cfg(feature = "ron")]
+    pub fn from_ron(string: &str) -> Result<Self, RONError> {
+        Self::from_ron_opts(string, Default::default())
+    }
+
+    /// Creates a new [`config`] from the RON `string` using provided [`options`].
+    ///
+    /// See [`from_ron`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.RONOptions.html
+    /// [`from_ron`]: #method.from_ron
+    #[cfg(feature = "ron")]
+    pub fn from_ron_opts(string: &str, options: RONOptions) -> Result<Self, RONError> {
+        RONParser::new(string, options).parse()
+    }
+
+    /// Tries to serialize this [`config`] to a RON string using default [`options`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToRONStringOptions.html
+    #[cfg(feature = "ron")]
+    pub fn to_ron_string(&self) -> Result<String, ToRONStringError> {
+        self.to_ron_string_opts(Default::default())
+    }
+
+    /// Tries to serialize this [`config`] to a RON string to the writer `w` using default [`options`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToRONStringOptions.html
+    #[cfg(feature = "ron")]
+    pub fn fmt_ron<W: Write>(&self, w: &mut W) -> Result<(), ToRONStringError> {
+        self.fmt_ron_opts(Default::default(), w)
+    }
+
+    /// Tries to serialize this [`config`] to a RON string using provided [`options`].
+    ///
+    /// Tables are serialized as RON maps with keys in alphabetical order, arrays are serialized as lists,
+    /// each element followed by a (trailing) comma. Nested maps / lists are indented with tabs.
+    ///
+    /// `I64` values are serialized as RON integers, `F64` values always contain a fractional part
+    /// or an exponent, so they may be told apart when parsed by [`from_ron`].
+    ///
+    /// NOTE: `U64` values which do not fit into a RON (64-bit signed) integer
+    /// cause an [`IntegerOutOfRange`] error, non-finite (NaN / infinite) float values
+    /// cause a [`NonFiniteFloat`] error with the path to the offending value.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToRONStringOptions.html
+    /// [`from_ron`]: #method.from_ron
+    /// [`IntegerOutOfRange`]: enum.ToRONStringError.html#variant.IntegerOutOfRange
+    /// [`NonFiniteFloat`]: enum.ToRONStringError.html#variant.NonFiniteFloat
+    #[cfg(feature = "ron")]
+    pub fn to_ron_string_opts(
+        &self,
+        options: ToRONStringOptions,
+    ) -> Result<String, ToRONStringError> {
+        let mut result = String::new();
+
+        self.fmt_ron_opts(options, &mut result)?;
+
+        result.shrink_to_fit();
+
+        Ok(result)
+    }
+
+    /// Tries to serialize this [`config`] to a RON string to the writer `w` using provided [`options`].
+    ///
+    /// See [`to_ron_string_opts`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`options`]: struct.ToRONStringOptions.html
+    /// [`to_ron_string_opts`]: #method.to_ron_string_opts
+    #[cfg(feature = "ron")]
+    pub fn fmt_ron_opts<W: Write>(
+        &self,
+        options: ToRONStringOptions,
+        w: &mut W,
+    ) -> Result<(), ToRONStringError> {
+        self.root()
+            .fmt_ron(w, 0, options)
+            .map_err(ToRONStringError::reverse)
+    }
+
+    /// Tries to deserialize a value of type `T` (e.g. a `#[derive(Deserialize)]` struct) from this [`config`],
+    /// starting at the root [`table`].
+    ///
+    /// Strings may be borrowed from the [`config`].
+    /// See the `Deserializer` implementation of [`DynConfigValueRef`] for the supported type mapping.
+    ///
+    /// [`config`]: struct.DynConfig.html
+    /// [`table`]: struct.DynTable.html
+    /// [`DynConfigValueRef`]: type.DynConfigValueRef.html
+    #[cfg(feature = "serde")]
+    pub fn deserialize_into<'de, T: serde::Deserialize<'de>>(
+        &'de self,
+    ) -> Result<T, DeserializeError> {
+        T::deserialize(Value::Table(self.root()))
+    }
+}
+
+impl Display for DynConfig {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.root()
+            .fmt_lua(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
+    }
+}
+
+/// Returns the I/O error stored in the I/O writer adapter `w` after a failed write.
+fn io_writer_error<W: std::io::Write>(w: &mut IoWriter<W>) -> std::io::Error {
+    w.take_error()
+        .unwrap_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "formatter error"))
+}
+
+/// Visits the elements of the (maybe missing) `left` and `right` dyn tables in lockstep, in alphabetical key order.
+fn walk_paired_tables<'c, F>(
+    left: Option<&'c DynTable>,
+    right: Option<&'c DynTable>,
+    path: &mut ConfigPath,
+    visitor: &mut F,
+) where
+    F: FnMut(&ConfigPath, Option<DynConfigValueRef<'c>>, Option<DynConfigValueRef<'c>>),
+{
+    // Gather the keys of both tables.
+    let mut keys: Vec<&NonEmptyStr> = left
+        .into_iter()
+        .chain(right.into_iter())
+        .flat_map(|table| table.keys())
+        .collect();
+
+    // Sort the keys in canonical order, skip the keys present in both tables.
+    sort_by_table_key(&mut keys, |key| key.as_str());
+    keys.dedup();
+
+    for key in keys.into_iter() {
+        path.0.push(key.into());
+
+        walk_paired_values(
+            left.and_then(|left| left.get_impl(key)),
+            right.and_then(|right| right.get_impl(key)),
+            path,
+            visitor,
+        );
+
+        path.0.pop();
+    }
+}
+
+/// Visits the elements of the (maybe missing) `left` and `right` dyn arrays in lockstep, in index order.
+fn walk_paired_arrays<'c, F>(
+    left: Option<&'c DynArray>,
+    right: Option<&'c DynArray>,
+    path: &mut ConfigPath,
+    visitor: &mut F,
+) where
+    F: FnMut(&ConfigPath, Option<DynConfigValueRef<'c>>, Option<DynConfigValueRef<'c>>),
+{
+    let len = left
+        .map(DynArray::len)
+        .unwrap_or(0)
+        .max(right.map(DynArray::len).unwrap_or(0));
+
+    for index in 0..len {
+        path.0.push(index.into());
+
+        walk_paired_values(
+            left.and_then(|left| left.get_val(index).ok()),
+            right.and_then(|right| right.get_val(index).ok()),
+            path,
+            visitor,
+        );
+
+        path.0.pop();
+    }
+}
+
+/// Visits the (maybe missing) `left` and `right` dyn config values at `path`,
+/// then recursively visits their nested elements, if any.
+fn walk_paired_values<'c, F>(
+    left: Option<DynConfigValueRef<'c>>,
+    right: Option<DynConfigValueRef<'c>>,
+    path: &mut ConfigPath,
+    visitor: &mut F,
+) where
+    F: FnMut(&ConfigPath, Option<DynConfigValueRef<'c>>, Option<DynConfigValueRef<'c>>),
+{
+    visitor(path, left.clone(), right.clone());
+
+    let left_table = left.clone().and_then(Value::table);
+    let right_table = right.clone().and_then(Value::table);
+
+    if left_table.is_some() || right_table.is_some() {
+        walk_paired_tables(left_table, right_table, path, visitor);
+    }
+
+    let left_array = left.and_then(Value::array);
+    let right_array = right.and_then(Value::array);
+
+    if left_array.is_some() || right_array.is_some() {
+        walk_paired_arrays(left_array, right_array, path, visitor);
+    }
+}
+
+/// Expands the environment variable references in the string values of the dyn `table` recursively.
+/// `path` is the path to the `table`.
+fn table_expand_env(
+    table: &mut DynTable,
+    path: &mut ConfigPath,
+    options: EnvExpandOptions,
+) -> Result<(), EnvExpandError> {
+    // Gather the keys.
+    let keys: Vec<NonEmptyString> = table.keys().map(Into::into).collect();
+
+    for key in keys.into_iter() {
+        path.0.push(key.as_ne_str().into());
+
+        // Must succeed - all keys are valid.
+        let value = unwrap_unchecked(
+            table.get_mut_impl(key.as_ne_str()),
+            "failed to get a value from a dyn config table with a valid key",
+        );
+
+        match value {
+            Value::String(value) => {
+                if let Some(value) = expand_env_string(value, path, options)? {
+                    table.set(key.as_ne_str(), value);
+                }
+            }
+            Value::Array(value) => array_expand_env(value, path, options)?,
+            Value::Table(value) => table_expand_env(value, path, options)?,
+            _ => {}
+        }
+
+        path.0.pop();
+    }
+
+    Ok(())
+}
+
+/// Parses the `raw` command line override value as per `DynConfig::apply_override`.
+fn parse_override_value(raw: &str) -> Result<DynConfigValue, OverrideError> {
+    // Quoted values are always treated as strings.
+    if let Some(quote) = raw.chars().next().filter(|&c| c == '"' || c == '\'') {
+        let value = raw[1..]
+            .strip_suffix(quote)
+            .ok_or(OverrideError::UnterminatedQuote)?;
+
+        return Ok(Value::String(value.into()));
+    }
+
+    let value = if raw.is_empty() {
+        Value::String(raw.into())
+    } else if raw == "true" {
+        Value::Bool(true)
+    } else if raw == "false" {
+        Value::Bool(false)
+    } else if let Some(value) = try_parse_integer(raw) {
+        Value::I64(value)
+    } else if let Ok(value) = raw.parse::<f64>() {
+        Value::F64(value)
+    } else {
+        Value::String(raw.into())
+    };
+
+    Ok(value)
+}
+
+/// Expands the environment variable references in the string values of the dyn `array` recursively.
+/// `path` is the path to the `array`.
+fn array_expand_env(
+    array: &mut DynArray,
+    path: &mut ConfigPath,
+    options: EnvExpandOptions,
+) -> Result<(), EnvExpandError> {
+    // Iterate the array in order.
+    for (index, mut value) in array.iter_mut().enumerate() {
+        path.0.push((index as u32).into());
+
+        match value.get_mut() {
+            Value::String(string) => {
+                if let Some(string) = expand_env_string(string, path, options)? {
+                    // Must succeed - the value is a string.
+                    unwrap_unchecked(
+                        value.set(string.into()),
+                        "failed to change a string value in a dyn config array",
+                    );
+                }
+            }
+            Value::Array(value) => array_expand_env(value, path, options)?,
+            Value::Table(value) => table_expand_env(value, path, options)?,
+            _ => {}
+        }
+
+        path.0.pop();
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the `name` is a valid environment variable name for expansion -
+/// non-empty, consisting of ASCII letters, digits and underscores, not starting with a digit.
+fn is_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(c) if c == '_' || c.is_ascii_alphabetic() => {
+            chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+        }
+        _ => false,
+    }
+}
+
+/// Expands the environment variable references in the `string` value at `path`.
+/// Returns `None` if the `string` contains no references / escape sequences.
+fn expand_env_string(
+    string: &str,
+    path: &ConfigPath,
+    options: EnvExpandOptions,
+) -> Result<Option<String>, EnvExpandError> {
+    use {std::env::VarError, EnvExpandError::*};
+
+    if !string.contains('$') {
+        return Ok(None);
+    }
+
+    let mut result = String::with_capacity(string.len());
+    let mut rest = string;
+
+    while let Some(dollar) = rest.find('$') {
+        result.push_str(&rest[..dollar]);
+        rest = &rest[dollar + 1..];
+
+        let name = if let Some(escaped) = rest.strip_prefix('$') {
+            // `"$$"` - escaped dollar sign.
+            result.push('$');
+            rest = escaped;
+            continue;
+        } else if let Some(braced) = rest.strip_prefix('{') {
+            // `"${NAME}"`.
+            let end = braced
+                .find('}')
+                .ok_or_else(|| InvalidReference(path.clone()))?;
+            let name = &braced[..end];
+
+            if !is_env_var_name(name) {
+                return Err(InvalidReference(path.clone()));
+            }
+
+            rest = &braced[end + 1..];
+            name
+        } else {
+            // `"$NAME"`.
+            let end = rest
+                .find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len());
+            let name = &rest[..end];
+
+            if !is_env_var_name(name) {
+                // Not a reference - keep the dollar sign.
+                result.push('$');
+                continue;
+            }
+
+            rest = &rest[end..];
+            name
+        };
+
+        match std::env::var(name) {
+            Ok(value) => result.push_str(&value),
+            Err(VarError::NotPresent) if options.allow_unset => {}
+            Err(VarError::NotPresent) => {
+                return Err(UnsetVariable {
+                    path: path.clone(),
+                    name: name.into(),
+                })
+            }
+            Err(VarError::NotUnicode(_)) => {
+                return Err(NonUnicodeVariable {
+                    path: path.clone(),
+                    name: name.into(),
+                })
+            }
+        }
+    }
+
+    result.push_str(rest);
+
+    Ok(Some(result))
+}
+
+/// Appends the env var pairs for the dyn `table` recursively to the `result`.
+/// `name` is the env var name of the `table`, or the prefix for the root table.
+fn table_to_env_vars(
+    table: &DynTable,
+    name: &mut String,
+    separator: &str,
+    result: &mut Vec<(String, String)>,
+) {
+    // Iterate the table in alphabetical key order.
+    for (key, value) in table.iter_sorted() {
+        value_to_env_vars(&key.as_str().to_uppercase(), value, name, separator, result);
+    }
+}
+
+/// Appends the env var pairs for the dyn `array` recursively to the `result`.
+/// `name` is the env var name of the `array`.
+fn array_to_env_vars(
+    array: &DynArray,
+    name: &mut String,
+    separator: &str,
+    result: &mut Vec<(String, String)>,
+) {
+    // Iterate the array in order.
+    for (index, value) in array.iter().enumerate() {
+        value_to_env_vars(&index.to_string(), value, name, separator, result);
+    }
+}
+
+/// Appends the env var pair(s) for the dyn config `value` with `key` recursively to the `result`.
+/// `name` is the env var name of the `value`'s parent table / array.
+fn value_to_env_vars(
+    key: &str,
+    value: DynConfigValueRef<'_>,
+    name: &mut String,
+    separator: &str,
+    result: &mut Vec<(String, String)>,
+) {
+    use Value::*;
+
+    let name_len = name.len();
+
+    if !name.is_empty() {
+        name.push_str(separator);
+    }
+
+    name.push_str(key);
+
+    match value {
+        Bool(value) => result.push((name.clone(), value.to_string())),
+        I64(value) => result.push((name.clone(), value.to_string())),
+        U64(value) => result.push((name.clone(), value.to_string())),
+        F64(value) => result.push((name.clone(), value.to_string())),
+        String(value) => result.push((name.clone(), value.to_owned())),
+        Array(value) => array_to_env_vars(value, name, separator, result),
+        Table(value) => table_to_env_vars(value, name, separator, result),
+    }
+
+    name.truncate(name_len);
+}
+
+/// Appends the (`path`, `value`) pairs for the dyn `table` recursively to the `result`.
+/// `path` is the dot-separated path of the `table`, or empty for the root table.
+fn table_to_flat<'c>(
+    table: &'c DynTable,
+    path: &mut String,
+    result: &mut Vec<(String, DynConfigValueRef<'c>)>,
+) {
+    // Iterate the table in alphabetical key order.
+    for (key, value) in table.iter_sorted() {
+        let path_len = path.len();
+
+        if !path.is_empty() {
+            path.push('.');
+        }
+
+        // Escape the numeric keys so that they are not mistaken for array indices by `from_flat`.
+        if is_flat_index(key.as_str()) {
+            path.push('\\');
+        }
+
+        push_dotted_path_key(path, key.as_str());
+
+        value_to_flat(value, path, result);
+
+        path.truncate(path_len);
+    }
+}
+
+/// Appends the (`path`, `value`) pairs for the dyn `array` recursively to the `result`.
+/// `path` is the dot-separated path of the `array`.
+fn array_to_flat<'c>(
+    array: &'c DynArray,
+    path: &mut String,
+    result: &mut Vec<(String, DynConfigValueRef<'c>)>,
+) {
+    // Iterate the array in order.
+    for (index, value) in array.iter().enumerate() {
+        let path_len = path.len();
+
+        write!(path, ".{}", index).unwrap();
+
+        value_to_flat(value, path, result);
+
+        path.truncate(path_len);
+    }
+}
+
+/// Appends the (`path`, `value`) pair(s) for the dyn config `value` at `path` recursively to the `result`.
+fn value_to_flat<'c>(
+    value: DynConfigValueRef<'c>,
+    path: &mut String,
+    result: &mut Vec<(String, DynConfigValueRef<'c>)>,
+) {
+    match value {
+        Value::Array(value) => array_to_flat(value, path, result),
+        Value::Table(value) => table_to_flat(value, path, result),
+        value => result.push((path.clone(), value)),
+    }
+}
+
+/// Returns `true` if the (unescaped) dotted path `key` is an array index.
+fn is_flat_index(key: &str) -> bool {
+    key.parse::<u32>().is_ok()
+}
+
+/// A key in a dotted path passed to `DynConfig::from_flat`.
+struct FlatKey {
+    /// Unescaped key.
+    key: String,
+    /// Array index, if the `key` is a valid `u32` number and its first digit is not escaped.
+    index: Option<u32>,
+}
+
+/// Splits the dotted `path` passed to `DynConfig::from_flat` into keys / array indices.
+fn split_flat_path(path: &str) -> Vec<FlatKey> {
+    split_dotted_path_escaped(path)
+        .into_iter()
+        .map(|(key, escaped)| FlatKey {
+            index: if escaped { None } else { key.parse().ok() },
+            key,
+        })
+        .collect()
+}
+
+/// Creates a new empty table, or an array if the dotted path `next_key` is an array index.
+fn new_flat_container(next_key: &FlatKey) -> DynConfigValue {
+    if next_key.index.is_some() {
+        DynArray::new().into()
+    } else {
+        DynTable::new().into()
+    }
+}
+
+/// Sets the `value` at the (non-empty) dotted path `keys` in the dyn `table`,
+/// creating the missing intermediate tables / arrays.
+/// `path` is the path to the `table`.
+fn flat_to_table(
+    table: &mut DynTable,
+    keys: &[FlatKey],
+    value: DynConfigValue,
+    path: &mut ConfigPath,
+) -> Result<(), UnflattenError> {
+    use UnflattenError::*;
+
+    // Must succeed - there's always at least one key.
+    let (key, keys) = unwrap_unchecked(keys.split_first(), "empty dotted path");
+    let key = NonEmptyStr::new(key.key.as_str()).ok_or_else(|| EmptyKey(path.clone()))?;
+
+    path.0.push(key.into());
+
+    let next_key = match keys.first() {
+        Some(next_key) => next_key,
+        // Set the leaf value, unless it's already a table / array.
+        None => {
+            return match table.get_val(key).map(|value| value.get_type()) {
+                Some(value_type @ ValueType::Table) | Some(value_type @ ValueType::Array) => {
+                    Err(TypeConflict {
+                        path: path.clone(),
+                        value_type,
+                    })
+                }
+                _ => {
+                    table.set(key, value);
+                    Ok(())
+                }
+            };
+        }
+    };
+
+    if !table.contains(key) {
+        table.set(key, new_flat_container(next_key));
+    }
+
+    match table.get_val_mut(key) {
+        Some(Value::Table(table)) if next_key.index.is_none() => {
+            flat_to_table(table, keys, value, path)
+        }
+        Some(Value::Array(array)) if next_key.index.is_some() => {
+            flat_to_array(array, keys, value, path)
+        }
+        Some(value) => Err(TypeConflict {
+            path: path.clone(),
+            value_type: value.get_type(),
+        }),
+        None => {
+            debug_unreachable!("failed to get a value from a dyn config table with a valid key")
+        }
+    }
+}
+
+/// Sets the `value` at the dotted path `keys`, starting with an array index, in the dyn `array`,
+/// creating the missing intermediate tables / arrays.
+/// `path` is the path to the `array`.
+fn flat_to_array(
+    array: &mut DynArray,
+    keys: &[FlatKey],
+    value: DynConfigValue,
+    path: &mut ConfigPath,
+) -> Result<(), UnflattenError> {
+    use UnflattenError::*;
+
+    // Must succeed - there's always at least one key, and it's an array index.
+    let (index, keys) = unwrap_unchecked(keys.split_first(), "empty dotted path");
+    let index = unwrap_unchecked(index.index, "invalid array index");
+
+    path.0.push(index.into());
+
+    let len = array.len();
+
+    if index > len {
+        return Err(IndexOutOfBounds {
+            path: path.clone(),
+            len,
+        });
+    }
+
+    let mixed_array = |array: &DynArray, path: &ConfigPath| MixedArray {
+        path: path.clone(),
+        // Must succeed - the array is not empty if a value of incorrect type was added.
+        value_type: unwrap_unchecked(array.get_val(0), "empty array").get_type(),
+    };
+
+    let next_key = match keys.first() {
+        Some(next_key) => next_key,
+        // Set or push the leaf value, unless it's already a table / array.
+        None => {
+            if index == len {
+                return array.push(value).map_err(|_| mixed_array(array, path));
+            }
+
+            // Must succeed - the index is in bounds.
+            return match unwrap_unchecked(array.get_val(index), "invalid array index").get_type() {
+                value_type @ ValueType::Table | value_type @ ValueType::Array => {
+                    Err(TypeConflict {
+                        path: path.clone(),
+                        value_type,
+                    })
+                }
+                _ => array
+                    .set(index, value)
+                    .map_err(|_| mixed_array(array, path)),
+            };
+        }
+    };
+
+    if index == len {
+        array
+            .push(new_flat_container(next_key))
+            .map_err(|_| mixed_array(array, path))?;
+    }
+
+    // Must succeed - the index is in bounds.
+    match unwrap_unchecked(array.get_val_mut(index), "invalid array index") {
+        Value::Table(table) if next_key.index.is_none() => flat_to_table(table, keys, value, path),
+        Value::Array(array) if next_key.index.is_some() => flat_to_array(array, keys, value, path),
+        value => Err(TypeConflict {
+            path: path.clone(),
+            value_type: value.get_type(),
+        }),
+    }
+}
+
+/// Returns the total number of values in the dyn `table`, recursively.
+fn table_node_count(table: &DynTable) -> usize {
+    table.iter().map(|(_, value)| value_node_count(value)).sum()
+}
+
+/// Returns the total number of values in the dyn `array`, recursively.
+fn array_node_count(array: &DynArray) -> usize {
+    array.iter().map(value_node_count).sum()
+}
+
+/// Returns the total number of values in the dyn config `value` (including itself), recursively.
+fn value_node_count(value: DynConfigValueRef<'_>) -> usize {
+    1 + match value {
+        Value::Array(value) => array_node_count(value),
+        Value::Table(value) => table_node_count(value),
+        _ => 0,
+    }
+}
+
+#[cfg(feature = "bin")]
+/// Returns the upper bound of the size in bytes of the dyn `table` contents in the binary config
+/// (except the table's own packed values), recursively.
+fn table_bin_size(table: &DynTable) -> usize {
+    table
+        .iter()
+        .map(|(key, value)| {
+            // Key table entry and the null-terminated key string.
+            BIN_CONFIG_KEY_SIZE + key.as_str().len() + 1 + value_bin_size(value)
+        })
+        .sum()
+}
+
+#[cfg(feature = "bin")]
+/// Returns the upper bound of the size in bytes of the dyn `array` contents in the binary config
+/// (except the array's own packed values), recursively.
+fn array_bin_size(array: &DynArray) -> usize {
+    array.iter().map(value_bin_size).sum()
+}
+
+#[cfg(feature = "bin")]
+/// Returns the upper bound of the size in bytes of the data referenced by the dyn config `value`'s packed value
+/// in the binary config, recursively.
+fn value_bin_size(value: DynConfigValueRef<'_>) -> usize {
+    match value {
+        // Null-terminated string.
+        Value::String(value) => value.len() + 1,
+        // Packed values of the elements and their contents.
+        Value::Array(value) => value.len() as usize * BIN_CONFIG_VALUE_SIZE + array_bin_size(value),
+        Value::Table(value) => value.len() as usize * BIN_CONFIG_VALUE_SIZE + table_bin_size(value),
+        Value::Bool(_) | Value::I64(_) | Value::U64(_) | Value::F64(_) => 0,
+    }
+}
+
+#[cfg(feature = "bin")]
+/// Writes the dyn table recursively to the binary config writer.
+fn table_to_bin_config(
+    table: &DynTable,
+    writer: &mut BinConfigWriter,
+) -> Result<(), BinConfigWriterError> {
+    // Iterate the table in alphabetical key order.
+    for (key, value) in table.iter_sorted() {
+        value_to_bin_config(Some(key), value, writer)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "bin")]
+/// Writes the dyn array recursively to the binary config writer.
+fn array_to_bin_config(
+    array: &DynArray,
+    writer: &mut BinConfigWriter,
+) -> Result<(), BinConfigWriterError> {
+    // Iterate the array in order.
+    for value in array.iter() {
+        value_to_bin_config(None, value, writer)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "bin")]
+/// Writes the dyn config value with `key` recursively to the binary config writer.
+fn value_to_bin_config(
+    key: Option<&NonEmptyStr>,
+    value: DynConfigValueRef<'_>,
+    writer: &mut BinConfigWriter,
+) -> Result<(), BinConfigWriterError> {
+    use Value::*;
+
+    match value {
+        Bool(value) => {
+            writer.bool(key, value)?;
+        }
+        I64(value) => {
+            writer.i64(key, value)?;
+        }
+        U64(value) => {
+            writer.u64(key, value)?;
+        }
+        F64(value) => {
+            writer.f64(key, value)?;
+        }
+        String(value) => {
+            writer.string(key, value)?;
+        }
+        Array(value) => {
+            writer.array(key, value.len())?;
+            array_to_bin_config(value, writer)?;
+            writer.end()?;
+        }
+        Table(value) => {
+            writer.table(key, value.len())?;
+            table_to_bin_config(value, writer)?;
+            writer.end()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "lua")]
+/// Deep-copies the dyn config `table` to the Lua config `lua_table`.
+fn table_to_lua_table<'lua>(
+    lua: rlua::Context<'lua>,
+    table: &DynTable,
+    lua_table: &mut LuaTable<'lua>,
+) {
+    for (key, value) in table.iter() {
+        let already_existed = lua_table.set(key, value_to_lua_value(lua, value));
+
+        // Must succeed - we are only adding values to the Lua table.
+        debug_assert!(
+            !already_existed,
+            "value unexpectedly already existed in the table"
+        );
+    }
+}
+
+#[cfg(feature = "lua")]
+/// Deep-copies the dyn config `array` to the Lua config `lua_array`.
+fn array_to_lua_array<'lua>(
+    lua: rlua::Context<'lua>,
+    array: &DynArray,
+    lua_array: &mut LuaArray<'lua>,
+) {
+    for value in array.iter() {
+        // Must succeed - we are adding values of the same type to the Lua array.
+        unwrap_unchecked(
+            lua_array.push(value_to_lua_value(lua, value)),
+            "incorrect array value type",
+        );
+    }
+}
+
+#[cfg(feature = "lua")]
+/// Converts the dyn config `value` to a value which may be added to a Lua config table / array,
+/// recursively deep-copying nested tables / arrays.
+fn value_to_lua_value<'s, 'lua>(
+    lua: rlua::Context<'lua>,
+    value: DynConfigValueRef<'s>,
+) -> Value<&'s str, LuaArray<'lua>, LuaTable<'lua>> {
+    use Value::*;
+
+    match value {
+        Bool(value) => Bool(value),
+        I64(value) => I64(value),
+        U64(value) => U64(value),
+        F64(value) => F64(value),
+        String(value) => String(value),
+        Array(value) => {
+            let mut array = LuaArray::new(lua);
+            array_to_lua_array(lua, value, &mut array);
+            Array(array)
+        }
+        Table(value) => {
+            let mut table = LuaTable::new(lua);
+            table_to_lua_table(lua, value, &mut table);
+            Table(table)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn GetPathError_PathDoesNotExist() {
+        let mut table = DynTable::new();
+
+        let mut foo = DynTable::new();
+        let mut bar = DynArray::new();
+        let mut baz = DynTable::new();
+
+        assert!(!baz.set(nestr!("bob"), true));
+        bar.push(baz.into()).unwrap();
+        assert!(!foo.set(nestr!("bar"), bar));
+        assert!(!table.set(nestr!("foo"), foo));
+
+        assert_eq!(
+            table.get_val_path(&["".into()]).err().unwrap(),
+            GetPathError::KeyDoesNotExist(ConfigPath::new())
+        );
+        assert_eq!(
+            table
+                .get_val_path(&["foo".into(), "".into()])
+                .err()
+                .unwrap(),
+            GetPathError::KeyDoesNotExist(vec![nestr!("foo").into()].into())
+        );
+        assert_eq!(
+            table
+                .get_val_path(&["foo".into(), "baz".into()])
+                .err()
+                .unwrap(),
+            GetPathError::KeyDoesNotExist(vec![nestr!("foo").into(), nestr!("baz").into()].into())
+        );
+
+        assert_eq!(
+            table
+                .get_val_path(&["foo".into(), "bar".into(), 0.into(), "bill".into()])
+                .err()
+                .unwrap(),
+            GetPathError::KeyDoesNotExist(
+                vec![
+                    nestr!("foo").into(),
+                    nestr!("bar").into(),
+                    0.into(),
+                    nestr!("bill").into()
+                ]
+                .into()
+            )
+        );
+
+        // But this works.
+
+        assert_eq!(
+            table
+                .get_bool_path(&["foo".into(), "bar".into(), 0.into(), "bob".into()])
+                .unwrap(),
+            true
+        );
+    }
+
+    #[test]
+    fn GetPathError_IndexOutOfBounds() {
+        let mut table = DynTable::new();
+
+        let mut array = DynArray::new();
+        array.push(true.into()).unwrap();
+
+        assert!(!table.set(nestr!("array"), array));
+
+        assert_eq!(
+            table
+                .get_val_path(&["array".into(), 1.into()])
+                .err()
+                .unwrap(),
+            GetPathError::IndexOutOfBounds {
+                path: vec![nestr!("array").into(), 1.into()].into(),
+                len: 1
+            }
+        );
+
+        // But this works.
+
+        assert_eq!(
+            table.get_bool_path(&["array".into(), 0.into()]).unwrap(),
+            true
+        );
+    }
+
+    #[test]
+    fn GetPathError_ValueNotAnArray() {
+        let mut table = DynTable::new();
+
+        let mut other_table = DynTable::new();
+        assert!(!other_table.set(nestr!("array"), true));
+
+        assert!(!table.set(nestr!("table"), other_table));
+
+        assert_eq!(
+            table
+                .get_val_path(&["table".into(), "array".into(), 1.into()])
+                .err()
+                .unwrap(),
+            GetPathError::ValueNotAnArray {
+                path: vec![nestr!("table").into(), nestr!("array").into()].into(),
+                value_type: ValueType::Bool
+            }
+        );
+
+        // But this works.
+
+        assert_eq!(
+            table
+                .get_bool_path(&["table".into(), "array".into()])
+                .unwrap(),
+            true,
+        );
+    }
+
+    #[test]
+    fn GetPathError_ValueNotATable() {
+        let mut table = DynTable::new();
+
+        let mut array = DynArray::new();
+        array.push(true.into()).unwrap();
+
+        assert!(!table.set(nestr!("array"), array));
+
+        assert_eq!(
+            table
+                .get_val_path(&["array".into(), 0.into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::ValueNotATable {
+                path: vec![nestr!("array").into(), 0.into()].into(),
+                value_type: ValueType::Bool
+            }
+        );
+
+        // But this works.
+
+        assert_eq!(
+            table.get_bool_path(&["array".into(), 0.into()]).unwrap(),
+            true,
+        );
+    }
+
+    #[test]
+    fn GetPathError_IncorrectValueType() {
+        let mut table = DynTable::new();
+
+        let mut other_table = DynTable::new();
+        assert!(!other_table.set(nestr!("foo"), true));
+        assert!(!other_table.set(nestr!("bar"), 3.14));
+
+        assert!(!table.set(nestr!("table"), other_table));
+
+        assert_eq!(
+            table
+                .get_i64_path(&["table".into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::IncorrectValueType(ValueType::Bool)
+        );
+        assert_eq!(
+            table
+                .get_f64_path(&["table".into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::IncorrectValueType(ValueType::Bool)
+        );
+        assert_eq!(
+            table
+                .get_string_path(&["table".into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::IncorrectValueType(ValueType::Bool)
+        );
+        assert_eq!(
+            table
+                .get_array_path(&["table".into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::IncorrectValueType(ValueType::Bool)
+        );
+        assert_eq!(
+            table
+                .get_table_path(&["table".into(), "foo".into()])
+                .err()
+                .unwrap(),
+            GetPathError::IncorrectValueType(ValueType::Bool)
+        );
+
+        // But this works.
+
+        assert_eq!(
+            table
+                .get_bool_path(&["table".into(), "foo".into()])
+                .unwrap(),
+            true
+        );
+
+        assert_eq!(
+            table.get_i64_path(&["table".into(), "bar".into()]).unwrap(),
+            3
+        );
+        assert!(cmp_f64(
+            table.get_f64_path(&["table".into(), "bar".into()]).unwrap(),
+            3.14
+        ));
+    }
+
+    // "array_value = { 54, 12, 78.9 } -- array_value
+    // bool_value = true
+    // float_value = 3.14
+    // int_value = 7
+    // string_value = \"foo\"
+    // table_value = {
+    // \tbar = 2020,
+    // \tbaz = \"hello\",
+    // \tfoo = false,
+    // } -- table_value";
+
+    #[test]
+    fn visit() {
+        let mut config = DynConfig::new();
+
+        let mut array = DynArray::new();
+        array.push(Value::I64(1)).unwrap();
+        array.push(Value::I64(2)).unwrap();
+
+        let mut table = DynTable::new();
+        assert!(!table.set(nestr!("foo"), "bar"));
+
+        assert!(!config.root_mut().set(nestr!("table"), table));
+        assert!(!config.root_mut().set(nestr!("bool"), true));
+        assert!(!config.root_mut().set(nestr!("array"), array));
+
+        struct Visitor(Vec<String>);
+
+        impl<'c> DynConfigVisitor<'c> for Visitor {
+            fn visit_value(&mut self, path: &ConfigPath, value: DynConfigValueRef<'c>) {
+                self.0.push(format!("{} = {}", path, value.get_type()));
+            }
+
+            fn enter_table(&mut self, path: &ConfigPath, table: &'c DynTable) {
+                self.0
+                    .push(format!("enter table {} ({})", path, table.len()));
+            }
+
+            fn leave_table(&mut self, path: &ConfigPath, _table: &'c DynTable) {
+                self.0.push(format!("leave table {}", path));
+            }
+
+            fn enter_array(&mut self, path: &ConfigPath, array: &'c DynArray) {
+                self.0
+                    .push(format!("enter array {} ({})", path, array.len()));
+            }
+
+            fn leave_array(&mut self, path: &ConfigPath, _array: &'c DynArray) {
+                self.0.push(format!("leave array {}", path));
+            }
+        }
+
+        let path = |keys: &[OwnedConfigKey]| -> ConfigPath { keys.to_vec().into() };
+        let array = || path(&[nestr!("array").into()]);
+        let table = || path(&[nestr!("table").into()]);
+
+        let mut visitor = Visitor(Vec::new());
+        config.visit(&mut visitor);
+
+        assert_eq!(
+            visitor.0,
+            vec![
+                format!("enter table {} (3)", ConfigPath::new()),
+                format!("enter array {} (2)", array()),
+                format!(
+                    "{} = {}",
+                    path(&[nestr!("array").into(), 0.into()]),
+                    ValueType::I64
+                ),
+                format!(
+                    "{} = {}",
+                    path(&[nestr!("array").into(), 1.into()]),
+                    ValueType::I64
+                ),
+                format!("leave array {}", array()),
+                format!("{} = {}", path(&[nestr!("bool").into()]), ValueType::Bool),
+                format!("enter table {} (1)", table()),
+                format!(
+                    "{} = {}",
+                    path(&[nestr!("table").into(), nestr!("foo").into()]),
+                    ValueType::String
+                ),
+                format!("leave table {}", table()),
+                format!("leave table {}", ConfigPath::new()),
+            ]
+        );
+
+        // Default methods do nothing.
+        struct Counter(u32);
+
+        impl<'c> DynConfigVisitor<'c> for Counter {
+            fn visit_value(&mut self, _path: &ConfigPath, _value: DynConfigValueRef<'c>) {
+                self.0 += 1;
+            }
+        }
+
+        let mut counter = Counter(0);
+        config.visit(&mut counter);
+        assert_eq!(counter.0, 4);
+    }
+
+    #[test]
+    fn walk_paired() {
+        let mut left = DynConfig::new();
+
+        let mut array = DynArray::new();
+        array.push(Value::I64(1)).unwrap();
+        array.push(Value::I64(2)).unwrap();
+
+        let mut table = DynTable::new();
+        assert!(!table.set(nestr!("foo"), true));
+
+        assert!(!left.root_mut().set(nestr!("array"), array));
+        assert!(!left.root_mut().set(nestr!("bool"), true));
+        assert!(!left.root_mut().set(nestr!("table"), table));
+
+        let mut right = DynConfig::new();
+
+        let mut array = DynArray::new();
+        array.push(Value::I64(1)).unwrap();
+
+        assert!(!right.root_mut().set(nestr!("array"), array));
+        assert!(!right.root_mut().set(nestr!("bool"), false));
+        assert!(!right.root_mut().set(nestr!("int"), 7));
+        assert!(!right.root_mut().set(nestr!("table"), "foo"));
+
+        let mut visited = Vec::new();
+
+        left.walk_paired(&right, |path, left, right| {
+            visited.push((
+                path.clone(),
+                left.map(|val| val.get_type()),
+                right.map(|val| val.get_type()),
+            ));
+        });
+
+        let path = |keys: &[OwnedConfigKey]| -> ConfigPath { keys.to_vec().into() };
+
+        assert_eq!(
+            visited,
+            vec![
+                (
+                    path(&[nestr!("array").into()]),
+                    Some(ValueType::Array),
+                    Some(ValueType::Array)
+                ),
+                (
+                    path(&[nestr!("array").into(), 0.into()]),
+                    Some(ValueType::I64),
+                    Some(ValueType::I64)
+                ),
+                (
+                    path(&[nestr!("array").into(), 1.into()]),
+                    Some(ValueType::I64),
+                    None
+                ),
+                (
+                    path(&[nestr!("bool").into()]),
+                    Some(ValueType::Bool),
+                    Some(ValueType::Bool)
+                ),
+                (path(&[nestr!("int").into()]), None, Some(ValueType::I64)),
+                (
+                    path(&[nestr!("table").into()]),
+                    Some(ValueType::Table),
+                    Some(ValueType::String)
+                ),
+                (
+                    path(&[nestr!("table").into(), nestr!("foo").into()]),
+                    Some(ValueType::Bool),
+                    None
+                ),
+            ]
+        );
+
+        // Values are passed through.
+        left.walk_paired(&right, |path, left, right| {
+            if path.0 == [OwnedConfigKey::from(nestr!("bool"))] {
+                assert_eq!(left.unwrap().bool().unwrap(), true);
+                assert_eq!(right.unwrap().bool().unwrap(), false);
+            }
+        });
+    }
+
+    #[test]
+    fn clone() {
+        let mut config = DynConfig::new();
+
+        let mut array = DynArray::new();
+        array.push(Value::F64(1.5)).unwrap();
+        array.push(Value::F64(7.0)).unwrap();
+
+        let mut table = DynTable::new();
+        assert!(!table.set(nestr!("bar"), "baz"));
+        assert!(!table.set(nestr!("array"), array));
+
+        assert!(!config.root_mut().set(nestr!("foo"), 7));
+        assert!(!config.root_mut().set(nestr!("table"), table));
+
+        let mut snapshot = config.clone();
+
+        assert!(snapshot == config);
+        assert_eq!(snapshot.root().len(), 2);
+        assert_eq!(snapshot.root().get_table("table").unwrap().len(), 2);
+
+        let array = snapshot
+            .root()
+            .get_array_path(&["table".into(), "array".into()])
+            .unwrap();
+        assert_eq!(array.len(), 2);
+        assert!(array.iter().all(|value| value.get_type() == ValueType::F64));
+
+        // Mutating the original does not affect the clone.
+        config
+            .root_mut()
+            .get_table_mut("table")
+            .unwrap()
+            .get_array_mut("array")
+            .unwrap()
+            .push(Value::F64(9.0))
+            .unwrap();
+        assert!(config.root_mut().set(nestr!("foo"), 9));
+
+        assert!(snapshot != config);
+        assert_eq!(snapshot.root().get_i64("foo").unwrap(), 7);
+        assert_eq!(
+            snapshot
+                .root()
+                .get_array_path(&["table".into(), "array".into()])
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Roll back.
+        config = snapshot.clone();
+        assert!(snapshot == config);
+
+        // Mutating the clone does not affect the original.
+        assert!(snapshot.root_mut().remove("table").is_some());
+        assert_eq!(config.root().len(), 2);
+    }
+
+    #[test]
+    fn flatten() {
+        let mut config = DynConfig::new();
+
+        let mut ports = DynArray::new();
+        ports.push(Value::I64(8080)).unwrap();
+        ports.push(Value::I64(8081)).unwrap();
+
+        let mut tables = DynArray::new();
+        let mut table = DynTable::new();
+        assert!(!table.set(nestr!("name"), "foo"));
+        tables.push(table.into()).unwrap();
+
+        let mut server = DynTable::new();
+        assert!(!server.set(nestr!("ports"), ports));
+        assert!(!server.set(nestr!("tables"), tables));
+        assert!(!server.set(nestr!("host"), "localhost"));
+        assert!(!server.set(nestr!("empty"), DynTable::new()));
+
+        assert!(!config.root_mut().set(nestr!("server"), server));
+        assert!(!config.root_mut().set(nestr!("debug"), true));
+        assert!(!config.root_mut().set(nestr!("a.b\\c"), 3.5));
+
+        let flat = config.flatten();
+
+        assert_eq!(
+            flat.iter()
+                .map(|(path, _)| path.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "a\\.b\\\\c",
+                "debug",
+                "server.host",
+                "server.ports.0",
+                "server.ports.1",
+                "server.tables.0.name",
+            ]
+        );
+
+        assert!(matches!(flat[1].1, Value::Bool(true)));
+        assert!(matches!(flat[3].1, Value::I64(8080)));
+        assert!(matches!(flat[5].1, Value::String("foo")));
+
+        // Paths without array indices may be looked up.
+        for (path, value) in &flat[..3] {
+            assert!(config.root().get_val_dotted_path(path).unwrap() == *value);
+        }
+    }
+
+    #[test]
+    fn from_flat() {
+        let config = DynConfig::from_flat(vec![
+            (
+                "server.host",
+                DynConfigValue::String("localhost".to_owned()),
+            ),
+            ("server.ports.0", 8080.into()),
+            ("server.ports.1", 8081.into()),
+            ("server.tables.0.name", "foo".into()),
+            ("server.tables.0.empty", false.into()),
+            ("server.tables.1.name", "bar".into()),
+            ("server.matrix.0.0", 1.into()),
+            ("server.matrix.0.1", 2.into()),
+            ("server.matrix.1.0", 3.into()),
+            ("a\\.b\\\\c", 3.5.into()),
+            ("0", true.into()),
+            ("server.ports.1", 9090.into()),
+        ])
+        .unwrap();
+
+        let root = config.root();
+
+        assert_eq!(root.len(), 3);
+        assert_eq!(
+            root.get_string_path(&["server".into(), "host".into()])
+                .unwrap(),
+            "localhost"
+        );
+        assert_eq!(
+            root.get_array_path(&["server".into(), "ports".into()])
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            root.get_i64_path(&["server".into(), "ports".into(), 1.into()])
+                .unwrap(),
+            9090
+        );
+        assert_eq!(
+            root.get_string_path(&["server".into(), "tables".into(), 1.into(), "name".into()])
+                .unwrap(),
+            "bar"
+        );
+        assert_eq!(
+            root.get_i64_path(&["server".into(), "matrix".into(), 1.into(), 0.into()])
+                .unwrap(),
+            3
+        );
+        assert!(cmp_f64(root.get_f64("a.b\\c").unwrap(), 3.5));
+        assert_eq!(root.get_bool("0").unwrap(), true);
+
+        // Round trip.
+        let flat = config.flatten().into_iter().map(|(path, value)| {
+            let value: DynConfigValue = match value {
+                Value::Bool(value) => value.into(),
+                Value::I64(value) => value.into(),
+                Value::U64(value) => Value::U64(value),
+                Value::F64(value) => value.into(),
+                Value::String(value) => value.into(),
+                Value::Array(_) | Value::Table(_) => unreachable!(),
+            };
+            (path, value)
+        });
+
+        assert!(DynConfig::from_flat(flat).unwrap() == config);
+
+        // Out of order array indices.
+        let config = DynConfig::from_flat(vec![
+            ("ports.2", 3),
+            ("ports.0", 1),
+            ("matrix.1.1", 4),
+            ("ports.1", 2),
+            ("matrix.0.0", 1),
+            ("matrix.1.0", 3),
+            ("matrix.0.1", 2),
+            ("ports.0", 7),
+        ])
+        .unwrap();
+
+        let ports = config.root().get_array("ports").unwrap();
+        assert_eq!(ports.len(), 3);
+        assert_eq!(ports.get_i64(0).unwrap(), 7);
+        assert_eq!(ports.get_i64(1).unwrap(), 2);
+        assert_eq!(ports.get_i64(2).unwrap(), 3);
+
+        let matrix = config.root().get_array("matrix").unwrap();
+        assert_eq!(matrix.len(), 2);
+        assert_eq!(
+            config
+                .root()
+                .get_i64_path(&["matrix".into(), 1.into(), 1.into()])
+                .unwrap(),
+            4
+        );
+
+        // Numeric table keys.
+        let mut config = DynConfig::new();
+
+        let mut table = DynTable::new();
+        assert!(!table.set(nestr!("0"), "foo"));
+        assert!(!table.set(nestr!("1"), "bar"));
+
+        let mut tables = DynArray::new();
+        tables.push(table.clone().into()).unwrap();
+
+        assert!(!config.root_mut().set(nestr!("table"), table));
+        assert!(!config.root_mut().set(nestr!("tables"), tables));
+
+        let flat = config
+            .flatten()
+            .into_iter()
+            .map(|(path, value)| (path, value.string().unwrap().to_owned()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            flat.iter()
+                .map(|(path, _)| path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["table.\\0", "table.\\1", "tables.0.\\0", "tables.0.\\1"]
+        );
+        assert_eq!(
+            config
+                .root()
+                .get_val_dotted_path(&flat[0].0)
+                .unwrap()
+                .string()
+                .unwrap(),
+            "foo"
+        );
+
+        assert!(DynConfig::from_flat(flat).unwrap() == config);
+
+        // Errors.
+        assert_eq!(
+            DynConfig::from_flat(vec![("server..port", 7)])
+                .err()
+                .unwrap(),
+            UnflattenError::EmptyKey(vec![nestr!("server").into()].into())
+        );
+        assert_eq!(
+            DynConfig::from_flat(vec![("server.port", 7), ("server.port.0", 8)])
+                .err()
+                .unwrap(),
+            UnflattenError::TypeConflict {
+                path: vec![nestr!("server").into(), nestr!("port").into()].into(),
+                value_type: ValueType::I64,
+            }
+        );
+        assert_eq!(
+            DynConfig::from_flat(vec![("server.ports.0", 7), ("server.ports.foo", 8)])
+                .err()
+                .unwrap(),
+            // Pairs without array indices are applied first.
+            UnflattenError::TypeConflict {
+                path: vec![nestr!("server").into(), nestr!("ports").into()].into(),
+                value_type: ValueType::Table,
+            }
+        );
+        assert_eq!(
+            DynConfig::from_flat(vec![("server.port", 7), ("server", 8)])
+                .err()
+                .unwrap(),
+            UnflattenError::TypeConflict {
+                path: vec![nestr!("server").into()].into(),
+                value_type: ValueType::Table,
+            }
+        );
+        assert_eq!(
+            DynConfig::from_flat(vec![("ports.1", 7)]).err().unwrap(),
+            UnflattenError::IndexOutOfBounds {
+                path: vec![nestr!("ports").into(), 1.into()].into(),
+                len: 0,
+            }
+        );
+        assert_eq!(
+            DynConfig::from_flat(vec![
+                ("ports.0", 7.into()),
+                ("ports.1", DynConfigValue::Bool(true))
+            ])
+            .err()
+            .unwrap(),
+            UnflattenError::MixedArray {
+                path: vec![nestr!("ports").into(), 1.into()].into(),
+                value_type: ValueType::I64,
+            }
+        );
+        assert_eq!(
+            DynConfig::from_flat(vec![
+                ("ports.0", 7.into()),
+                ("ports.1.foo", DynConfigValue::Bool(true))
+            ])
+            .err()
+            .unwrap(),
+            UnflattenError::MixedArray {
+                path: vec![nestr!("ports").into(), 1.into()].into(),
+                value_type: ValueType::I64,
+            }
+        );
+    }
+
+    #[test]
+    fn expand_env() {
+        std::env::set_var("MINICONFIG_TEST_EXPAND_HOST", "localhost");
+        std::env::set_var("MINICONFIG_TEST_EXPAND_PORT", "8080");
+        std::env::remove_var("MINICONFIG_TEST_EXPAND_UNSET");
+
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        root.set_dotted_path(
+            "db.url",
+            Some("${MINICONFIG_TEST_EXPAND_HOST}:$MINICONFIG_TEST_EXPAND_PORT/db".into()),
+        )
+        .unwrap();
+        root.set_dotted_path("db.port", Some(Value::I64(7)))
+            .unwrap();
+
+        let mut array = DynArray::new();
+        array.push("$MINICONFIG_TEST_EXPAND_HOST".into()).unwrap();
+        array
+            .push("$${MINICONFIG_TEST_EXPAND_HOST}".into())
+            .unwrap();
+        array.push("5$ and $ and $1".into()).unwrap();
+        root.set(nestr!("array"), Value::Array(array));
+
+        config.expand_env().unwrap();
+
+        let root = config.root();
+
+        assert_eq!(
+            root.get_val_dotted_path("db.url")
+                .unwrap()
+                .string()
+                .unwrap(),
+            "localhost:8080/db"
+        );
+        assert_eq!(
+            root.get_val_dotted_path("db.port").unwrap().i64().unwrap(),
+            7
+        );
+        assert_eq!(
+            root.get_array("array").unwrap().as_string_vec().unwrap(),
+            vec![
+                "localhost",
+                "${MINICONFIG_TEST_EXPAND_HOST}",
+                "5$ and $ and $1"
+            ]
+        );
+
+        // Unset variables.
+        let mut array = DynArray::new();
+        array.push("foo".into()).unwrap();
+        array
+            .push("${MINICONFIG_TEST_EXPAND_UNSET}bar".into())
+            .unwrap();
+        config.root_mut().set(nestr!("array"), Value::Array(array));
+
+        let original = config.clone();
+
+        assert_eq!(
+            config.expand_env().err().unwrap(),
+            EnvExpandError::UnsetVariable {
+                path: vec![nestr!("array").into(), 1.into()].into(),
+                name: "MINICONFIG_TEST_EXPAND_UNSET".into(),
+            }
+        );
+        assert!(config == original);
+
+        config
+            .expand_env_opts(EnvExpandOptions { allow_unset: true })
+            .unwrap();
+
+        assert_eq!(
+            config
+                .root()
+                .get_array("array")
+                .unwrap()
+                .as_string_vec()
+                .unwrap(),
+            vec!["foo", "bar"]
+        );
+
+        // Invalid references.
+        for string in &["${MINICONFIG_TEST_EXPAND_HOST", "${}", "${1A}", "${A-B}"] {
+            let mut config = DynConfig::new();
+            config.root_mut().set(nestr!("foo"), *string);
+
+            assert_eq!(
+                config.expand_env().err().unwrap(),
+                EnvExpandError::InvalidReference(vec![nestr!("foo").into()].into())
+            );
+        }
+    }
+
+    #[test]
+    fn apply_override() {
+        let mut config = DynConfig::new();
+
+        config.apply_override("bool", "true").unwrap();
+        config.apply_override("int", "-7").unwrap();
+        config.apply_override("hex", "0x17").unwrap();
+        config.apply_override("float", "3.14").unwrap();
+        config.apply_override("string", "foo bar").unwrap();
+        config.apply_override("empty", "").unwrap();
+        config.apply_override("quoted_bool", "\"true\"").unwrap();
+        config.apply_override("quoted_int", "'7'").unwrap();
+        config.apply_override("capitalized", "True").unwrap();
+        config
+            .apply_override("server.network.port", "8080")
+            .unwrap();
+
+        let root = config.root();
+
+        assert_eq!(root.get_bool("bool").unwrap(), true);
+        assert_eq!(root.get_i64("int").unwrap(), -7);
+        assert_eq!(root.get_i64("hex").unwrap(), 23);
+        assert!(cmp_f64(root.get_f64("float").unwrap(), 3.14));
+        assert_eq!(root.get_string("string").unwrap(), "foo bar");
+        assert_eq!(root.get_string("empty").unwrap(), "");
+        assert_eq!(root.get_string("quoted_bool").unwrap(), "true");
+        assert_eq!(root.get_string("quoted_int").unwrap(), "7");
+        assert_eq!(root.get_string("capitalized").unwrap(), "True");
+        assert!(matches!(
+            root.get_val_dotted_path("server.network.port").unwrap(),
+            Value::I64(8080)
+        ));
+
+        // Overwrites existing values, including with values of different type.
+        config.apply_override("int", "foo").unwrap();
+        assert_eq!(config.root().get_string("int").unwrap(), "foo");
+
+        // Matching quotes are required.
+        assert_eq!(
+            config.apply_override("foo", "\"bar").err().unwrap(),
+            OverrideError::UnterminatedQuote
+        );
+        assert_eq!(
+            config.apply_override("foo", "'bar\"").err().unwrap(),
+            OverrideError::UnterminatedQuote
+        );
+        assert_eq!(
+            config.apply_override("foo", "\"").err().unwrap(),
+            OverrideError::UnterminatedQuote
+        );
+
+        // Intermediate values must be tables.
+        assert_eq!(
+            config.apply_override("bool.foo", "7").err().unwrap(),
+            OverrideError::Path(DynTablePathError::IntermediateValueNotATable {
+                path: vec![nestr!("bool").into()].into(),
+                value_type: ValueType::Bool,
+            })
+        );
+        assert!(!config.root().contains("foo"));
+    }
+
+    #[test]
+    fn to_env_vars() {
+        let mut config = DynConfig::new();
+
+        let mut db = DynTable::new();
+        assert!(!db.set(nestr!("host"), "localhost"));
+        assert!(!db.set(nestr!("port"), 5432));
+
+        let mut servers = DynArray::new();
+
+        let mut server = DynTable::new();
+        assert!(!server.set(nestr!("port"), 80));
+        servers.push(server.into()).unwrap();
+
+        let mut server = DynTable::new();
+        assert!(!server.set(nestr!("port"), 8080));
+        servers.push(server.into()).unwrap();
+
+        assert!(!config.root_mut().set(nestr!("db"), db));
+        assert!(!config.root_mut().set(nestr!("servers"), servers));
+        assert!(!config.root_mut().set(nestr!("debug"), true));
+        assert!(!config.root_mut().set(nestr!("ratio"), 0.5));
+        assert!(!config.root_mut().set(nestr!("empty"), DynTable::new()));
+
+        let vars =
+            |prefix, separator| -> Vec<(String, String)> { config.to_env_vars(prefix, separator) };
+        let var = |name: &str, value: &str| (name.to_owned(), value.to_owned());
+
+        assert_eq!(
+            vars("prefix", "_"),
+            vec![
+                var("PREFIX_DB_HOST", "localhost"),
+                var("PREFIX_DB_PORT", "5432"),
+                var("PREFIX_DEBUG", "true"),
+                var("PREFIX_RATIO", "0.5"),
+                var("PREFIX_SERVERS_0_PORT", "80"),
+                var("PREFIX_SERVERS_1_PORT", "8080"),
+            ]
+        );
+
+        assert_eq!(
+            vars("", "__"),
+            vec![
+                var("DB__HOST", "localhost"),
+                var("DB__PORT", "5432"),
+                var("DEBUG", "true"),
+                var("RATIO", "0.5"),
+                var("SERVERS__0__PORT", "80"),
+                var("SERVERS__1__PORT", "8080"),
+            ]
+        );
+    }
+
+    #[test]
+    fn to_lua_string_opts() {
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        root.set(nestr!("bool"), true);
+        root.set_dotted_path("table.int", Some(Value::I64(7)))
+            .unwrap();
+
+        // Default options match the default output.
+        assert_eq!(
+            config.to_lua_string_opts(Default::default()).unwrap(),
+            config.to_lua_string().unwrap()
+        );
+        assert_eq!(
+            config.to_lua_string().unwrap(),
+            "{\n\tbool = true,\n\ttable = {\n\t\tint = 7,\n\t}, -- table\n}"
+        );
+
+        assert_eq!(
+            config
+                .to_lua_string_opts(LuaDisplayOptions {
+                    indent: LuaIndent::Spaces(2),
+                    key_comments: false,
+                    ..Default::default()
+                })
+                .unwrap(),
+            "{\n  bool = true,\n  table = {\n    int = 7,\n  },\n}"
+        );
+    }
+
+    #[test]
+    fn to_lua_string_compact() {
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        let mut array = DynArray::new();
+        array.push(Value::I64(1)).unwrap();
+        array.push(Value::I64(2)).unwrap();
+
+        root.set(nestr!("array"), array);
+        root.set(nestr!("empty"), DynTable::new());
+        root.set_dotted_path("table.int", Some(Value::I64(7)))
+            .unwrap();
+
+        assert_eq!(
+            config
+                .to_lua_string_opts(LuaDisplayOptions {
+                    compact: true,
+                    trailing_comma: false,
+                    ..Default::default()
+                })
+                .unwrap(),
+            "{ array = { 1, 2 }, empty = {}, table = { int = 7 } }"
+        );
+        assert_eq!(
+            config
+                .to_lua_string_opts(LuaDisplayOptions {
+                    compact: true,
+                    ..Default::default()
+                })
+                .unwrap(),
+            "{ array = { 1, 2, }, empty = {}, table = { int = 7, }, }"
+        );
+
+        // No trailing comma in multi-line mode.
+        assert_eq!(
+            config
+                .to_lua_string_opts(LuaDisplayOptions {
+                    trailing_comma: false,
+                    ..Default::default()
+                })
+                .unwrap(),
+            "{\n\tarray = {\n\t\t1,\n\t\t2\n\t}, -- array\n\tempty = {\n\t}, -- empty\n\ttable = {\n\t\tint = 7\n\t} -- table\n}"
+        );
+    }
+
+    #[cfg(feature = "lua")]
+    #[test]
+    fn from_lua() {
+        let config = DynConfig::from_lua(
+            r#"{
+                array_value = { 54, 12, 78.9 },
+                bool_value = true,
+                float_value = 3.14,
+                int_value = 7,
+                string_value = "foo",
+                table_value = {
+                    bar = 2020,
+                    baz = "hello",
+                    foo = false,
+                },
+            }"#,
+        )
+        .unwrap();
+
+        let array_value = config.root().get_array("array_value").unwrap();
+
+        assert_eq!(array_value.len(), 3);
+        assert_eq!(array_value.get_i64(0).unwrap(), 54);
+        assert_eq!(array_value.get_i64(1).unwrap(), 12);
+        assert!(cmp_f64(array_value.get_f64(2).unwrap(), 78.9));
+
+        assert_eq!(config.root().get_bool("bool_value").unwrap(), true);
+        assert!(cmp_f64(config.root().get_f64("float_value").unwrap(), 3.14));
+        assert_eq!(config.root().get_i64("int_value").unwrap(), 7);
+        assert_eq!(config.root().get_string("string_value").unwrap(), "foo");
+
+        let table_value = config.root().get_table("table_value").unwrap();
+
+        assert_eq!(table_value.len(), 3);
+        assert_eq!(table_value.get_i64("bar").unwrap(), 2020);
+        assert_eq!(table_value.get_string("baz").unwrap(), "hello");
+        assert_eq!(table_value.get_bool("foo").unwrap(), false);
+
+        assert!(matches!(
+            DynConfig::from_lua("7").err().unwrap(),
+            LuaConfigError::InvalidRootType(_)
+        ));
+        assert!(matches!(
+            DynConfig::from_lua(" ?!#>& ").err().unwrap(),
+            LuaConfigError::LuaScriptError(_)
+        ));
+
+        // The Lua error is exposed as the error source.
+        let err: Box<dyn std::error::Error> = DynConfig::from_lua(" ?!#>& ").err().unwrap().into();
+        assert!(err.source().is_some());
+    }
+
+    #[cfg(feature = "lua")]
+    #[test]
+    fn to_lua_config() {
+        let config = DynConfig::from_lua(
+            "{
+                bool = true,
+                int = -7,
+                float = 3.5,
+                string = \"foo\",
+                array = { { 1, 2 }, { 3.5 }, {} },
+                table = { foo = { bar = \"baz\" }, tables = { { a = 1 }, { b = 2 } } },
+            }",
+        )
+        .unwrap();
+
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let lua_config = config.to_lua_config(lua);
+            let root = lua_config.root();
+
+            assert_eq!(root.len(), 6);
+            assert_eq!(root.get_bool("bool").unwrap(), true);
+            assert_eq!(root.get_i64("int").unwrap(), -7);
+            assert!(cmp_f64(root.get_f64("float").unwrap(), 3.5));
+            assert_eq!(root.get_string("string").unwrap().as_ref(), "foo");
+
+            // Nested array lengths are set.
+            let array = root.get_array("array").unwrap();
+            assert_eq!(array.len(), 3);
+            assert_eq!(array.get_array(0).unwrap().len(), 2);
+            assert_eq!(array.get_array(1).unwrap().len(), 1);
+            assert_eq!(array.get_array(2).unwrap().len(), 0);
+            assert_eq!(array.iter().len(), 3);
+
+            // Nested table lengths are set.
+            let table = root.get_table("table").unwrap();
+            assert_eq!(table.len(), 2);
+            assert_eq!(table.get_table("foo").unwrap().len(), 1);
+            assert_eq!(table.get_array("tables").unwrap().len(), 2);
+            assert_eq!(
+                table
+                    .get_string_path(&["foo".into(), "bar".into()])
+                    .unwrap()
+                    .as_ref(),
+                "baz"
+            );
+            assert_eq!(
+                table
+                    .get_i64_path(&["tables".into(), 1.into(), "b".into()])
+                    .unwrap(),
+                2
+            );
+
+            // Round trip.
+            assert!(lua_config.to_dyn_config() == config);
+            assert_eq!(
+                lua_config.to_lua_string().unwrap(),
+                config.to_lua_string().unwrap()
+            );
+        });
+
+        // An empty config.
+        lua.context(|lua| {
+            assert!(DynConfig::new().to_lua_config(lua).root().is_empty());
+        });
+    }
+
+    #[cfg(feature = "lua")]
+    #[test]
+    fn lua_keys() {
+        let keys = [
+            "end",
+            "function",
+            "nil",
+            "3d",
+            "my-key",
+            "weird key",
+            "_underscore",
+            "\"quoted\"",
+            "ключ",
+            "End",
+            "identifier_7",
+        ];
+
+        let mut config = DynConfig::new();
+
+        for (idx, key) in keys.iter().enumerate() {
+            assert!(!config
+                .root_mut()
+                .set(NonEmptyStr::new(key).unwrap(), idx as i64));
+        }
+
+        let script = config.to_lua_string().unwrap();
+
+        // Valid identifiers are written as-is, other keys are bracket-quoted.
+        assert!(script.contains("[\"end\"] = 0,"));
+        assert!(script.contains("[\"3d\"] = 3,"));
+        assert!(script.contains("[\"my-key\"] = 4,"));
+        assert!(script.contains("\t_underscore = 6,"));
+        assert!(script.contains("[\"\\\"quoted\\\"\"] = 7,"));
+        assert!(script.contains("\tEnd = 9,"));
+        assert!(script.contains("\tidentifier_7 = 10,"));
+
+        let other = DynConfig::from_lua(&script).unwrap();
+
+        assert!(other == config);
+
+        for (idx, key) in keys.iter().enumerate() {
+            assert_eq!(other.root().get_i64(key).unwrap(), idx as i64);
+        }
+    }
+
+    #[cfg(feature = "bin")]
+    #[test]
+    fn to_bin_config() {
+        let mut config = DynConfig::new();
+
+        let root = config.root_mut();
+
+        let mut array_value = DynArray::new();
+
+        array_value.push(Value::I64(54)).unwrap();
+        array_value.push(Value::I64(12)).unwrap();
+        array_value.push(Value::F64(78.9)).unwrap();
+
+        assert!(!root.set(nestr!("array_value"), array_value));
+        assert!(!root.set(nestr!("bool_value"), true));
+        assert!(!root.set(nestr!("float_value"), 3.14));
+        assert!(!root.set(nestr!("int_value"), 7));
+        assert!(!root.set(nestr!("string_value"), "foo"));
+
+        let mut table_value = DynTable::new();
+
+        assert!(!table_value.set(nestr!("bar"), 2020));
+        assert!(!table_value.set(nestr!("baz"), "hello"));
+        assert!(!table_value.set(nestr!("foo"), false));
+        assert!(!root.set(nestr!("table_value"), table_value));
+
+        // Serialize to binary config.
+        let data = config.to_bin_config().unwrap();
+
+        // Same data written to the writer.
+        let mut written = Vec::new();
+        config.write_bin_config(&mut written).unwrap();
+        assert_eq!(&data[..], &written[..]);
+
+        // Writer errors are reported.
+        let mut buffer = [0u8; 4];
+        assert_eq!(
+            config.write_bin_config(&mut &mut buffer[..]).err().unwrap(),
+            BinConfigWriterError::WriteError
+        );
+
+        // Load the binary config.
+        let config = BinConfig::new(data).unwrap();
+
+        let array_value = config.root().get_array("array_value".into()).unwrap();
+
+        assert_eq!(array_value.len(), 3);
+        assert_eq!(array_value.get_i64(0).unwrap(), 54);
+        assert!(cmp_f64(array_value.get_f64(0).unwrap(), 54.0));
+        assert_eq!(array_value.get_i64(1).unwrap(), 12);
+        assert!(cmp_f64(array_value.get_f64(1).unwrap(), 12.0));
+        assert_eq!(array_value.get_i64(2).unwrap(), 78);
+        assert!(cmp_f64(array_value.get_f64(2).unwrap(), 78.9));
+
+        assert_eq!(config.root().get_bool("bool_value".into()).unwrap(), true);
+
+        assert!(cmp_f64(
+            config.root().get_f64("float_value".into()).unwrap(),
+            3.14
+        ));
+
+        assert_eq!(config.root().get_i64("int_value".into()).unwrap(), 7);
+
+        assert_eq!(
+            config.root().get_string("string_value".into()).unwrap(),
+            "foo"
+        );
+
+        let table_value = config.root().get_table("table_value".into()).unwrap();
+
+        assert_eq!(table_value.len(), 3);
+        assert_eq!(table_value.get_i64("bar".into()).unwrap(), 2020);
+        assert!(cmp_f64(table_value.get_f64("bar".into()).unwrap(), 2020.0));
+        assert_eq!(table_value.get_string("baz".into()).unwrap(), "hello");
+        assert_eq!(table_value.get_bool("foo".into()).unwrap(), false);
+    }
+
+    #[cfg(all(feature = "bin", feature = "ini"))]
+    #[test]
+    fn key_order() {
+        let keys = ["b", "a", "B", "Z", "z1", "a_b", "ab", "A1", "_"];
+
+        let mut config = DynConfig::new();
+
+        for (idx, key) in keys.iter().enumerate() {
+            assert!(!config
+                .root_mut()
+                .set(NonEmptyStr::new(key).unwrap(), idx as i64));
+        }
+
+        // Ascending by Unicode code point.
+        let mut expected: Vec<_> = keys.iter().map(|key| key.to_string()).collect();
+        expected.sort();
+
+        let text_keys = |text: &str| -> Vec<String> {
+            text.lines()
+                .filter(|line| line.contains('='))
+                .map(|line| line.split('=').next().unwrap().trim().to_string())
+                .collect()
+        };
+
+        // Lua.
+        assert_eq!(text_keys(&config.to_lua_string().unwrap()), expected);
+
+        // INI.
+        assert_eq!(text_keys(&config.to_ini_string().unwrap()), expected);
+
+        // Binary.
+        let bin_config = BinConfig::new(config.to_bin_config().unwrap()).unwrap();
+        let bin_keys: Vec<_> = bin_config
+            .root()
+            .iter()
+            .map(|(key, _)| key.as_str().to_string())
+            .collect();
+
+        assert_eq!(bin_keys, expected);
+    }
+
+    #[test]
+    fn node_count() {
+        let mut config = DynConfig::new();
+
+        assert_eq!(config.node_count(), 1);
+
+        let root = config.root_mut();
+
+        let mut array_value = DynArray::new();
+
+        array_value.push(Value::I64(54)).unwrap();
+        array_value.push(Value::I64(12)).unwrap();
+
+        assert!(!root.set(nestr!("array_value"), array_value));
+        assert!(!root.set(nestr!("bool_value"), true));
+
+        let mut table_value = DynTable::new();
+
+        assert!(!table_value.set(nestr!("bar"), 2020));
+        assert!(!table_value.set(nestr!("baz"), "hello"));
+        assert!(!root.set(nestr!("table_value"), table_value));
+
+        // Root, 3 root values, 2 array elements, 2 table values.
+        assert_eq!(config.node_count(), 8);
+    }
+
+    #[test]
+    fn subtree() {
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        assert!(!root.set(nestr!("int"), 7));
+        root.set_dotted_path("components.renderer.vsync", Some(Value::Bool(true)))
+            .unwrap();
+        root.set_dotted_path("components.audio.volume", Some(Value::F64(0.5)))
+            .unwrap();
+
+        let renderer = config.subtree("components.renderer").unwrap();
+        assert_eq!(renderer.len(), 1);
+        assert!(renderer.get_bool("vsync").unwrap());
+
+        assert_eq!(config.subtree("components").unwrap().len(), 2);
+
+        let mut audio = config.extract("components.audio").unwrap();
+        assert!(cmp_f64(audio.root().get_f64("volume").unwrap(), 0.5));
+
+        // The extracted config is a copy.
+        assert!(!audio.root_mut().set(nestr!("muted"), false));
+        assert_eq!(config.subtree("components.audio").unwrap().len(), 1);
+
+        // Missing / non-table / empty prefixes.
+        for prefix in [
+            "",
+            "missing",
+            "int",
+            "components.renderer.vsync",
+            "components.",
+        ]
+        .iter()
+        {
+            assert!(config.subtree(prefix).is_none());
+            assert!(config.extract(prefix).is_none());
+        }
+    }
+
+    #[test]
+    fn validate_finite() {
+        let mut config = DynConfig::new();
+
+        assert!(config.validate_finite().is_ok());
+
+        let root = config.root_mut();
+
+        assert!(!root.set(nestr!("float_value"), 3.14));
+        assert!(!root.set(nestr!("nan_value"), f64::NAN));
+
+        let mut array_value = DynArray::new();
+
+        array_value.push(Value::F64(1.0)).unwrap();
+        array_value.push(Value::F64(f64::INFINITY)).unwrap();
+
+        let mut table_value = DynTable::new();
+
+        assert!(!table_value.set(nestr!("array_value"), array_value));
+        assert!(!table_value.set(nestr!("inf_value"), f64::NEG_INFINITY));
+        assert!(!root.set(nestr!("table_value"), table_value));
+
+        let paths: Vec<_> = config
+            .validate_finite()
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "nan_value",
+                "table_value/array_value/1",
+                "table_value/inf_value"
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_table_vs_empty_array() {
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        assert!(!root.set(nestr!("array"), DynArray::new()));
+        assert!(!root.set(nestr!("table"), DynTable::new()));
+
+        let check = |config: &DynConfig, array_is_array: bool| {
+            let root = config.root();
+
+            assert_eq!(root.len(), 2);
+            assert!(root.get_table("table").unwrap().is_empty());
+
+            if array_is_array {
+                assert!(root.get_array("array").unwrap().is_empty());
+            } else {
+                assert!(root.get_table("array").unwrap().is_empty());
+            }
+        };
+
+        check(&config, true);
+
+        // Binary config preserves the distinction.
+        #[cfg(feature = "bin")]
+        {
+            let data = config.to_bin_config().unwrap();
+            check(&DynConfig::from_bin_config(&data).unwrap(), true);
+        }
+
+        #[cfg(feature = "lua")]
+        {
+            // Lua config preserves the distinction.
+            let lua = rlua::Lua::new();
+
+            lua.context(|lua| {
+                check(&config.to_lua_config(lua).to_dyn_config(), true);
+            });
+
+            // Lua script does not - empty arrays are read back as empty tables.
+            let string = config.to_lua_string().unwrap();
+            check(&DynConfig::from_lua(&string).unwrap(), false);
+        }
+
+        // JSON preserves the distinction (`[]` vs `{}`).
+        #[cfg(feature = "json")]
+        {
+            let string = config.to_json_string().unwrap();
+            check(&DynConfig::from_json(&string).unwrap(), true);
+        }
+
+        // `.ini` preserves the distinction if arrays are enabled.
+        #[cfg(feature = "ini")]
+        {
+            let string = config
+                .to_ini_string_opts(ToIniStringOptions {
+                    arrays: true,
+                    ..Default::default()
+                })
+                .unwrap();
+            check(
+                &DynConfig::from_ini(IniParser::new(&string).arrays(true)).unwrap(),
+                true,
+            );
+        }
+    }
+
+    #[cfg(feature = "bin")]
+    #[test]
+    fn estimated_bin_size() {
+        let mut config = DynConfig::new();
+
+        assert_eq!(
+            config.estimated_bin_size(),
+            config.to_bin_config().unwrap().len()
+        );
+
+        let root = config.root_mut();
+
+        let mut array_value = DynArray::new();
+
+        array_value.push(Value::String("foo".into())).unwrap();
+        array_value.push(Value::String("bar".into())).unwrap();
+
+        assert!(!root.set(nestr!("array_value"), array_value));
+        assert!(!root.set(nestr!("float_value"), 3.14));
+
+        let mut table_value = DynTable::new();
+
+        assert!(!table_value.set(nestr!("int_value"), 2020));
+        assert!(!table_value.set(nestr!("string_value"), "hello"));
+        assert!(!root.set(nestr!("table_value"), table_value));
+
+        // All strings are unique - the estimate is exact.
+        assert_eq!(
+            config.estimated_bin_size(),
+            config.to_bin_config().unwrap().len()
+        );
+
+        // Repeated strings are deduplicated - the estimate is an upper bound.
+        let root = config.root_mut();
+
+        assert!(!root.set(nestr!("foo"), "foo"));
+        assert!(!root.set(nestr!("hello"), "hello"));
+
+        assert!(config.estimated_bin_size() > config.to_bin_config().unwrap().len());
+    }
+
+    #[cfg(feature = "bin")]
+    #[test]
+    fn from_bin_config() {
+        let mut config = DynConfig::new();
+
+        let root = config.root_mut();
+
+        let mut array_value = DynArray::new();
+
+        array_value.push(Value::I64(54)).unwrap();
+        array_value.push(Value::I64(12)).unwrap();
+        array_value.push(Value::F64(78.9)).unwrap();
+
+        assert!(!root.set(nestr!("array_value"), array_value));
+        assert!(!root.set(nestr!("bool_value"), true));
+        assert!(!root.set(nestr!("float_value"), 3.14));
+        assert!(!root.set(nestr!("int_value"), 7));
+        assert!(!root.set(nestr!("string_value"), "foo"));
+
+        let mut table_value = DynTable::new();
+
+        assert!(!table_value.set(nestr!("bar"), 2020));
+        assert!(!table_value.set(nestr!("baz"), "hello"));
+        assert!(!table_value.set(nestr!("foo"), false));
+        assert!(!root.set(nestr!("table_value"), table_value));
+
+        let data = config.to_bin_config().unwrap();
+
+        // Round trip.
+        let other = DynConfig::from_bin_config(&data).unwrap();
+
+        assert_eq!(
+            config.to_lua_string().unwrap(),
+            other.to_lua_string().unwrap()
+        );
+
+        // Empty / truncated / corrupted data.
+        assert_eq!(
+            DynConfig::from_bin_config(&[]).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+
+        for len in 0..data.len() {
+            assert_eq!(
+                DynConfig::from_bin_config(&data[..len]).err().unwrap(),
+                BinConfigError::InvalidBinaryConfigData
+            );
+        }
+
+        let mut corrupted = data.to_vec();
+        corrupted[0] = !corrupted[0];
+
+        assert_eq!(
+            DynConfig::from_bin_config(&corrupted).err().unwrap(),
+            BinConfigError::InvalidBinaryConfigData
+        );
+
+        // Empty config round trip.
+        let data = DynConfig::new().to_bin_config().unwrap();
+
+        let other = DynConfig::from_bin_config(&data).unwrap();
+        assert!(other.root().is_empty());
+
+        let config = BinConfig::new(data.clone()).unwrap();
+        assert!(config.root().is_empty());
+        assert!(config.trailing_bytes().is_empty());
+
+        for len in 0..data.len() {
+            assert_eq!(
+                DynConfig::from_bin_config(&data[..len]).err().unwrap(),
+                BinConfigError::InvalidBinaryConfigData
+            );
+        }
+    }
+
+    #[test]
+    fn write_lua() {
+        let mut config = DynConfig::new();
+        assert!(!config.root_mut().set(nestr!("int"), 7));
+        assert!(!config.root_mut().set(nestr!("string"), "foo"));
+
+        let mut buffer = Vec::new();
+        config.write_lua(&mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            config.to_lua_string().unwrap()
+        );
+
+        // Write errors are propagated.
+        let mut buffer = [0u8; 4];
+        assert_eq!(
+            config.write_lua(&mut &mut buffer[..]).err().unwrap().kind(),
+            std::io::ErrorKind::WriteZero
+        );
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn write_ini() {
+        let mut config = DynConfig::new();
+        assert!(!config.root_mut().set(nestr!("int"), 7));
+        assert!(!config.root_mut().set(nestr!("string"), "foo"));
+
+        let mut buffer = Vec::new();
+        config.write_ini(&mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            config.to_ini_string().unwrap()
+        );
+
+        // Serialization errors are wrapped.
+        let mut array = DynArray::new();
+        array.push(Value::I64(7)).unwrap();
+        assert!(!config.root_mut().set(nestr!("array"), array));
+
+        let err = config
+            .write_ini_opts(
+                ToIniStringOptions {
+                    arrays: false,
+                    ..Default::default()
+                },
+                &mut Vec::new(),
+            )
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            *err.into_inner()
+                .unwrap()
+                .downcast::<ToIniStringError>()
+                .unwrap(),
+            ToIniStringError::ArraysNotAllowed
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn to_json_string() {
+        let json = r#"{
+	"array": [
+		54,
+		12,
+		78.9
+	],
+	"bool": true,
+	"empty_array": [],
+	"empty_table": {},
+	"float": 3.0,
+	"int": 7,
+	"string": "\"foo\"\n",
+	"table": {
+		"bar": [
+			{
+				"baz": -1.5e300
+			}
+		]
+	}
+}"#;
+
+        let mut config = DynConfig::new();
+
+        let mut array = DynArray::new();
+
+        array.push(Value::I64(54)).unwrap();
+        array.push(Value::I64(12)).unwrap();
+        array.push(Value::F64(78.9)).unwrap();
+
+        assert!(!config.root_mut().set(nestr!("array"), array));
+        assert!(!config.root_mut().set(nestr!("bool"), true));
+        assert!(!config
+            .root_mut()
+            .set(nestr!("empty_array"), DynArray::new()));
+        assert!(!config
+            .root_mut()
+            .set(nestr!("empty_table"), DynTable::new()));
+        assert!(!config.root_mut().set(nestr!("float"), 3.0));
+        assert!(!config.root_mut().set(nestr!("int"), 7));
+        assert!(!config.root_mut().set(nestr!("string"), "\"foo\"\n"));
+
+        let mut baz = DynTable::new();
+        assert!(!baz.set(nestr!("baz"), -1.5e300));
+
+        let mut bar = DynArray::new();
+        bar.push(baz.into()).unwrap();
+
+        let mut table = DynTable::new();
+        assert!(!table.set(nestr!("bar"), bar));
+
+        assert!(!config.root_mut().set(nestr!("table"), table));
+
+        assert_eq!(config.to_json_string().unwrap(), json);
+
+        // Non-finite floats.
+        let baz = config
+            .root_mut()
+            .get_table_path_mut(&["table".into(), "bar".into(), 0.into()])
+            .unwrap();
+        assert!(baz.set(nestr!("baz"), f64::NAN));
+
+        assert_eq!(
+            config.to_json_string().err().unwrap(),
+            ToJSONStringError::NonFiniteFloat(
+                vec![
+                    nestr!("table").into(),
+                    nestr!("bar").into(),
+                    0.into(),
+                    nestr!("baz").into()
+                ]
+                .into()
+            )
+        );
+
+        let non_finite_floats = |config: &DynConfig, non_finite_floats| {
+            config
+                .to_json_string_opts(ToJSONStringOptions {
+                    non_finite_floats,
+                    ..Default::default()
+                })
+                .unwrap()
+        };
+
+        assert!(non_finite_floats(&config, JSONNonFiniteFloats::Null).contains(r#""baz": null"#));
+        assert!(non_finite_floats(&config, JSONNonFiniteFloats::Literal).contains(r#""baz": NaN"#));
+
+        let baz = config
+            .root_mut()
+            .get_table_path_mut(&["table".into(), "bar".into(), 0.into()])
+            .unwrap();
+        assert!(baz.set(nestr!("baz"), f64::NEG_INFINITY));
+
+        assert!(non_finite_floats(&config, JSONNonFiniteFloats::Literal)
+            .contains(r#""baz": -Infinity"#));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn from_json() {
+        let json = r#"{
+	"array": [
+		54,
+		12,
+		78
+	],
+	"bool": true,
+	"empty_array": [],
+	"empty_table": {},
+	"float": 3.0,
+	"int": 7,
+	"string": "\"foo\"\n",
+	"table": {
+		"bar": [
+			{
+				"baz": -1.5e300
+			}
+		]
+	}
+}"#;
+
+        let config = DynConfig::from_json(json).unwrap();
+
+        assert_eq!(
+            config
+                .root()
+                .get_array(nestr!("array"))
+                .unwrap()
+                .iter()
+                .map(|value| value.i64().unwrap())
+                .collect::<Vec<_>>(),
+            vec![54, 12, 78]
+        );
+        assert!(config.root().get_bool(nestr!("bool")).unwrap());
+        assert!(config
+            .root()
+            .get_array(nestr!("empty_array"))
+            .unwrap()
+            .is_empty());
+        assert!(config
+            .root()
+            .get_table(nestr!("empty_table"))
+            .unwrap()
+            .is_empty());
+        // `3.0` is a float, `7` is an integer.
+        assert_eq!(
+            config.root().get_val(nestr!("float")).unwrap().get_type(),
+            ValueType::F64
+        );
+        assert!(cmp_f64(
+            config.root().get_f64(nestr!("float")).unwrap(),
+            3.0
+        ));
+        assert_eq!(
+            config.root().get_val(nestr!("int")).unwrap().get_type(),
+            ValueType::I64
+        );
+        assert_eq!(config.root().get_i64(nestr!("int")).unwrap(), 7);
+        assert_eq!(
+            config.root().get_string(nestr!("string")).unwrap(),
+            "\"foo\"\n"
+        );
+        assert!(cmp_f64(
+            config
+                .root()
+                .get_f64_path(&["table".into(), "bar".into(), 0.into(), "baz".into()])
+                .unwrap(),
+            -1.5e300
+        ));
+
+        // Round trip.
+        assert_eq!(config.to_json_string().unwrap(), json);
+
+        // Integers which don't fit into an `i64` are parsed as unsigned integers, or as floats.
+        let config = DynConfig::from_json(
+            r#"{"big": 9223372036854775808, "huge": 18446744073709551616, "min": -9223372036854775808}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.root().get_u64(nestr!("big")).unwrap(),
+            9223372036854775808
+        );
+        assert_eq!(
+            config.root().get_val(nestr!("huge")).unwrap().get_type(),
+            ValueType::F64
+        );
+        assert_eq!(config.root().get_i64(nestr!("min")).unwrap(), i64::MIN);
+
+        // Escape sequences.
+        let config =
+            DynConfig::from_json(r#"{"s": "\/\b\f\r\t\u0041\u00e9\ud83d\ude00"}"#).unwrap();
+        assert_eq!(
+            config.root().get_string(nestr!("s")).unwrap(),
+            "/\x08\x0c\r\tA\u{e9}\u{1f600}"
+        );
+
+        // Errors.
+        let error = |json| DynConfig::from_json(json).err().unwrap();
+
+        assert_eq!(
+            error("{\n\t\"a\": null\n}"),
+            JSONError {
+                line: 2,
+                column: 10,
+                path: vec![nestr!("a").into()].into(),
+                error: JSONErrorKind::NullValue,
+            }
+        );
+        assert_eq!(error("").error, JSONErrorKind::UnexpectedEndOfFile);
+        assert_eq!(error("[]").error, JSONErrorKind::RootNotAnObject);
+        assert_eq!(error("{} 7").error, JSONErrorKind::UnexpectedCharacter('7'));
+        assert_eq!(
+            error(r#"{"a": 7,}"#).error,
+            JSONErrorKind::UnexpectedCharacter('}')
+        );
+        assert_eq!(error(r#"{"": 7}"#).error, JSONErrorKind::EmptyKey);
+
+        let err = error(r#"{"a": 7, "a": 9}"#);
+        assert_eq!(err.error, JSONErrorKind::DuplicateKey);
+        assert_eq!(err.path, vec![nestr!("a").into()].into());
+
+        // Integers and floats are compatible array element types.
+        let config = DynConfig::from_json(r#"{"a": [1, 2.5]}"#).unwrap();
+        assert_eq!(config.root().get_array("a").unwrap().len(), 2);
+
+        let err = error(r#"{"a": {"b": [1, "2"]}}"#);
+        assert_eq!(err.error, JSONErrorKind::MixedArray);
+        assert_eq!(
+            err.path,
+            vec![nestr!("a").into(), nestr!("b").into(), 1.into()].into()
+        );
+
+        assert_eq!(error(r#"{"a": 07}"#).error, JSONErrorKind::InvalidNumber);
+        assert_eq!(error(r#"{"a": 7.}"#).error, JSONErrorKind::InvalidNumber);
+        assert_eq!(
+            error(r#"{"a": +7}"#).error,
+            JSONErrorKind::UnexpectedCharacter('+')
+        );
+        assert_eq!(
+            error(r#"{"a": NaN}"#).error,
+            JSONErrorKind::UnexpectedCharacter('N')
+        );
+        assert_eq!(
+            error(r#"{"a": tru}"#).error,
+            JSONErrorKind::UnexpectedCharacter('}')
+        );
+        assert_eq!(
+            error("{\"a\": \"\t\"}").error,
+            JSONErrorKind::InvalidCharacterInString('\t')
+        );
+        assert_eq!(
+            error(r#"{"a": "\x"}"#).error,
+            JSONErrorKind::InvalidEscapeCharacter('x')
+        );
+        assert_eq!(
+            error(r#"{"a": "\ud83d"}"#).error,
+            JSONErrorKind::InvalidUnicodeEscapeSequence
+        );
+        assert_eq!(
+            error(r#"{"a": "\u00g0"}"#).error,
+            JSONErrorKind::InvalidUnicodeEscapeSequence
+        );
+        assert_eq!(
+            error(r#"{"a": "foo"#).error,
+            JSONErrorKind::UnexpectedEndOfFile
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_numeric_edge_values() {
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        assert!(!root.set(nestr!("i64_min"), i64::MIN));
+        assert!(!root.set(nestr!("i64_max"), i64::MAX));
+        assert!(!root.set(nestr!("negative_zero"), -0.0));
+        assert!(!root.set(nestr!("min_positive"), f64::MIN_POSITIVE));
+        assert!(!root.set(nestr!("max"), f64::MAX));
+        assert!(!root.set(nestr!("min"), f64::MIN));
+
+        let config = DynConfig::from_json(&config.to_json_string().unwrap()).unwrap();
+        let root = config.root();
+
+        assert_eq!(root.get_i64("i64_min").unwrap(), i64::MIN);
+        assert_eq!(root.get_i64("i64_max").unwrap(), i64::MAX);
+
+        for (key, value) in [
+            ("negative_zero", -0.0),
+            ("min_positive", f64::MIN_POSITIVE),
+            ("max", f64::MAX),
+            ("min", f64::MIN),
+        ]
+        .iter()
+        {
+            assert_eq!(root.get_val(key).unwrap().get_type(), ValueType::F64);
+            // Compare the bits to check the sign of zero.
+            assert_eq!(root.get_f64(key).unwrap().to_bits(), value.to_bits());
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_skip_null_members() {
+        let json = r#"{"a": null, "b": {"c": 7, "d": null}, "e": [1, 2]}"#;
+
+        assert_eq!(
+            DynConfig::from_json(json).err().unwrap().error,
+            JSONErrorKind::NullValue
+        );
+
+        let options = JSONOptions {
+            skip_null_members: true,
+            ..Default::default()
+        };
+
+        let config = DynConfig::from_json_opts(json, options).unwrap();
+        let root = config.root();
+
+        assert_eq!(root.len(), 2);
+        assert!(!root.contains("a"));
+        assert_eq!(root.get_table("b").unwrap().len(), 1);
+        assert_eq!(root.get_table("b").unwrap().get_i64("c").unwrap(), 7);
+        assert_eq!(root.get_array("e").unwrap().len(), 2);
+
+        // `null` array elements are still not supported.
+        let error = DynConfig::from_json_opts(r#"{"a": [null]}"#, options)
+            .err()
+            .unwrap();
+        assert_eq!(error.error, JSONErrorKind::NullValue);
+        assert_eq!(error.path, vec![nestr!("a").into(), 0.into()].into());
+
+        // Invalid literals are still reported.
+        assert_eq!(
+            DynConfig::from_json_opts(r#"{"a": nul}"#, options)
+                .err()
+                .unwrap()
+                .error,
+            JSONErrorKind::UnexpectedCharacter('}')
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_max_depth() {
+        // Parsing.
+        let depth = 10_000;
+        let json = format!("{{\"a\": {}{}}}", "[".repeat(depth), "]".repeat(depth));
+
+        let error = DynConfig::from_json(&json).err().unwrap();
+        assert_eq!(error.error, JSONErrorKind::DepthExceeded);
+        assert_eq!(error.path.0.len(), 129);
+
+        let config = DynConfig::from_json_opts(
+            r#"{"a": {"b": [7]}}"#,
+            JSONOptions {
+                max_depth: 2,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            config
+                .root()
+                .get_i64_path(&["a".into(), "b".into(), 0.into()])
+                .unwrap(),
+            7
+        );
+
+        assert_eq!(
+            DynConfig::from_json_opts(
+                r#"{"a": {"b": [7]}}"#,
+                JSONOptions {
+                    max_depth: 1,
+                    ..Default::default()
+                }
+            )
+            .err()
+            .unwrap()
+            .error,
+            JSONErrorKind::DepthExceeded
+        );
+
+        // Serialization.
+        let mut config = DynConfig::new();
+        let mut table = config.root_mut();
+
+        for _ in 0..200 {
+            table.set(nestr!("a"), DynTable::new());
+            table = table.get_table_mut("a").unwrap();
+        }
+
+        match config.to_json_string().err().unwrap() {
+            ToJSONStringError::DepthExceeded(path) => assert_eq!(path.0.len(), 129),
+            _ => panic!("expected a `DepthExceeded` error"),
+        }
 
-    fn add_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>, overwrite: bool) {
-        let table = self.current_section.as_mut().unwrap_or(&mut self.root);
+        assert!(config
+            .to_json_string_opts(ToJSONStringOptions {
+                max_depth: 200,
+                ..Default::default()
+            })
+            .is_ok());
+    }
 
-        let key = key.as_ne_str();
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml() {
+        let yaml = r#"array:
+  - 1
+  - 2
+bool: true
+empty_array: []
+empty_table: {}
+float: 3.0
+int: 7
+plain: foo
+quoted: "true"
+string: "\"foo\"\n"
+table:
+  bar:
+    - baz: -1.5e300
+      qux: "7"
+  nested:
+    - - 1
+      - 2
+    - []
+u64: 18446744073709551615
+"#;
 
-        let already_existed = match value {
-            IniValue::Bool(value) => table.set(key, value),
-            IniValue::I64(value) => table.set(key, value),
-            IniValue::F64(value) => table.set(key, value),
-            IniValue::String(value) => table.set(key, value.as_str()),
-        };
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
 
-        debug_assert!(
-            overwrite == already_existed,
-            "overwrite flag mismatch when adding a value"
-        );
-    }
+        let mut array = DynArray::new();
+        array.push(Value::I64(1)).unwrap();
+        array.push(Value::I64(2)).unwrap();
 
-    fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, overwrite: bool) {
-        let start_section_in_section =
-            |parent: &mut DynTable, current_section: &mut Option<DynTable>| {
-                // Overwrite the previous value / section with this key in the parent section.
-                if overwrite {
-                    let already_existed = parent.remove(section.as_ne_str());
-                    debug_assert!(
-                        already_existed.is_some(),
-                        "overwrite flag mismatch when starting a section"
-                    );
-                    current_section.replace(DynTable::new());
-
-                // Add a new section or continue the previous section with this key in the parent section.
-                } else {
-                    // Previous value at this key was a section - continue it.
-                    if let Some(previous) = parent
-                        .remove_impl(section.as_ne_str())
-                        .map(Value::table)
-                        .flatten()
-                    {
-                        current_section.replace(previous);
-
-                    // Else it was a value and we will overwrite it.
-                    } else {
-                        current_section.replace(DynTable::new());
-                    }
-                }
-            };
+        assert!(!root.set(nestr!("array"), array.clone()));
+        assert!(!root.set(nestr!("bool"), true));
+        assert!(!root.set(nestr!("empty_array"), DynArray::new()));
+        assert!(!root.set(nestr!("empty_table"), DynTable::new()));
+        assert!(!root.set(nestr!("float"), 3.0));
+        assert!(!root.set(nestr!("int"), 7));
+        assert!(!root.set(nestr!("plain"), "foo"));
+        assert!(!root.set(nestr!("quoted"), "true"));
+        assert!(!root.set(nestr!("string"), "\"foo\"\n"));
+        assert!(!root.set(nestr!("u64"), Value::U64(u64::MAX)));
 
-        if let Some(mut current_section) = self.current_section.take() {
-            start_section_in_section(&mut current_section, &mut self.current_section);
+        let mut baz = DynTable::new();
+        assert!(!baz.set(nestr!("baz"), -1.5e300));
+        assert!(!baz.set(nestr!("qux"), "7"));
 
-            self.section_stack.push(current_section);
-        } else {
-            start_section_in_section(&mut self.root, &mut self.current_section);
-        }
-    }
+        let mut bar = DynArray::new();
+        bar.push(baz.into()).unwrap();
 
-    fn end_section(&mut self, section: NonEmptyIniStr<'s, '_>) {
-        if let Some(current_section) = self.current_section.take() {
-            if let Some(mut parent_section) = self.section_stack.pop() {
-                let already_existed = parent_section.set(section.as_ne_str(), current_section);
-                debug_assert!(!already_existed);
-                self.current_section.replace(parent_section);
-            } else {
-                let already_existed = self.root.set(section.as_ne_str(), current_section);
-                debug_assert!(!already_existed);
-            }
-        } else {
-            debug_assert!(
-                false,
-                "`end_section()` call without a matching `start_section()`"
-            );
-        }
-    }
+        let mut nested = DynArray::new();
+        nested.push(array.into()).unwrap();
+        nested.push(DynArray::new().into()).unwrap();
 
-    fn start_array(&mut self, array: NonEmptyIniStr<'s, '_>, overwrite: bool) {
-        let table = self.current_section.as_mut().unwrap_or(&mut self.root);
+        let mut table = DynTable::new();
+        assert!(!table.set(nestr!("bar"), bar));
+        assert!(!table.set(nestr!("nested"), nested));
 
-        if overwrite {
-            let previous = table.remove(array.as_ne_str());
-            debug_assert!(
-                previous.is_some(),
-                "overwrite flag mismatch when starting an array"
-            );
-        }
+        assert!(!root.set(nestr!("table"), table));
 
-        debug_assert!(
-            self.current_array.is_none(),
-            "nested arrays are not supported"
-        );
-        self.current_array.replace(DynArray::new());
-    }
+        assert_eq!(config.to_yaml_string().unwrap(), yaml);
 
-    fn add_array_value(&mut self, value: IniValue<'s, '_>) {
-        if let Some(current_array) = self.current_array.as_mut() {
-            let result = current_array.push(match value {
-                IniValue::Bool(value) => Value::Bool(value),
-                IniValue::I64(value) => Value::I64(value),
-                IniValue::F64(value) => Value::F64(value),
-                IniValue::String(value) => Value::String(value.into()),
-            });
-            debug_assert!(result.is_ok(), "incorrect array value type");
-        } else {
-            debug_assert!(
-                false,
-                "`add_array_value()` call without a matching `start_array()`"
-            );
-        }
-    }
+        // Round trip.
+        assert!(DynConfig::from_yaml(yaml).unwrap() == config);
 
-    fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>) {
-        if let Some(current_array) = self.current_array.take() {
-            let root = &mut self.root;
-            let table = self.current_section.as_mut().unwrap_or(root);
-            let existed = table.set(array.as_ne_str(), current_array);
-            debug_assert!(!existed);
-        } else {
-            debug_assert!(
-                false,
-                "`end_array()` call without a matching `start_array()`"
-            );
+        // Transcoding to the binary config and back.
+        #[cfg(feature = "bin")]
+        {
+            let data = DynConfig::from_yaml(yaml).unwrap().to_bin_config().unwrap();
+            let bin_config = DynConfig::from_bin_config(&data).unwrap();
+
+            assert!(bin_config == config);
+            assert_eq!(bin_config.to_yaml_string().unwrap(), yaml);
         }
-    }
-}
 
-#[cfg(feature = "bin")]
-/// Writes the dyn table recursively to the binary config writer.
-fn table_to_bin_config(
-    table: &DynTable,
-    writer: &mut BinConfigWriter,
-) -> Result<(), BinConfigWriterError> {
-    // Gather the keys.
-    let mut keys: Vec<_> = table.iter().map(|(key, _)| key).collect();
+        assert_eq!(DynConfig::new().to_yaml_string().unwrap(), "{}\n");
+        assert!(DynConfig::from_yaml("{}").unwrap() == DynConfig::new());
 
-    // Sort the keys in alphabetical order.
-    keys.sort();
+        // Non-finite floats.
+        let config = DynConfig::from_yaml("a: .inf\nb: .nan\n").unwrap();
+        assert_eq!(config.root().get_f64("a").unwrap(), f64::INFINITY);
+        assert!(config.root().get_f64("b").unwrap().is_nan());
+        assert_eq!(config.to_yaml_string().unwrap(), "a: .inf\nb: .nan\n");
 
-    // Iterate the table using the sorted keys.
-    for key in keys.into_iter() {
-        // Must succeed - all keys are valid.
-        let value = unwrap_unchecked(
-            table.get_val(key),
-            "failed to get a value from a dyn config table with a valid key",
+        // Errors.
+        let error = |yaml| DynConfig::from_yaml(yaml).err().unwrap();
+
+        assert_eq!(
+            error("a: ~\n"),
+            YAMLError {
+                path: vec![nestr!("a").into()].into(),
+                error: YAMLErrorKind::NullValue,
+            }
         );
+        assert_eq!(error("").error, YAMLErrorKind::InvalidDocumentCount(0));
+        assert_eq!(error("- 7\n").error, YAMLErrorKind::RootNotAMapping);
+        assert_eq!(error("7: 7\n").error, YAMLErrorKind::NonStringKey);
+        assert_eq!(error("\"\": 7\n").error, YAMLErrorKind::EmptyKey);
+        assert!(matches!(
+            error("a: [7\n").error,
+            YAMLErrorKind::InvalidYAML { .. }
+        ));
 
-        value_to_bin_config(Some(key), value, writer)?;
+        let err = error("a:\n  b:\n    - 1\n    - foo\n");
+        assert_eq!(err.error, YAMLErrorKind::MixedArray);
+        assert_eq!(
+            err.path,
+            vec![nestr!("a").into(), nestr!("b").into(), 1.into()].into()
+        );
     }
 
-    Ok(())
-}
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_max_depth() {
+        let depth = 200;
+        let yaml = format!("a: {}{}\n", "[".repeat(depth), "]".repeat(depth));
 
-#[cfg(feature = "bin")]
-/// Writes the dyn array recursively to the binary config writer.
-fn array_to_bin_config(
-    array: &DynArray,
-    writer: &mut BinConfigWriter,
-) -> Result<(), BinConfigWriterError> {
-    // Iterate the array in order.
-    for value in array.iter() {
-        value_to_bin_config(None, value, writer)?;
-    }
+        let error = DynConfig::from_yaml(&yaml).err().unwrap();
+        assert_eq!(error.error, YAMLErrorKind::DepthExceeded);
+        assert_eq!(error.path.0.len(), 129);
 
-    Ok(())
-}
+        assert!(DynConfig::from_yaml_opts(&yaml, YAMLOptions { max_depth: 200 }).is_ok());
 
-#[cfg(feature = "bin")]
-/// Writes the dyn config value with `key` recursively to the binary config writer.
-fn value_to_bin_config(
-    key: Option<&NonEmptyStr>,
-    value: DynConfigValueRef<'_>,
-    writer: &mut BinConfigWriter,
-) -> Result<(), BinConfigWriterError> {
-    use Value::*;
+        let config =
+            DynConfig::from_yaml_opts("a:\n  b:\n    - 7\n", YAMLOptions { max_depth: 2 }).unwrap();
+        assert_eq!(
+            config
+                .root()
+                .get_i64_path(&["a".into(), "b".into(), 0.into()])
+                .unwrap(),
+            7
+        );
 
-    match value {
-        Bool(value) => {
-            writer.bool(key, value)?;
-        }
-        I64(value) => {
-            writer.i64(key, value)?;
-        }
-        F64(value) => {
-            writer.f64(key, value)?;
-        }
-        String(value) => {
-            writer.string(key, value)?;
-        }
-        Array(value) => {
-            writer.array(key, value.len())?;
-            array_to_bin_config(value, writer)?;
-            writer.end()?;
-        }
-        Table(value) => {
-            writer.table(key, value.len())?;
-            table_to_bin_config(value, writer)?;
-            writer.end()?;
-        }
+        assert_eq!(
+            DynConfig::from_yaml_opts("a:\n  b:\n    - 7\n", YAMLOptions { max_depth: 1 })
+                .err()
+                .unwrap()
+                .error,
+            YAMLErrorKind::DepthExceeded
+        );
     }
 
-    Ok(())
-}
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml() {
+        let toml = r#"array = [1, 2]
+bool = true
+date = 1979-05-27T07:32:00Z
+float = 3.0
+int = 7
+string = "foo"
 
-#[cfg(test)]
-mod tests {
-    #![allow(non_snake_case)]
+[table]
+bar = "baz"
 
-    use {crate::*, ministr_macro::nestr};
+[table.nested]
+a = 1
 
-    #[test]
-    fn GetPathError_PathDoesNotExist() {
-        let mut table = DynTable::new();
+[[tables]]
+a = 1
 
-        let mut foo = DynTable::new();
-        let mut bar = DynArray::new();
-        let mut baz = DynTable::new();
+[[tables]]
+a = 2
+"#;
 
-        assert!(!baz.set(nestr!("bob"), true));
-        bar.push(baz.into()).unwrap();
-        assert!(!foo.set(nestr!("bar"), bar));
-        assert!(!table.set(nestr!("foo"), foo));
+        let config = DynConfig::from_toml(toml).unwrap();
+        let root = config.root();
 
+        assert_eq!(root.get_array("array").unwrap().len(), 2);
+        assert_eq!(root.get_array("array").unwrap().get_i64(1).unwrap(), 2);
+        assert!(root.get_bool("bool").unwrap());
+        assert_eq!(root.get_string("date").unwrap(), "1979-05-27T07:32:00Z");
+        assert_eq!(root.get_val("float").unwrap().get_type(), ValueType::F64);
+        assert!(cmp_f64(root.get_f64("float").unwrap(), 3.0));
+        assert_eq!(root.get_val("int").unwrap().get_type(), ValueType::I64);
+        assert_eq!(root.get_i64("int").unwrap(), 7);
+        assert_eq!(root.get_string("string").unwrap(), "foo");
         assert_eq!(
-            table.get_val_path(&["".into()]).err().unwrap(),
-            GetPathError::KeyDoesNotExist(ConfigPath::new())
+            root.get_string_path(&["table".into(), "bar".into()])
+                .unwrap(),
+            "baz"
         );
         assert_eq!(
-            table
-                .get_val_path(&["foo".into(), "".into()])
-                .err()
+            root.get_i64_path(&["table".into(), "nested".into(), "a".into()])
                 .unwrap(),
-            GetPathError::KeyDoesNotExist(vec![nestr!("foo").into()].into())
+            1
         );
         assert_eq!(
-            table
-                .get_val_path(&["foo".into(), "baz".into()])
-                .err()
+            root.get_i64_path(&["tables".into(), 1.into(), "a".into()])
                 .unwrap(),
-            GetPathError::KeyDoesNotExist(vec![nestr!("foo").into(), nestr!("baz").into()].into())
+            2
         );
 
+        // Round trip.
+        let string = config.to_toml_string().unwrap();
+        assert!(DynConfig::from_toml(&string).unwrap() == config);
+
+        // Arrays of tables are serialized as such; datetimes were parsed as strings.
+        assert!(string.contains("\n[[tables]]\na = 1\n"));
+        assert!(string.contains("\ndate = \"1979-05-27T07:32:00Z\"\n"));
+
+        // Strict mode.
+        let err = DynConfig::from_toml_opts(
+            toml,
+            TOMLOptions {
+                datetimes: TOMLDatetimes::Forbid,
+                ..Default::default()
+            },
+        )
+        .err()
+        .unwrap();
         assert_eq!(
-            table
-                .get_val_path(&["foo".into(), "bar".into(), 0.into(), "bill".into()])
-                .err()
-                .unwrap(),
-            GetPathError::KeyDoesNotExist(
-                vec![
-                    nestr!("foo").into(),
-                    nestr!("bar").into(),
-                    0.into(),
-                    nestr!("bill").into()
-                ]
-                .into()
-            )
+            err,
+            TOMLError {
+                path: vec![nestr!("date").into()].into(),
+                error: TOMLErrorKind::Datetime,
+            }
         );
 
-        // But this works.
+        // Errors.
+        let error = |toml| DynConfig::from_toml(toml).err().unwrap();
+
+        assert!(matches!(
+            error("a = \n").error,
+            TOMLErrorKind::InvalidTOML(_)
+        ));
+        assert_eq!(error("\"\" = 7\n").error, TOMLErrorKind::EmptyKey);
+
+        let mut config = DynConfig::new();
+        let mut table = DynTable::new();
+        assert!(!table.set(nestr!("u64"), Value::U64(u64::MAX)));
+        assert!(!config.root_mut().set(nestr!("table"), table));
 
         assert_eq!(
-            table
-                .get_bool_path(&["foo".into(), "bar".into(), 0.into(), "bob".into()])
-                .unwrap(),
-            true
+            config.to_toml_string().err().unwrap(),
+            ToTOMLStringError::IntegerOutOfRange(
+                vec![nestr!("table").into(), nestr!("u64").into()].into()
+            )
         );
     }
 
+    #[cfg(feature = "toml")]
     #[test]
-    fn GetPathError_IndexOutOfBounds() {
-        let mut table = DynTable::new();
+    fn toml_max_depth() {
+        let depth = 200;
+        let toml = format!("a = {}{}\n", "[".repeat(depth), "]".repeat(depth));
 
-        let mut array = DynArray::new();
-        array.push(true.into()).unwrap();
+        let error = DynConfig::from_toml(&toml).err().unwrap();
+        assert_eq!(error.error, TOMLErrorKind::DepthExceeded);
+        assert!(error.path.0.is_empty());
 
-        assert!(!table.set(nestr!("array"), array));
+        assert!(DynConfig::from_toml_opts(
+            &toml,
+            TOMLOptions {
+                max_depth: 200,
+                ..Default::default()
+            }
+        )
+        .is_ok());
+
+        let toml = "[a]\nb = [7]\n";
 
+        let config = DynConfig::from_toml_opts(
+            toml,
+            TOMLOptions {
+                max_depth: 2,
+                ..Default::default()
+            },
+        )
+        .unwrap();
         assert_eq!(
-            table
-                .get_val_path(&["array".into(), 1.into()])
-                .err()
+            config
+                .root()
+                .get_i64_path(&["a".into(), "b".into(), 0.into()])
                 .unwrap(),
-            GetPathError::IndexOutOfBounds {
-                path: vec![nestr!("array").into(), 1.into()].into(),
-                len: 1
-            }
+            7
         );
 
-        // But this works.
+        assert_eq!(
+            DynConfig::from_toml_opts(
+                toml,
+                TOMLOptions {
+                    max_depth: 1,
+                    ..Default::default()
+                }
+            )
+            .err()
+            .unwrap()
+            .error,
+            TOMLErrorKind::DepthExceeded
+        );
+
+        // Would overflow the stack in the `toml` crate.
+        let depth = 100_000;
+        let toml = format!("a = {}{}\n", "[".repeat(depth), "]".repeat(depth));
+
+        let error = DynConfig::from_toml(&toml).err().unwrap();
+        assert_eq!(error.error, TOMLErrorKind::DepthExceeded);
+        assert!(error.path.0.is_empty());
+
+        let toml = format!("a = {}7{}\n", "{ a = ".repeat(depth), " }".repeat(depth));
 
         assert_eq!(
-            table.get_bool_path(&["array".into(), 0.into()]).unwrap(),
-            true
+            DynConfig::from_toml(&toml).err().unwrap().error,
+            TOMLErrorKind::DepthExceeded
         );
-    }
 
-    #[test]
-    fn GetPathError_ValueNotAnArray() {
-        let mut table = DynTable::new();
+        let toml = format!("[{}]\n", vec!["a"; depth].join("."));
 
-        let mut other_table = DynTable::new();
-        assert!(!other_table.set(nestr!("array"), true));
+        assert_eq!(
+            DynConfig::from_toml(&toml).err().unwrap().error,
+            TOMLErrorKind::DepthExceeded
+        );
 
-        assert!(!table.set(nestr!("table"), other_table));
+        let toml = format!("{} = 7\n", vec!["a"; depth].join("."));
 
         assert_eq!(
-            table
-                .get_val_path(&["table".into(), "array".into(), 1.into()])
-                .err()
-                .unwrap(),
-            GetPathError::ValueNotAnArray {
-                path: vec![nestr!("table").into(), nestr!("array").into()].into(),
-                value_type: ValueType::Bool
-            }
+            DynConfig::from_toml(&toml).err().unwrap().error,
+            TOMLErrorKind::DepthExceeded
         );
 
-        // But this works.
+        // Brackets / braces in strings and comments are ignored, headers / dotted keys / inline tables are counted.
+        let toml = format!(
+            "a = \"{0}\" # {0}\nb = '{0}'\nc = \"\"\"\n{0}\"\"\"\n[d.e]\nf = {{ g.h = [[7]] }}\n",
+            "[{".repeat(depth)
+        );
 
+        let config = DynConfig::from_toml_opts(
+            &toml,
+            TOMLOptions {
+                max_depth: 6,
+                ..Default::default()
+            },
+        )
+        .unwrap();
         assert_eq!(
-            table
-                .get_bool_path(&["table".into(), "array".into()])
+            config
+                .root()
+                .get_i64_path(&[
+                    "d".into(),
+                    "e".into(),
+                    "f".into(),
+                    "g".into(),
+                    "h".into(),
+                    0.into(),
+                    0.into()
+                ])
                 .unwrap(),
-            true,
+            7
+        );
+
+        assert_eq!(
+            DynConfig::from_toml_opts(
+                &toml,
+                TOMLOptions {
+                    max_depth: 5,
+                    ..Default::default()
+                }
+            )
+            .err()
+            .unwrap()
+            .error,
+            TOMLErrorKind::DepthExceeded
         );
     }
 
+    #[cfg(feature = "ron")]
     #[test]
-    fn GetPathError_ValueNotATable() {
-        let mut table = DynTable::new();
-
-        let mut array = DynArray::new();
-        array.push(true.into()).unwrap();
+    fn ron() {
+        let ron = r#"{
+    "array": [1, 2],
+    "bool": true,
+    "char": 'c',
+    "float": 3.0,
+    "int": 7,
+    "option": Some("foo"),
+    "string": "foo \"bar\"",
+    "table": {
+        "bar": "baz",
+        "nested": { "a": 1 },
+    },
+    "tables": [{ "a": 1 }, { "a": 2 }],
+}"#;
 
-        assert!(!table.set(nestr!("array"), array));
+        let config = DynConfig::from_ron(ron).unwrap();
+        let root = config.root();
 
+        assert_eq!(root.get_array("array").unwrap().len(), 2);
+        assert_eq!(root.get_array("array").unwrap().get_i64(1).unwrap(), 2);
+        assert!(root.get_bool("bool").unwrap());
+        assert_eq!(root.get_string("char").unwrap(), "c");
+        assert_eq!(root.get_val("float").unwrap().get_type(), ValueType::F64);
+        assert!(cmp_f64(root.get_f64("float").unwrap(), 3.0));
+        assert_eq!(root.get_val("int").unwrap().get_type(), ValueType::I64);
+        assert_eq!(root.get_i64("int").unwrap(), 7);
+        assert_eq!(root.get_string("option").unwrap(), "foo");
+        assert_eq!(root.get_string("string").unwrap(), "foo \"bar\"");
         assert_eq!(
-            table
-                .get_val_path(&["array".into(), 0.into(), "foo".into()])
-                .err()
+            root.get_i64_path(&["table".into(), "nested".into(), "a".into()])
                 .unwrap(),
-            GetPathError::ValueNotATable {
-                path: vec![nestr!("array").into(), 0.into()].into(),
-                value_type: ValueType::Bool
+            1
+        );
+        assert_eq!(
+            root.get_i64_path(&["tables".into(), 1.into(), "a".into()])
+                .unwrap(),
+            2
+        );
+
+        // Round trip.
+        let string = config.to_ron_string().unwrap();
+        assert!(DynConfig::from_ron(&string).unwrap() == config);
+
+        // Floats keep the fractional part; keys are sorted.
+        assert!(string.starts_with("{\n\t\"array\": [\n\t\t1,\n\t\t2,\n\t],\n"));
+        assert!(string.contains("\n\t\"float\": 3.0,\n"));
+
+        // Transcoding to the binary config and back.
+        #[cfg(feature = "bin")]
+        {
+            let data = config.to_bin_config().unwrap();
+            let bin_config = DynConfig::from_bin_config(&data).unwrap();
+
+            assert!(bin_config == config);
+            assert_eq!(bin_config.to_ron_string().unwrap(), string);
+        }
+
+        // Errors.
+        let error = |ron| DynConfig::from_ron(ron).err().unwrap();
+
+        assert!(matches!(
+            error("{ \"a\": ").error,
+            RONErrorKind::InvalidRON(_)
+        ));
+        assert_eq!(error("[1, 2]").error, RONErrorKind::RootNotAMap);
+        assert_eq!(error("{ 7: 7 }").error, RONErrorKind::NonStringKey);
+        assert_eq!(error("{ \"\": 7 }").error, RONErrorKind::EmptyKey);
+        assert_eq!(
+            error("{ \"a\": { \"b\": None } }"),
+            RONError {
+                path: vec![nestr!("a").into(), nestr!("b").into()].into(),
+                error: RONErrorKind::UnitValue,
+            }
+        );
+        assert_eq!(
+            error("{ \"a\": [1, true] }"),
+            RONError {
+                path: vec![nestr!("a").into(), 1.into()].into(),
+                error: RONErrorKind::MixedArray,
             }
         );
 
-        // But this works.
+        let mut config = DynConfig::new();
+        let mut table = DynTable::new();
+        assert!(!table.set(nestr!("u64"), Value::U64(u64::MAX)));
+        assert!(!config.root_mut().set(nestr!("table"), table));
 
         assert_eq!(
-            table.get_bool_path(&["array".into(), 0.into()]).unwrap(),
-            true,
+            config.to_ron_string().err().unwrap(),
+            ToRONStringError::IntegerOutOfRange(
+                vec![nestr!("table").into(), nestr!("u64").into()].into()
+            )
+        );
+
+        let mut config = DynConfig::new();
+        let mut array = DynArray::new();
+        array.push(Value::F64(f64::NAN)).unwrap();
+        assert!(!config.root_mut().set(nestr!("array"), array));
+
+        assert_eq!(
+            config.to_ron_string().err().unwrap(),
+            ToRONStringError::NonFiniteFloat(vec![nestr!("array").into(), 0.into()].into())
         );
     }
 
+    #[cfg(feature = "ron")]
     #[test]
-    fn GetPathError_IncorrectValueType() {
-        let mut table = DynTable::new();
+    fn ron_max_depth() {
+        // Parsing.
+        let ron = |depth| format!("{{\"a\": {}{}}}", "[".repeat(depth), "]".repeat(depth));
 
-        let mut other_table = DynTable::new();
-        assert!(!other_table.set(nestr!("foo"), true));
-        assert!(!other_table.set(nestr!("bar"), 3.14));
+        // At the limit.
+        assert!(DynConfig::from_ron(&ron(128)).is_ok());
 
-        assert!(!table.set(nestr!("table"), other_table));
+        // One level past the limit - reported with the path.
+        let error = DynConfig::from_ron(&ron(129)).err().unwrap();
+        assert_eq!(error.error, RONErrorKind::DepthExceeded);
+        assert_eq!(error.path.0.len(), 129);
 
+        // Way past the limit - reported by the `ron` crate, without the path.
+        let error = DynConfig::from_ron(&ron(100_000)).err().unwrap();
+        assert_eq!(error.error, RONErrorKind::DepthExceeded);
+        assert!(error.path.0.is_empty());
+
+        assert!(DynConfig::from_ron_opts(&ron(200), RONOptions { max_depth: 200 }).is_ok());
+
+        let config =
+            DynConfig::from_ron_opts(r#"{"a": {"b": [7]}}"#, RONOptions { max_depth: 2 }).unwrap();
         assert_eq!(
-            table
-                .get_i64_path(&["table".into(), "foo".into()])
-                .err()
-                .unwrap(),
-            GetPathError::IncorrectValueType(ValueType::Bool)
-        );
-        assert_eq!(
-            table
-                .get_f64_path(&["table".into(), "foo".into()])
-                .err()
-                .unwrap(),
-            GetPathError::IncorrectValueType(ValueType::Bool)
-        );
-        assert_eq!(
-            table
-                .get_string_path(&["table".into(), "foo".into()])
-                .err()
-                .unwrap(),
-            GetPathError::IncorrectValueType(ValueType::Bool)
-        );
-        assert_eq!(
-            table
-                .get_array_path(&["table".into(), "foo".into()])
-                .err()
+            config
+                .root()
+                .get_i64_path(&["a".into(), "b".into(), 0.into()])
                 .unwrap(),
-            GetPathError::IncorrectValueType(ValueType::Bool)
+            7
         );
+
         assert_eq!(
-            table
-                .get_table_path(&["table".into(), "foo".into()])
+            DynConfig::from_ron_opts(r#"{"a": {"b": [7]}}"#, RONOptions { max_depth: 1 })
                 .err()
                 .unwrap(),
-            GetPathError::IncorrectValueType(ValueType::Bool)
+            RONError {
+                path: vec![nestr!("a").into(), nestr!("b").into()].into(),
+                error: RONErrorKind::DepthExceeded,
+            }
         );
 
-        // But this works.
+        // Serialization.
+        let mut config = DynConfig::new();
+        let mut table = config.root_mut();
 
-        assert_eq!(
-            table
-                .get_bool_path(&["table".into(), "foo".into()])
-                .unwrap(),
-            true
-        );
+        for _ in 0..200 {
+            table.set(nestr!("a"), DynTable::new());
+            table = table.get_table_mut("a").unwrap();
+        }
 
-        assert_eq!(
-            table.get_i64_path(&["table".into(), "bar".into()]).unwrap(),
-            3
-        );
-        assert!(cmp_f64(
-            table.get_f64_path(&["table".into(), "bar".into()]).unwrap(),
-            3.14
-        ));
-    }
+        match config.to_ron_string().err().unwrap() {
+            ToRONStringError::DepthExceeded(path) => assert_eq!(path.0.len(), 129),
+            _ => panic!("expected a `DepthExceeded` error"),
+        }
 
-    // "array_value = { 54, 12, 78.9 } -- array_value
-    // bool_value = true
-    // float_value = 3.14
-    // int_value = 7
-    // string_value = \"foo\"
-    // table_value = {
-    // \tbar = 2020,
-    // \tbaz = \"hello\",
-    // \tfoo = false,
-    // } -- table_value";
+        assert!(config
+            .to_ron_string_opts(ToRONStringOptions { max_depth: 200 })
+            .is_ok());
+    }
 
     #[cfg(feature = "bin")]
     #[test]
-    fn to_bin_config() {
+    fn bin_config_max_depth() {
         let mut config = DynConfig::new();
+        let mut table = config.root_mut();
 
-        let root = config.root_mut();
+        for _ in 0..200 {
+            table.set(nestr!("a"), DynTable::new());
+            table = table.get_table_mut("a").unwrap();
+        }
 
-        let mut array_value = DynArray::new();
+        assert_eq!(
+            config.to_bin_config().err().unwrap(),
+            BinConfigWriterError::DepthExceeded(128)
+        );
+    }
 
-        array_value.push(Value::I64(54)).unwrap();
-        array_value.push(Value::I64(12)).unwrap();
-        array_value.push(Value::F64(78.9)).unwrap();
+    #[test]
+    fn lua_max_depth() {
+        let mut config = DynConfig::new();
+        let mut table = config.root_mut();
 
-        assert!(!root.set(nestr!("array_value"), array_value));
-        assert!(!root.set(nestr!("bool_value"), true));
-        assert!(!root.set(nestr!("float_value"), 3.14));
-        assert!(!root.set(nestr!("int_value"), 7));
-        assert!(!root.set(nestr!("string_value"), "foo"));
+        for _ in 0..200 {
+            table.set(nestr!("a"), DynTable::new());
+            table = table.get_table_mut("a").unwrap();
+        }
 
-        let mut table_value = DynTable::new();
+        match config.to_lua_string().err().unwrap() {
+            ToLuaStringError::DepthExceeded(path) => {
+                assert_eq!(path.0.len(), 129);
+                assert!(path.0.iter().all(|key| key == &nestr!("a").into()));
+            }
+            err => panic!("unexpected error: {}", err),
+        }
 
-        assert!(!table_value.set(nestr!("bar"), 2020));
-        assert!(!table_value.set(nestr!("baz"), "hello"));
-        assert!(!table_value.set(nestr!("foo"), false));
-        assert!(!root.set(nestr!("table_value"), table_value));
+        let err = config.write_lua(&mut Vec::new()).err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
 
-        // Serialize to binary config.
-        let data = config.to_bin_config().unwrap();
+        let script = config
+            .to_lua_string_opts(LuaDisplayOptions {
+                max_depth: 200,
+                ..Default::default()
+            })
+            .unwrap();
 
-        // Load the binary config.
-        let config = BinConfig::new(data).unwrap();
+        // `Display` is not limited.
+        assert_eq!(config.to_string(), script);
+    }
 
-        let array_value = config.root().get_array("array_value".into()).unwrap();
+    #[cfg(feature = "bin")]
+    #[test]
+    fn from_bin_file() {
+        let path = std::env::temp_dir().join("miniconfig_from_bin_file.bin");
 
-        assert_eq!(array_value.len(), 3);
-        assert_eq!(array_value.get_i64(0).unwrap(), 54);
-        assert!(cmp_f64(array_value.get_f64(0).unwrap(), 54.0));
-        assert_eq!(array_value.get_i64(1).unwrap(), 12);
-        assert!(cmp_f64(array_value.get_f64(1).unwrap(), 12.0));
-        assert_eq!(array_value.get_i64(2).unwrap(), 78);
-        assert!(cmp_f64(array_value.get_f64(2).unwrap(), 78.9));
+        let mut config = DynConfig::new();
+        assert!(!config.root_mut().set(nestr!("foo"), 7));
+        std::fs::write(&path, config.to_bin_config().unwrap()).unwrap();
 
-        assert_eq!(config.root().get_bool("bool_value".into()).unwrap(), true);
+        let loaded = DynConfig::from_bin_file(&path).unwrap();
+        assert_eq!(loaded.root().get_i64("foo").unwrap(), 7);
 
-        assert!(cmp_f64(
-            config.root().get_f64("float_value".into()).unwrap(),
-            3.14
+        std::fs::write(&path, b"foo").unwrap();
+
+        let error = DynConfig::from_bin_file(&path).err().unwrap();
+        assert_eq!(error.path, path);
+        assert!(matches!(
+            error.error,
+            FileErrorKind::ConfigError(BinConfigError::InvalidBinaryConfigData)
         ));
 
-        assert_eq!(config.root().get_i64("int_value".into()).unwrap(), 7);
+        std::fs::remove_file(&path).unwrap();
 
-        assert_eq!(
-            config.root().get_string("string_value".into()).unwrap(),
-            "foo"
-        );
+        let error = DynConfig::from_bin_file(&path).err().unwrap();
+        assert_eq!(error.path, path);
+        assert!(matches!(error.error, FileErrorKind::IOError(_)));
+    }
 
-        let table_value = config.root().get_table("table_value".into()).unwrap();
+    #[cfg(feature = "ini")]
+    #[test]
+    fn from_ini_file() {
+        let path = std::env::temp_dir().join("miniconfig_from_ini_file.ini");
 
-        assert_eq!(table_value.len(), 3);
-        assert_eq!(table_value.get_i64("bar".into()).unwrap(), 2020);
-        assert!(cmp_f64(table_value.get_f64("bar".into()).unwrap(), 2020.0));
-        assert_eq!(table_value.get_string("baz".into()).unwrap(), "hello");
-        assert_eq!(table_value.get_bool("foo".into()).unwrap(), false);
+        std::fs::write(&path, "foo = 7").unwrap();
+
+        let config = DynConfig::from_ini_file(&path).unwrap();
+        assert_eq!(config.root().get_i64("foo").unwrap(), 7);
+
+        std::fs::write(&path, "[foo").unwrap();
+
+        let error = DynConfig::from_ini_file(&path).err().unwrap();
+        assert_eq!(error.path, path);
+        assert!(matches!(error.error, FileErrorKind::ConfigError(_)));
+        assert!(error
+            .to_string()
+            .starts_with(&format!("error in \"{}\": ", path.display())));
+
+        std::fs::remove_file(&path).unwrap();
+
+        let error = DynConfig::from_ini_file(&path).err().unwrap();
+        assert_eq!(error.path, path);
+        assert!(matches!(error.error, FileErrorKind::IOError(_)));
     }
 
     #[cfg(feature = "ini")]
@@ -755,4 +4637,268 @@ string = "bar""#;
 
         assert_eq!(string, ini);
     }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn to_canonical_ini_string() {
+        let canonical = r#"array = ["foo", "bar"]
+bool = true
+int = 7
+
+[a]
+float = 3.5
+string = "foo"
+
+[b]
+int = 9
+
+[b/c]
+bool = false"#;
+
+        let l = r#"; Comment.
+int=7
+array = [ "foo","bar" ]
+bool   =   true
+
+[b]
+int = 9 ; Inline comment.
+
+[b/c]
+bool = false
+
+[a]
+string = foo
+float = 3.5"#;
+
+        let r = "bool = true\r\narray = [\"foo\", \"bar\"]\r\nint = 7\r\n\r\n[a]\r\nfloat = 3.5\r\nstring = \"foo\"\r\n[b]\r\nint = 9\r\n[b/c]\r\nbool = false";
+
+        let parse = |string: &str| {
+            DynConfig::from_ini(
+                IniParser::new(string)
+                    .arrays(true)
+                    .inline_comments(true)
+                    .nested_section_depth(u32::MAX),
+            )
+            .unwrap()
+        };
+
+        let l = parse(l);
+        let r = parse(r);
+
+        assert!(l == r);
+
+        assert_eq!(l.to_canonical_ini_string().unwrap(), canonical);
+        assert_eq!(r.to_canonical_ini_string().unwrap(), canonical);
+
+        // Round trip.
+        assert_eq!(
+            parse(canonical).to_canonical_ini_string().unwrap(),
+            canonical
+        );
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn merge() {
+        let base = r#"array = ["foo", "bar"]
+bool = true
+int = 7
+
+[section]
+float = 3.5
+string = "foo""#;
+
+        let overrides = r#"array = ["baz"]
+int = 9
+
+[section]
+string = "bar"
+other_string = "baz"
+
+[other_section]
+bool = false"#;
+
+        let parse =
+            |string: &str| DynConfig::from_ini(IniParser::new(string).arrays(true)).unwrap();
+
+        // Default - arrays are replaced.
+        let mut config = parse(base);
+        config.root_mut().merge(parse(overrides).root()).unwrap();
+
+        let root = config.root();
+
+        assert_eq!(root.len(), 5);
+        assert_eq!(root.get_array("array").unwrap().len(), 1);
+        assert_eq!(
+            root.get_array("array").unwrap().get_string(0).unwrap(),
+            "baz"
+        );
+        assert!(root.get_bool("bool").unwrap());
+        assert_eq!(root.get_i64("int").unwrap(), 9);
+
+        let section = root.get_table("section").unwrap();
+
+        assert_eq!(section.len(), 3);
+        assert_eq!(section.get_f64("float").unwrap(), 3.5);
+        assert_eq!(section.get_string("string").unwrap(), "bar");
+        assert_eq!(section.get_string("other_string").unwrap(), "baz");
+
+        assert!(!root
+            .get_table("other_section")
+            .unwrap()
+            .get_bool("bool")
+            .unwrap());
+
+        // Arrays are appended.
+        let mut config = parse(base);
+        config
+            .root_mut()
+            .merge_opts(
+                parse(overrides).root(),
+                MergeOptions {
+                    array_strategy: MergeArrayStrategy::Append,
+                },
+            )
+            .unwrap();
+
+        let array = config.root().get_array("array").unwrap();
+
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.get_string(0).unwrap(), "foo");
+        assert_eq!(array.get_string(1).unwrap(), "bar");
+        assert_eq!(array.get_string(2).unwrap(), "baz");
+
+        // Incompatible array value types.
+        let mut config = parse(base);
+        assert_eq!(
+            config
+                .root_mut()
+                .merge_opts(
+                    parse("array = [7]").root(),
+                    MergeOptions {
+                        array_strategy: MergeArrayStrategy::Append,
+                    },
+                )
+                .err()
+                .unwrap(),
+            MergeError::MixedArray {
+                path: vec![nestr!("array").into()].into(),
+                value_type: ValueType::String,
+                other_value_type: ValueType::I64,
+            }
+        );
+
+        // Type conflict - the config is not modified.
+        let mut config = parse(base);
+        assert_eq!(
+            config
+                .root_mut()
+                .merge(parse("int = 9\nsection = 7").root())
+                .err()
+                .unwrap(),
+            MergeError::TypeConflict {
+                path: vec![nestr!("section").into()].into(),
+                value_type: ValueType::Table,
+                other_value_type: ValueType::I64,
+            }
+        );
+        assert_eq!(config.root().get_i64("int").unwrap(), 7);
+
+        let mut other_section = DynTable::new();
+        assert!(!other_section.set(nestr!("float"), DynTable::new()));
+
+        let mut other = DynTable::new();
+        assert!(!other.set(nestr!("section"), other_section));
+
+        let mut config = parse(base);
+        assert_eq!(
+            config.root_mut().merge(&other).err().unwrap(),
+            MergeError::TypeConflict {
+                path: vec![nestr!("section").into(), nestr!("float").into()].into(),
+                value_type: ValueType::F64,
+                other_value_type: ValueType::Table,
+            }
+        );
+    }
+
+    #[cfg(feature = "ini")]
+    #[test]
+    fn merge_with_provenance() {
+        let base = r#"array = ["foo", "bar"]
+host = "localhost"
+
+[server]
+port = 80
+timeout = 30"#;
+
+        let overrides = r#"array = ["baz"]
+
+[server]
+port = 8080"#;
+
+        let base = DynConfig::from_ini(IniParser::new(base).arrays(true)).unwrap();
+        let overrides = DynConfig::from_ini(IniParser::new(overrides).arrays(true)).unwrap();
+
+        let (config, provenance) = DynConfig::merge_with_provenance(
+            vec![("base.ini", &base), ("env-override.ini", &overrides)],
+            Default::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config
+                .root()
+                .get_i64_path(&["server".into(), "port".into()])
+                .unwrap(),
+            8080
+        );
+
+        assert_eq!(provenance.len(), 4);
+        assert!(!provenance.is_empty());
+
+        assert_eq!(
+            provenance.source(&["array".into()]).unwrap(),
+            "env-override.ini"
+        );
+        assert_eq!(provenance.source(&["host".into()]).unwrap(), "base.ini");
+        assert_eq!(
+            provenance
+                .source(&["server".into(), "port".into()])
+                .unwrap(),
+            "env-override.ini"
+        );
+        assert_eq!(
+            provenance
+                .source(&["server".into(), "timeout".into()])
+                .unwrap(),
+            "base.ini"
+        );
+
+        // Tables and missing values have no source.
+        assert!(provenance.source(&["server".into()]).is_none());
+        assert!(provenance.source(&["missing".into()]).is_none());
+        assert!(provenance.source(&["".into()]).is_none());
+
+        assert_eq!(
+            provenance.to_string(),
+            "array: env-override.ini\nhost: base.ini\nserver/port: env-override.ini\nserver/timeout: base.ini\n"
+        );
+
+        // Merge errors are reported.
+        let conflict = DynConfig::from_ini(IniParser::new("host = 7\nserver = 7")).unwrap();
+
+        assert_eq!(
+            DynConfig::merge_with_provenance(
+                vec![("base.ini", &base), ("conflict.ini", &conflict)],
+                Default::default(),
+            )
+            .err()
+            .unwrap(),
+            MergeError::TypeConflict {
+                path: vec![nestr!("server").into()].into(),
+                value_type: ValueType::Table,
+                other_value_type: ValueType::I64,
+            }
+        );
+    }
 }
@@ -0,0 +1,98 @@
+use {
+    crate::*,
+    std::{borrow::Borrow, collections::hash_map::Entry},
+};
+
+/// A view into a single entry in a [`table`], which may either be occupied or vacant.
+///
+/// Returned by [`DynTable::entry`].
+/// Allows to insert or modify a [`value`] with a single key lookup.
+///
+/// [`table`]: struct.DynTable.html
+/// [`DynTable::entry`]: struct.DynTable.html#method.entry
+/// [`value`]: type.DynConfigValue.html
+pub struct DynTableEntry<'t>(Entry<'t, NonEmptyString, DynConfigValue>);
+
+impl<'t> DynTableEntry<'t> {
+    pub(super) fn new(entry: Entry<'t, NonEmptyString, DynConfigValue>) -> Self {
+        Self(entry)
+    }
+
+    /// Returns the (non-empty) string key of this entry.
+    pub fn key(&self) -> &NonEmptyStr {
+        self.0.key().borrow()
+    }
+
+    /// Inserts the `default` [`value`] if the entry is vacant.
+    /// Returns a mutable reference to the (existing or inserted) [`value`] in the [`table`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    pub fn or_insert<V: Into<DynConfigValue>>(self, default: V) -> &'t mut DynConfigValue {
+        self.0.or_insert_with(|| default.into())
+    }
+
+    /// Inserts the [`value`] returned by `default` if the entry is vacant.
+    /// Returns a mutable reference to the (existing or inserted) [`value`] in the [`table`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    pub fn or_insert_with<V: Into<DynConfigValue>, F: FnOnce() -> V>(
+        self,
+        default: F,
+    ) -> &'t mut DynConfigValue {
+        self.0.or_insert_with(|| default().into())
+    }
+
+    /// Calls `f` with a mutable reference to the existing [`value`], if the entry is occupied.
+    /// Returns the entry for further chaining.
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    pub fn and_modify<F: FnOnce(&mut DynConfigValue)>(self, f: F) -> Self {
+        Self(self.0.and_modify(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn entry() {
+        let mut table = DynTable::new();
+
+        for word in ["foo", "bar", "foo"].iter() {
+            let key = NonEmptyStr::new(word).unwrap();
+
+            table
+                .entry(key)
+                .and_modify(|count| {
+                    if let Value::I64(count) = count {
+                        *count += 1;
+                    }
+                })
+                .or_insert(1);
+        }
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get_i64("foo").unwrap(), 2);
+        assert_eq!(table.get_i64("bar").unwrap(), 1);
+
+        // Modify the existing value in place.
+        let entry = table.entry(nestr!("foo"));
+        assert_eq!(entry.key(), nestr!("foo"));
+
+        if let Value::I64(count) = entry.or_insert_with(|| 0) {
+            *count = 7;
+        }
+
+        assert_eq!(table.get_i64("foo").unwrap(), 7);
+
+        // Insert lazily.
+        assert!(matches!(
+            table.entry(nestr!("baz")).or_insert_with(|| "qux"),
+            Value::String(string) if *string == "qux"
+        ));
+        assert_eq!(table.get_string("baz").unwrap(), "qux");
+    }
+}
@@ -0,0 +1,442 @@
+use {
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// An error returned by [`get_val_dotted_path`], [`get_val_dotted_path_mut`], [`get_table_dotted_path_mut`],
+/// [`set_dotted_path`] and [`set_dotted_path_no_create`].
+///
+/// [`get_val_dotted_path`]: struct.DynTable.html#method.get_val_dotted_path
+/// [`get_val_dotted_path_mut`]: struct.DynTable.html#method.get_val_dotted_path_mut
+/// [`get_table_dotted_path_mut`]: struct.DynTable.html#method.get_table_dotted_path_mut
+/// [`set_dotted_path`]: struct.DynTable.html#method.set_dotted_path
+/// [`set_dotted_path_no_create`]: struct.DynTable.html#method.set_dotted_path_no_create
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DynTablePathError {
+    /// One of the intermediate keys does not exist in the [`table`], or is empty.
+    /// Contains the path to the invalid key (or to its parent [`table`] if the key is empty).
+    ///
+    /// [`table`]: struct.DynTable.html
+    IntermediateKeyDoesNotExist(ConfigPath),
+    /// Value at one of the intermediate keys is not a [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    IntermediateValueNotATable {
+        /// Path to the value.
+        path: ConfigPath,
+        /// Actual value [`type`].
+        ///
+        /// [`type`]: enum.ValueType.html
+        value_type: ValueType,
+    },
+    /// The final key does not exist in the [`table`], or is empty.
+    /// Contains the path to the invalid key (or to its parent [`table`] if the key is empty).
+    ///
+    /// [`table`]: struct.DynTable.html
+    KeyDoesNotExist(ConfigPath),
+    /// Value at the final key is not a [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    ValueNotATable {
+        /// Path to the value.
+        path: ConfigPath,
+        /// Actual value [`type`].
+        ///
+        /// [`type`]: enum.ValueType.html
+        value_type: ValueType,
+    },
+}
+
+impl Error for DynTablePathError {}
+
+impl Display for DynTablePathError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use DynTablePathError::*;
+
+        match self {
+            IntermediateKeyDoesNotExist(path) => {
+                write!(f, "intermediate key {} does not exist in the table", path)
+            }
+            IntermediateValueNotATable { path, value_type } => write!(
+                f,
+                "intermediate value at {} is not a table (but a \"{}\")",
+                path, value_type
+            ),
+            KeyDoesNotExist(path) => write!(f, "key {} does not exist in the table", path),
+            ValueNotATable { path, value_type } => write!(
+                f,
+                "value at {} is not a table (but a \"{}\")",
+                path, value_type
+            ),
+        }
+    }
+}
+
+/// An error returned by [`merge`] and [`merge_opts`].
+///
+/// [`merge`]: struct.DynTable.html#method.merge
+/// [`merge_opts`]: struct.DynTable.html#method.merge_opts
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum MergeError {
+    /// A [`table`] and a non-[`table`] value, or an [`array`] and a non-[`array`] value,
+    /// are present at the same key in both [`tables`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    TypeConflict {
+        /// Path to the conflicting values.
+        path: ConfigPath,
+        /// Merged-into value [`type`].
+        ///
+        /// [`type`]: enum.ValueType.html
+        value_type: ValueType,
+        /// Merged-from value [`type`].
+        ///
+        /// [`type`]: enum.ValueType.html
+        other_value_type: ValueType,
+    },
+    /// [`Arrays`] with incompatible value types are present at the same key in both [`tables`]
+    /// and must be appended.
+    ///
+    /// [`Arrays`]: struct.DynArray.html
+    /// [`tables`]: struct.DynTable.html
+    MixedArray {
+        /// Path to the [`arrays`].
+        ///
+        /// [`arrays`]: struct.DynArray.html
+        path: ConfigPath,
+        /// Merged-into [`array`] value [`type`].
+        ///
+        /// [`array`]: struct.DynArray.html
+        /// [`type`]: enum.ValueType.html
+        value_type: ValueType,
+        /// Merged-from [`array`] value [`type`].
+        ///
+        /// [`array`]: struct.DynArray.html
+        /// [`type`]: enum.ValueType.html
+        other_value_type: ValueType,
+    },
+}
+
+impl Error for MergeError {}
+
+impl Display for MergeError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use MergeError::*;
+
+        match self {
+            TypeConflict {
+                path,
+                value_type,
+                other_value_type,
+            } => write!(
+                f,
+                "conflicting value types at {} (\"{}\" and \"{}\")",
+                path, value_type, other_value_type
+            ),
+            MixedArray {
+                path,
+                value_type,
+                other_value_type,
+            } => write!(
+                f,
+                "incompatible array value types at {} (\"{}\" and \"{}\")",
+                path, value_type, other_value_type
+            ),
+        }
+    }
+}
+
+/// An error returned by [`TagRegistry`] and the tagged value accessors
+/// ([`get_tagged`] / [`set_tagged`]).
+///
+/// [`TagRegistry`]: struct.TagRegistry.html
+/// [`get_tagged`]: struct.DynTable.html#method.get_tagged
+/// [`set_tagged`]: struct.DynTable.html#method.set_tagged
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TagError {
+    /// The tag contains spaces (`' '`) and cannot be registered in the [`TagRegistry`].
+    ///
+    /// [`TagRegistry`]: struct.TagRegistry.html
+    InvalidTag,
+    /// The requested type is not registered in the [`TagRegistry`].
+    ///
+    /// [`TagRegistry`]: struct.TagRegistry.html
+    UnregisteredType,
+    /// The string is not tagged with the tag registered for the requested type.
+    TagMismatch,
+    /// The tagged string payload failed to parse as the requested type.
+    InvalidPayload,
+    /// The value could not be accessed in the [`table`].
+    /// Contains the [`table`] error.
+    ///
+    /// [`table`]: struct.DynTable.html
+    TableError(TableError),
+}
+
+impl Error for TagError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TagError::TableError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TagError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            TagError::InvalidTag => "tags must not contain spaces".fmt(f),
+            TagError::UnregisteredType => "type is not registered in the tag registry".fmt(f),
+            TagError::TagMismatch => {
+                "string is not tagged with the tag registered for the type".fmt(f)
+            }
+            TagError::InvalidPayload => "tagged string payload failed to parse".fmt(f),
+            TagError::TableError(err) => write!(f, "table error: {}", err),
+        }
+    }
+}
+
+/// An error returned by [`DynTable::from_pairs`].
+///
+/// [`DynTable::from_pairs`]: struct.DynTable.html#method.from_pairs
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FromPairsError {
+    /// One of the keys is empty.
+    /// Contains the index of the offending pair.
+    EmptyKey(u32),
+}
+
+impl Error for FromPairsError {}
+
+impl Display for FromPairsError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use FromPairsError::*;
+
+        match self {
+            EmptyKey(index) => write!(f, "empty key at pair {}", index),
+        }
+    }
+}
+
+/// An error returned by [`DynConfig::from_flat`].
+///
+/// [`DynConfig::from_flat`]: struct.DynConfig.html#method.from_flat
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum UnflattenError {
+    /// One of the keys in the path is empty.
+    /// Contains the path to the parent [`table`] of the empty key.
+    ///
+    /// [`table`]: struct.DynTable.html
+    EmptyKey(ConfigPath),
+    /// The same path is used for values of different types,
+    /// e.g. both as a [`table`] and as an [`array`], or both as a [`table`] / [`array`] and as a leaf value.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    TypeConflict {
+        /// Path to the conflicting value.
+        path: ConfigPath,
+        /// Actual value [`type`].
+        ///
+        /// [`type`]: enum.ValueType.html
+        value_type: ValueType,
+    },
+    /// An [`array`] index skips over missing [`array`] elements.
+    ///
+    /// [`array`]: struct.DynArray.html
+    IndexOutOfBounds {
+        /// Path to the [`array`] element.
+        ///
+        /// [`array`]: struct.DynArray.html
+        path: ConfigPath,
+        /// Current [`array`] length.
+        ///
+        /// [`array`]: struct.DynArray.html
+        len: u32,
+    },
+    /// An [`array`] element is of incorrect and incompatible [`type`].
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`type`]: enum.ValueType.html
+    MixedArray {
+        /// Path to the [`array`] element.
+        ///
+        /// [`array`]: struct.DynArray.html
+        path: ConfigPath,
+        /// [`array`] value [`type`].
+        ///
+        /// [`array`]: struct.DynArray.html
+        /// [`type`]: enum.ValueType.html
+        value_type: ValueType,
+    },
+}
+
+impl Error for UnflattenError {}
+
+impl Display for UnflattenError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use UnflattenError::*;
+
+        match self {
+            EmptyKey(path) => write!(f, "empty key in table at {}", path),
+            TypeConflict { path, value_type } => write!(
+                f,
+                "conflicting value types at {} (already a \"{}\")",
+                path, value_type
+            ),
+            IndexOutOfBounds { path, len } => write!(
+                f,
+                "array index at {} is out of bounds (array length is {})",
+                path, len
+            ),
+            MixedArray { path, value_type } => write!(
+                f,
+                "incompatible array value type at {} (array value type is \"{}\")",
+                path, value_type
+            ),
+        }
+    }
+}
+
+/// An error returned by [`Schema::validate`] and [`Schema::validate_table`].
+///
+/// [`Schema::validate`]: struct.Schema.html#method.validate
+/// [`Schema::validate_table`]: struct.Schema.html#method.validate_table
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SchemaError {
+    /// A required key declared in the [`schema`] is missing from the [`table`].
+    /// Contains the path to the missing key.
+    ///
+    /// [`schema`]: struct.Schema.html
+    /// [`table`]: struct.DynTable.html
+    MissingKey(ConfigPath),
+    /// Value is of incorrect and incompatible [`type`].
+    ///
+    /// [`type`]: enum.ValueType.html
+    IncorrectValueType {
+        /// Path to the value.
+        path: ConfigPath,
+        /// Expected value [`type`].
+        ///
+        /// [`type`]: enum.ValueType.html
+        expected: ValueType,
+        /// Actual value [`type`].
+        ///
+        /// [`type`]: enum.ValueType.html
+        value_type: ValueType,
+    },
+    /// A key not declared in a [`strict`] [`schema`] is present in the [`table`].
+    /// Contains the path to the unexpected key.
+    ///
+    /// [`strict`]: struct.Schema.html#method.strict
+    /// [`schema`]: struct.Schema.html
+    /// [`table`]: struct.DynTable.html
+    UnexpectedKey(ConfigPath),
+}
+
+impl Error for SchemaError {}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use SchemaError::*;
+
+        match self {
+            MissingKey(path) => write!(f, "required key {} is missing", path),
+            IncorrectValueType {
+                path,
+                expected,
+                value_type,
+            } => write!(
+                f,
+                "value at {} is of incorrect type (expected a \"{}\", found a \"{}\")",
+                path, expected, value_type
+            ),
+            UnexpectedKey(path) => write!(f, "unexpected key {}", path),
+        }
+    }
+}
+
+/// An error returned by [`expand_env`] and [`expand_env_opts`].
+///
+/// [`expand_env`]: struct.DynConfig.html#method.expand_env
+/// [`expand_env_opts`]: struct.DynConfig.html#method.expand_env_opts
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum EnvExpandError {
+    /// A `"${"` environment variable reference is not terminated with a `'}'`,
+    /// or does not contain a valid variable name.
+    /// Contains the path to the string value.
+    InvalidReference(ConfigPath),
+    /// A referenced environment variable is not set.
+    UnsetVariable {
+        /// Path to the string value.
+        path: ConfigPath,
+        /// Environment variable name.
+        name: String,
+    },
+    /// A referenced environment variable's value is not valid Unicode.
+    NonUnicodeVariable {
+        /// Path to the string value.
+        path: ConfigPath,
+        /// Environment variable name.
+        name: String,
+    },
+}
+
+impl Error for EnvExpandError {}
+
+impl Display for EnvExpandError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use EnvExpandError::*;
+
+        match self {
+            InvalidReference(path) => write!(
+                f,
+                "invalid environment variable reference in string value at {}",
+                path
+            ),
+            UnsetVariable { path, name } => write!(
+                f,
+                "environment variable \"{}\" referenced at {} is not set",
+                name, path
+            ),
+            NonUnicodeVariable { path, name } => write!(
+                f,
+                "environment variable \"{}\" referenced at {} is not valid Unicode",
+                name, path
+            ),
+        }
+    }
+}
+
+/// An error returned by [`DynConfig::apply_override`].
+///
+/// [`DynConfig::apply_override`]: struct.DynConfig.html#method.apply_override
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum OverrideError {
+    /// The raw value starts with a string quote (`'"'` / `'\''`),
+    /// but does not end with the same (unescaped) string quote.
+    UnterminatedQuote,
+    /// The value could not be set at the path.
+    /// Contains the [`path error`].
+    ///
+    /// [`path error`]: enum.DynTablePathError.html
+    Path(DynTablePathError),
+}
+
+impl Error for OverrideError {}
+
+impl Display for OverrideError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use OverrideError::*;
+
+        match self {
+            UnterminatedQuote => write!(f, "unterminated string quote in override value"),
+            Path(err) => write!(f, "failed to set the override value: {}", err),
+        }
+    }
+}
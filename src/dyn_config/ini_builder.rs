@@ -0,0 +1,571 @@
+use {crate::*, std::convert::Infallible};
+
+/// A hook called by the [`.ini builder`] for each `.ini` key / value pair before it is added to the [`config`].
+///
+/// Allows to validate / transform the values during parsing instead of in a second pass over the [`config`].
+///
+/// Implemented for `()` (adds all values as is) and for closures with a matching signature.
+///
+/// [`.ini builder`]: struct.DynIniBuilder.html
+/// [`config`]: struct.DynConfig.html
+pub trait DynIniHook {
+    /// Error returned by the hook to reject a value.
+    type Error;
+
+    /// Called for each `.ini` key / value pair in the root of the config or in a (nested) section.
+    /// Array values are not passed to the hook, except for the values appended to arrays at duplicate keys
+    /// (see [`IniDuplicateKeys::Array`]), which are passed one by one.
+    ///
+    /// `path` is the full path to the key - (nested) section names, if any, followed by the key.
+    ///
+    /// Return `Ok(Some(value))` to add the (maybe transformed) `value` to the config,
+    /// `Ok(None)` to skip the value, or an error to reject the value.
+    /// The first error is returned by [`finish`]; the hook is not called after an error.
+    ///
+    /// [`finish`]: struct.DynIniBuilder.html#method.finish
+    /// [`IniDuplicateKeys::Array`]: enum.IniDuplicateKeys.html#variant.Array
+    fn add_value(
+        &mut self,
+        path: &ConfigPath,
+        value: DynConfigValue,
+    ) -> Result<Option<DynConfigValue>, Self::Error>;
+}
+
+impl DynIniHook for () {
+    type Error = Infallible;
+
+    fn add_value(
+        &mut self,
+        _path: &ConfigPath,
+        value: DynConfigValue,
+    ) -> Result<Option<DynConfigValue>, Self::Error> {
+        Ok(Some(value))
+    }
+}
+
+impl<F, E> DynIniHook for F
+where
+    F: FnMut(&ConfigPath, DynConfigValue) -> Result<Option<DynConfigValue>, E>,
+{
+    type Error = E;
+
+    fn add_value(
+        &mut self,
+        path: &ConfigPath,
+        value: DynConfigValue,
+    ) -> Result<Option<DynConfigValue>, Self::Error> {
+        self(path, value)
+    }
+}
+
+/// Implements the [`IniConfig`] `.ini` parser event handler which fills a [`DynConfig`].
+///
+/// This is what [`DynConfig::from_ini`] uses internally.
+/// Use it directly with [`IniParser::parse`] to intercept the parsed `.ini` key / value pairs via a [`hook`].
+///
+/// [`IniConfig`]: trait.IniConfig.html
+/// [`DynConfig`]: struct.DynConfig.html
+/// [`DynConfig::from_ini`]: struct.DynConfig.html#method.from_ini
+/// [`IniParser::parse`]: struct.IniParser.html#method.parse
+/// [`hook`]: trait.DynIniHook.html
+pub struct DynIniBuilder<H: DynIniHook = ()> {
+    hook: H,
+    // First error returned by the hook, if any.
+    error: Option<H::Error>,
+    // Path to the current (nested) section.
+    path: ConfigPath,
+    root: DynTable,
+    current_section: Option<DynTable>,
+    // Never allocates if we don't support nested sections.
+    section_stack: Vec<DynTable>,
+    // Always `None` if we don't support arrays.
+    current_array: Option<DynArray>,
+    // Comment lines preceding the next key / section / array, if any.
+    // Always `None` if we don't preserve comments.
+    // Comment lines at the end of the `.ini` string are dropped.
+    pending_comment: Option<String>,
+    // Comments preceding the current section and its parent sections,
+    // attached to the section in its parent section when the section ends.
+    section_comments: Vec<Option<String>>,
+    // Whether the last call was `start_section()` - then the next `start_section()` call
+    // starts a nested section on the same line, to which the comment belongs instead of its parent section.
+    section_started: bool,
+    // Key of the last value / array added to the current section, to which the following inline comment, if any, belongs.
+    // Always `None` if we don't preserve comments.
+    inline_comment_key: Option<NonEmptyString>,
+}
+
+impl DynIniBuilder<()> {
+    /// Creates a new [`.ini builder`] which adds all parsed values to the [`config`] as is.
+    ///
+    /// [`.ini builder`]: struct.DynIniBuilder.html
+    /// [`config`]: struct.DynConfig.html
+    pub fn new() -> Self {
+        Self::with_hook(())
+    }
+
+    pub(crate) fn into_inner(self) -> DynConfig {
+        match self.finish() {
+            Ok(config) => config,
+            Err(err) => match err {},
+        }
+    }
+}
+
+impl Default for DynIniBuilder<()> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: DynIniHook> DynIniBuilder<H> {
+    /// Creates a new [`.ini builder`] which passes the parsed `.ini` key / value pairs to the [`hook`].
+    ///
+    /// [`.ini builder`]: struct.DynIniBuilder.html
+    /// [`hook`]: trait.DynIniHook.html
+    pub fn with_hook(hook: H) -> Self {
+        Self {
+            hook,
+            error: None,
+            path: ConfigPath::new(),
+            root: DynTable::new(),
+            current_section: None,
+            section_stack: Vec::new(),
+            current_array: None,
+            pending_comment: None,
+            section_comments: Vec::new(),
+            section_started: false,
+            inline_comment_key: None,
+        }
+    }
+
+    /// Consumes the [`.ini builder`] and returns the filled [`config`],
+    /// or the first error returned by the [`hook`].
+    ///
+    /// Must be called after the [`.ini parser`] successfully finished parsing.
+    ///
+    /// [`.ini builder`]: struct.DynIniBuilder.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`hook`]: trait.DynIniHook.html
+    /// [`.ini parser`]: struct.IniParser.html#method.parse
+    pub fn finish(self) -> Result<DynConfig, H::Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        debug_assert!(
+            self.current_section.is_none(),
+            "missing `end_section()` call"
+        );
+        debug_assert!(
+            self.section_stack.is_empty(),
+            "missing `end_section()` call"
+        );
+        debug_assert!(self.current_array.is_none(), "missing `end_array()` call");
+
+        let mut config = DynConfig::new();
+        *config.root_mut() = self.root;
+
+        Ok(config)
+    }
+
+    /// Attaches the pending comment, if any, to the `key` in the `table`,
+    /// appending it to the `key`'s previous comment, if any.
+    fn add_comment_to_table(table: &mut DynTable, key: &NonEmptyStr, comment: Option<String>) {
+        if let Some(comment) = comment {
+            let comment = match table.remove_comment(key) {
+                Some(mut previous) => {
+                    previous.push('\n');
+                    previous.push_str(&comment);
+                    previous
+                }
+                None => comment,
+            };
+            table.set_comment(key, comment);
+        }
+    }
+
+    /// Passes the `value` at `key` in the current section to the hook, unless it already returned an error.
+    fn call_hook(&mut self, key: &NonEmptyStr, value: DynConfigValue) -> Option<DynConfigValue> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        self.path.0.push(key.into());
+        let result = self.hook.add_value(&self.path, value);
+        self.path.0.pop();
+
+        result.unwrap_or_else(|err| {
+            self.error.replace(err);
+            None
+        })
+    }
+}
+
+impl<'s, H: DynIniHook> IniConfig<'s> for DynIniBuilder<H> {
+    fn contains_key(&self, key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+        let table = self.current_section.as_ref().unwrap_or(&self.root);
+        table
+            .get_impl(key.as_ne_str())
+            .map(|val| val.table().is_some())
+    }
+
+    fn add_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>, overwrite: bool) {
+        self.section_started = false;
+        self.inline_comment_key.take();
+
+        let key = key.as_ne_str();
+
+        let value = match self.call_hook(key, dyn_config_value_from_ini_value(value)) {
+            Some(value) => value,
+            // The value was skipped / rejected by the hook.
+            None => {
+                self.pending_comment.take();
+                return;
+            }
+        };
+
+        let table = self.current_section.as_mut().unwrap_or(&mut self.root);
+
+        let already_existed = table.set(key, value);
+
+        debug_assert!(
+            overwrite == already_existed,
+            "overwrite flag mismatch when adding a value"
+        );
+
+        Self::add_comment_to_table(table, key, self.pending_comment.take());
+        self.inline_comment_key.replace(key.into());
+    }
+
+    fn append_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>) -> bool {
+        self.section_started = false;
+        self.inline_comment_key.take();
+
+        let key = key.as_ne_str();
+
+        let value = match self.call_hook(key, dyn_config_value_from_ini_value(value)) {
+            Some(value) => value,
+            // The value was skipped / rejected by the hook - leave the current value unchanged.
+            None => {
+                self.pending_comment.take();
+                return true;
+            }
+        };
+
+        self.inline_comment_key.replace(key.into());
+
+        let table = self.current_section.as_mut().unwrap_or(&mut self.root);
+
+        Self::add_comment_to_table(table, key, self.pending_comment.take());
+
+        match table.remove_impl(key) {
+            // Append the value to the array.
+            Some(Value::Array(mut array)) => {
+                let result = array.push(value).is_ok();
+                table.set(key, array);
+                result
+            }
+            // Convert the previous value to a one-element array and append the value to it.
+            Some(previous) => {
+                if previous.get_type().is_compatible(value.get_type()) {
+                    let mut array = DynArray::new();
+
+                    // Must succeed - the value types are compatible.
+                    unwrap_unchecked(array.push(previous), "incorrect array value type");
+                    unwrap_unchecked(array.push(value), "incorrect array value type");
+
+                    table.set(key, array);
+
+                    true
+                } else {
+                    table.set(key, previous);
+
+                    false
+                }
+            }
+            None => {
+                debug_assert!(false, "`append_value()` call for a missing key");
+                false
+            }
+        }
+    }
+
+    fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        let start_section_in_section =
+            |parent: &mut DynTable, current_section: &mut Option<DynTable>| {
+                // Overwrite the previous value / section with this key in the parent section.
+                if overwrite {
+                    let already_existed = parent.remove(section.as_ne_str());
+                    debug_assert!(
+                        already_existed.is_some(),
+                        "overwrite flag mismatch when starting a section"
+                    );
+                    current_section.replace(DynTable::new());
+
+                // Add a new section or continue the previous section with this key in the parent section.
+                } else {
+                    // Previous value at this key was a section - continue it.
+                    if let Some(previous) = parent
+                        .remove_impl(section.as_ne_str())
+                        .map(Value::table)
+                        .flatten()
+                    {
+                        current_section.replace(previous);
+
+                    // Else it was a value and we will overwrite it.
+                    } else {
+                        current_section.replace(DynTable::new());
+                    }
+                }
+            };
+
+        let comment = if self.section_started {
+            self.section_comments.last_mut().map(Option::take).flatten()
+        } else {
+            self.pending_comment.take()
+        };
+        self.section_comments.push(comment);
+        self.section_started = true;
+        self.path.0.push(section.as_ne_str().into());
+
+        if let Some(mut current_section) = self.current_section.take() {
+            start_section_in_section(&mut current_section, &mut self.current_section);
+
+            self.section_stack.push(current_section);
+        } else {
+            start_section_in_section(&mut self.root, &mut self.current_section);
+        }
+    }
+
+    fn end_section(&mut self, section: NonEmptyIniStr<'s, '_>) {
+        self.section_started = false;
+        self.path.0.pop();
+
+        if let Some(current_section) = self.current_section.take() {
+            let comment = self.section_comments.pop().flatten();
+
+            if let Some(mut parent_section) = self.section_stack.pop() {
+                let already_existed = parent_section.set(section.as_ne_str(), current_section);
+                debug_assert!(!already_existed);
+                Self::add_comment_to_table(&mut parent_section, section.as_ne_str(), comment);
+                self.current_section.replace(parent_section);
+            } else {
+                let already_existed = self.root.set(section.as_ne_str(), current_section);
+                debug_assert!(!already_existed);
+                Self::add_comment_to_table(&mut self.root, section.as_ne_str(), comment);
+            }
+        } else {
+            debug_assert!(
+                false,
+                "`end_section()` call without a matching `start_section()`"
+            );
+        }
+    }
+
+    fn start_array(&mut self, array: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        self.section_started = false;
+        self.inline_comment_key.replace(array.as_ne_str().into());
+
+        let table = self.current_section.as_mut().unwrap_or(&mut self.root);
+
+        if overwrite {
+            let previous = table.remove(array.as_ne_str());
+            debug_assert!(
+                previous.is_some(),
+                "overwrite flag mismatch when starting an array"
+            );
+        }
+
+        Self::add_comment_to_table(table, array.as_ne_str(), self.pending_comment.take());
+
+        debug_assert!(
+            self.current_array.is_none(),
+            "nested arrays are not supported"
+        );
+        self.current_array.replace(DynArray::new());
+    }
+
+    fn add_array_value(&mut self, value: IniValue<'s, '_>) {
+        if let Some(current_array) = self.current_array.as_mut() {
+            let result = current_array.push(dyn_config_value_from_ini_value(value));
+            debug_assert!(result.is_ok(), "incorrect array value type");
+        } else {
+            debug_assert!(
+                false,
+                "`add_array_value()` call without a matching `start_array()`"
+            );
+        }
+    }
+
+    fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>) {
+        if let Some(current_array) = self.current_array.take() {
+            let root = &mut self.root;
+            let table = self.current_section.as_mut().unwrap_or(root);
+            let existed = table.set(array.as_ne_str(), current_array);
+            debug_assert!(!existed);
+        } else {
+            debug_assert!(
+                false,
+                "`end_array()` call without a matching `start_array()`"
+            );
+        }
+    }
+
+    fn add_comment(&mut self, comment: &'s str) {
+        match self.pending_comment.as_mut() {
+            Some(pending_comment) => {
+                pending_comment.push('\n');
+                pending_comment.push_str(comment);
+            }
+            None => {
+                self.pending_comment.replace(comment.into());
+            }
+        }
+    }
+
+    fn add_inline_comment(&mut self, comment: &'s str) {
+        if let Some(key) = self.inline_comment_key.take() {
+            let table = self.current_section.as_mut().unwrap_or(&mut self.root);
+
+            if table.contains(key.as_str()) {
+                table.set_inline_comment(key.as_ne_str(), comment);
+            }
+        }
+    }
+}
+
+fn dyn_config_value_from_ini_value(value: IniValue<'_, '_>) -> DynConfigValue {
+    match value {
+        IniValue::Bool(value) => Value::Bool(value),
+        IniValue::I64(value) => Value::I64(value),
+        IniValue::F64(value) => Value::F64(value),
+        IniValue::String(value) => Value::String(value.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn hook() {
+        let ini = "a = 7\nb = foo\n[section]\nc = 9\nd = true";
+
+        // Transform / skip values.
+        let mut paths = Vec::new();
+
+        let mut builder = DynIniBuilder::with_hook(|path: &ConfigPath, value: DynConfigValue| {
+            paths.push(path.clone());
+
+            Result::<_, ()>::Ok(match value {
+                Value::I64(value) => Some(Value::I64(value * 2)),
+                Value::String(_) => None,
+                value => Some(value),
+            })
+        });
+        IniParser::new(ini).parse(&mut builder).unwrap();
+        let config = builder.finish().unwrap();
+
+        assert_eq!(config.root().get_i64("a").unwrap(), 14);
+        assert!(!config.root().contains("b"));
+        assert_eq!(
+            config
+                .root()
+                .get_i64_path(&["section".into(), "c".into()])
+                .unwrap(),
+            18
+        );
+        assert!(config
+            .root()
+            .get_bool_path(&["section".into(), "d".into()])
+            .unwrap());
+
+        let expected_paths: Vec<ConfigPath> = vec![
+            vec![nestr!("a").into()].into(),
+            vec![nestr!("b").into()].into(),
+            vec![nestr!("section").into(), nestr!("c").into()].into(),
+            vec![nestr!("section").into(), nestr!("d").into()].into(),
+        ];
+        assert_eq!(paths, expected_paths);
+
+        // Reject values.
+        let mut builder = DynIniBuilder::with_hook(|path: &ConfigPath, value: DynConfigValue| {
+            if value.get_type() == ValueType::Bool {
+                Err(path.clone())
+            } else {
+                Ok(Some(value))
+            }
+        });
+        IniParser::new(ini).parse(&mut builder).unwrap();
+
+        assert_eq!(
+            builder.finish().err().unwrap(),
+            vec![nestr!("section").into(), nestr!("d").into()].into()
+        );
+
+        // No hook.
+        let mut builder = DynIniBuilder::new();
+        IniParser::new(ini).parse(&mut builder).unwrap();
+
+        assert!(builder.finish().unwrap() == DynConfig::from_ini(IniParser::new(ini)).unwrap());
+    }
+
+    #[test]
+    fn hook_duplicate_keys() {
+        let ini = "a = 1\na = foo\na = 3\n[section]\nb = 7\nb = 9";
+        let parser = || IniParser::new(ini).duplicate_keys(IniDuplicateKeys::Array);
+
+        // Transform / skip appended values.
+        let mut paths = Vec::new();
+
+        let mut builder = DynIniBuilder::with_hook(|path: &ConfigPath, value: DynConfigValue| {
+            paths.push(path.clone());
+
+            Result::<_, ()>::Ok(match value {
+                Value::I64(value) => Some(Value::I64(value * 2)),
+                Value::String(_) => None,
+                value => Some(value),
+            })
+        });
+        parser().parse(&mut builder).unwrap();
+        let config = builder.finish().unwrap();
+
+        let a = config.root().get_array("a").unwrap();
+        assert_eq!(a.len(), 2);
+        assert_eq!(a.get_i64(0).unwrap(), 2);
+        assert_eq!(a.get_i64(1).unwrap(), 6);
+
+        let b = config
+            .root()
+            .get_array_path(&["section".into(), "b".into()])
+            .unwrap();
+        assert_eq!(b.len(), 2);
+        assert_eq!(b.get_i64(0).unwrap(), 14);
+        assert_eq!(b.get_i64(1).unwrap(), 18);
+
+        let expected_paths: Vec<ConfigPath> = vec![
+            vec![nestr!("a").into()].into(),
+            vec![nestr!("a").into()].into(),
+            vec![nestr!("a").into()].into(),
+            vec![nestr!("section").into(), nestr!("b").into()].into(),
+            vec![nestr!("section").into(), nestr!("b").into()].into(),
+        ];
+        assert_eq!(paths, expected_paths);
+
+        // Reject appended values.
+        let mut builder = DynIniBuilder::with_hook(|path: &ConfigPath, value: DynConfigValue| {
+            if value.get_type() == ValueType::String {
+                Err(path.clone())
+            } else {
+                Ok(Some(value))
+            }
+        });
+        parser().parse(&mut builder).unwrap();
+
+        assert_eq!(
+            builder.finish().err().unwrap(),
+            vec![nestr!("a").into()].into()
+        );
+    }
+}
@@ -0,0 +1,412 @@
+use {
+    crate::*,
+    std::{collections::HashMap, ops::Range},
+};
+
+/// A dynamic [`config`] created from an `.ini` string by [`DynConfig::from_ini_lazy`],
+/// which only parses the root keys and the section headers on creation,
+/// and defers parsing the contents of each (top-level) section until it is first accessed.
+///
+/// Retains the borrowed `.ini` source string until all sections are parsed.
+///
+/// [`config`]: struct.DynConfig.html
+/// [`DynConfig::from_ini_lazy`]: struct.DynConfig.html#method.from_ini_lazy
+pub struct LazyDynConfig<'s> {
+    source: &'s str,
+    options: IniOptions,
+    // Root keys and the sections parsed so far.
+    root: DynTable,
+    // Source byte range of the root keys (everything before the first section header).
+    root_range: Range<usize>,
+    // Not yet parsed top-level section names -> all source chunks which belong to them, in source order.
+    sections: HashMap<NonEmptyString, Vec<LazyIniChunk>>,
+}
+
+/// A source chunk which starts with a section header line
+/// and ends before the next section header line or at the end of the source string.
+struct LazyIniChunk {
+    range: Range<usize>,
+    // Source line of the section header, `1`-based.
+    line: u32,
+}
+
+impl<'s> LazyDynConfig<'s> {
+    pub(super) fn new(parser: IniParser<'s>) -> Result<Self, IniError> {
+        let (source, options) = parser.into_parts();
+
+        let mut sections = HashMap::<NonEmptyString, Vec<LazyIniChunk>>::new();
+        let mut root_end = source.len();
+
+        // Section name of the current chunk, its start offset and header line.
+        let mut current: Option<(NonEmptyString, usize, u32)> = None;
+
+        for (line_range, line) in Lines::new(source, options.line_continuation) {
+            if !source[line_range.clone()].trim_start().starts_with('[') {
+                continue;
+            }
+
+            let name = Self::parse_section_name(&source[line_range.clone()], line, options)?;
+
+            if let Some((name, start, line)) = current.take() {
+                sections.entry(name).or_default().push(LazyIniChunk {
+                    range: start..line_range.start,
+                    line,
+                });
+            } else {
+                root_end = line_range.start;
+            }
+
+            current.replace((name, line_range.start, line));
+        }
+
+        if let Some((name, start, line)) = current.take() {
+            sections.entry(name).or_default().push(LazyIniChunk {
+                range: start..source.len(),
+                line,
+            });
+        }
+
+        let mut config = DynIniBuilder::new();
+        IniParser::with_options(&source[..root_end], options).parse(&mut config)?;
+
+        let mut root = DynTable::new();
+        std::mem::swap(&mut root, config.into_inner().root_mut());
+
+        Ok(Self {
+            source,
+            options,
+            root,
+            root_range: 0..root_end,
+            sections,
+        })
+    }
+
+    /// Returns the root [`table`] of the [`config`],
+    /// which contains the root keys and the sections parsed so far.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`config`]: struct.LazyDynConfig.html
+    pub fn root(&self) -> &DynTable {
+        &self.root
+    }
+
+    /// Returns the (top-level) section [`table`] with the (non-empty) string `name`,
+    /// parsing it first if it was not accessed before.
+    ///
+    /// Returns `Ok(None)` if the [`config`] does not contain a section with the `name`.
+    /// Returns an [`error`] if the section contents are not valid;
+    /// the section remains unparsed and the following calls will return the same error.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`config`]: struct.LazyDynConfig.html
+    /// [`error`]: struct.IniError.html
+    pub fn section<N: AsRef<str>>(&mut self, name: N) -> Result<Option<&DynTable>, IniError> {
+        let name = name.as_ref();
+
+        if let Some(name) = NonEmptyStr::new(name) {
+            self.parse_section(name)?;
+        }
+
+        Ok(self.root.get_table(name).ok())
+    }
+
+    /// Returns `true` if the [`config`] contains the (top-level) section with the (non-empty) string `name`
+    /// which was not accessed yet.
+    ///
+    /// [`config`]: struct.LazyDynConfig.html
+    pub fn is_unparsed<N: AsRef<str>>(&self, name: N) -> bool {
+        NonEmptyStr::new(name.as_ref())
+            .map(|name| self.sections.contains_key(name))
+            .unwrap_or(false)
+    }
+
+    /// Returns the number of (top-level) sections which were not accessed yet.
+    pub fn num_unparsed(&self) -> u32 {
+        self.sections.len() as u32
+    }
+
+    /// Consumes this [`config`], parses all sections which were not accessed yet
+    /// and returns the resulting [`dynamic config`].
+    ///
+    /// [`config`]: struct.LazyDynConfig.html
+    /// [`dynamic config`]: struct.DynConfig.html
+    pub fn into_config(mut self) -> Result<DynConfig, IniError> {
+        let names: Vec<_> = self.sections.keys().cloned().collect();
+
+        for name in names.iter() {
+            // Safe to call - we validated the section name.
+            self.parse_section(unwrap_unchecked(
+                NonEmptyStr::new(name.as_ref()),
+                "empty section name",
+            ))?;
+        }
+
+        let mut config = DynConfig::new();
+        *config.root_mut() = self.root;
+
+        Ok(config)
+    }
+
+    /// Parses the section header `line` and returns the (top-level) section name.
+    fn parse_section_name(
+        line: &str,
+        line_index: u32,
+        options: IniOptions,
+    ) -> Result<NonEmptyString, IniError> {
+        let mut recorder = IniSectionNameRecorder(None);
+
+        // Parent sections of nested sections are not declared within the header line.
+        IniParser::with_options(line, options)
+            .implicit_parent_sections(true)
+            .parse(&mut recorder)
+            .map_err(|mut err| {
+                err.line += line_index - 1;
+                err
+            })?;
+
+        // Must succeed - the line starts with a section start delimiter and was parsed successfully.
+        Ok(unwrap_unchecked(
+            recorder.0,
+            "failed to parse the section name in an `.ini` section header",
+        ))
+    }
+
+    /// Parses the section with `name`, if it was not parsed yet, and adds it to the root table.
+    fn parse_section(&mut self, name: &NonEmptyStr) -> Result<(), IniError> {
+        let chunks = match self.sections.get(name) {
+            Some(chunks) => chunks,
+            None => return Ok(()),
+        };
+
+        // Parse the section chunks together with the root keys,
+        // so that root keys which clash with the section name are handled according to the options.
+        let root = &self.source[self.root_range.clone()];
+
+        let mut source = String::from(root);
+
+        for chunk in chunks.iter() {
+            source.push_str(&self.source[chunk.range.clone()]);
+        }
+
+        let mut config = DynIniBuilder::new();
+
+        IniParser::with_options(&source, self.options)
+            .parse(&mut config)
+            .map_err(|mut err| {
+                err.line = Self::source_line(root, chunks, self.source, err.line);
+                err
+            })?;
+
+        if let Some(Value::Table(table)) = config.into_inner().root_mut().remove(name) {
+            self.root.set(name, table);
+        }
+
+        self.sections.remove(name);
+
+        Ok(())
+    }
+
+    /// Maps the `line` in the concatenation of the `root` string and section `chunks` to the line in the `source` string.
+    fn source_line(root: &str, chunks: &[LazyIniChunk], source: &str, line: u32) -> u32 {
+        let mut offset = count_line_breaks(root);
+
+        if line <= offset {
+            return line;
+        }
+
+        for (index, chunk) in chunks.iter().enumerate() {
+            let line_breaks = count_line_breaks(&source[chunk.range.clone()]);
+
+            if (line <= offset + line_breaks) || (index == chunks.len() - 1) {
+                return chunk.line + (line - offset - 1);
+            }
+
+            offset += line_breaks;
+        }
+
+        line
+    }
+}
+
+/// Returns the number of line breaks in the `string`, treating `"\r\n"` as a single line break.
+fn count_line_breaks(string: &str) -> u32 {
+    let mut count = 0;
+    let mut cr = false;
+
+    for c in string.chars() {
+        match c {
+            '\n' if cr => {}
+            '\r' | '\n' => count += 1,
+            _ => {}
+        }
+
+        cr = c == '\r';
+    }
+
+    count
+}
+
+/// Iterator over the (byte range, `1`-based line index) tuples of the lines in the `.ini` source string,
+/// excluding the line breaks and the lines continued from the previous line.
+struct Lines<'s> {
+    source: &'s str,
+    line_continuation: bool,
+    offset: usize,
+    line: u32,
+}
+
+impl<'s> Lines<'s> {
+    fn new(source: &'s str, line_continuation: bool) -> Self {
+        Self {
+            source,
+            line_continuation,
+            offset: 0,
+            line: 1,
+        }
+    }
+
+    /// Returns the range of the line starting at the current offset, excluding the line break,
+    /// and the offset of the next line.
+    fn next_line(&self) -> (Range<usize>, usize) {
+        let rest = &self.source[self.offset..];
+
+        match rest.find(|c| c == '\r' || c == '\n') {
+            Some(end) => {
+                let next = if rest[end..].starts_with("\r\n") {
+                    end + 2
+                } else {
+                    end + 1
+                };
+
+                (self.offset..self.offset + end, self.offset + next)
+            }
+            None => (self.offset..self.source.len(), self.source.len()),
+        }
+    }
+
+    /// Returns `true` if the `line` ends with an (unescaped) line continuation escape sequence.
+    fn is_continued(&self, line: &str) -> bool {
+        self.line_continuation && (line.len() - line.trim_end_matches('\\').len()) % 2 == 1
+    }
+}
+
+impl<'s> Iterator for Lines<'s> {
+    type Item = (Range<usize>, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.source.len() {
+            return None;
+        }
+
+        let (range, next) = self.next_line();
+        let line = self.line;
+
+        self.offset = next;
+        self.line += 1;
+
+        // Skip the continued lines.
+        let mut continued = self.is_continued(&self.source[range.clone()]);
+
+        while continued && (self.offset < self.source.len()) {
+            let (range, next) = self.next_line();
+
+            self.offset = next;
+            self.line += 1;
+
+            continued = self.is_continued(&self.source[range]);
+        }
+
+        Some((range, line))
+    }
+}
+
+/// Records the name of the first (top-level) section started by the `.ini` parser.
+struct IniSectionNameRecorder(Option<NonEmptyString>);
+
+impl<'s> IniConfig<'s> for IniSectionNameRecorder {
+    fn contains_key(&self, _key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+        None
+    }
+
+    fn add_value(
+        &mut self,
+        _key: NonEmptyIniStr<'s, '_>,
+        _value: IniValue<'s, '_>,
+        _overwrite: bool,
+    ) {
+    }
+
+    fn append_value(&mut self, _key: NonEmptyIniStr<'s, '_>, _value: IniValue<'s, '_>) -> bool {
+        true
+    }
+
+    fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, _overwrite: bool) {
+        if self.0.is_none() {
+            self.0.replace(section.as_ne_str().into());
+        }
+    }
+
+    fn end_section(&mut self, _section: NonEmptyIniStr<'s, '_>) {}
+
+    fn start_array(&mut self, _array: NonEmptyIniStr<'s, '_>, _overwrite: bool) {}
+
+    fn add_array_value(&mut self, _value: IniValue<'s, '_>) {}
+
+    fn end_array(&mut self, _array: NonEmptyIniStr<'s, '_>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn from_ini_lazy() {
+        let ini = "a = 7\n\n[foo]\nbar = true\n\n[baz]\nbob = \"bill\"\n\n[foo]\nbaz = 3.5";
+
+        let mut config = DynConfig::from_ini_lazy(IniParser::new(ini)).unwrap();
+
+        // Only the root keys are parsed.
+        assert_eq!(config.num_unparsed(), 2);
+        assert!(config.is_unparsed("foo"));
+        assert!(config.is_unparsed("baz"));
+        assert_eq!(config.root().len(), 1);
+        assert_eq!(config.root().get_i64("a").unwrap(), 7);
+
+        // Duplicate sections are merged by default.
+        let foo = config.section("foo").unwrap().unwrap();
+        assert_eq!(foo.len(), 2);
+        assert!(foo.get_bool("bar").unwrap());
+        assert_eq!(foo.get_f64("baz").unwrap(), 3.5);
+
+        assert!(!config.is_unparsed("foo"));
+        assert_eq!(config.num_unparsed(), 1);
+        assert_eq!(config.root().len(), 2);
+
+        assert!(config.section("missing").unwrap().is_none());
+        assert!(config.section("").unwrap().is_none());
+
+        assert!(config.into_config().unwrap() == DynConfig::from_ini(IniParser::new(ini)).unwrap());
+    }
+
+    #[test]
+    fn from_ini_lazy_errors() {
+        // Errors in section contents are only reported when the section is accessed.
+        let ini = "a = 7\n[foo]\nbar = true\n[baz]\nbob = \"bill\n";
+
+        let mut config = DynConfig::from_ini_lazy(IniParser::new(ini)).unwrap();
+
+        assert!(config.section("foo").unwrap().is_some());
+
+        let error = config.section("baz").err().unwrap();
+        assert_eq!(error.line, 5);
+        assert_eq!(error.error, IniErrorKind::UnexpectedNewLineInQuotedValue);
+
+        assert!(config.is_unparsed("baz"));
+
+        // Errors in section headers are reported immediately.
+        let error = DynConfig::from_ini_lazy(IniParser::new("a = 7\n[foo\n"))
+            .err()
+            .unwrap();
+        assert_eq!(error.line, 2);
+    }
+}
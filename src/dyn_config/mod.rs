@@ -1,6 +1,24 @@
 mod array;
+mod builder;
 mod config;
+mod entry;
+mod error;
+#[cfg(feature = "ini")]
+mod ini_builder;
+#[cfg(feature = "ini")]
+mod lazy;
+mod options;
+mod provenance;
+mod schema;
 mod table;
+mod tag;
 mod value;
+mod visitor;
 
-pub use {array::*, config::*, table::*, value::*};
+pub use {
+    array::*, builder::*, config::*, entry::*, error::*, options::*, provenance::*, schema::*,
+    table::*, tag::*, value::*, visitor::DynConfigVisitor,
+};
+
+#[cfg(feature = "ini")]
+pub use {ini_builder::*, lazy::*};
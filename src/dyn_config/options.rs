@@ -0,0 +1,52 @@
+/// Controls how [`arrays`] present at the same key in both [`tables`] are merged.
+///
+/// [`arrays`]: struct.DynArray.html
+/// [`tables`]: struct.DynTable.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MergeArrayStrategy {
+    /// The merged-into [`array`] is replaced wholesale by the merged-from [`array`].
+    ///
+    /// [`array`]: struct.DynArray.html
+    Replace,
+    /// The merged-from [`array`]'s values are appended to the merged-into [`array`].
+    /// Value types of both [`arrays`] must be compatible.
+    ///
+    /// [`array`]: struct.DynArray.html
+    /// [`arrays`]: struct.DynArray.html
+    Append,
+}
+
+/// Configuration options for merging one [`table`] into another.
+///
+/// [`table`]: struct.DynTable.html
+#[derive(Clone, Copy, Debug)]
+pub struct MergeOptions {
+    /// [`Array`] merge strategy.
+    ///
+    /// Default: [`Replace`](enum.MergeArrayStrategy.html#variant.Replace).
+    ///
+    /// [`Array`]: struct.DynArray.html
+    pub array_strategy: MergeArrayStrategy,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        Self {
+            array_strategy: MergeArrayStrategy::Replace,
+        }
+    }
+}
+
+/// Configuration options for expanding environment variable references in a [`config`]'s string values.
+///
+/// [`config`]: struct.DynConfig.html
+#[derive(Clone, Copy, Default, Debug)]
+pub struct EnvExpandOptions {
+    /// Whether references to unset environment variables are replaced with empty strings.
+    /// If `false`, they cause an [`error`].
+    ///
+    /// Default: `false`.
+    ///
+    /// [`error`]: enum.EnvExpandError.html#variant.UnsetVariable
+    pub allow_unset: bool,
+}
@@ -0,0 +1,122 @@
+use {
+    crate::{util::*, *},
+    std::{
+        borrow::Borrow,
+        collections::HashMap,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// Maps the paths to the leaf (non-[`table`]) values of a [`config`] merged from several labeled sources
+/// to the labels of the sources the values came from.
+///
+/// Returned by [`merge_with_provenance`].
+///
+/// [`table`]: struct.DynTable.html
+/// [`config`]: struct.DynConfig.html
+/// [`merge_with_provenance`]: struct.DynConfig.html#method.merge_with_provenance
+pub struct MergeProvenance {
+    // Source labels, in merge order.
+    labels: Vec<String>,
+    // Leaf value paths -> indices into `labels`.
+    sources: HashMap<ConfigPath, usize>,
+}
+
+impl MergeProvenance {
+    pub(crate) fn new() -> Self {
+        Self {
+            labels: Vec::new(),
+            sources: HashMap::new(),
+        }
+    }
+
+    /// Records the `label` as the source of all leaf values of the `source` [`table`],
+    /// overriding the previously recorded sources at the same paths.
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub(crate) fn add_source(&mut self, label: String, source: &DynTable) {
+        let label_index = self.labels.len();
+        self.labels.push(label);
+
+        self.add_table(source, label_index, &mut ConfigPath::new());
+    }
+
+    /// Returns the number of leaf values with a recorded source.
+    pub fn len(&self) -> u32 {
+        self.sources.len() as u32
+    }
+
+    /// Returns `true` if no leaf values have a recorded source.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the label of the source the leaf value at `path` came from,
+    /// or `None` if the `path` does not correspond to a leaf value of the merged [`config`].
+    ///
+    /// [`config`]: struct.DynConfig.html
+    pub fn source<'k, K, P>(&self, path: P) -> Option<&str>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        let mut config_path = ConfigPath::new();
+
+        for key in path {
+            config_path.0.push(match key.borrow() {
+                ConfigKey::Table(key) => NonEmptyStr::new(key.as_str())?.into(),
+                ConfigKey::Array(index) => (*index).into(),
+            });
+        }
+
+        self.sources
+            .get(&config_path)
+            .map(|&label_index| self.label(label_index))
+    }
+
+    /// Returns an iterator over (`path`, `label`) pairs of the leaf values and their sources, in unspecified order.
+    pub fn iter(&self) -> impl Iterator<Item = (&ConfigPath, &str)> {
+        self.sources
+            .iter()
+            .map(move |(path, &label_index)| (path, self.label(label_index)))
+    }
+
+    fn add_table(&mut self, table: &DynTable, label_index: usize, path: &mut ConfigPath) {
+        for (key, value) in table.iter() {
+            path.0.push(key.into());
+
+            match value {
+                Value::Table(table) => self.add_table(table, label_index, path),
+                _ => {
+                    self.sources.insert(path.clone(), label_index);
+                }
+            }
+
+            path.0.pop();
+        }
+    }
+
+    fn label(&self, label_index: usize) -> &str {
+        // Must succeed - we only store valid label indices.
+        unwrap_unchecked(self.labels.get(label_index), "invalid label index").as_str()
+    }
+}
+
+/// Prints the provenance report - one `path: label` line per leaf value, sorted by path.
+impl Display for MergeProvenance {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let mut report: Vec<_> = self
+            .iter()
+            .map(|(path, label)| (path.to_string(), label))
+            .collect();
+
+        // Paths are unique.
+        report.sort();
+
+        for (path, label) in report {
+            writeln!(f, "{}: {}", path, label)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,327 @@
+use {crate::*, std::collections::HashMap};
+
+/// Describes the expected structure of a [`table`] - its (required and optional) keys and their value types.
+///
+/// Used to [`validate`] a loaded [`config`] before accessing it.
+///
+/// [`table`]: struct.DynTable.html
+/// [`validate`]: #method.validate
+/// [`config`]: struct.DynConfig.html
+#[derive(Clone, Default, Debug)]
+pub struct Schema {
+    keys: HashMap<NonEmptyString, SchemaKey>,
+    strict: bool,
+}
+
+#[derive(Clone, Debug)]
+struct SchemaKey {
+    value: SchemaValue,
+    required: bool,
+}
+
+/// Describes the expected value at a key in a [`Schema`], or the expected [`array`] element.
+///
+/// Numeric value types ([`I64`], [`U64`] and [`F64`]) are interchangeable, as in the value accessors.
+///
+/// [`Schema`]: struct.Schema.html
+/// [`array`]: struct.DynArray.html
+/// [`I64`]: enum.ValueType.html#variant.I64
+/// [`U64`]: enum.ValueType.html#variant.U64
+/// [`F64`]: enum.ValueType.html#variant.F64
+#[derive(Clone, Debug)]
+pub enum SchemaValue {
+    /// A value of the [`type`].
+    /// [`Array`] / [`Table`] types match any [`array`] / [`table`].
+    ///
+    /// [`type`]: enum.ValueType.html
+    /// [`Array`]: enum.ValueType.html#variant.Array
+    /// [`Table`]: enum.ValueType.html#variant.Table
+    /// [`array`]: struct.DynArray.html
+    /// [`table`]: struct.DynTable.html
+    Type(ValueType),
+    /// An [`array`], all elements of which match the element schema.
+    ///
+    /// [`array`]: struct.DynArray.html
+    Array(Box<SchemaValue>),
+    /// A [`table`] matching the nested [`Schema`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`Schema`]: struct.Schema.html
+    Table(Schema),
+}
+
+impl SchemaValue {
+    /// Creates an [`array`] schema value, all elements of which must match the `element` schema value.
+    ///
+    /// [`array`]: #variant.Array
+    pub fn array<V: Into<SchemaValue>>(element: V) -> Self {
+        Self::Array(Box::new(element.into()))
+    }
+
+    fn value_type(&self) -> ValueType {
+        match self {
+            SchemaValue::Type(value_type) => *value_type,
+            SchemaValue::Array(_) => ValueType::Array,
+            SchemaValue::Table(_) => ValueType::Table,
+        }
+    }
+
+    fn validate(
+        &self,
+        value: DynConfigValueRef<'_>,
+        path: &mut ConfigPath,
+        errors: &mut Vec<SchemaError>,
+    ) {
+        let expected = self.value_type();
+        let value_type = value.get_type();
+
+        if !expected.is_compatible(value_type) {
+            errors.push(SchemaError::IncorrectValueType {
+                path: path.clone(),
+                expected,
+                value_type,
+            });
+            return;
+        }
+
+        match (self, value) {
+            (SchemaValue::Array(element), Value::Array(array)) => {
+                for (index, value) in array.iter().enumerate() {
+                    path.0.push((index as u32).into());
+                    element.validate(value, path, errors);
+                    path.0.pop();
+                }
+            }
+            (SchemaValue::Table(schema), Value::Table(table)) => {
+                schema.validate_impl(table, path, errors);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl From<ValueType> for SchemaValue {
+    fn from(value_type: ValueType) -> Self {
+        SchemaValue::Type(value_type)
+    }
+}
+
+impl From<Schema> for SchemaValue {
+    fn from(schema: Schema) -> Self {
+        SchemaValue::Table(schema)
+    }
+}
+
+impl Schema {
+    /// Creates a new empty, non-strict [`schema`].
+    ///
+    /// [`schema`]: struct.Schema.html
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a required (non-empty) string `key`, the value at which must match the schema `value`.
+    /// Replaces the previous declaration of the `key`, if any.
+    pub fn required<K, V>(self, key: K, value: V) -> Self
+    where
+        K: AsRef<NonEmptyStr>,
+        V: Into<SchemaValue>,
+    {
+        self.key(key.as_ref(), value.into(), true)
+    }
+
+    /// Declares an optional (non-empty) string `key`; if present, the value at it must match the schema `value`.
+    /// Replaces the previous declaration of the `key`, if any.
+    pub fn optional<K, V>(self, key: K, value: V) -> Self
+    where
+        K: AsRef<NonEmptyStr>,
+        V: Into<SchemaValue>,
+    {
+        self.key(key.as_ref(), value.into(), false)
+    }
+
+    /// Sets whether keys not declared in the [`schema`] are reported as [`errors`].
+    /// Does not affect nested [`schemas`].
+    ///
+    /// Default: `false`.
+    ///
+    /// [`schema`]: struct.Schema.html
+    /// [`errors`]: enum.SchemaError.html#variant.UnexpectedKey
+    /// [`schemas`]: struct.Schema.html
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Validates the root [`table`] of the [`config`] against the [`schema`].
+    ///
+    /// Returns all mismatches found in the [`config`], not just the first one.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`config`]: struct.DynConfig.html
+    /// [`schema`]: struct.Schema.html
+    pub fn validate(&self, config: &DynConfig) -> Result<(), Vec<SchemaError>> {
+        self.validate_table(config.root())
+    }
+
+    /// Validates the [`table`] against the [`schema`].
+    ///
+    /// Returns all mismatches found in the [`table`], not just the first one.
+    /// Error paths are relative to the [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`schema`]: struct.Schema.html
+    pub fn validate_table(&self, table: &DynTable) -> Result<(), Vec<SchemaError>> {
+        let mut errors = Vec::new();
+
+        self.validate_impl(table, &mut ConfigPath::new(), &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn key(mut self, key: &NonEmptyStr, value: SchemaValue, required: bool) -> Self {
+        self.keys.insert(key.into(), SchemaKey { value, required });
+        self
+    }
+
+    fn validate_impl(
+        &self,
+        table: &DynTable,
+        path: &mut ConfigPath,
+        errors: &mut Vec<SchemaError>,
+    ) {
+        // Gather the declared keys.
+        let mut keys: Vec<_> = self.keys.iter().collect();
+
+        // Sort the keys in alphabetical order for deterministic error order.
+        sort_by_table_key(&mut keys, |(key, _)| key.as_str());
+
+        for (key, schema_key) in keys.into_iter() {
+            path.0.push(key.as_ne_str().into());
+
+            match table.get_impl(key.as_ne_str()) {
+                Some(value) => schema_key.value.validate(value, path, errors),
+                None => {
+                    if schema_key.required {
+                        errors.push(SchemaError::MissingKey(path.clone()));
+                    }
+                }
+            }
+
+            path.0.pop();
+        }
+
+        if self.strict {
+            for (key, _) in table.iter_sorted() {
+                if !self.keys.contains_key(key) {
+                    path.0.push(key.into());
+                    errors.push(SchemaError::UnexpectedKey(path.clone()));
+                    path.0.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn validate() {
+        let schema = Schema::new()
+            .required(nestr!("name"), ValueType::String)
+            .optional(nestr!("debug"), ValueType::Bool)
+            .required(
+                nestr!("server"),
+                Schema::new()
+                    .required(nestr!("port"), ValueType::I64)
+                    .required(nestr!("hosts"), SchemaValue::array(ValueType::String))
+                    .strict(true),
+            );
+
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        root.set(nestr!("name"), "foo");
+        root.set_dotted_path("server.port", Some(Value::F64(8080.0)))
+            .unwrap();
+
+        let mut hosts = DynArray::new();
+        hosts.push("localhost".into()).unwrap();
+        root.set_dotted_path("server.hosts", Some(Value::Array(hosts)))
+            .unwrap();
+
+        assert!(schema.validate(&config).is_ok());
+
+        // Unexpected keys are allowed in non-strict schemas.
+        config.root_mut().set(nestr!("extra"), true);
+
+        assert!(schema.validate(&config).is_ok());
+
+        // Collect all errors.
+        let root = config.root_mut();
+
+        root.remove("name").unwrap();
+        root.set(nestr!("debug"), 7);
+        root.set_dotted_path("server.extra", Some(Value::Bool(true)))
+            .unwrap();
+
+        let mut hosts = DynArray::new();
+        hosts.push(Value::I64(7)).unwrap();
+        hosts.push(Value::I64(9)).unwrap();
+        root.set_dotted_path("server.hosts", Some(Value::Array(hosts)))
+            .unwrap();
+
+        let errors = schema.validate(&config).err().unwrap();
+
+        assert_eq!(
+            errors,
+            vec![
+                SchemaError::IncorrectValueType {
+                    path: vec![nestr!("debug").into()].into(),
+                    expected: ValueType::Bool,
+                    value_type: ValueType::I64,
+                },
+                SchemaError::MissingKey(vec![nestr!("name").into()].into()),
+                SchemaError::IncorrectValueType {
+                    path: vec![nestr!("server").into(), nestr!("hosts").into(), 0.into()].into(),
+                    expected: ValueType::String,
+                    value_type: ValueType::I64,
+                },
+                SchemaError::IncorrectValueType {
+                    path: vec![nestr!("server").into(), nestr!("hosts").into(), 1.into()].into(),
+                    expected: ValueType::String,
+                    value_type: ValueType::I64,
+                },
+                SchemaError::UnexpectedKey(
+                    vec![nestr!("server").into(), nestr!("extra").into()].into()
+                ),
+            ]
+        );
+
+        // Nested schemas are not validated if the value is not a table.
+        config.root_mut().set(nestr!("server"), 7);
+
+        assert_eq!(
+            schema.validate_table(config.root()).err().unwrap(),
+            vec![
+                SchemaError::IncorrectValueType {
+                    path: vec![nestr!("debug").into()].into(),
+                    expected: ValueType::Bool,
+                    value_type: ValueType::I64,
+                },
+                SchemaError::MissingKey(vec![nestr!("name").into()].into()),
+                SchemaError::IncorrectValueType {
+                    path: vec![nestr!("server").into()].into(),
+                    expected: ValueType::Table,
+                    value_type: ValueType::I64,
+                },
+            ]
+        );
+    }
+}
@@ -5,22 +5,77 @@ use {
         collections::{hash_map::Iter as HashMapIter, HashMap},
         convert::TryInto,
         fmt::{Display, Formatter, Write},
-        iter::{IntoIterator, Iterator},
+        hash::{Hash, Hasher},
+        iter::{FromIterator, IntoIterator, Iterator},
+        ops::Deref,
     },
 };
 
 /// Represents a mutable hashmap of [`Value`]'s with (non-empty) string keys.
 ///
+/// Tables are equal if they have the same set of keys and their values are equal for each key,
+/// regardless of the order of the keys. [`Comments`] are not compared.
+///
 /// [`Value`]: enum.Value.html
+/// [`Comments`]: #method.comment
 #[derive(Clone)]
-pub struct DynTable(HashMap<NonEmptyString, DynConfigValue>);
+pub struct DynTable(
+    HashMap<NonEmptyString, DynConfigValue>,
+    // Comments attached to the keys, if any.
+    HashMap<NonEmptyString, KeyComments>,
+);
+
+/// Comments attached to a key in a [`table`].
+///
+/// [`table`]: struct.DynTable.html
+#[derive(Clone, Default)]
+struct KeyComments {
+    // Comment lines preceding the key, if any.
+    comment: Option<String>,
+    // Comment following the key's value on the same line, if any.
+    inline_comment: Option<String>,
+}
 
 impl DynTable {
     /// Creates a new empty [`table`].
     ///
     /// [`table`]: struct.DynTable.html
     pub fn new() -> Self {
-        Self(HashMap::new())
+        Self(HashMap::new(), HashMap::new())
+    }
+
+    /// Creates a new empty [`table`] with space for at least `capacity` entries
+    /// without reallocating.
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn with_capacity(capacity: u32) -> Self {
+        Self(HashMap::with_capacity(capacity as _), HashMap::new())
+    }
+
+    /// Tries to create a new [`table`] from the string key / [`value`] pairs, as if by [`set`].
+    /// Later pairs overwrite the earlier ones with the same key.
+    ///
+    /// Unlike collecting into a [`table`], which requires `NonEmptyStr` keys,
+    /// accepts any string keys (e.g. `String` / `&str`) and returns an [`error`] if a key is empty.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`value`]: type.DynConfigValue.html
+    /// [`set`]: #method.set
+    /// [`error`]: enum.FromPairsError.html
+    pub fn from_pairs<I, K, V>(pairs: I) -> Result<Self, FromPairsError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<DynConfigValue>,
+    {
+        let mut table = Self::new();
+
+        for (index, (key, value)) in pairs.into_iter().enumerate() {
+            let key = NonEmptyStr::new(key.as_ref()).ok_or(FromPairsError::EmptyKey(index as _))?;
+            table.set(key, value);
+        }
+
+        Ok(table)
     }
 
     /// Returns the number of entries in the [`table`].
@@ -41,7 +96,8 @@ impl DynTable {
     ///
     /// [`table`]: struct.DynTable.html
     pub fn clear(&mut self) {
-        self.0.clear()
+        self.0.clear();
+        self.1.clear();
     }
 
     /// Returns `true` if the [`table`] contains a [`value`] with the (non-empty) string `key`.
@@ -82,6 +138,83 @@ impl DynTable {
         .map_err(TableError::IncorrectValueType)
     }
 
+    /// Like [`get`], but returns the `default` value if the [`table`] does not contain the `key`.
+    ///
+    /// Still returns an [`error`] if the [`value`] is of incorrect and incompatible type.
+    ///
+    /// [`get`]: #method.get
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`value`]: type.DynConfigValueRef.html
+    pub fn get_or<'t, K: AsRef<str>, V: TryFromValue<&'t str, &'t DynArray, &'t DynTable>>(
+        &'t self,
+        key: K,
+        default: V,
+    ) -> Result<V, TableError> {
+        match self.get(key) {
+            Err(TableError::KeyDoesNotExist) => Ok(default),
+            result => result,
+        }
+    }
+
+    /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// or returns the `default` value if the [`table`] does not contain the `key`.
+    ///
+    /// Returns an [`error`] if the value is not a [`bool`].
+    ///
+    /// [`bool`]: enum.Value.html#variant.Bool
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    pub fn get_bool_or<K: AsRef<str>>(&self, key: K, default: bool) -> Result<bool, TableError> {
+        self.get_or(key, default)
+    }
+
+    /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// or returns the `default` value if the [`table`] does not contain the `key`.
+    ///
+    /// Returns an [`error`] if the value is not an [`i64`] / [`f64`].
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn get_i64_or<K: AsRef<str>>(&self, key: K, default: i64) -> Result<i64, TableError> {
+        self.get_or(key, default)
+    }
+
+    /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// or returns the `default` value if the [`table`] does not contain the `key`.
+    ///
+    /// Returns an [`error`] if the value is not an [`f64`] / [`i64`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    pub fn get_f64_or<K: AsRef<str>>(&self, key: K, default: f64) -> Result<f64, TableError> {
+        self.get_or(key, default)
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// or returns the `default` value if the [`table`] does not contain the `key`.
+    ///
+    /// Returns an [`error`] if the value is not a [`string`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    pub fn get_string_or<'t, K: AsRef<str>>(
+        &'t self,
+        key: K,
+        default: &'t str,
+    ) -> Result<&'t str, TableError> {
+        self.get_or(key, default)
+    }
+
     /// Tries to get an immutable reference to a [`value`] in the [`table`] at `path`.
     ///
     /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
@@ -134,6 +267,201 @@ impl DynTable {
         V::try_from(self.get_val_path(path)?).map_err(GetPathError::IncorrectValueType)
     }
 
+    /// Tries to get an immutable reference to a [`value`] in the [`table`] at the dot-separated `path`
+    /// (e.g. `"server.network.port"`).
+    ///
+    /// All keys except the last one must correspond to [`table`] values.
+    /// The last key may correspond to a value of any [`type`].
+    ///
+    /// Dots (`'.'`) in keys must be escaped with a backslash (`"\."`), backslashes must be escaped as `"\\"`;
+    /// digits may be escaped (`"\0"`), as done by [`flatten`] for numeric keys;
+    /// all other backslashes are treated literally.
+    ///
+    /// NOTE: [`array`] values may not be indexed - use [`get_val_path`] instead.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    /// [`type`]: enum.ValueType.html
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`get_val_path`]: #method.get_val_path
+    /// [`flatten`]: struct.DynConfig.html#method.flatten
+    pub fn get_val_dotted_path(
+        &self,
+        path: &str,
+    ) -> Result<DynConfigValueRef<'_>, DynTablePathError> {
+        use DynTablePathError::*;
+
+        let keys = split_dotted_path(path);
+        // Must succeed - there's always at least one key.
+        let (last_key, keys) = unwrap_unchecked(keys.split_last(), "empty dotted path");
+
+        let mut table = self;
+        let mut config_path = ConfigPath::new();
+
+        for key in keys {
+            let value = NonEmptyStr::new(key.as_str()).and_then(|key| {
+                config_path.0.push(key.into());
+                table.get_impl(key)
+            });
+
+            table = match value {
+                Some(Value::Table(value)) => value,
+                Some(value) => {
+                    return Err(IntermediateValueNotATable {
+                        path: config_path,
+                        value_type: value.get_type(),
+                    })
+                }
+                None => return Err(IntermediateKeyDoesNotExist(config_path)),
+            };
+        }
+
+        NonEmptyStr::new(last_key.as_str())
+            .and_then(|key| {
+                config_path.0.push(key.into());
+                table.get_impl(key)
+            })
+            .ok_or_else(|| KeyDoesNotExist(config_path))
+    }
+
+    /// Returns `true` if the [`table`] contains a [`value`] at the dot-separated `path`
+    /// (e.g. `"features.logging"`).
+    ///
+    /// Returns `false` if any key in the `path` is empty, or does not exist,
+    /// or if any key except the last one does not correspond to a [`table`] value.
+    ///
+    /// Dots (`'.'`) and backslashes in keys must be escaped as in [`get_val_dotted_path`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`get_val_dotted_path`]: #method.get_val_dotted_path
+    pub fn contains_dotted_path(&self, path: &str) -> bool {
+        self.type_at_dotted_path(path).is_some()
+    }
+
+    /// Returns the [`type`] of the [`value`] in the [`table`] at the dot-separated `path`
+    /// (e.g. `"features.logging"`), without returning the [`value`] itself.
+    ///
+    /// Returns `None` if any key in the `path` is empty, or does not exist,
+    /// or if any key except the last one does not correspond to a [`table`] value.
+    ///
+    /// Dots (`'.'`) and backslashes in keys must be escaped as in [`get_val_dotted_path`].
+    ///
+    /// [`type`]: enum.ValueType.html
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    /// [`get_val_dotted_path`]: #method.get_val_dotted_path
+    pub fn type_at_dotted_path(&self, path: &str) -> Option<ValueType> {
+        let keys = split_dotted_path(path);
+        // Must succeed - there's always at least one key.
+        let (last_key, keys) = unwrap_unchecked(keys.split_last(), "empty dotted path");
+
+        let mut table = self;
+
+        for key in keys {
+            table = match table.0.get(NonEmptyStr::new(key.as_str())?)? {
+                Value::Table(value) => value,
+                _ => return None,
+            };
+        }
+
+        table
+            .0
+            .get(NonEmptyStr::new(last_key.as_str())?)
+            .map(Value::get_type)
+    }
+
+    /// Tries to get a mutable reference to a [`value`] in the [`table`] at the dot-separated `path`
+    /// (e.g. `"server.network"`).
+    ///
+    /// All keys except the last one must correspond to [`table`] values.
+    /// The last key may correspond to a value of any [`type`].
+    ///
+    /// Dots (`'.'`) and backslashes in keys must be escaped as in [`get_val_dotted_path`].
+    ///
+    /// NOTE: mutable reference extends to [`arrays`] and [`tables`], not other value types.
+    /// Use [`set_dotted_path`] to mutate other value types in the [`table`].
+    ///
+    /// [`value`]: type.DynConfigValueMut.html
+    /// [`table`]: struct.DynTable.html
+    /// [`type`]: enum.ValueType.html
+    /// [`get_val_dotted_path`]: #method.get_val_dotted_path
+    /// [`arrays`]: enum.Value.html#variant.Array
+    /// [`tables`]: enum.Value.html#variant.Table
+    /// [`set_dotted_path`]: #method.set_dotted_path
+    pub fn get_val_dotted_path_mut(
+        &mut self,
+        path: &str,
+    ) -> Result<DynConfigValueMut<'_>, DynTablePathError> {
+        use DynTablePathError::*;
+
+        let keys = split_dotted_path(path);
+        // Must succeed - there's always at least one key.
+        let (last_key, keys) = unwrap_unchecked(keys.split_last(), "empty dotted path");
+
+        let mut table = self;
+        let mut config_path = ConfigPath::new();
+
+        for key in keys {
+            let key = match NonEmptyStr::new(key.as_str()) {
+                Some(key) => key,
+                None => return Err(IntermediateKeyDoesNotExist(config_path)),
+            };
+
+            config_path.0.push(key.into());
+
+            table = match table.0.get_mut(key) {
+                Some(Value::Table(value)) => value,
+                Some(value) => {
+                    return Err(IntermediateValueNotATable {
+                        path: config_path,
+                        value_type: value.get_type(),
+                    })
+                }
+                None => return Err(IntermediateKeyDoesNotExist(config_path)),
+            };
+        }
+
+        match NonEmptyStr::new(last_key.as_str()) {
+            Some(key) => {
+                config_path.0.push(key.into());
+                table
+                    .get_mut_impl(key)
+                    .ok_or_else(|| KeyDoesNotExist(config_path))
+            }
+            None => Err(KeyDoesNotExist(config_path)),
+        }
+    }
+
+    /// Tries to get a mutable reference to a [`table`](enum.Value.html#variant.Table) [`value`] in the [`table`]
+    /// at the dot-separated `path` (e.g. `"server.network"`).
+    ///
+    /// All keys, including the last one, must correspond to [`table`](enum.Value.html#variant.Table) values.
+    ///
+    /// Dots (`'.'`) and backslashes in keys must be escaped as in [`get_val_dotted_path`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`get_val_dotted_path`]: #method.get_val_dotted_path
+    pub fn get_table_dotted_path_mut(
+        &mut self,
+        path: &str,
+    ) -> Result<&mut DynTable, DynTablePathError> {
+        match self.get_val_dotted_path_mut(path)? {
+            Value::Table(table) => Ok(table),
+            value => Err(DynTablePathError::ValueNotATable {
+                // All keys are valid if the value was found.
+                path: ConfigPath(
+                    split_dotted_path(path)
+                        .iter()
+                        .filter_map(|key| NonEmptyStr::new(key.as_str()).map(Into::into))
+                        .collect(),
+                ),
+                value_type: value.get_type(),
+            }),
+        }
+    }
+
     /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`bool`].
@@ -204,6 +532,44 @@ impl DynTable {
         self.get_path(path)
     }
 
+    /// Tries to get a [`u64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`u64`] / [`i64`] / [`f64`].
+    ///
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn get_u64<K: AsRef<str>>(&self, key: K) -> Result<u64, TableError> {
+        self.get(key)
+    }
+
+    /// Tries to get a [`u64`] [`value`] in the [`table`] at `path`.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`](enum.Value.html#variant.Table) or an [`array`] value.
+    /// The last key must correspond to a [`u64`] / [`i64`] / [`f64`] [`value`].
+    ///
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn get_u64_path<'k, K, P>(&self, path: P) -> Result<u64, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_path(path)
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`].
@@ -240,6 +606,48 @@ impl DynTable {
         self.get_path(path)
     }
 
+    /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// converting a [`u64`] / [`f64`] [`value`] only if it is exactly representable as an [`i64`].
+    ///
+    /// Unlike [`get_i64`], does not truncate [`f64`] values with a fractional part.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`u64`] / [`f64`],
+    /// or if the conversion is lossy.
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`get_i64`]: #method.get_i64
+    /// [`error`]: enum.TableError.html
+    pub fn get_i64_coerced<K: AsRef<str>>(&self, key: K) -> Result<i64, TableError> {
+        self.get_val(key)
+            .ok_or(TableError::KeyDoesNotExist)?
+            .i64_coerced()
+    }
+
+    /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// converting an [`i64`] / [`u64`] [`value`] only if it is exactly representable as an [`f64`].
+    ///
+    /// Unlike [`get_f64`], does not round integers with a magnitude greater than `2^53`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`f64`] / [`i64`] / [`u64`],
+    /// or if the conversion is lossy.
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`get_f64`]: #method.get_f64
+    /// [`error`]: enum.TableError.html
+    pub fn get_f64_coerced<K: AsRef<str>>(&self, key: K) -> Result<f64, TableError> {
+        self.get_val(key)
+            .ok_or(TableError::KeyDoesNotExist)?
+            .f64_coerced()
+    }
+
     /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`string`].
@@ -274,6 +682,24 @@ impl DynTable {
         self.get_path(path)
     }
 
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`
+    /// and parse it as a tagged string (e.g. `"!color 0xFF0000"`) into a value of the type `T` registered in the `registry`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not a [`string`],
+    /// or if the [`string`] is not a valid tagged string for the type `T`.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TagError.html
+    pub fn get_tagged<T: 'static, K: AsRef<str>>(
+        &self,
+        key: K,
+        registry: &TagRegistry,
+    ) -> Result<T, TagError> {
+        registry.parse(self.get_string(key).map_err(TagError::TableError)?)
+    }
+
     /// Tries to get an immutable reference to an [`array`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`array`].
@@ -343,10 +769,54 @@ impl DynTable {
     ///
     /// [`value`]: type.DynConfigValueRef.html
     /// [`table`]: struct.DynTable.html
-    pub fn iter(&self) -> impl Iterator<Item = (&NonEmptyStr, DynConfigValueRef<'_>)> {
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&NonEmptyStr, DynConfigValueRef<'_>)> {
         DynTableIter(self.0.iter())
     }
 
+    /// Returns an iterator over the keys of the [`table`], in unspecified order.
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &NonEmptyStr> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the [`values`] of the [`table`], in unspecified order.
+    ///
+    /// [`values`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    pub fn values(&self) -> impl ExactSizeIterator<Item = DynConfigValueRef<'_>> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over (`key`, [`value`]) pairs of the [`table`], in ascending (alphabetical) key order.
+    ///
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`table`]: struct.DynTable.html
+    pub fn iter_sorted(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (&NonEmptyStr, DynConfigValueRef<'_>)> {
+        // Gather the key / value pairs.
+        let mut pairs: Vec<_> = self.iter().collect();
+
+        // Sort the pairs in canonical key order.
+        sort_by_table_key(&mut pairs, |(key, _)| key.as_str());
+
+        pairs.into_iter()
+    }
+
+    /// Returns a [`view`] of the [`table`] statically guaranteed to be non-empty,
+    /// or `None` if the [`table`] is empty.
+    ///
+    /// [`view`]: struct.NonEmptyDynTable.html
+    /// [`table`]: struct.DynTable.html
+    pub fn non_empty(&self) -> Option<NonEmptyDynTable<'_>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(NonEmptyDynTable(self))
+        }
+    }
+
     /// Tries to get a mutable reference to a [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] or if the [`table`] does not contain the `key`.
@@ -549,28 +1019,433 @@ impl DynTable {
         self.set_impl(key.as_ref(), value.into())
     }
 
-    /// Tries to remove the [`value`] at (non-empty) string `key`.
+    /// Returns the [`entry`] at (non-empty) string `key` in the [`table`] for in-place insertion / modification,
+    /// looking up the `key` only once.
+    ///
+    /// [`entry`]: struct.DynTableEntry.html
+    /// [`table`]: struct.DynTable.html
+    pub fn entry<K: AsRef<NonEmptyStr>>(&mut self, key: K) -> DynTableEntry<'_> {
+        DynTableEntry::new(self.0.entry(key.as_ref().into()))
+    }
+
+    /// Formats the `value` of the type `T` registered in the `registry` as a tagged string (e.g. `"!color 0xFF0000"`)
+    /// and sets it as a [`string`] [`value`] at (non-empty) string `key` in the [`table`].
+    ///
+    /// Returns `true` if the value at `key` already existed and was modified.
+    /// Returns an [`error`] if the type `T` is not registered in the `registry`.
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`error`]: enum.TagError.html
+    pub fn set_tagged<K: AsRef<NonEmptyStr>, T: 'static>(
+        &mut self,
+        key: K,
+        value: &T,
+        registry: &TagRegistry,
+    ) -> Result<bool, TagError> {
+        let string = registry.format(value)?;
+        Ok(self.set(key, string))
+    }
+
+    /// Tries to remove the [`value`] at (non-empty) string `key`, and the [`comment`] / [`inline comment`] attached to it, if any.
     /// Returns the now-removed [`value`] at `key` if it existed,
     /// otherwise returns `None`.
     ///
     /// [`value`]: type.DynConfigValue.html
+    /// [`comment`]: #method.comment
+    /// [`inline comment`]: #method.inline_comment
     pub fn remove<K: AsRef<str>>(&mut self, key: K) -> Option<DynConfigValue> {
-        self.remove_impl(key.as_ref().try_into().ok()?)
+        let key = key.as_ref().try_into().ok()?;
+        self.1.remove(key);
+        self.remove_impl(key)
     }
 
-    fn len_impl(&self) -> u32 {
-        self.0.len() as u32
-    }
+    /// Retains only the [`values`] for which `f` returns `true`,
+    /// removing all other [`values`] (and the [`comments`] attached to them, if any) from the [`table`].
+    ///
+    /// `f` is called once for each (non-empty) string key and [`value`] in the [`table`], in unspecified order.
+    ///
+    /// [`values`]: type.DynConfigValueRef.html
+    /// [`value`]: type.DynConfigValueRef.html
+    /// [`comments`]: #method.comment
+    /// [`table`]: struct.DynTable.html
+    pub fn retain<F: FnMut(&NonEmptyStr, DynConfigValueRef<'_>) -> bool>(&mut self, mut f: F) {
+        self.0.retain(|key, value| {
+            // Safe to call - we validated the key.
+            f(
+                unwrap_unchecked(NonEmptyStr::new(key.as_ref()), "empty key"),
+                (&*value).into(),
+            )
+        });
 
-    pub(crate) fn get_impl(&self, key: &NonEmptyStr) -> Option<DynConfigValueRef<'_>> {
-        self.0.get(key).map(|val| val.into())
+        let values = &self.0;
+        self.1.retain(|key, _| values.contains_key(key));
     }
 
-    fn set_impl(&mut self, key: &NonEmptyStr, value: DynConfigValue) -> bool {
-        // Modify.
-        if let Some(cur_value) = self.0.get_mut(key) {
-            *cur_value = value;
-            true
+    /// Returns the comment attached to the (non-empty) string `key` in the [`table`], if any.
+    ///
+    /// Comments are read from `.ini` comment lines preceding the keys / sections
+    /// if the parser is [`configured`] to preserve them,
+    /// and are written back by [`to_ini_string`]. Other formats ignore comments.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`configured`]: struct.IniParser.html#method.preserve_comments
+    /// [`to_ini_string`]: struct.DynConfig.html#method.to_ini_string
+    pub fn comment<K: AsRef<str>>(&self, key: K) -> Option<&str> {
+        let key: &NonEmptyStr = key.as_ref().try_into().ok()?;
+        self.1.get(key)?.comment.as_deref()
+    }
+
+    /// Attaches the (possibly multi-line) `comment` to the (non-empty) string `key` in the [`table`].
+    /// Returns the previous comment attached to the `key`, if any.
+    ///
+    /// The comment is only written (as one `.ini` comment line per line) if the [`table`] contains the `key`.
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn set_comment<K: AsRef<NonEmptyStr>, C: Into<String>>(
+        &mut self,
+        key: K,
+        comment: C,
+    ) -> Option<String> {
+        self.1
+            .entry(key.as_ref().into())
+            .or_default()
+            .comment
+            .replace(comment.into())
+    }
+
+    /// Removes the comment attached to the (non-empty) string `key` in the [`table`], if any, and returns it.
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn remove_comment<K: AsRef<str>>(&mut self, key: K) -> Option<String> {
+        self.remove_key_comment(key.as_ref(), |comments| comments.comment.take())
+    }
+
+    /// Returns the inline comment attached to the (non-empty) string `key` in the [`table`], if any.
+    ///
+    /// Inline comments are read from `.ini` comments following the values on the same line
+    /// if the parser is [`configured`] to preserve comments (and [`supports`] inline comments),
+    /// and are written back by [`to_ini_string`] as `key = value ; comment`. Other formats ignore comments.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`configured`]: struct.IniParser.html#method.preserve_comments
+    /// [`supports`]: struct.IniParser.html#method.inline_comments
+    /// [`to_ini_string`]: struct.DynConfig.html#method.to_ini_string
+    pub fn inline_comment<K: AsRef<str>>(&self, key: K) -> Option<&str> {
+        let key: &NonEmptyStr = key.as_ref().try_into().ok()?;
+        self.1.get(key)?.inline_comment.as_deref()
+    }
+
+    /// Attaches the inline `comment` to the (non-empty) string `key` in the [`table`].
+    /// Returns the previous inline comment attached to the `key`, if any.
+    ///
+    /// The comment is only written if the [`table`] contains a non-[`table`] value at `key`.
+    /// Line breaks in the comment are written as spaces.
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn set_inline_comment<K: AsRef<NonEmptyStr>, C: Into<String>>(
+        &mut self,
+        key: K,
+        comment: C,
+    ) -> Option<String> {
+        self.1
+            .entry(key.as_ref().into())
+            .or_default()
+            .inline_comment
+            .replace(comment.into())
+    }
+
+    /// Removes the inline comment attached to the (non-empty) string `key` in the [`table`], if any, and returns it.
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn remove_inline_comment<K: AsRef<str>>(&mut self, key: K) -> Option<String> {
+        self.remove_key_comment(key.as_ref(), |comments| comments.inline_comment.take())
+    }
+
+    /// Inserts or changes the [`value`] at (non-empty) string `key`, like [`set`],
+    /// and attaches the `inline_comment` to it, or removes the previous inline comment if `inline_comment` is `None`.
+    /// Returns `true` if the [`value`] at `key` already existed and was modified.
+    /// Returns `false` if the [`value`] at `key` did not exist and was added.
+    ///
+    /// See [`inline_comment`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`set`]: #method.set
+    /// [`inline_comment`]: #method.inline_comment
+    pub fn set_with_inline_comment<K, V>(
+        &mut self,
+        key: K,
+        value: V,
+        inline_comment: Option<&str>,
+    ) -> bool
+    where
+        K: AsRef<NonEmptyStr>,
+        V: Into<DynConfigValue>,
+    {
+        let key = key.as_ref();
+
+        if let Some(inline_comment) = inline_comment {
+            self.set_inline_comment(key, inline_comment);
+        } else {
+            self.remove_inline_comment(key);
+        }
+
+        self.set_impl(key, value.into())
+    }
+
+    /// Removes one of the comments attached to the `key`, as selected by `f`,
+    /// removing the `key`'s comments entry if no comments remain.
+    fn remove_key_comment<F>(&mut self, key: &str, f: F) -> Option<String>
+    where
+        F: FnOnce(&mut KeyComments) -> Option<String>,
+    {
+        let key: &NonEmptyStr = key.try_into().ok()?;
+        let comments = self.1.get_mut(key)?;
+        let comment = f(comments);
+
+        if comments.comment.is_none() && comments.inline_comment.is_none() {
+            self.1.remove(key);
+        }
+
+        comment
+    }
+
+    /// Inserts, changes or (if `value` is `None`) removes the [`value`] in the [`table`] at the dot-separated `path`
+    /// (e.g. `"server.network.port"`).
+    ///
+    /// Missing intermediate [`tables`] are created when inserting / changing the [`value`] (but not when removing it).
+    /// Intermediate keys which correspond to non-[`table`] values are not overwritten, but cause an [`error`].
+    /// Removing a missing [`value`] is an [`error`].
+    ///
+    /// Dots (`'.'`) and backslashes in keys must be escaped as in [`get_val_dotted_path`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`error`]: enum.DynTablePathError.html
+    /// [`get_val_dotted_path`]: #method.get_val_dotted_path
+    pub fn set_dotted_path(
+        &mut self,
+        path: &str,
+        value: Option<DynConfigValue>,
+    ) -> Result<(), DynTablePathError> {
+        self.set_dotted_path_impl(path, value, true)
+    }
+
+    /// Changes or (if `value` is `None`) removes the [`value`] in the [`table`] at the dot-separated `path`
+    /// (e.g. `"server.network.port"`).
+    ///
+    /// Same as [`set_dotted_path`], except missing intermediate [`tables`] are not created, but cause an [`error`].
+    ///
+    /// [`value`]: type.DynConfigValue.html
+    /// [`table`]: struct.DynTable.html
+    /// [`set_dotted_path`]: #method.set_dotted_path
+    /// [`tables`]: struct.DynTable.html
+    /// [`error`]: enum.DynTablePathError.html
+    pub fn set_dotted_path_no_create(
+        &mut self,
+        path: &str,
+        value: Option<DynConfigValue>,
+    ) -> Result<(), DynTablePathError> {
+        self.set_dotted_path_impl(path, value, false)
+    }
+
+    /// Recursively merges the `other` [`table`] into this [`table`], using default [`merge options`].
+    ///
+    /// See [`merge_opts`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`merge options`]: struct.MergeOptions.html
+    /// [`merge_opts`]: #method.merge_opts
+    pub fn merge(&mut self, other: &DynTable) -> Result<(), MergeError> {
+        self.merge_opts(other, Default::default())
+    }
+
+    /// Recursively merges the `other` [`table`] into this [`table`].
+    ///
+    /// Values in `other` at keys missing in this [`table`] are added.
+    /// Nested [`tables`] present at the same key in both [`tables`] are merged recursively.
+    /// [`Arrays`] present at the same key in both [`tables`] are replaced or appended to,
+    /// depending on the `options`' [`array strategy`].
+    /// Other values in `other` overwrite the values at the same key in this [`table`].
+    ///
+    /// A [`table`] / [`array`] and a value of a different type at the same key is an [`error`].
+    /// This [`table`] is not modified if an [`error`] is returned.
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`Arrays`]: struct.DynArray.html
+    /// [`array`]: struct.DynArray.html
+    /// [`array strategy`]: struct.MergeOptions.html#structfield.array_strategy
+    /// [`error`]: enum.MergeError.html
+    pub fn merge_opts(
+        &mut self,
+        other: &DynTable,
+        options: MergeOptions,
+    ) -> Result<(), MergeError> {
+        // Validate the merge before modifying the table.
+        self.validate_merge(other, options, &mut ConfigPath::new())?;
+        self.merge_impl(other, options);
+
+        Ok(())
+    }
+
+    fn len_impl(&self) -> u32 {
+        self.0.len() as u32
+    }
+
+    fn validate_merge(
+        &self,
+        other: &DynTable,
+        options: MergeOptions,
+        path: &mut ConfigPath,
+    ) -> Result<(), MergeError> {
+        use MergeError::*;
+
+        for (key, other_value) in other.0.iter() {
+            let value = match self.0.get(key) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            path.0.push(key.clone().into());
+
+            match (value, other_value) {
+                (Value::Table(table), Value::Table(other_table)) => {
+                    table.validate_merge(other_table, options, path)?;
+                }
+                (Value::Array(array), Value::Array(other_array)) => {
+                    if options.array_strategy == MergeArrayStrategy::Append {
+                        if let (Some(value_type), Some(other_value_type)) =
+                            (array.value_type(), other_array.value_type())
+                        {
+                            if !value_type.is_compatible(other_value_type) {
+                                return Err(MixedArray {
+                                    path: path.clone(),
+                                    value_type,
+                                    other_value_type,
+                                });
+                            }
+                        }
+                    }
+                }
+                (Value::Table(_), _)
+                | (_, Value::Table(_))
+                | (Value::Array(_), _)
+                | (_, Value::Array(_)) => {
+                    return Err(TypeConflict {
+                        path: path.clone(),
+                        value_type: value.get_type(),
+                        other_value_type: other_value.get_type(),
+                    });
+                }
+                _ => {}
+            }
+
+            path.0.pop();
+        }
+
+        Ok(())
+    }
+
+    fn merge_impl(&mut self, other: &DynTable, options: MergeOptions) {
+        for (key, other_value) in other.0.iter() {
+            match (self.0.get_mut(key), other_value) {
+                (Some(Value::Table(table)), Value::Table(other_table)) => {
+                    table.merge_impl(other_table, options);
+                }
+                (Some(Value::Array(array)), Value::Array(other_array))
+                    if options.array_strategy == MergeArrayStrategy::Append =>
+                {
+                    array.append_impl(other_array);
+                }
+                // Type conflicts were validated before.
+                (Some(value), other_value) => {
+                    *value = other_value.clone();
+                }
+                (None, other_value) => {
+                    self.0.insert(key.clone(), other_value.clone());
+                }
+            }
+        }
+    }
+
+    fn set_dotted_path_impl(
+        &mut self,
+        path: &str,
+        value: Option<DynConfigValue>,
+        create: bool,
+    ) -> Result<(), DynTablePathError> {
+        use DynTablePathError::*;
+
+        let keys = split_dotted_path(path);
+
+        // Validate all keys before modifying the table.
+        let mut config_path = ConfigPath::new();
+        let mut ne_keys = Vec::with_capacity(keys.len());
+
+        for (index, key) in keys.iter().enumerate() {
+            let key = match NonEmptyStr::new(key.as_str()) {
+                Some(key) => key,
+                None if index + 1 == keys.len() => return Err(KeyDoesNotExist(config_path)),
+                None => return Err(IntermediateKeyDoesNotExist(config_path)),
+            };
+
+            config_path.0.push(key.into());
+            ne_keys.push(key);
+        }
+
+        config_path.0.clear();
+
+        // Must succeed - there's always at least one key.
+        let (last_key, keys) = unwrap_unchecked(ne_keys.split_last(), "empty dotted path");
+
+        // Never create intermediate tables when removing the value.
+        let create = create && value.is_some();
+
+        let mut table = self;
+
+        for key in keys {
+            config_path.0.push((*key).into());
+
+            if create && !table.0.contains_key(*key) {
+                table.0.insert((*key).into(), Value::Table(DynTable::new()));
+            }
+
+            table = match table.0.get_mut(*key) {
+                Some(Value::Table(value)) => value,
+                Some(value) => {
+                    return Err(IntermediateValueNotATable {
+                        path: config_path,
+                        value_type: value.get_type(),
+                    })
+                }
+                None => return Err(IntermediateKeyDoesNotExist(config_path)),
+            };
+        }
+
+        if let Some(value) = value {
+            table.set_impl(last_key, value);
+
+            Ok(())
+        } else {
+            table.remove_impl(last_key).map(|_| ()).ok_or_else(|| {
+                config_path.0.push((*last_key).into());
+                KeyDoesNotExist(config_path)
+            })
+        }
+    }
+
+    pub(crate) fn get_impl(&self, key: &NonEmptyStr) -> Option<DynConfigValueRef<'_>> {
+        self.0.get(key).map(|val| val.into())
+    }
+
+    fn set_impl(&mut self, key: &NonEmptyStr, value: DynConfigValue) -> bool {
+        // Modify.
+        if let Some(cur_value) = self.0.get_mut(key) {
+            *cur_value = value;
+            true
 
         // Add.
         } else {
@@ -587,43 +1462,46 @@ impl DynTable {
         self.0.get_mut(key).map(|val| val.into())
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        writeln!(w, "{{")?;
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        <Self as DisplayLua>::write_lua_open(w, options)?;
 
-        // Gather the keys.
-        let mut keys: Vec<_> = self.iter().map(|(key, _)| key).collect();
+        // Gather the key / value pairs.
+        let mut pairs: Vec<_> = self.iter().collect();
 
-        // Sort the keys in alphabetical order.
-        keys.sort();
+        // Sort the pairs in canonical key order, if required.
+        if options.sort_keys {
+            sort_by_table_key(&mut pairs, |(key, _)| key.as_str());
+        }
 
-        // Iterate the table using the sorted keys.
-        for key in keys.into_iter() {
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+        let len = pairs.len();
+
+        for (index, (key, value)) in pairs.into_iter().enumerate() {
+            <Self as DisplayLua>::write_lua_element_start(w, indent, options)?;
 
             write_lua_key(w, key)?;
             write!(w, " = ")?;
 
-            // Must succeed - all keys are valid.
-            let value = unwrap_unchecked(
-                self.get_val(key),
-                "failed to get a value from a dyn config table with a valid key",
-            );
-
             let is_array_or_table = matches!(value.get_type(), ValueType::Array | ValueType::Table);
 
-            value.fmt_lua(w, indent + 1)?;
+            value
+                .fmt_lua(w, indent + 1, options)
+                .map_err(|err| err.push_key(key))?;
 
-            write!(w, ",")?;
+            <Self as DisplayLua>::write_lua_comma(w, index + 1 == len, options)?;
 
-            if is_array_or_table {
+            if is_array_or_table && options.write_key_comments() {
                 write!(w, " -- {}", key)?;
             }
 
-            writeln!(w)?;
+            <Self as DisplayLua>::write_lua_element_end(w, options)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
-        write!(w, "}}")?;
+        <Self as DisplayLua>::write_lua_close(w, indent, len == 0, options)?;
 
         Ok(())
     }
@@ -640,9 +1518,9 @@ impl DynTable {
         debug_assert!(options.nested_sections() || level < 2);
 
         // Gather the keys.
-        let mut keys: Vec<_> = self.iter().map(|(key, _)| key).collect();
+        let mut keys: Vec<_> = self.keys().collect();
 
-        // Sort the keys in alphabetical order, non-tables first.
+        // Sort the keys in canonical order, non-tables first.
         keys.sort_by(|l, r| {
             // Must succeed - all keys are valid.
             let l_val = unwrap_unchecked(
@@ -654,16 +1532,12 @@ impl DynTable {
                 "failed to get a value from a dyn config table with a valid key",
             );
 
-            let l_is_a_table = l_val.get_type() == ValueType::Table;
-            let r_is_a_table = r_val.get_type() == ValueType::Table;
-
-            if !l_is_a_table && r_is_a_table {
-                std::cmp::Ordering::Less
-            } else if l_is_a_table && !r_is_a_table {
-                std::cmp::Ordering::Greater
-            } else {
-                l.cmp(r)
-            }
+            cmp_ini_keys(
+                l.as_str(),
+                l_val.get_type() == ValueType::Table,
+                r.as_str(),
+                r_val.get_type() == ValueType::Table,
+            )
         });
 
         let len = self.len() as usize;
@@ -683,6 +1557,8 @@ impl DynTable {
                     write_ini_array(
                         w,
                         key,
+                        self.comment(key),
+                        self.inline_comment(key),
                         value.iter(),
                         value.len() as usize,
                         last,
@@ -699,6 +1575,7 @@ impl DynTable {
                     write_ini_table(
                         w,
                         key,
+                        self.comment(key),
                         key_index as u32,
                         value,
                         value.len(),
@@ -710,13 +1587,104 @@ impl DynTable {
                     )?;
                 }
                 value => {
-                    write_ini_value(w, key, &value, last, level, false, path, options)?;
+                    write_ini_value(
+                        w,
+                        key,
+                        self.comment(key),
+                        self.inline_comment(key),
+                        &value,
+                        last,
+                        level,
+                        false,
+                        path,
+                        options,
+                    )?;
                 }
             }
         }
 
         Ok(())
     }
+
+    #[cfg(feature = "json")]
+    fn fmt_json_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToJSONStringOptions,
+    ) -> Result<(), ToJSONStringError> {
+        if self.is_empty() {
+            write!(w, "{{}}")?;
+            return Ok(());
+        }
+
+        writeln!(w, "{{")?;
+
+        let len = self.len() as usize;
+
+        // Iterate the table in alphabetical key order.
+        for (key_index, (key, value)) in self.iter_sorted().enumerate() {
+            write_json_key_value(w, key, &value, key_index == len - 1, indent + 1, options)?;
+        }
+
+        <Self as DisplayJSON>::do_indent(w, indent)?;
+        write!(w, "}}")?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "yaml")]
+    fn fmt_yaml_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(w, "{{}}");
+        }
+
+        // Iterate the table in alphabetical key order.
+        for (key_index, (key, value)) in self.iter_sorted().enumerate() {
+            write_yaml_key_value(w, key, &value, key_index == 0, indent)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ron")]
+    fn fmt_ron_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToRONStringOptions,
+    ) -> Result<(), ToRONStringError> {
+        if self.is_empty() {
+            write!(w, "{{}}")?;
+            return Ok(());
+        }
+
+        writeln!(w, "{{")?;
+
+        // Iterate the table in alphabetical key order.
+        for (key, value) in self.iter_sorted() {
+            write_ron_key_value(w, key, &value, indent + 1, options)?;
+        }
+
+        <Self as DisplayRON>::do_indent(w, indent)?;
+        write!(w, "}}")?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "toml")]
+    fn to_toml_value_impl(&self) -> Result<toml_rs::Value, ToTOMLStringError> {
+        let mut table = toml_rs::value::Table::new();
+
+        for (key, value) in self.iter() {
+            table.insert(
+                key.as_str().to_owned(),
+                value.to_toml_value().map_err(|err| err.push_key(key))?,
+            );
+        }
+
+        Ok(toml_rs::Value::Table(table))
+    }
 }
 
 /// Iterator over (`key`, [`value`]) tuples of the [`table`], in unspecified order.
@@ -733,6 +1701,7 @@ impl<'t> Iterator for DynTableIter<'t> {
             let value = match value {
                 Value::Bool(value) => Value::Bool(*value),
                 Value::I64(value) => Value::I64(*value),
+                Value::U64(value) => Value::U64(*value),
                 Value::F64(value) => Value::F64(*value),
                 Value::String(value) => Value::String(value.as_str()),
                 Value::Array(value) => Value::Array(value),
@@ -748,23 +1717,72 @@ impl<'t> Iterator for DynTableIter<'t> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'t> ExactSizeIterator for DynTableIter<'t> {}
+
+/// An immutable view of a [`dynamic table`] statically guaranteed to contain at least one [`value`].
+///
+/// Returned by [`DynTable::non_empty`].
+/// Dereferences to the [`dynamic table`].
+///
+/// [`dynamic table`]: struct.DynTable.html
+/// [`value`]: type.DynConfigValueRef.html
+/// [`DynTable::non_empty`]: struct.DynTable.html#method.non_empty
+#[derive(Clone, Copy)]
+pub struct NonEmptyDynTable<'t>(&'t DynTable);
+
+impl<'t> NonEmptyDynTable<'t> {
+    /// Returns the viewed [`table`].
+    ///
+    /// [`table`]: struct.DynTable.html
+    pub fn table(&self) -> &'t DynTable {
+        self.0
+    }
+}
+
+impl<'t> Deref for NonEmptyDynTable<'t> {
+    type Target = DynTable;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
 }
 
 impl DisplayLua for DynTable {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
 impl<'t> DisplayLua for &'t DynTable {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
 impl<'t> DisplayLua for &'t mut DynTable {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
@@ -796,9 +1814,191 @@ impl<'t> DisplayIni for &'t DynTable {
     }
 }
 
+#[cfg(feature = "json")]
+impl DisplayJSON for DynTable {
+    fn fmt_json<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToJSONStringOptions,
+    ) -> Result<(), ToJSONStringError> {
+        self.fmt_json_impl(w, indent, options)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'t> DisplayJSON for &'t DynTable {
+    fn fmt_json<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToJSONStringOptions,
+    ) -> Result<(), ToJSONStringError> {
+        self.fmt_json_impl(w, indent, options)
+    }
+}
+
+#[cfg(feature = "ron")]
+impl DisplayRON for DynTable {
+    fn fmt_ron<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToRONStringOptions,
+    ) -> Result<(), ToRONStringError> {
+        self.fmt_ron_impl(w, indent, options)
+    }
+}
+
+#[cfg(feature = "ron")]
+impl<'t> DisplayRON for &'t DynTable {
+    fn fmt_ron<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToRONStringOptions,
+    ) -> Result<(), ToRONStringError> {
+        self.fmt_ron_impl(w, indent, options)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl DisplayYAML for DynTable {
+    fn fmt_yaml<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+        self.fmt_yaml_impl(w, indent)
+    }
+
+    fn is_block(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+#[cfg(feature = "toml")]
+impl DisplayTOML for DynTable {
+    fn to_toml_value(&self) -> Result<toml_rs::Value, ToTOMLStringError> {
+        self.to_toml_value_impl()
+    }
+}
+
+#[cfg(feature = "toml")]
+impl<'t> DisplayTOML for &'t DynTable {
+    fn to_toml_value(&self) -> Result<toml_rs::Value, ToTOMLStringError> {
+        self.to_toml_value_impl()
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<'t> DisplayYAML for &'t DynTable {
+    fn fmt_yaml<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+        self.fmt_yaml_impl(w, indent)
+    }
+
+    fn is_block(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl PartialEq for DynTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for DynTable {}
+
+/// Tables are hashed consistently with their equality, regardless of the order of the keys:
+/// the key / value pairs are hashed in alphabetical key order.
+/// [`Comments`] are not hashed.
+///
+/// [`Comments`]: #method.comment
+impl Hash for DynTable {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len() as usize);
+
+        for (key, value) in self.iter_sorted() {
+            key.hash(state);
+            value.hash(state);
+        }
+    }
+}
+
 impl Display for DynTable {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
+    }
+}
+
+/// Creates a [`table`] from the (non-empty) string key / [`value`] pairs, as if by [`set`].
+/// Later pairs overwrite the earlier ones with the same key.
+/// See [`from_pairs`] for `String` / `&str` keys.
+///
+/// [`table`]: struct.DynTable.html
+/// [`value`]: type.DynConfigValue.html
+/// [`set`]: struct.DynTable.html#method.set
+/// [`from_pairs`]: struct.DynTable.html#method.from_pairs
+impl<K: AsRef<NonEmptyStr>, V: Into<DynConfigValue>> FromIterator<(K, V)> for DynTable {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut table = Self::new();
+
+        for (key, value) in iter {
+            table.set(key, value);
+        }
+
+        table
+    }
+}
+
+/// Splits the dot-separated `path` into keys, processing the escaped dots (`"\."`), backslashes (`"\\"`)
+/// and digits (`"\0"`).
+/// Always returns at least one (possibly empty) key.
+pub(super) fn split_dotted_path(path: &str) -> Vec<String> {
+    split_dotted_path_escaped(path)
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect()
+}
+
+/// Same as [`split_dotted_path`], but also returns whether each key starts with an escaped character.
+pub(super) fn split_dotted_path_escaped(path: &str) -> Vec<(String, bool)> {
+    let mut keys = Vec::new();
+    let mut key = String::new();
+    let mut escaped = false;
+
+    let mut chars = path.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => keys.push((std::mem::take(&mut key), std::mem::take(&mut escaped))),
+            '\\' => match chars.next() {
+                Some(c) if c == '.' || c == '\\' || c.is_ascii_digit() => {
+                    escaped |= key.is_empty();
+                    key.push(c);
+                }
+                Some(c) => {
+                    key.push('\\');
+                    key.push(c);
+                }
+                None => key.push('\\'),
+            },
+            c => key.push(c),
+        }
+    }
+
+    keys.push((key, escaped));
+
+    keys
+}
+
+/// Appends the `key` to the dot-separated `path`, escaping the dots (`"\."`) and backslashes (`"\\"`)
+/// so that [`split_dotted_path`] splits it back into the original key.
+pub(super) fn push_dotted_path_key(path: &mut String, key: &str) {
+    for c in key.chars() {
+        if c == '.' || c == '\\' {
+            path.push('\\');
+        }
+
+        path.push(c);
     }
 }
 
@@ -817,18 +2017,83 @@ mod tests {
 
         assert!(!table.set(nestr!("foo"), true));
 
-        assert_eq!(table.len(), 1);
-        assert!(!table.is_empty());
+        assert_eq!(table.len(), 1);
+        assert!(!table.is_empty());
+
+        assert!(!table.set(nestr!("bar"), 7));
+
+        assert_eq!(table.len(), 2);
+        assert!(!table.is_empty());
+
+        table.clear();
+
+        assert_eq!(table.len(), 0);
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn partial_eq() {
+        let mut l = DynTable::new();
+        let mut r = DynTable::new();
+
+        assert!(l == r);
+
+        l.set(nestr!("foo"), 7);
+        l.set(nestr!("bar"), "bob");
+
+        assert!(l != r);
+
+        // Table equality does not depend on the key insertion order.
+        r.set(nestr!("bar"), "bob");
+        r.set(nestr!("foo"), 7);
+
+        assert!(l == r);
+
+        let mut nested = DynTable::new();
+        nested.set(nestr!("baz"), true);
+
+        l.set(nestr!("nested"), nested.clone());
+
+        assert!(l != r);
+
+        r.set(nestr!("nested"), nested);
+
+        assert!(l == r);
+        assert!(l.get_val("nested").unwrap() == r.get_val("nested").unwrap());
+
+        r.set(nestr!("foo"), 7.0);
+
+        assert!(l != r);
+
+        // Floats are compared bit-exactly.
+        l.set(nestr!("foo"), f64::NAN);
+        r.set(nestr!("foo"), f64::NAN);
+
+        assert!(l == r);
+
+        l.set(nestr!("foo"), 0.0);
+        r.set(nestr!("foo"), -0.0);
+
+        assert!(l != r);
+
+        r.set(nestr!("foo"), 0.0);
+
+        assert!(l == r);
+    }
+
+    #[test]
+    fn non_empty() {
+        let mut table = DynTable::new();
 
-        assert!(!table.set(nestr!("bar"), 7));
+        assert!(table.non_empty().is_none());
 
-        assert_eq!(table.len(), 2);
-        assert!(!table.is_empty());
+        assert!(!table.set(nestr!("foo"), 7));
 
-        table.clear();
+        let non_empty = table.non_empty().unwrap();
 
-        assert_eq!(table.len(), 0);
-        assert!(table.is_empty());
+        assert_eq!(non_empty.len(), 1);
+        assert_eq!(non_empty.get_i64("foo").unwrap(), 7);
+        assert_eq!(non_empty.table().len(), 1);
     }
 
     #[test]
@@ -855,6 +2120,153 @@ mod tests {
         assert!(!table.contains("bar"));
     }
 
+    #[test]
+    fn retain() {
+        let mut table = DynTable::new();
+
+        assert!(!table.set(nestr!("_foo"), 7));
+        assert!(!table.set(nestr!("bar"), true));
+        assert!(!table.set(nestr!("_baz"), "qux"));
+        assert!(table.set_comment(nestr!("_foo"), "Comment.").is_none());
+        assert!(table.set_comment(nestr!("bar"), "Comment.").is_none());
+
+        table.retain(|key, _| !key.as_str().starts_with('_'));
+
+        assert_eq!(table.len(), 1);
+        assert!(table.contains("bar"));
+        assert!(table.comment("_foo").is_none());
+        assert_eq!(table.comment("bar").unwrap(), "Comment.");
+
+        table.retain(|_, value| !value.bool().unwrap());
+
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn from_iter() {
+        let table: DynTable = vec![
+            (nestr!("foo"), Value::I64(7)),
+            (nestr!("bar"), "baz".into()),
+            (nestr!("foo"), Value::I64(9)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get_i64("foo").unwrap(), 9);
+        assert_eq!(table.get_string("bar").unwrap(), "baz");
+
+        let table: DynTable = ["a", "b", "c"]
+            .iter()
+            .map(|key| (NonEmptyStr::new(key).unwrap(), true))
+            .collect();
+
+        assert_eq!(table.len(), 3);
+        assert!(table.iter().all(|(_, value)| value.bool().unwrap()));
+    }
+
+    #[test]
+    fn from_pairs() {
+        let table = DynTable::from_pairs(vec![
+            ("foo".to_owned(), Value::I64(7)),
+            ("bar".to_owned(), "baz".into()),
+            ("foo".to_owned(), Value::I64(9)),
+        ])
+        .unwrap();
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get_i64("foo").unwrap(), 9);
+        assert_eq!(table.get_string("bar").unwrap(), "baz");
+
+        let table = DynTable::from_pairs(["a", "b", "c"].iter().map(|key| (key, true))).unwrap();
+
+        assert_eq!(table.len(), 3);
+        assert!(table.iter().all(|(_, value)| value.bool().unwrap()));
+
+        assert_eq!(
+            DynTable::from_pairs(vec![("foo", true), ("", false)])
+                .err()
+                .unwrap(),
+            FromPairsError::EmptyKey(1)
+        );
+    }
+
+    #[test]
+    fn iter_sorted() {
+        let table: DynTable = vec![
+            (nestr!("foo"), Value::I64(1)),
+            (nestr!("bar"), Value::I64(2)),
+            (nestr!("baz"), Value::I64(3)),
+            (nestr!("Qux"), Value::I64(4)),
+        ]
+        .into_iter()
+        .collect();
+
+        let iter = table.iter_sorted();
+        assert_eq!(iter.len(), 4);
+
+        let pairs: Vec<_> = iter
+            .map(|(key, value)| (key.as_str(), value.i64().unwrap()))
+            .collect();
+
+        assert_eq!(pairs, vec![("Qux", 4), ("bar", 2), ("baz", 3), ("foo", 1)]);
+
+        assert_eq!(DynTable::new().iter_sorted().len(), 0);
+    }
+
+    #[test]
+    fn keys_and_values() {
+        let table: DynTable = vec![
+            (nestr!("foo"), Value::I64(1)),
+            (nestr!("bar"), Value::I64(2)),
+            (nestr!("baz"), Value::I64(3)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(table.keys().len(), 3);
+        assert_eq!(table.values().len(), 3);
+
+        let mut keys: Vec<_> = table.keys().map(NonEmptyStr::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["bar", "baz", "foo"]);
+
+        let mut values: Vec<_> = table.values().map(|value| value.i64().unwrap()).collect();
+        values.sort();
+        assert_eq!(values, vec![1, 2, 3]);
+
+        assert_eq!(DynTable::new().keys().len(), 0);
+        assert_eq!(DynTable::new().values().len(), 0);
+    }
+
+    #[test]
+    fn remove() {
+        let mut table = DynTable::new();
+
+        let mut nested = DynTable::new();
+        assert!(!nested.set(nestr!("bar"), 7));
+
+        assert!(!table.set(nestr!("foo"), nested));
+        assert!(!table.set(nestr!("baz"), true));
+        assert_eq!(table.len(), 2);
+
+        // Missing / empty keys.
+        assert!(table.remove("qux").is_none());
+        assert!(table.remove("").is_none());
+        assert_eq!(table.len(), 2);
+
+        // Move the removed value to another table.
+        let mut other = DynTable::new();
+        assert!(!other.set(nestr!("foo"), table.remove("foo").unwrap()));
+
+        assert_eq!(table.len(), 1);
+        assert!(!table.contains("foo"));
+        assert_eq!(
+            other.get_i64_path(&["foo".into(), "bar".into()]).unwrap(),
+            7
+        );
+    }
+
     #[test]
     fn DynTableError_KeyDoesNotExist() {
         let mut table = DynTable::new();
@@ -1211,6 +2623,12 @@ mod tests {
             -17.235
         ));
 
+        // Iterators report their exact length.
+        let mut iter = table.iter();
+        assert_eq!(iter.len(), table.len() as usize);
+        iter.next().unwrap();
+        assert_eq!(iter.len(), table.len() as usize - 1);
+
         // Iterate the table.
         for (key, value) in table.iter() {
             match key.as_ref() {
@@ -1245,4 +2663,463 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn u64() {
+        let mut table = DynTable::new();
+
+        assert!(!table.set(nestr!("u64"), DynConfigValue::U64(u64::MAX)));
+        assert!(!table.set(nestr!("small_u64"), DynConfigValue::U64(9)));
+        assert!(!table.set(nestr!("i64"), 7));
+        assert!(!table.set(nestr!("negative_i64"), -7));
+
+        assert_eq!(table.get_u64("u64").unwrap(), u64::MAX);
+        assert_eq!(table.get_val("u64").unwrap().get_type(), ValueType::U64);
+        assert!(cmp_f64(table.get_f64("u64").unwrap(), u64::MAX as f64));
+
+        // `U64` values may be fetched as `i64`'s if they fit.
+        assert_eq!(table.get_i64("small_u64").unwrap(), 9);
+        assert_eq!(
+            table.get_i64("u64").err().unwrap(),
+            TableError::IncorrectValueType(ValueType::U64)
+        );
+
+        // Non-negative `I64` values may be fetched as `u64`'s.
+        assert_eq!(table.get_u64("i64").unwrap(), 7);
+        assert_eq!(
+            table.get_u64("negative_i64").err().unwrap(),
+            TableError::IncorrectValueType(ValueType::I64)
+        );
+
+        // `I64` and `U64` values are never equal.
+        assert!(table.get_val("small_u64").unwrap() != DynConfigValueRef::I64(9));
+    }
+
+    #[test]
+    fn value_conversions() {
+        let mut table = DynTable::new();
+
+        let owned = String::from("owned");
+
+        let mut array = DynArray::new();
+        array.push(8080.into()).unwrap();
+        array.push(Value::I64(7)).unwrap();
+
+        assert!(!table.set(nestr!("bool"), true));
+        assert!(!table.set(nestr!("i64"), 8080));
+        assert!(!table.set(nestr!("f64"), 3.5));
+        assert!(!table.set(nestr!("str"), "foo"));
+        assert!(!table.set(nestr!("string"), owned.clone()));
+        assert!(!table.set(nestr!("string_ref"), &owned));
+        assert!(!table.set(nestr!("array"), array));
+        assert!(!table.set(nestr!("table"), DynTable::new()));
+
+        assert_eq!(table.get_bool("bool").unwrap(), true);
+        assert_eq!(table.get_i64("i64").unwrap(), 8080);
+        assert!(cmp_f64(table.get_f64("f64").unwrap(), 3.5));
+        assert_eq!(table.get_string("str").unwrap(), "foo");
+        assert_eq!(table.get_string("string").unwrap(), "owned");
+        assert_eq!(table.get_string("string_ref").unwrap(), "owned");
+        assert_eq!(table.get_array("array").unwrap().len(), 2);
+        assert!(table.get_table("table").unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_or() {
+        let mut table = DynTable::new();
+
+        assert!(!table.set(nestr!("bool"), true));
+        assert!(!table.set(nestr!("i64"), 7));
+        assert!(!table.set(nestr!("f64"), 3.5));
+        assert!(!table.set(nestr!("string"), "foo"));
+
+        // Existing values are returned.
+        assert_eq!(table.get_bool_or("bool", false).unwrap(), true);
+        assert_eq!(table.get_i64_or("i64", 9).unwrap(), 7);
+        assert!(cmp_f64(table.get_f64_or("f64", 9.0).unwrap(), 3.5));
+        assert_eq!(table.get_string_or("string", "bar").unwrap(), "foo");
+
+        // Missing (or empty) keys return the default.
+        assert_eq!(table.get_bool_or("missing", false).unwrap(), false);
+        assert_eq!(table.get_i64_or("missing", 9).unwrap(), 9);
+        assert!(cmp_f64(table.get_f64_or("", 9.0).unwrap(), 9.0));
+        assert_eq!(table.get_string_or("missing", "bar").unwrap(), "bar");
+        assert_eq!(table.get_or("missing", 9u64).unwrap(), 9);
+
+        // Values of incorrect type are still an error.
+        assert_eq!(
+            table.get_i64_or("string", 9).err().unwrap(),
+            TableError::IncorrectValueType(ValueType::String)
+        );
+        assert_eq!(
+            table.get_string_or("bool", "bar").err().unwrap(),
+            TableError::IncorrectValueType(ValueType::Bool)
+        );
+    }
+
+    #[test]
+    fn coerced() {
+        let mut table = DynTable::new();
+
+        assert!(!table.set(nestr!("i64"), 7));
+        assert!(!table.set(nestr!("big_i64"), i64::MAX));
+        assert!(!table.set(nestr!("min_i64"), i64::MIN));
+        assert!(!table.set(nestr!("u64"), DynConfigValue::U64(u64::MAX)));
+        assert!(!table.set(nestr!("f64"), 3.0));
+        assert!(!table.set(nestr!("fractional_f64"), 3.5));
+        assert!(!table.set(nestr!("nan"), f64::NAN));
+        assert!(!table.set(nestr!("bool"), true));
+
+        // Exactly representable values are converted.
+        assert!(cmp_f64(table.get_f64_coerced("i64").unwrap(), 7.0));
+        assert!(cmp_f64(
+            table.get_f64_coerced("min_i64").unwrap(),
+            i64::MIN as f64
+        ));
+        assert_eq!(table.get_i64_coerced("i64").unwrap(), 7);
+        assert_eq!(table.get_i64_coerced("f64").unwrap(), 3);
+        assert_eq!(
+            table.get_i64_coerced("min_i64").unwrap(),
+            table.get_i64("min_i64").unwrap()
+        );
+
+        // Lossy conversions fail.
+        assert_eq!(
+            table.get_f64_coerced("big_i64").err().unwrap(),
+            TableError::LossyConversion(ValueType::I64)
+        );
+        assert_eq!(
+            table.get_f64_coerced("u64").err().unwrap(),
+            TableError::LossyConversion(ValueType::U64)
+        );
+        assert_eq!(
+            table.get_i64_coerced("u64").err().unwrap(),
+            TableError::LossyConversion(ValueType::U64)
+        );
+        assert_eq!(
+            table.get_i64_coerced("fractional_f64").err().unwrap(),
+            TableError::LossyConversion(ValueType::F64)
+        );
+        assert_eq!(
+            table.get_i64_coerced("nan").err().unwrap(),
+            TableError::LossyConversion(ValueType::F64)
+        );
+
+        // Unlike the coerced getters, `get_i64` truncates.
+        assert_eq!(table.get_i64("fractional_f64").unwrap(), 3);
+
+        assert_eq!(
+            table.get_i64_coerced("bool").err().unwrap(),
+            TableError::IncorrectValueType(ValueType::Bool)
+        );
+        assert_eq!(
+            table.get_f64_coerced("missing").err().unwrap(),
+            TableError::KeyDoesNotExist
+        );
+    }
+
+    #[test]
+    fn type_at_dotted_path() {
+        let mut logging = DynTable::new();
+        assert!(!logging.set(nestr!("level"), "info"));
+
+        let mut features = DynTable::new();
+        assert!(!features.set(nestr!("logging"), logging));
+        assert!(!features.set(nestr!("my.feature"), true));
+
+        let mut root = DynTable::new();
+        assert!(!root.set(nestr!("features"), features));
+        assert!(!root.set(nestr!("port"), 8080));
+
+        assert_eq!(
+            root.type_at_dotted_path("features.logging"),
+            Some(ValueType::Table)
+        );
+        assert_eq!(
+            root.type_at_dotted_path("features.logging.level"),
+            Some(ValueType::String)
+        );
+        assert_eq!(
+            root.type_at_dotted_path(r"features.my\.feature"),
+            Some(ValueType::Bool)
+        );
+        assert_eq!(root.type_at_dotted_path("port"), Some(ValueType::I64));
+
+        assert!(root.contains_dotted_path("features.logging.level"));
+        assert!(root.contains_dotted_path("port"));
+
+        // Missing keys / intermediate tables.
+        assert_eq!(root.type_at_dotted_path("features.tracing"), None);
+        assert_eq!(root.type_at_dotted_path("features.tracing.level"), None);
+        assert_eq!(root.type_at_dotted_path("missing.logging"), None);
+        assert!(!root.contains_dotted_path("features.logging.format"));
+
+        // Intermediate values which are not tables.
+        assert_eq!(root.type_at_dotted_path("port.number"), None);
+        assert!(!root.contains_dotted_path("features.logging.level.value"));
+
+        // Empty keys.
+        assert_eq!(root.type_at_dotted_path(""), None);
+        assert_eq!(root.type_at_dotted_path("features..logging"), None);
+        assert!(!root.contains_dotted_path("features."));
+    }
+
+    #[test]
+    fn get_val_dotted_path() {
+        let mut network = DynTable::new();
+        assert!(!network.set(nestr!("port"), 8080));
+        assert!(!network.set(nestr!("host.name"), "localhost"));
+        assert!(!network.set(nestr!("back\\slash"), true));
+
+        let mut server = DynTable::new();
+        assert!(!server.set(nestr!("network"), network));
+        assert!(!server.set(nestr!("name"), "foo"));
+
+        let mut root = DynTable::new();
+        assert!(!root.set(nestr!("server"), server));
+
+        assert_eq!(
+            root.get_val_dotted_path("server.network.port")
+                .unwrap()
+                .i64()
+                .unwrap(),
+            8080
+        );
+        assert_eq!(
+            root.get_val_dotted_path("server.name")
+                .unwrap()
+                .string()
+                .unwrap(),
+            "foo"
+        );
+        assert_eq!(
+            root.get_val_dotted_path("server.network")
+                .unwrap()
+                .table()
+                .unwrap()
+                .len(),
+            3
+        );
+        // Escaped dots / backslashes.
+        assert_eq!(
+            root.get_val_dotted_path(r"server.network.host\.name")
+                .unwrap()
+                .string()
+                .unwrap(),
+            "localhost"
+        );
+        assert!(root
+            .get_val_dotted_path(r"server.network.back\\slash")
+            .unwrap()
+            .bool()
+            .unwrap());
+        assert!(root
+            .get_val_dotted_path(r"server.network.back\slash")
+            .unwrap()
+            .bool()
+            .unwrap());
+
+        assert_eq!(
+            root.get_val_dotted_path("server.missing.port")
+                .err()
+                .unwrap(),
+            DynTablePathError::IntermediateKeyDoesNotExist(
+                vec![nestr!("server").into(), nestr!("missing").into()].into()
+            )
+        );
+        assert_eq!(
+            root.get_val_dotted_path("server..port").err().unwrap(),
+            DynTablePathError::IntermediateKeyDoesNotExist(vec![nestr!("server").into()].into())
+        );
+        assert_eq!(
+            root.get_val_dotted_path("server.name.port").err().unwrap(),
+            DynTablePathError::IntermediateValueNotATable {
+                path: vec![nestr!("server").into(), nestr!("name").into()].into(),
+                value_type: ValueType::String,
+            }
+        );
+        assert_eq!(
+            root.get_val_dotted_path("server.network.missing")
+                .err()
+                .unwrap(),
+            DynTablePathError::KeyDoesNotExist(
+                vec![
+                    nestr!("server").into(),
+                    nestr!("network").into(),
+                    nestr!("missing").into()
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            root.get_val_dotted_path("").err().unwrap(),
+            DynTablePathError::KeyDoesNotExist(ConfigPath::new())
+        );
+        assert_eq!(
+            root.get_val_dotted_path("server.").err().unwrap(),
+            DynTablePathError::KeyDoesNotExist(vec![nestr!("server").into()].into())
+        );
+    }
+
+    #[test]
+    fn get_dotted_path_mut() {
+        let mut root = DynTable::new();
+
+        root.set_dotted_path("a.b.c", Some(Value::I64(7))).unwrap();
+        root.set_dotted_path("a.arr", Some(Value::Array(DynArray::new())))
+            .unwrap();
+
+        // Descend mutably and modify the nested table in place.
+        root.get_table_dotted_path_mut("a.b")
+            .unwrap()
+            .set(nestr!("c"), 1);
+        assert_eq!(root.get_val_dotted_path("a.b.c").unwrap().i64().unwrap(), 1);
+
+        root.get_table_mut("a")
+            .unwrap()
+            .get_array_mut("arr")
+            .unwrap()
+            .push(Value::Bool(true))
+            .unwrap();
+        match root.get_val_dotted_path_mut("a.arr").unwrap() {
+            Value::Array(arr) => arr.push(Value::Bool(false)).unwrap(),
+            _ => panic!("expected an array"),
+        }
+        assert_eq!(
+            root.get_table("a")
+                .unwrap()
+                .get_array("arr")
+                .unwrap()
+                .as_bool_vec()
+                .unwrap(),
+            vec![true, false]
+        );
+
+        assert_eq!(
+            root.get_table_dotted_path_mut("a.b.c").err().unwrap(),
+            DynTablePathError::ValueNotATable {
+                path: vec![nestr!("a").into(), nestr!("b").into(), nestr!("c").into()].into(),
+                value_type: ValueType::I64,
+            }
+        );
+        assert_eq!(
+            root.get_table_dotted_path_mut("a.b.c.d").err().unwrap(),
+            DynTablePathError::IntermediateValueNotATable {
+                path: vec![nestr!("a").into(), nestr!("b").into(), nestr!("c").into()].into(),
+                value_type: ValueType::I64,
+            }
+        );
+        assert_eq!(
+            root.get_val_dotted_path_mut("a.missing").err().unwrap(),
+            DynTablePathError::KeyDoesNotExist(
+                vec![nestr!("a").into(), nestr!("missing").into()].into()
+            )
+        );
+        assert_eq!(
+            root.get_val_dotted_path_mut("a..b").err().unwrap(),
+            DynTablePathError::IntermediateKeyDoesNotExist(vec![nestr!("a").into()].into())
+        );
+    }
+
+    #[test]
+    fn set_dotted_path() {
+        let mut root = DynTable::new();
+
+        // Create the intermediate tables.
+        root.set_dotted_path("server.network.port", Some(Value::I64(8080)))
+            .unwrap();
+        assert_eq!(
+            root.get_val_dotted_path("server.network.port")
+                .unwrap()
+                .i64()
+                .unwrap(),
+            8080
+        );
+
+        // Change the value.
+        root.set_dotted_path("server.network.port", Some(Value::I64(9090)))
+            .unwrap();
+        root.set_dotted_path_no_create(r"server.network.host\.name", Some("localhost".into()))
+            .unwrap();
+        assert_eq!(
+            root.get_val_dotted_path("server.network.port")
+                .unwrap()
+                .i64()
+                .unwrap(),
+            9090
+        );
+        assert_eq!(
+            root.get_table("server")
+                .unwrap()
+                .get_table("network")
+                .unwrap()
+                .get_string("host.name")
+                .unwrap(),
+            "localhost"
+        );
+
+        // Intermediate values are not overwritten.
+        assert_eq!(
+            root.set_dotted_path("server.network.port.foo", Some(Value::Bool(true)))
+                .err()
+                .unwrap(),
+            DynTablePathError::IntermediateValueNotATable {
+                path: vec![
+                    nestr!("server").into(),
+                    nestr!("network").into(),
+                    nestr!("port").into()
+                ]
+                .into(),
+                value_type: ValueType::I64,
+            }
+        );
+
+        // Intermediate tables are not created.
+        assert_eq!(
+            root.set_dotted_path_no_create("server.missing.foo", Some(Value::Bool(true)))
+                .err()
+                .unwrap(),
+            DynTablePathError::IntermediateKeyDoesNotExist(
+                vec![nestr!("server").into(), nestr!("missing").into()].into()
+            )
+        );
+        assert!(root.get_val_dotted_path("server.missing").is_err());
+
+        // Empty keys.
+        assert_eq!(
+            root.set_dotted_path("server..foo", Some(Value::Bool(true)))
+                .err()
+                .unwrap(),
+            DynTablePathError::IntermediateKeyDoesNotExist(vec![nestr!("server").into()].into())
+        );
+        assert_eq!(
+            root.set_dotted_path("new.", Some(Value::Bool(true)))
+                .err()
+                .unwrap(),
+            DynTablePathError::KeyDoesNotExist(vec![nestr!("new").into()].into())
+        );
+        assert!(!root.contains("new"));
+
+        // Remove the value.
+        root.set_dotted_path("server.network.port", None).unwrap();
+        assert!(root.get_val_dotted_path("server.network.port").is_err());
+        assert_eq!(
+            root.set_dotted_path("server.network.port", None)
+                .err()
+                .unwrap(),
+            DynTablePathError::KeyDoesNotExist(
+                vec![
+                    nestr!("server").into(),
+                    nestr!("network").into(),
+                    nestr!("port").into()
+                ]
+                .into()
+            )
+        );
+        assert_eq!(
+            root.set_dotted_path("missing.port", None).err().unwrap(),
+            DynTablePathError::IntermediateKeyDoesNotExist(vec![nestr!("missing").into()].into())
+        );
+        assert!(!root.contains("missing"));
+    }
 }
@@ -0,0 +1,261 @@
+use {
+    crate::*,
+    std::{
+        any::{Any, TypeId},
+        collections::HashMap,
+    },
+};
+
+type TagParseFn<T> = Box<dyn Fn(&str) -> Option<T>>;
+type TagFormatFn<T> = Box<dyn Fn(&T) -> String>;
+
+struct TagEntry {
+    // Non-empty.
+    tag: String,
+    // `TagParseFn<T>` for the registered type `T`.
+    parse: Box<dyn Any>,
+    // `TagFormatFn<T>` for the registered type `T`.
+    format: Box<dyn Any>,
+}
+
+/// A registry of custom scalar "tag" types,
+/// which are stored in [`string`] config values as tagged strings, e.g. `"!color 0xFF0000"`.
+///
+/// A tagged string consists of an exclamation mark (`'!'`), the (non-empty) tag, a single space (`' '`)
+/// and the value payload (e.g. `"0xFF0000"`) produced / consumed by the closures registered for the type.
+///
+/// Used by [`get_tagged`] / [`set_tagged`] to read / write the custom type values.
+///
+/// [`string`]: enum.Value.html#variant.String
+/// [`get_tagged`]: struct.DynTable.html#method.get_tagged
+/// [`set_tagged`]: struct.DynTable.html#method.set_tagged
+#[derive(Default)]
+pub struct TagRegistry {
+    entries: HashMap<TypeId, TagEntry>,
+}
+
+impl TagRegistry {
+    /// Creates a new empty [`tag registry`].
+    ///
+    /// [`tag registry`]: struct.TagRegistry.html
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the type `T` with the (non-empty) `tag`,
+    /// and the closures used to `parse` the tagged string payload into a `T` / `format` a `T` into a payload.
+    /// `parse` returns `None` if the payload is not a valid `T`.
+    ///
+    /// If the type `T` was already registered, its tag and closures are replaced and `true` is returned.
+    /// Otherwise returns `false`.
+    ///
+    /// Returns an [`error`] if the `tag` contains spaces (`' '`).
+    ///
+    /// [`error`]: enum.TagError.html
+    pub fn register<T, P, F>(
+        &mut self,
+        tag: &NonEmptyStr,
+        parse: P,
+        format: F,
+    ) -> Result<bool, TagError>
+    where
+        T: 'static,
+        P: Fn(&str) -> Option<T> + 'static,
+        F: Fn(&T) -> String + 'static,
+    {
+        if tag.as_str().contains(' ') {
+            return Err(TagError::InvalidTag);
+        }
+
+        let parse: TagParseFn<T> = Box::new(parse);
+        let format: TagFormatFn<T> = Box::new(format);
+
+        Ok(self
+            .entries
+            .insert(
+                TypeId::of::<T>(),
+                TagEntry {
+                    tag: tag.as_str().into(),
+                    parse: Box::new(parse),
+                    format: Box::new(format),
+                },
+            )
+            .is_some())
+    }
+
+    /// Returns the tag the type `T` was registered with, if any.
+    pub fn tag<T: 'static>(&self) -> Option<&NonEmptyStr> {
+        self.entries.get(&TypeId::of::<T>()).map(|entry| {
+            // Safe to call - the tag is non-empty.
+            unwrap_unchecked(NonEmptyStr::new(&entry.tag), "empty tag")
+        })
+    }
+
+    /// Tries to parse the tagged `string` into a value of the registered type `T`.
+    ///
+    /// Returns an [`error`] if the type `T` is not registered, if the `string` is not tagged with the tag of the type `T`,
+    /// or if the payload is not a valid `T`.
+    ///
+    /// [`error`]: enum.TagError.html
+    pub fn parse<T: 'static>(&self, string: &str) -> Result<T, TagError> {
+        let entry = self.entry::<T>()?;
+
+        let payload = string
+            .strip_prefix('!')
+            .and_then(|string| string.strip_prefix(entry.tag.as_str()))
+            .and_then(|string| string.strip_prefix(' '))
+            .ok_or(TagError::TagMismatch)?;
+
+        // Must succeed - we only store `TagParseFn<T>` for `T`.
+        let parse = unwrap_unchecked(
+            entry.parse.downcast_ref::<TagParseFn<T>>(),
+            "invalid tag parse closure type",
+        );
+
+        parse(payload).ok_or(TagError::InvalidPayload)
+    }
+
+    /// Tries to format the `value` of the registered type `T` into a tagged string.
+    ///
+    /// Returns an [`error`] if the type `T` is not registered.
+    ///
+    /// [`error`]: enum.TagError.html
+    pub fn format<T: 'static>(&self, value: &T) -> Result<String, TagError> {
+        let entry = self.entry::<T>()?;
+
+        // Must succeed - we only store `TagFormatFn<T>` for `T`.
+        let format = unwrap_unchecked(
+            entry.format.downcast_ref::<TagFormatFn<T>>(),
+            "invalid tag format closure type",
+        );
+
+        Ok(format!("!{} {}", entry.tag, format(value)))
+    }
+
+    fn entry<T: 'static>(&self) -> Result<&TagEntry, TagError> {
+        self.entries
+            .get(&TypeId::of::<T>())
+            .ok_or(TagError::UnregisteredType)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {crate::*, ministr_macro::nestr};
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    struct Color(u32);
+
+    fn registry() -> TagRegistry {
+        let mut registry = TagRegistry::new();
+
+        assert!(!registry
+            .register(
+                nestr!("color"),
+                |payload| {
+                    u32::from_str_radix(payload.strip_prefix("0x")?, 16)
+                        .ok()
+                        .map(Color)
+                },
+                |color: &Color| format!("0x{:06X}", color.0),
+            )
+            .unwrap());
+
+        registry
+    }
+
+    #[test]
+    fn invalid_tag() {
+        let mut registry = TagRegistry::new();
+
+        assert_eq!(
+            registry
+                .register(
+                    nestr!("my color"),
+                    |_| Some(Color(0)),
+                    |color: &Color| color.0.to_string(),
+                )
+                .err()
+                .unwrap(),
+            TagError::InvalidTag
+        );
+        assert!(registry.tag::<Color>().is_none());
+    }
+
+    #[test]
+    fn tagged() {
+        let registry = registry();
+
+        assert_eq!(registry.tag::<Color>().unwrap(), nestr!("color"));
+        assert!(registry.tag::<u32>().is_none());
+
+        let mut table = DynTable::new();
+
+        assert!(!table
+            .set_tagged(nestr!("color"), &Color(0xFF0000), &registry)
+            .unwrap());
+        assert_eq!(table.get_string("color").unwrap(), "!color 0xFF0000");
+        assert_eq!(
+            table.get_tagged::<Color, _>("color", &registry).unwrap(),
+            Color(0xFF0000)
+        );
+
+        assert!(!table.set(nestr!("foo"), "!colour 0xFF0000"));
+        assert!(!table.set(nestr!("bar"), "!color red"));
+        assert!(!table.set(nestr!("baz"), 7));
+
+        assert_eq!(
+            table
+                .get_tagged::<Color, _>("foo", &registry)
+                .err()
+                .unwrap(),
+            TagError::TagMismatch
+        );
+        assert_eq!(
+            table
+                .get_tagged::<Color, _>("bar", &registry)
+                .err()
+                .unwrap(),
+            TagError::InvalidPayload
+        );
+        assert_eq!(
+            table
+                .get_tagged::<Color, _>("baz", &registry)
+                .err()
+                .unwrap(),
+            TagError::TableError(TableError::IncorrectValueType(ValueType::I64))
+        );
+        assert_eq!(
+            table
+                .get_tagged::<Color, _>("missing", &registry)
+                .err()
+                .unwrap(),
+            TagError::TableError(TableError::KeyDoesNotExist)
+        );
+
+        // The table error is exposed as the error source.
+        let err: Box<dyn std::error::Error> = table
+            .get_tagged::<Color, _>("missing", &registry)
+            .err()
+            .unwrap()
+            .into();
+        assert_eq!(
+            err.source().unwrap().to_string(),
+            TableError::KeyDoesNotExist.to_string()
+        );
+        assert_eq!(
+            table
+                .get_tagged::<u32, _>("color", &registry)
+                .err()
+                .unwrap(),
+            TagError::UnregisteredType
+        );
+        assert_eq!(
+            table
+                .set_tagged(nestr!("foo"), &7u32, &registry)
+                .err()
+                .unwrap(),
+            TagError::UnregisteredType
+        );
+    }
+}
@@ -25,6 +25,12 @@ impl<'a> From<&'a str> for DynConfigValue {
     }
 }
 
+impl<'a> From<&'a String> for DynConfigValue {
+    fn from(val: &'a String) -> Self {
+        Value::String(val.clone())
+    }
+}
+
 impl From<DynArray> for DynConfigValue {
     fn from(val: DynArray) -> Self {
         Value::Array(val)
@@ -39,7 +45,8 @@ impl From<DynTable> for DynConfigValue {
 
 impl Display for DynConfigValue {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua(f, 0)
+        self.fmt_lua(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
     }
 }
 
@@ -55,6 +62,7 @@ impl<'at> From<&'at Value<String, DynArray, DynTable>> for DynConfigValueRef<'at
         match value {
             Value::Bool(value) => Value::Bool(*value),
             Value::I64(value) => Value::I64(*value),
+            Value::U64(value) => Value::U64(*value),
             Value::F64(value) => Value::F64(*value),
             Value::String(value) => Value::String(value.as_str()),
             Value::Array(value) => Value::Array(value),
@@ -114,7 +122,8 @@ impl<'at> DynConfigValueRef<'at> {
 
 impl<'a> Display for DynConfigValueRef<'a> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua(f, 0)
+        self.fmt_lua(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
     }
 }
 
@@ -130,6 +139,7 @@ impl<'at> From<&'at mut Value<String, DynArray, DynTable>> for DynConfigValueMut
         match value {
             Value::Bool(value) => Value::Bool(*value),
             Value::I64(value) => Value::I64(*value),
+            Value::U64(value) => Value::U64(*value),
             Value::F64(value) => Value::F64(*value),
             Value::String(value) => Value::String(value.as_str()),
             Value::Array(value) => Value::Array(value),
@@ -189,7 +199,8 @@ impl<'at> DynConfigValueMut<'at> {
 
 impl<'a> Display for DynConfigValueMut<'a> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua(f, 0)
+        self.fmt_lua(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
     }
 }
 
@@ -0,0 +1,83 @@
+use crate::*;
+
+/// A visitor for the elements of a [`dyn config`], passed to [`DynConfig::visit`].
+///
+/// Each method receives the full path to the element
+/// (empty for the root [`table`]); the default implementations do nothing.
+///
+/// [`dyn config`]: struct.DynConfig.html
+/// [`DynConfig::visit`]: struct.DynConfig.html#method.visit
+/// [`table`]: struct.DynTable.html
+pub trait DynConfigVisitor<'c> {
+    /// Called for each non-[`table`] / [`array`] value (i.e. a bool / number / string).
+    ///
+    /// [`table`]: struct.DynTable.html
+    /// [`array`]: struct.DynArray.html
+    fn visit_value(&mut self, _path: &ConfigPath, _value: DynConfigValueRef<'c>) {}
+
+    /// Called for each [`table`] (including the root [`table`]) before its elements are visited.
+    ///
+    /// [`table`]: struct.DynTable.html
+    fn enter_table(&mut self, _path: &ConfigPath, _table: &'c DynTable) {}
+
+    /// Called for each [`table`] (including the root [`table`]) after its elements are visited.
+    ///
+    /// [`table`]: struct.DynTable.html
+    fn leave_table(&mut self, _path: &ConfigPath, _table: &'c DynTable) {}
+
+    /// Called for each [`array`] before its elements are visited.
+    ///
+    /// [`array`]: struct.DynArray.html
+    fn enter_array(&mut self, _path: &ConfigPath, _array: &'c DynArray) {}
+
+    /// Called for each [`array`] after its elements are visited.
+    ///
+    /// [`array`]: struct.DynArray.html
+    fn leave_array(&mut self, _path: &ConfigPath, _array: &'c DynArray) {}
+}
+
+/// Visits the dyn `table` at `path` and, recursively, its elements in alphabetical key order.
+pub(super) fn visit_table<'c, V: DynConfigVisitor<'c>>(
+    table: &'c DynTable,
+    path: &mut ConfigPath,
+    visitor: &mut V,
+) {
+    visitor.enter_table(path, table);
+
+    for (key, value) in table.iter_sorted() {
+        path.0.push(key.into());
+        visit_value(value, path, visitor);
+        path.0.pop();
+    }
+
+    visitor.leave_table(path, table);
+}
+
+/// Visits the dyn `array` at `path` and, recursively, its elements in index order.
+fn visit_array<'c, V: DynConfigVisitor<'c>>(
+    array: &'c DynArray,
+    path: &mut ConfigPath,
+    visitor: &mut V,
+) {
+    visitor.enter_array(path, array);
+
+    for (index, value) in array.iter().enumerate() {
+        path.0.push((index as u32).into());
+        visit_value(value, path, visitor);
+        path.0.pop();
+    }
+
+    visitor.leave_array(path, array);
+}
+
+fn visit_value<'c, V: DynConfigVisitor<'c>>(
+    value: DynConfigValueRef<'c>,
+    path: &mut ConfigPath,
+    visitor: &mut V,
+) {
+    match value {
+        Value::Table(table) => visit_table(table, path, visitor),
+        Value::Array(array) => visit_array(array, path, visitor),
+        value => visitor.visit_value(path, value),
+    }
+}
@@ -3,6 +3,7 @@ use {
     std::{
         error::Error,
         fmt::{Display, Formatter},
+        path::PathBuf,
     },
 };
 
@@ -19,8 +20,15 @@ pub enum TableError {
     /// Contains the actual value [`type`].
     ///
     /// [`Table`]: enum.Value.html#variant.Table
-    /// [`type`]: enum.Value.html#variant.Table
+    /// [`type`]: enum.ValueType.html
     IncorrectValueType(ValueType),
+    /// [`Table`] value is of a compatible numeric [`type`],
+    /// but cannot be converted to the requested type without loss of precision.
+    /// Contains the actual value [`type`].
+    ///
+    /// [`Table`]: enum.Value.html#variant.Table
+    /// [`type`]: enum.ValueType.html
+    LossyConversion(ValueType),
 }
 
 impl Error for TableError {}
@@ -34,7 +42,14 @@ impl Display for TableError {
             IncorrectValueType(actual_type) => {
                 write!(
                     f,
-                    "table value is of incorrect and incompatible type (expected {})",
+                    "table value is of incorrect and incompatible type (found {})",
+                    actual_type
+                )
+            }
+            LossyConversion(actual_type) => {
+                write!(
+                    f,
+                    "table value of type {} cannot be converted to the requested type without loss of precision",
                     actual_type
                 )
             }
@@ -61,7 +76,7 @@ pub enum ArrayError {
     /// Contains the actual value [`type`].
     ///
     /// [`Array`]: enum.Value.html#variant.Array
-    /// [`type`]: enum.Value.html#variant.Array
+    /// [`type`]: enum.ValueType.html
     IncorrectValueType(ValueType),
 }
 
@@ -226,9 +241,82 @@ impl<'a> Display for GetPathError {
             ),
             IncorrectValueType(actual_type) => write!(
                 f,
-                "value is of incorrect and incompatible type (expected {})",
+                "value is of incorrect and incompatible type (found {})",
                 actual_type
             ),
         }
     }
 }
+
+/// An error returned by the methods which load a config from a file,
+/// e.g. [`LuaConfig::from_file`], [`DynConfig::from_ini_file`], [`DynConfig::from_bin_file`].
+///
+/// [`LuaConfig::from_file`]: struct.LuaConfig.html#method.from_file
+/// [`DynConfig::from_ini_file`]: struct.DynConfig.html#method.from_ini_file
+/// [`DynConfig::from_bin_file`]: struct.DynConfig.html#method.from_bin_file
+#[derive(Debug)]
+pub struct FileError<E> {
+    /// Path to the file.
+    pub path: PathBuf,
+    /// Actual error.
+    pub error: FileErrorKind<E>,
+}
+
+#[cfg(any(
+    feature = "lua",
+    all(feature = "dyn", any(feature = "bin", feature = "ini"))
+))]
+impl<E> FileError<E> {
+    pub(crate) fn io(path: &std::path::Path, error: std::io::Error) -> Self {
+        Self {
+            path: path.to_owned(),
+            error: FileErrorKind::IOError(error),
+        }
+    }
+
+    pub(crate) fn config(path: &std::path::Path, error: E) -> Self {
+        Self {
+            path: path.to_owned(),
+            error: FileErrorKind::ConfigError(error),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for FileError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.error {
+            FileErrorKind::IOError(error) => Some(error),
+            FileErrorKind::ConfigError(error) => Some(error),
+        }
+    }
+}
+
+impl<E: Display> Display for FileError<E> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "error in \"{}\": {}", self.path.display(), self.error)
+    }
+}
+
+/// Actual error contained in the [`FileError`].
+///
+/// [`FileError`]: struct.FileError.html
+#[derive(Debug)]
+pub enum FileErrorKind<E> {
+    /// Failed to read the file.
+    /// Contains the actual I/O error.
+    IOError(std::io::Error),
+    /// Failed to load the config from the file contents.
+    /// Contains the actual config error.
+    ConfigError(E),
+}
+
+impl<E: Display> Display for FileErrorKind<E> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use FileErrorKind::*;
+
+        match self {
+            IOError(error) => write!(f, "failed to read the file: {}", error),
+            ConfigError(error) => error.fmt(f),
+        }
+    }
+}
@@ -20,6 +20,25 @@ pub trait IniConfig<'s> {
     /// If `overwrite` is `false`, the `key` / `value` pair is added for the first time.
     fn add_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>, overwrite: bool);
 
+    /// Appends the `value` to the array at `key` in the current section.
+    /// If the current value at `key` is not an array, it must be first converted to a one-element array.
+    ///
+    /// Only called for duplicate keys (i.e. [`contains_key`](#method.contains_key)
+    /// previously returned `Some(false)` for this `key`) if the parser is [`configured`](enum.IniDuplicateKeys.html)
+    /// to [`accumulate`](enum.IniDuplicateKeys.html#variant.Array) them into arrays.
+    ///
+    /// Returns `false` (and must leave the current value at `key` unchanged) if the `value` type
+    /// is incompatible with the current value at `key`, or with the array element type, if it is an array;
+    /// else returns `true`.
+    ///
+    /// Returns `false` by default, i.e. the parser reports a [`MixedArray`] error for the duplicate key
+    /// if the config does not support accumulating duplicate keys into arrays.
+    ///
+    /// [`MixedArray`]: enum.IniErrorKind.html#variant.MixedArray
+    fn append_value(&mut self, _key: NonEmptyIniStr<'s, '_>, _value: IniValue<'s, '_>) -> bool {
+        false
+    }
+
     /// Adds the `section` to the current section and makes it the current section for the following calls to
     /// [`contains_key`](#method.contains_key), [`add_value`](#method.add_value), [`start_array`](#method.start_array),
     /// [`end_section`](#method.end_section).
@@ -58,4 +77,24 @@ pub trait IniConfig<'s> {
 
     /// Finishes the current `array`, started by the preceding call to [`start_array`](#method.start_array) with the same `array` name.
     fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>);
+
+    /// Reports the (trimmed, possibly empty) text of a comment line following the comment delimiter.
+    /// Only called if the parser is [`configured`](struct.IniParser.html#method.preserve_comments) to preserve comments.
+    ///
+    /// The comment relates to the following call to [`add_value`](#method.add_value),
+    /// [`start_section`](#method.start_section) or [`start_array`](#method.start_array), if any.
+    ///
+    /// Does nothing by default.
+    fn add_comment(&mut self, _comment: &'s str) {}
+
+    /// Reports the (trimmed, possibly empty) text of an inline comment following the comment delimiter
+    /// after a value on the same line.
+    /// Only called if the parser is [`configured`](struct.IniParser.html#method.preserve_comments) to preserve comments
+    /// and [`supports`](struct.IniParser.html#method.inline_comments) inline comments.
+    ///
+    /// The comment relates to the immediately preceding call to [`add_value`](#method.add_value),
+    /// [`append_value`](#method.append_value) or [`end_array`](#method.end_array).
+    ///
+    /// Does nothing by default.
+    fn add_inline_comment(&mut self, _comment: &'s str) {}
 }
@@ -27,7 +27,10 @@ pub enum IniErrorKind {
     EmptySectionName,
     /// Invalid (missing or not a section) parent section name.
     InvalidParentSection,
-    /// Maximum allowed nested section depth exceeded.
+    /// Maximum allowed [`nested section depth`](struct.IniParser.html#method.nested_section_depth) exceeded -
+    /// a section encountered when sections are not supported (`nested_section_depth == 0`),
+    /// or a nested section path is longer than `nested_section_depth`.
+    /// The [`error`](struct.IniError.html) contains the line and column of the offending section.
     NestedSectionDepthExceeded,
     /// Duplicate section name encountered and is not allowed by options.
     DuplicateSection,
@@ -86,6 +89,8 @@ pub enum IniErrorKind {
     UnexpectedEndOfFileInQuotedArrayValue,
 }
 
+impl Error for IniErrorKind {}
+
 impl Display for IniErrorKind {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         use IniErrorKind::*;
@@ -159,9 +164,11 @@ impl Display for IniErrorKind {
 /// An error returned by the [`.ini parser`](struct.IniParser.html).
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct IniError {
-    /// Line in the source string where the error occured.
+    /// Line in the source string where the error occured (1-based).
+    /// `\n`, `\r` and `\r\n` are all treated as new lines.
     pub line: u32,
-    /// Column in the source string where the error occured.
+    /// Column in the source string where the error occured (1-based).
+    /// Counts characters, not bytes.
     pub column: u32,
     /// Path to the key / value in which the error happened, or an empty path for the root section.
     pub path: ConfigPath,
@@ -175,8 +182,8 @@ impl Display for IniError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         write!(
             f,
-            "`.ini` parse error; line: {}, column: {}, path: {}, error: {}",
-            self.line, self.column, self.path, self.error
+            "`.ini` parse error at line {}, column {}: {} (path: {})",
+            self.line, self.column, self.error, self.path
         )
     }
 }
@@ -194,9 +201,17 @@ pub enum ToIniStringError {
     InvalidArrayType,
     /// Maximum allowed nested section depth exceeded.
     NestedSectionDepthExceeded,
-    /// Encountered an escaped character not allowed by options.
+    /// Encountered an escaped character in a string value not allowed by options.
     /// Contains the escaped character.
     EscapedCharacterNotAllowed(char),
+    /// Encountered a key which cannot be represented unambiguously under the options -
+    /// it contains a character which must be escaped, but escape sequences are not allowed by options.
+    /// Contains the character.
+    InvalidKey(char),
+    /// Encountered a section name which cannot be represented unambiguously under the options -
+    /// it contains a character which must be escaped, but escape sequences are not allowed by options.
+    /// Contains the character.
+    InvalidSectionName(char),
     /// General write error.
     WriteError,
 }
@@ -229,6 +244,16 @@ impl Display for ToIniStringError {
                 "encountered an escaped character not allowed by options: \'{}\'",
                 c
             ),
+            InvalidKey(c) => write!(
+                f,
+                "encountered a key with a character which must be escaped, but escape sequences are not allowed by options: \'{}\'",
+                c
+            ),
+            InvalidSectionName(c) => write!(
+                f,
+                "encountered a section name with a character which must be escaped, but escape sequences are not allowed by options: \'{}\'",
+                c
+            ),
         }
     }
 }
@@ -36,6 +36,63 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Flags which specify which (case-sensitive) unquoted tokens are valid `.ini` config boolean values.
+    pub struct IniBoolTokens: u8 {
+        /// Booleans not supported - such tokens are parsed as (unquoted) strings.
+        const None = 0b000;
+        /// `true` / `false`
+        const TrueFalse = 0b001;
+        /// `yes` / `no`
+        const YesNo = 0b010;
+        /// `on` / `off`
+        const OnOff = 0b100;
+    }
+}
+
+impl IniBoolTokens {
+    /// Parses the unquoted `value` as a boolean, if it is one of the allowed tokens.
+    pub(crate) fn parse(self, value: &str) -> Option<bool> {
+        let tokens = [
+            (IniBoolTokens::TrueFalse, "true", "false"),
+            (IniBoolTokens::YesNo, "yes", "no"),
+            (IniBoolTokens::OnOff, "on", "off"),
+        ];
+
+        tokens
+            .iter()
+            .filter(|(flag, _, _)| self.contains(*flag))
+            .find_map(|(_, true_token, false_token)| {
+                if value == *true_token {
+                    Some(true)
+                } else if value == *false_token {
+                    Some(false)
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Returns the token to write for the boolean `value`.
+    /// Uses the first set flag in declaration order, or `true` / `false` if none is set.
+    pub(crate) fn token(self, value: bool) -> &'static str {
+        let (true_token, false_token) =
+            if self.contains(IniBoolTokens::TrueFalse) || self.is_empty() {
+                ("true", "false")
+            } else if self.contains(IniBoolTokens::YesNo) {
+                ("yes", "no")
+            } else {
+                ("on", "off")
+            };
+
+        if value {
+            true_token
+        } else {
+            false_token
+        }
+    }
+}
+
 /// Controls how duplicate sections, if any, are handled in the `.ini` config.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum IniDuplicateSections {
@@ -62,6 +119,36 @@ pub enum IniDuplicateKeys {
     /// Use the last encountered instance of the key in the root / section,
     /// overwriting all prior, if any.
     Last,
+    /// Accumulate all encountered instances of the key in the root / section into an array.
+    /// The second instance of the key converts the first (non-array) value to a one-element array,
+    /// and it and all following values are appended to it.
+    /// Values of array instances of the key (if arrays are supported) are appended individually.
+    /// All values must be of compatible types, or a [`MixedArray`] error is raised.
+    /// Section instances of the key (and keys duplicating a section name) cause a [`DuplicateKey`] error.
+    ///
+    /// [`MixedArray`]: enum.IniErrorKind.html#variant.MixedArray
+    /// [`DuplicateKey`]: enum.IniErrorKind.html#variant.DuplicateKey
+    Array,
+}
+
+/// Controls how arrays with values of incompatible types (e.g. `[1, 2.5, "three"]`), if any,
+/// are handled in the `.ini` config.
+///
+/// Integers and floats are always compatible.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IniMixedArrays {
+    /// Do not allow mixed arrays - a [`MixedArray`] error is raised.
+    ///
+    /// [`MixedArray`]: enum.IniErrorKind.html#variant.MixedArray
+    Forbid,
+    /// Convert all values of a mixed array to strings.
+    /// Arrays with values of compatible types are not converted.
+    CoerceToString,
+    /// Convert all integer values of an array which contains floats to floats.
+    /// Arrays with values of other incompatible types still cause a [`MixedArray`] error.
+    ///
+    /// [`MixedArray`]: enum.IniErrorKind.html#variant.MixedArray
+    PromoteToFloat,
 }
 
 /// Configuration options for the `.ini` parser.
@@ -77,6 +164,12 @@ pub(crate) struct IniOptions {
     ///
     /// Default: `false`.
     pub(crate) inline_comments: bool,
+    /// Whether comment lines (i.e. those which begin with a comment delimiter) are reported to the config
+    /// via [`add_comment`](trait.IniConfig.html#method.add_comment).
+    /// If `comments` is [`None`](struct.IniCommentDelimiter.html#associatedconstant.None), this value is ignored.
+    ///
+    /// Default: `false`.
+    pub(crate) preserve_comments: bool,
     /// Valid key-value separator character(s).
     /// If no flag is set, [`Equals`](struct.IniKeyValueSeparator.html#associatedconstant.Equals) is assumed.
     ///
@@ -89,6 +182,11 @@ pub(crate) struct IniOptions {
     ///
     /// Default: [`Double`](struct.IniStringQuote.html#associatedconstant.Double).
     pub(crate) string_quotes: IniStringQuote,
+    /// Valid unquoted boolean value token(s).
+    /// If [`None`](struct.IniBoolTokens.html#associatedconstant.None), boolean values are not supported.
+    ///
+    /// Default: [`TrueFalse`](struct.IniBoolTokens.html#associatedconstant.TrueFalse).
+    pub(crate) bool_tokens: IniBoolTokens,
     /// Whether unquoted string values are supported.
     /// If `false`, an unquoted value must parse as a boolean / integer / float, or an error will be raised.
     /// If `string_quotes` is [`None`](struct.IniStringQuote.html#associatedconstant.None), this value is ignored.
@@ -132,6 +230,13 @@ pub(crate) struct IniOptions {
     ///
     /// Default: [`Merge`](enum.IniDuplicateSections.html#variant.Merge).
     pub(crate) duplicate_sections: IniDuplicateSections,
+    /// Whether section names are case-insensitive.
+    /// If `true`, section names which only differ in case (e.g. `[Server]` and `[server]`)
+    /// refer to the same section, which keeps the casing of its first encountered instance;
+    /// the duplicate section handling policy then applies to all following instances.
+    ///
+    /// Default: `false`.
+    pub(crate) case_insensitive_sections: bool,
     /// Duplicate key handling policy.
     ///
     /// Default: [`Forbid`](enum.IniDuplicateKeys.html#variant.Forbid).
@@ -143,6 +248,13 @@ pub(crate) struct IniOptions {
     ///
     /// Default: `false`.
     pub(crate) arrays: bool,
+    /// Mixed array handling policy.
+    /// Does not apply to values appended to arrays at duplicate keys
+    /// (see [`IniDuplicateKeys::Array`](enum.IniDuplicateKeys.html#variant.Array)).
+    /// If `arrays` is `false`, this value is ignored.
+    ///
+    /// Default: [`Forbid`](enum.IniMixedArrays.html#variant.Forbid).
+    pub(crate) mixed_arrays: IniMixedArrays,
     /// Maximum supported depth of nested sections.
     /// If `0`, sections are not supported at all.
     /// If `1`, one level of sections is supported; forward slashes (`'/'`) are treated as normal section name character.
@@ -158,6 +270,12 @@ pub(crate) struct IniOptions {
     ///
     /// Default: `false`.
     pub(crate) implicit_parent_sections: bool,
+    /// Whether bare keys (i.e. keys not followed by a key-value separator and a value) are supported.
+    /// If `true`, bare keys are parsed as boolean `true` values.
+    /// Otherwise bare keys are treated as an error.
+    ///
+    /// Default: `false`.
+    pub(crate) bare_key_as_true: bool,
 }
 
 impl Default for IniOptions {
@@ -165,26 +283,40 @@ impl Default for IniOptions {
         Self {
             comments: IniCommentDelimiter::Semicolon,
             inline_comments: false,
+            preserve_comments: false,
             key_value_separator: IniKeyValueSeparator::Equals,
             string_quotes: IniStringQuote::Double,
+            bool_tokens: IniBoolTokens::TrueFalse,
             unquoted_strings: true,
             escape: true,
             line_continuation: false,
             duplicate_sections: IniDuplicateSections::Merge,
+            case_insensitive_sections: false,
             duplicate_keys: IniDuplicateKeys::Forbid,
             arrays: false,
+            mixed_arrays: IniMixedArrays::Forbid,
             nested_section_depth: 1,
             implicit_parent_sections: false,
+            bare_key_as_true: false,
         }
     }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub(super) enum StringQuote {
+pub(crate) enum StringQuote {
     Single,
     Double,
 }
 
+impl StringQuote {
+    pub(super) fn as_char(self) -> char {
+        match self {
+            StringQuote::Single => '\'',
+            StringQuote::Double => '"',
+        }
+    }
+}
+
 impl IniOptions {
     /// Is the character a supported comment delimiter?
     pub(super) fn is_comment_char(&self, val: char) -> bool {
@@ -343,6 +475,45 @@ pub struct ToIniStringOptions {
     ///
     /// Default: `false`.
     pub implicit_parent_sections: bool,
+    /// Key-value separator character written between keys and values.
+    /// If both (or neither) [`Equals`](struct.IniKeyValueSeparator.html#associatedconstant.Equals)
+    /// and [`Colon`](struct.IniKeyValueSeparator.html#associatedconstant.Colon) are set, `=` is used.
+    ///
+    /// See [`key_value_separator`](struct.IniParser.html#method.key_value_separator).
+    ///
+    /// Default: [`Equals`](struct.IniKeyValueSeparator.html#associatedconstant.Equals).
+    pub key_value_separator: IniKeyValueSeparator,
+    /// Quote character used for string values, and for keys / section names which contain special characters.
+    /// If both [`Double`](struct.IniStringQuote.html#associatedconstant.Double)
+    /// and [`Single`](struct.IniStringQuote.html#associatedconstant.Single) are set, double quotes are used.
+    /// If [`None`](struct.IniStringQuote.html#associatedconstant.None), strings are written unquoted,
+    /// with special characters and spaces escaped;
+    /// note that unquoted string values which look like booleans / numbers are not parsed back as strings.
+    ///
+    /// See [`string_quotes`](struct.IniParser.html#method.string_quotes).
+    ///
+    /// Default: [`Double`](struct.IniStringQuote.html#associatedconstant.Double).
+    pub string_quotes: IniStringQuote,
+    /// Comment delimiter character used to write the comments attached to keys / sections, if any.
+    /// If both [`Semicolon`](struct.IniCommentDelimiter.html#associatedconstant.Semicolon)
+    /// and [`NumberSign`](struct.IniCommentDelimiter.html#associatedconstant.NumberSign) are set, `;` is used.
+    /// If [`None`](struct.IniCommentDelimiter.html#associatedconstant.None), comments are not written.
+    ///
+    /// See [`comments`](struct.IniParser.html#method.comments).
+    ///
+    /// Default: [`Semicolon`](struct.IniCommentDelimiter.html#associatedconstant.Semicolon).
+    pub comments: IniCommentDelimiter,
+    /// Tokens used to write boolean values.
+    /// If several flags are set, the first one in declaration order is used
+    /// (i.e. [`TrueFalse`](struct.IniBoolTokens.html#associatedconstant.TrueFalse), then
+    /// [`YesNo`](struct.IniBoolTokens.html#associatedconstant.YesNo), then
+    /// [`OnOff`](struct.IniBoolTokens.html#associatedconstant.OnOff)).
+    /// If [`None`](struct.IniBoolTokens.html#associatedconstant.None), `true` / `false` are used.
+    ///
+    /// See [`bool_tokens`](struct.IniParser.html#method.bool_tokens).
+    ///
+    /// Default: [`TrueFalse`](struct.IniBoolTokens.html#associatedconstant.TrueFalse).
+    pub bool_tokens: IniBoolTokens,
 }
 
 impl Default for ToIniStringOptions {
@@ -352,6 +523,10 @@ impl Default for ToIniStringOptions {
             arrays: false,
             nested_section_depth: 1,
             implicit_parent_sections: false,
+            key_value_separator: IniKeyValueSeparator::Equals,
+            string_quotes: IniStringQuote::Double,
+            comments: IniCommentDelimiter::Semicolon,
+            bool_tokens: IniBoolTokens::TrueFalse,
         }
     }
 }
@@ -360,6 +535,43 @@ impl ToIniStringOptions {
     pub(crate) fn nested_sections(&self) -> bool {
         self.nested_section_depth > 1
     }
+
+    /// Returns the key-value separator character to write.
+    pub(crate) fn key_value_separator_char(&self) -> char {
+        if self
+            .key_value_separator
+            .contains(IniKeyValueSeparator::Colon)
+            && !self
+                .key_value_separator
+                .contains(IniKeyValueSeparator::Equals)
+        {
+            ':'
+        } else {
+            '='
+        }
+    }
+
+    /// Returns the comment delimiter character to write, if any.
+    pub(crate) fn comment_delimiter_char(&self) -> Option<char> {
+        if self.comments.contains(IniCommentDelimiter::Semicolon) {
+            Some(';')
+        } else if self.comments.contains(IniCommentDelimiter::NumberSign) {
+            Some('#')
+        } else {
+            None
+        }
+    }
+
+    /// Returns the string quote to write, if any.
+    pub(super) fn string_quote(&self) -> Option<StringQuote> {
+        if self.string_quotes.contains(IniStringQuote::Double) {
+            Some(StringQuote::Double)
+        } else if self.string_quotes.contains(IniStringQuote::Single) {
+            Some(StringQuote::Single)
+        } else {
+            None
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,348 @@
+use {
+    super::{fsm_state::IniParserFSMState, *},
+    std::collections::{HashMap, VecDeque},
+};
+
+/// An `.ini` config value, as reported by the [`.ini event iterator`](struct.IniEvents.html).
+///
+/// Unlike the [`IniValue`](enum.IniValue.html), it does not borrow the temporary helper buffers in the parser.
+#[derive(Clone, PartialEq, Debug)]
+pub enum IniEventValue<'s> {
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    String(IniString<'s>),
+}
+
+impl<'s, 'a> From<IniValue<'s, 'a>> for IniEventValue<'s> {
+    fn from(value: IniValue<'s, 'a>) -> Self {
+        match value {
+            IniValue::Bool(value) => IniEventValue::Bool(value),
+            IniValue::I64(value) => IniEventValue::I64(value),
+            IniValue::F64(value) => IniEventValue::F64(value),
+            IniValue::String(value) => IniEventValue::String(value.into()),
+        }
+    }
+}
+
+/// An event generated by the [`.ini event iterator`](struct.IniEvents.html).
+///
+/// Events correspond to the calls to the [`IniConfig`](trait.IniConfig.html) methods
+/// made by the [`parser`](struct.IniParser.html) during the call to [`parse`](struct.IniParser.html#method.parse).
+#[derive(Clone, PartialEq, Debug)]
+pub enum IniEvent<'s> {
+    /// A (nested) `section` was started, see [`IniConfig::start_section`](trait.IniConfig.html#method.start_section).
+    /// Will be eventually followed by the matching [`SectionEnd`](#variant.SectionEnd) event.
+    SectionStart {
+        section: NonEmptyIniString<'s>,
+        overwrite: bool,
+    },
+    /// The current `section` was finished, see [`IniConfig::end_section`](trait.IniConfig.html#method.end_section).
+    SectionEnd(NonEmptyIniString<'s>),
+    /// A `key` / `value` pair was added to the current section, see [`IniConfig::add_value`](trait.IniConfig.html#method.add_value).
+    KeyValue {
+        key: NonEmptyIniString<'s>,
+        value: IniEventValue<'s>,
+        overwrite: bool,
+    },
+    /// A `value` was appended to the array at the duplicate `key` in the current section,
+    /// see [`IniConfig::append_value`](trait.IniConfig.html#method.append_value).
+    AppendValue {
+        key: NonEmptyIniString<'s>,
+        value: IniEventValue<'s>,
+    },
+    /// An `array` was started in the current section, see [`IniConfig::start_array`](trait.IniConfig.html#method.start_array).
+    /// Will be eventually followed by the matching [`ArrayEnd`](#variant.ArrayEnd) event.
+    ArrayStart {
+        array: NonEmptyIniString<'s>,
+        overwrite: bool,
+    },
+    /// A value was added to the current array, see [`IniConfig::add_array_value`](trait.IniConfig.html#method.add_array_value).
+    ArrayValue(IniEventValue<'s>),
+    /// The current `array` was finished, see [`IniConfig::end_array`](trait.IniConfig.html#method.end_array).
+    ArrayEnd(NonEmptyIniString<'s>),
+    /// A comment line was parsed, see [`IniConfig::add_comment`](trait.IniConfig.html#method.add_comment).
+    /// Only generated if the parser is [`configured`](struct.IniParser.html#method.preserve_comments) to preserve comments.
+    Comment(&'s str),
+    /// An inline comment was parsed after a value, see [`IniConfig::add_inline_comment`](trait.IniConfig.html#method.add_inline_comment).
+    /// Only generated if the parser is [`configured`](struct.IniParser.html#method.preserve_comments) to preserve comments.
+    InlineComment(&'s str),
+}
+
+/// A pull-based alternative to [`IniParser::parse`](struct.IniParser.html#method.parse),
+/// which parses the `.ini` config string on demand and returns the generated [`events`](enum.IniEvent.html) one by one.
+///
+/// Created by [`IniParser::events`](struct.IniParser.html#method.events).
+///
+/// The parser does not keep track of previously parsed keys, so the iterator keeps track of the keys (but not the values)
+/// in all sections in order to detect duplicate keys / sections.
+///
+/// Returns at most one error, after which the iteration stops.
+pub struct IniEvents<'s> {
+    source: &'s str,
+    reader: CharIndices<'s>,
+    options: IniOptions,
+    persistent_state: IniParserPersistentState<'s>,
+    src_pos_state: IniParserSrcPositionState,
+    // `None` when finished, or after an error.
+    fsm_state: Option<IniParserFSMState>,
+    config: IniEventsConfig<'s>,
+}
+
+impl<'s> IniEvents<'s> {
+    pub(super) fn new(parser: IniParser<'s>) -> Self {
+        Self {
+            source: parser.source,
+            reader: parser.reader,
+            options: parser.options,
+            persistent_state: IniParserPersistentState::new(),
+            src_pos_state: IniParserSrcPositionState::new(),
+            fsm_state: Some(IniParserFSMState::StartLine),
+            config: IniEventsConfig::new(),
+        }
+    }
+}
+
+impl<'s> Iterator for IniEvents<'s> {
+    type Item = Result<IniEvent<'s>, IniError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let IniEvents {
+            source,
+            reader,
+            options,
+            persistent_state,
+            src_pos_state,
+            fsm_state,
+            config,
+        } = self;
+
+        let source = *source;
+        let substr = |range| IniParser::substr(source, range);
+
+        loop {
+            if let Some(event) = config.events.pop_front() {
+                return Some(Ok(event));
+            }
+
+            let current_state = fsm_state.take()?;
+
+            // Read the next char, process according to current state; or finish on EOF.
+            let result = if let Some((c, idx)) = IniParser::next(reader, src_pos_state) {
+                current_state
+                    .process(
+                        c,
+                        idx,
+                        || IniParser::next(reader, src_pos_state).map(|(c, _)| c),
+                        substr,
+                        config,
+                        persistent_state,
+                        options,
+                    )
+                    .map(Some)
+            } else {
+                current_state
+                    .finish(substr, config, persistent_state, options)
+                    .map(|_| None)
+                    .map_err(|err| (err, false))
+            };
+
+            match result {
+                Ok(Some(next_state)) => {
+                    fsm_state.replace(next_state);
+                }
+                Ok(None) => {
+                    persistent_state.clear_path(config);
+                }
+                Err((err, offset)) => {
+                    config.events.clear();
+
+                    return Some(Err(IniParser::error(
+                        err,
+                        offset,
+                        src_pos_state,
+                        persistent_state.path.to_config_path(),
+                    )));
+                }
+            }
+        }
+    }
+}
+
+/// Keys in a section (or the root), tracked by the [`.ini event iterator`](struct.IniEvents.html).
+type IniKeys = HashMap<NonEmptyString, IniKey>;
+
+enum IniKey {
+    Value(IniValueType),
+    // Array element type, if the array is not empty.
+    Array(Option<IniValueType>),
+    Section(IniKeys),
+}
+
+/// The [`IniConfig`](trait.IniConfig.html) event handler used by the [`.ini event iterator`](struct.IniEvents.html).
+/// Buffers the events generated by the parser and keeps track of the keys in all sections.
+struct IniEventsConfig<'s> {
+    events: VecDeque<IniEvent<'s>>,
+    root: IniKeys,
+    // Stack of the current (nested) sections' names and keys.
+    // Keys of each section are removed from the parent section on section start
+    // and returned to it on section end.
+    sections: Vec<(NonEmptyString, IniKeys)>,
+    // Key of the current array, if any.
+    array: Option<NonEmptyString>,
+}
+
+impl<'s> IniEventsConfig<'s> {
+    fn new() -> Self {
+        Self {
+            events: VecDeque::new(),
+            root: HashMap::new(),
+            sections: Vec::new(),
+            array: None,
+        }
+    }
+
+    fn current(&self) -> &IniKeys {
+        self.sections
+            .last()
+            .map(|(_, keys)| keys)
+            .unwrap_or(&self.root)
+    }
+
+    fn current_mut(&mut self) -> &mut IniKeys {
+        match self.sections.last_mut() {
+            Some((_, keys)) => keys,
+            None => &mut self.root,
+        }
+    }
+}
+
+impl<'s> IniConfig<'s> for IniEventsConfig<'s> {
+    fn contains_key(&self, key: NonEmptyIniStr<'s, '_>) -> Option<bool> {
+        self.current()
+            .get(key.as_ne_str())
+            .map(|key| matches!(key, IniKey::Section(_)))
+    }
+
+    fn add_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>, overwrite: bool) {
+        self.current_mut()
+            .insert(key.into(), IniKey::Value(value.get_ini_type()));
+
+        self.events.push_back(IniEvent::KeyValue {
+            key: key.into(),
+            value: value.into(),
+            overwrite,
+        });
+    }
+
+    fn append_value(&mut self, key: NonEmptyIniStr<'s, '_>, value: IniValue<'s, '_>) -> bool {
+        let value_type = value.get_ini_type();
+
+        let array_type = match self.current_mut().get_mut(key.as_ne_str()) {
+            Some(IniKey::Value(array_type)) => Some(*array_type),
+            Some(IniKey::Array(array_type)) => *array_type,
+            Some(IniKey::Section(_)) => {
+                return false;
+            }
+            None => {
+                debug_assert!(false, "`append_value()` call for a missing key");
+                return false;
+            }
+        };
+
+        if let Some(array_type) = array_type {
+            if !array_type.is_compatible(value_type) {
+                return false;
+            }
+        }
+
+        self.current_mut().insert(
+            key.into(),
+            IniKey::Array(Some(array_type.unwrap_or(value_type))),
+        );
+
+        self.events.push_back(IniEvent::AppendValue {
+            key: key.into(),
+            value: value.into(),
+        });
+
+        true
+    }
+
+    fn start_section(&mut self, section: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        // Continue the previous section with this key, unless we overwrite it
+        // (or it was a value and we will overwrite it).
+        let keys = match self.current_mut().remove(section.as_ne_str()) {
+            Some(IniKey::Section(keys)) if !overwrite => keys,
+            _ => HashMap::new(),
+        };
+
+        self.sections.push((section.into(), keys));
+
+        self.events.push_back(IniEvent::SectionStart {
+            section: section.into(),
+            overwrite,
+        });
+    }
+
+    fn end_section(&mut self, section: NonEmptyIniStr<'s, '_>) {
+        if let Some((name, keys)) = self.sections.pop() {
+            debug_assert!(name.as_ne_str() == section.as_ne_str());
+            self.current_mut().insert(name, IniKey::Section(keys));
+        } else {
+            debug_assert!(
+                false,
+                "`end_section()` call without a matching `start_section()`"
+            );
+        }
+
+        self.events.push_back(IniEvent::SectionEnd(section.into()));
+    }
+
+    fn start_array(&mut self, array: NonEmptyIniStr<'s, '_>, overwrite: bool) {
+        self.current_mut().insert(array.into(), IniKey::Array(None));
+
+        debug_assert!(self.array.is_none(), "nested arrays are not supported");
+        self.array.replace(array.into());
+
+        self.events.push_back(IniEvent::ArrayStart {
+            array: array.into(),
+            overwrite,
+        });
+    }
+
+    fn add_array_value(&mut self, value: IniValue<'s, '_>) {
+        if let Some(array) = self.array.take() {
+            if let Some(IniKey::Array(array_type)) = self.current_mut().get_mut(array.as_ne_str()) {
+                array_type.get_or_insert(value.get_ini_type());
+            }
+
+            self.array.replace(array);
+        } else {
+            debug_assert!(
+                false,
+                "`add_array_value()` call without a matching `start_array()`"
+            );
+        }
+
+        self.events.push_back(IniEvent::ArrayValue(value.into()));
+    }
+
+    fn end_array(&mut self, array: NonEmptyIniStr<'s, '_>) {
+        let current_array = self.array.take();
+        debug_assert!(
+            current_array.is_some(),
+            "`end_array()` call without a matching `start_array()`"
+        );
+
+        self.events.push_back(IniEvent::ArrayEnd(array.into()));
+    }
+
+    fn add_comment(&mut self, comment: &'s str) {
+        self.events.push_back(IniEvent::Comment(comment));
+    }
+
+    fn add_inline_comment(&mut self, comment: &'s str) {
+        self.events.push_back(IniEvent::InlineComment(comment));
+    }
+}
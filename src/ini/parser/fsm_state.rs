@@ -1,4 +1,4 @@
-use {super::*, crate::*};
+use {super::*, crate::*, std::collections::HashMap};
 
 /// `.ini` parser FSM states.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -9,7 +9,7 @@ pub(super) enum IniParserFSMState {
     /// valid key chars (-> Key),
     /// escape sequences (if supported) (-> Key),
     /// string quotes (`'"'` / `'\'`') (if supported) (-> QuotedKey).
-    /// comment delimiters (`';'` / `'#'`) (if supported) (-> SkipLine).
+    /// comment delimiters (`';'` / `'#'`) (if supported) (-> SkipLine, or -> Comment if comments are preserved).
     StartLine,
     /// We encountered a section start delimiter (or a nested section separator) and started parsing a (nested) section name.
     /// Accept whitespace (except new lines),
@@ -41,10 +41,18 @@ pub(super) enum IniParserFSMState {
     /// Accept new lines (-> StartLine),
     /// skip everything else.
     SkipLine,
+    /// We encountered a comment delimiter at the start of the line and preserve comments.
+    /// Accept new lines (-> StartLine),
+    /// everything else is the comment text.
+    Comment,
+    /// We encountered an inline comment delimiter after a value and preserve comments.
+    /// Accept new lines (-> StartLine),
+    /// everything else is the inline comment text.
+    InlineComment,
     /// We finished parsing a section name or a value and expect the next line or the comment delimiter.
     /// Accept new lines (-> StartLine),
     /// whitespace,
-    /// comment start delimiters (`';'` / `'#'`) (if supported) (-> SkipLine).
+    /// comment start delimiters (`';'` / `'#'`) (if supported) (-> SkipLine, or -> InlineComment if comments are preserved).
     SkipLineWhitespaceOrComments,
     /// We started parsing an unquoted key.
     /// Accept valid key chars,
@@ -66,7 +74,7 @@ pub(super) enum IniParserFSMState {
     KeyValueSeparator,
     /// We finished parsing a key-value separator and expect a value (or a new line).
     /// Accept whitespace (except new lines (->StartLine)),
-    /// inline comment delimiters (`';'` / `'#'`) (if supported) (-> SkipLine),
+    /// inline comment delimiters (`';'` / `'#'`) (if supported) (-> SkipLine, or -> InlineComment if comments are preserved),
     /// string quotes (`'"'` / `'\'`') (if supported) (-> QuotedValue),
     /// escape sequences (if supported) (-> Value),
     /// array start delimiters (if supported) (-> BeforeArrayValue),
@@ -75,7 +83,7 @@ pub(super) enum IniParserFSMState {
     /// We started parsing an unquoted value.
     /// Accept whitespace (-> SkipLineWhitespaceOrComments)
     /// (including new lines (-> StartLine)),
-    /// inline comment delimiters (`';'` / `'#'`) (if supported) (-> SkipLine),
+    /// inline comment delimiters (`';'` / `'#'`) (if supported) (-> SkipLine, or -> InlineComment if comments are preserved),
     /// escape sequences (if supported),
     /// valid value chars.
     Value,
@@ -151,6 +159,8 @@ impl IniParserFSMState {
 
                 state.is_key_unique = true;
                 state.skip_value = false;
+                state.append_to_array = false;
+                state.is_section_line = false;
 
                 // Skip whitespace at the start of the line (including new lines).
                 if c.is_whitespace() {
@@ -167,12 +177,21 @@ impl IniParserFSMState {
                     state.clear_path(config);
 
                     state.skip_section = false;
+                    state.is_section_line = true;
 
                     IniParserFSMState::BeforeSection
 
-                // Line comment (if supported) - skip the rest of the line.
+                // Line comment (if supported) - skip the rest of the line,
+                // or parse it as the comment text if we preserve comments.
                 } else if options.is_comment_char(c) {
-                    IniParserFSMState::SkipLine
+                    if options.preserve_comments {
+                        let start = idx + c.len_utf8();
+                        state.comment = start..start;
+
+                        IniParserFSMState::Comment
+                    } else {
+                        IniParserFSMState::SkipLine
+                    }
 
                 // String quote (if supported) - parse the key in quotes, expecting the matching quotes.
                 } else if let Some(quote) = options.is_string_quote_char(c) {
@@ -269,6 +288,9 @@ impl IniParserFSMState {
                 } else if options.is_nested_section_separator(c) {
                     // Must succeed.
                     let section = unwrap_unchecked(state.key.key(&substr), "empty section name");
+                    let section =
+                        section_name(&mut state.section_names, &state.path, section, options)
+                            .unwrap_or(section);
 
                     state.path.push(section);
 
@@ -323,6 +345,9 @@ impl IniParserFSMState {
 
                     // Must succeed.
                     let section = unwrap_unchecked(state.key.key(&substr), "empty section name");
+                    let section =
+                        section_name(&mut state.section_names, &state.path, section, options)
+                            .unwrap_or(section);
 
                     // Try to add the section to the config at the current path.
                     state.path.push(section);
@@ -359,9 +384,9 @@ impl IniParserFSMState {
                         ParseEscapeSequenceResult::EscapedChar(c) => {
                             state.key.push_owned(c, substr);
                         }
-                        // Line continuation - keep parsing the (now owned) section name.
+                        // Line continuation - not supported in quoted strings, new line before the closing quotes - error.
                         ParseEscapeSequenceResult::LineContinuation => {
-                            state.key.to_owned(substr);
+                            return Err((UnexpectedNewLineInSectionName, true));
                         }
                     }
 
@@ -401,6 +426,9 @@ impl IniParserFSMState {
                         .key
                         .key(&substr)
                         .ok_or_else(|| (EmptySectionName, true))?;
+                    let section =
+                        section_name(&mut state.section_names, &state.path, section, options)
+                            .unwrap_or(section);
 
                     // Try to add the section to the config at the current path.
                     state.path.push(section);
@@ -416,6 +444,9 @@ impl IniParserFSMState {
                         .key
                         .key(&substr)
                         .ok_or_else(|| (EmptySectionName, true))?;
+                    let section =
+                        section_name(&mut state.section_names, &state.path, section, options)
+                            .unwrap_or(section);
 
                     state.path.push(section);
 
@@ -467,6 +498,36 @@ impl IniParserFSMState {
                     self
                 }
             }
+            IniParserFSMState::Comment => {
+                debug_assert!(state.key.is_empty());
+                debug_assert!(state.value.is_empty());
+
+                // If it's a new line, add the comment and start parsing the next line.
+                if options.is_new_line(c) {
+                    add_comment_to_config(config, substr, state);
+                    IniParserFSMState::StartLine
+
+                // Else accumulate the comment text.
+                } else {
+                    state.comment.end = idx + c.len_utf8();
+                    self
+                }
+            }
+            IniParserFSMState::InlineComment => {
+                debug_assert!(state.key.is_empty());
+                debug_assert!(state.value.is_empty());
+
+                // If it's a new line, add the inline comment and start parsing the next line.
+                if options.is_new_line(c) {
+                    add_inline_comment_to_config(config, substr, state);
+                    IniParserFSMState::StartLine
+
+                // Else accumulate the inline comment text.
+                } else {
+                    state.comment.end = idx + c.len_utf8();
+                    self
+                }
+            }
             IniParserFSMState::SkipLineWhitespaceOrComments => {
                 debug_assert!(state.key.is_empty());
                 debug_assert!(state.value.is_empty());
@@ -479,9 +540,9 @@ impl IniParserFSMState {
                 } else if c.is_whitespace() {
                     self
 
-                // Inline comment (if supported) - skip the rest of the line.
+                // Inline comment (if supported) - skip the rest of the line, or parse it as the inline comment text.
                 } else if options.is_inline_comment_char(c) {
-                    IniParserFSMState::SkipLine
+                    inline_comment(c, idx, state, options)
 
                 // Else an error.
                 } else {
@@ -505,6 +566,7 @@ impl IniParserFSMState {
                         state.skip_section,
                         &mut state.skip_value,
                         &mut state.is_key_unique,
+                        &mut state.append_to_array,
                         options.duplicate_keys,
                     )?;
 
@@ -512,9 +574,15 @@ impl IniParserFSMState {
 
                 // Whitespace between the key and the separator - skip it, finish the key, parse the separator.
                 } else if c.is_whitespace() {
-                    // Unless it's a new line.
+                    // Unless it's a new line - it's a bare key (if supported).
                     if options.is_new_line(c) {
-                        return Err((UnexpectedNewLineInKey, true));
+                        if !options.bare_key_as_true {
+                            return Err((UnexpectedNewLineInKey, true));
+                        }
+
+                        add_bare_key_to_config(config, substr, state, options, true)?;
+
+                        return Ok(IniParserFSMState::StartLine);
                     }
 
                     // Must succeed.
@@ -527,6 +595,7 @@ impl IniParserFSMState {
                         state.skip_section,
                         &mut state.skip_value,
                         &mut state.is_key_unique,
+                        &mut state.append_to_array,
                         options.duplicate_keys,
                     )?;
 
@@ -547,6 +616,12 @@ impl IniParserFSMState {
 
                     self
 
+                // Inline comment (if supported) after a bare key (if supported) - finish the key, skip the rest of the line.
+                } else if options.bare_key_as_true && options.is_inline_comment_char(c) {
+                    add_bare_key_to_config(config, substr, state, options, true)?;
+
+                    inline_comment(c, idx, state, options)
+
                 // Valid key char - keep parsing the key.
                 } else if options.is_key_or_value_char(c, false, None) {
                     state.key.push(c, idx);
@@ -576,6 +651,7 @@ impl IniParserFSMState {
                         state.skip_section,
                         &mut state.skip_value,
                         &mut state.is_key_unique,
+                        &mut state.append_to_array,
                         options.duplicate_keys,
                     )?;
 
@@ -588,9 +664,9 @@ impl IniParserFSMState {
                         ParseEscapeSequenceResult::EscapedChar(c) => {
                             state.key.push_owned(c, substr);
                         }
-                        // Line continuation - keep parsing the (now owned) key.
+                        // Line continuation - not supported in quoted strings, new line before the closing quotes - error.
                         ParseEscapeSequenceResult::LineContinuation => {
-                            state.key.to_owned(substr);
+                            return Err((UnexpectedNewLineInKey, true));
                         }
                     }
 
@@ -622,13 +698,25 @@ impl IniParserFSMState {
 
                 // Skip the whitespace between the key and the separator.
                 } else if c.is_whitespace() {
-                    // Unless it's a new line.
+                    // Unless it's a new line - it's a bare key (if supported).
                     if options.is_new_line(c) {
-                        return Err((UnexpectedNewLineInKey, true));
+                        if !options.bare_key_as_true {
+                            return Err((UnexpectedNewLineInKey, true));
+                        }
+
+                        add_bare_key_to_config(config, substr, state, options, false)?;
+
+                        IniParserFSMState::StartLine
                     } else {
                         self
                     }
 
+                // Inline comment (if supported) after a bare key (if supported) - skip the rest of the line.
+                } else if options.bare_key_as_true && options.is_inline_comment_char(c) {
+                    add_bare_key_to_config(config, substr, state, options, false)?;
+
+                    inline_comment(c, idx, state, options)
+
                 // Else an error.
                 } else {
                     return Err((InvalidKeyValueSeparator(c), false));
@@ -651,7 +739,8 @@ impl IniParserFSMState {
                             false,
                             state.skip_section | state.skip_value,
                             state.is_key_unique,
-                            options.unquoted_strings,
+                            state.append_to_array,
+                            options,
                         )
                         .map_err(|error_kind| (error_kind, false))?;
 
@@ -673,14 +762,15 @@ impl IniParserFSMState {
                         false,
                         state.skip_section | state.skip_value,
                         state.is_key_unique,
-                        options.unquoted_strings,
+                        state.append_to_array,
+                        options,
                     )
                     .map_err(|error_kind| (error_kind, false))?;
 
                     state.key.clear();
                     state.path.pop();
 
-                    IniParserFSMState::SkipLine
+                    inline_comment(c, idx, state, options)
 
                 // String quote - parse the string value in quotes, expecting the matching quotes.
                 } else if let Some(quote) = options.is_string_quote_char(c) {
@@ -708,6 +798,7 @@ impl IniParserFSMState {
                         array_key,
                         state.skip_section | state.skip_value,
                         state.is_key_unique,
+                        state.append_to_array,
                     );
 
                     //state.path.push(array_key);
@@ -739,7 +830,8 @@ impl IniParserFSMState {
                         false,
                         state.skip_section | state.skip_value,
                         state.is_key_unique,
-                        options.unquoted_strings,
+                        state.append_to_array,
+                        options,
                     )
                     .map_err(|error_kind| (error_kind, false))?;
 
@@ -766,7 +858,8 @@ impl IniParserFSMState {
                         false,
                         state.skip_section | state.skip_value,
                         state.is_key_unique,
-                        options.unquoted_strings,
+                        state.append_to_array,
+                        options,
                     )
                     .map_err(|error_kind| (error_kind, false))?;
 
@@ -774,7 +867,7 @@ impl IniParserFSMState {
                     state.value.clear();
                     state.path.pop();
 
-                    IniParserFSMState::SkipLine
+                    inline_comment(c, idx, state, options)
 
                 // Escaped char (if supported) - parse the escape sequence.
                 } else if options.is_escape_char(c) {
@@ -819,7 +912,8 @@ impl IniParserFSMState {
                         true,
                         state.skip_section | state.skip_value,
                         state.is_key_unique,
-                        options.unquoted_strings,
+                        state.append_to_array,
+                        options,
                     )
                     .map_err(|error_kind| (error_kind, false))?;
 
@@ -836,9 +930,9 @@ impl IniParserFSMState {
                         ParseEscapeSequenceResult::EscapedChar(c) => {
                             state.value.push_owned(c, substr);
                         }
-                        // Line continuation - keep parsing the (now owned) quoted value.
+                        // Line continuation - not supported in quoted strings, new line before the closing quotes - error.
                         ParseEscapeSequenceResult::LineContinuation => {
-                            state.value.to_owned(substr);
+                            return Err((UnexpectedNewLineInQuotedValue, true));
                         }
                     }
 
@@ -879,7 +973,14 @@ impl IniParserFSMState {
                     // Must succeed.
                     let array_key = unwrap_unchecked(state.key.key(&substr), "empty array key");
 
-                    config.end_array(array_key);
+                    end_array_in_config(
+                        config,
+                        array_key,
+                        state.skip_section | state.skip_value,
+                        state.append_to_array,
+                        &mut state.array_values,
+                        options.mixed_arrays,
+                    );
 
                     // Pop the array key off the path.
                     state.path.pop();
@@ -889,9 +990,12 @@ impl IniParserFSMState {
 
                 // String quote - parse the string array value in quotes, expecting the matching quotes.
                 } else if let Some(quote) = options.is_string_quote_char(c) {
-                    // Make sure the array is empty or contains strings (is not mixed).
+                    // Make sure the array is empty or contains strings (is not mixed),
+                    // unless mixed arrays are coerced to strings.
                     if let Some(array_type) = array_type {
-                        if !array_type.is_compatible(IniValueType::String) {
+                        if !array_type.is_compatible(IniValueType::String)
+                            && options.mixed_arrays != IniMixedArrays::CoerceToString
+                        {
                             return Err((IniErrorKind::MixedArray, false));
                         }
                     }
@@ -936,11 +1040,15 @@ impl IniParserFSMState {
 
                     add_value_to_array(
                         config,
+                        // Must succeed.
+                        unwrap_unchecked(state.key.key(&substr), "empty array key"),
                         state.value.value(&substr),
                         false,
                         state.skip_value | state.skip_section,
                         &mut array_type,
-                        options.unquoted_strings,
+                        state.append_to_array,
+                        &mut state.array_values,
+                        options,
                     )?;
 
                     state.value.clear();
@@ -955,11 +1063,15 @@ impl IniParserFSMState {
                 } else if options.is_array_value_separator(c) {
                     add_value_to_array(
                         config,
+                        // Must succeed.
+                        unwrap_unchecked(state.key.key(&substr), "empty array key"),
                         state.value.value(&substr),
                         false,
                         state.skip_value | state.skip_section,
                         &mut array_type,
-                        options.unquoted_strings,
+                        state.append_to_array,
+                        &mut state.array_values,
+                        options,
                     )?;
 
                     state.value.clear();
@@ -970,11 +1082,15 @@ impl IniParserFSMState {
                 } else if options.is_array_end(c) {
                     add_value_to_array(
                         config,
+                        // Must succeed.
+                        unwrap_unchecked(state.key.key(&substr), "empty array key"),
                         state.value.value(&substr),
                         false,
                         state.skip_value | state.skip_section,
                         &mut array_type,
-                        options.unquoted_strings,
+                        state.append_to_array,
+                        &mut state.array_values,
+                        options,
                     )?;
 
                     state.value.clear();
@@ -982,7 +1098,14 @@ impl IniParserFSMState {
                     // Must succeed.
                     let array_key = unwrap_unchecked(state.key.key(&substr), "empty array key");
 
-                    config.end_array(array_key);
+                    end_array_in_config(
+                        config,
+                        array_key,
+                        state.skip_section | state.skip_value,
+                        state.append_to_array,
+                        &mut state.array_values,
+                        options.mixed_arrays,
+                    );
 
                     // Pop the array key off the path.
                     state.path.pop();
@@ -1030,11 +1153,15 @@ impl IniParserFSMState {
                     let mut dummy_array_type = None;
                     add_value_to_array(
                         config,
+                        // Must succeed.
+                        unwrap_unchecked(state.key.key(&substr), "empty array key"),
                         state.value.value(&substr),
                         true,
                         state.skip_value | state.skip_section,
                         &mut dummy_array_type,
-                        options.unquoted_strings,
+                        state.append_to_array,
+                        &mut state.array_values,
+                        options,
                     )?;
                     debug_assert_eq!(dummy_array_type, Some(IniValueType::String));
 
@@ -1049,9 +1176,9 @@ impl IniParserFSMState {
                         ParseEscapeSequenceResult::EscapedChar(c) => {
                             state.value.push_owned(c, substr);
                         }
-                        // Line continuation - keep parsing the (now owned) quoted array value.
+                        // Line continuation - not supported in quoted strings, new line before the closing quotes - error.
                         ParseEscapeSequenceResult::LineContinuation => {
-                            state.value.to_owned(substr);
+                            return Err((UnexpectedNewLineInQuotedValue, true));
                         }
                     }
 
@@ -1096,7 +1223,14 @@ impl IniParserFSMState {
                     // Must succeed.
                     let array_key = unwrap_unchecked(state.key.key(&substr), "empty array key");
 
-                    config.end_array(array_key);
+                    end_array_in_config(
+                        config,
+                        array_key,
+                        state.skip_section | state.skip_value,
+                        state.append_to_array,
+                        &mut state.array_values,
+                        options.mixed_arrays,
+                    );
 
                     // Pop the array key off the path.
                     state.path.pop();
@@ -1117,7 +1251,7 @@ impl IniParserFSMState {
         self,
         substr: S,
         config: &mut C,
-        state: &mut IniParserPersistentState,
+        state: &mut IniParserPersistentState<'s>,
         options: &IniOptions,
     ) -> Result<(), IniErrorKind>
     where
@@ -1130,6 +1264,11 @@ impl IniParserFSMState {
             BeforeSection | Section | QuotedSection(_) | AfterSection => {
                 return Err(UnexpectedEndOfFileInSectionName)
             }
+            // Add the bare key (if supported) if we were parsing it right before EOF.
+            Key | KeyValueSeparator if options.bare_key_as_true => {
+                add_bare_key_to_config(config, substr, state, options, self == Key)
+                    .map_err(|(error_kind, _)| error_kind)
+            }
             Key | QuotedKey(_) | KeyValueSeparator => {
                 return Err(UnexpectedEndOfFileBeforeKeyValueSeparator)
             }
@@ -1148,7 +1287,8 @@ impl IniParserFSMState {
                     false,
                     state.skip_section | state.skip_value,
                     state.is_key_unique,
-                    options.unquoted_strings,
+                    state.append_to_array,
+                    options,
                 )?;
 
                 state.path.pop();
@@ -1159,6 +1299,16 @@ impl IniParserFSMState {
                 return Err(UnexpectedEndOfFileInArray)
             }
             QuotedArrayValue(_) => return Err(UnexpectedEndOfFileInQuotedArrayValue),
+            // Add the comment if we were parsing it right before EOF.
+            Comment => {
+                add_comment_to_config(config, substr, state);
+                Ok(())
+            }
+            // Add the inline comment if we were parsing it right before EOF.
+            InlineComment => {
+                add_inline_comment_to_config(config, substr, state);
+                Ok(())
+            }
             StartLine | SkipLine | SkipLineWhitespaceOrComments => Ok(()),
         }
     }
@@ -1341,6 +1491,31 @@ fn try_parse_escape_sequence<F: FnMut() -> Option<char>>(
     }
 }
 
+/// If section names are case-insensitive, returns the first encountered casing of the `section` name
+/// at the current `path`, recording the `section` name if it was not encountered before.
+/// Otherwise returns `None` and the `section` name is used as-is.
+fn section_name<'s, 'a>(
+    section_names: &'a mut HashMap<Vec<String>, NonEmptyString>,
+    path: &IniPath<'s>,
+    section: NonEmptyIniStr<'s, '_>,
+    options: &IniOptions,
+) -> Option<NonEmptyIniStr<'s, 'a>> {
+    if !options.case_insensitive_sections {
+        return None;
+    }
+
+    // The parent sections' names are already in their first encountered casing.
+    let key = path
+        .iter()
+        .map(|parent| parent.as_str().to_lowercase())
+        .chain(std::iter::once(section.as_str().to_lowercase()))
+        .collect();
+
+    let section_name = section_names.entry(key).or_insert_with(|| section.into());
+
+    Some(NonEmptyIniStr::Owned(section_name.as_ne_str()))
+}
+
 /// Returns `Ok(true)` if we need to skip the current section;
 /// else returns `Ok(false)`.
 fn start_section<'s, C: IniConfig<'s>>(
@@ -1387,6 +1562,10 @@ fn start_section<'s, C: IniConfig<'s>>(
                     config.start_section(section, true);
                     Ok(false)
                 }
+                // Sections may not be appended to arrays - error.
+                IniDuplicateKeys::Array => {
+                    return Err((IniErrorKind::DuplicateKey, true));
+                }
             }
         // Key does not exist - add the section.
         } else {
@@ -1397,17 +1576,21 @@ fn start_section<'s, C: IniConfig<'s>>(
 }
 
 /// Sets `skip_value` to `true` if we need to skip the current value;
-/// sets `is_key_unique` to `true` if the key is not contained in `config`'s current section.
+/// sets `is_key_unique` to `true` if the key is not contained in `config`'s current section;
+/// sets `append_to_array` to `true` if we need to append the current value to the array at the (duplicate) key.
 fn check_is_key_duplicate<'s, C: IniConfig<'s>>(
     config: &C,
     key: NonEmptyIniStr<'s, '_>,
     skip_section: bool,
     skip_value: &mut bool,
     is_key_unique: &mut bool,
+    append_to_array: &mut bool,
     duplicate_keys: IniDuplicateKeys,
 ) -> Result<(), (IniErrorKind, bool)> {
     use IniErrorKind::*;
 
+    *append_to_array = false;
+
     if skip_section {
         *skip_value = true;
         *is_key_unique = false;
@@ -1415,7 +1598,8 @@ fn check_is_key_duplicate<'s, C: IniConfig<'s>>(
         return Ok(());
     }
 
-    let is_unique = config.contains_key(key).is_none();
+    let contains_key = config.contains_key(key);
+    let is_unique = contains_key.is_none();
 
     match duplicate_keys {
         IniDuplicateKeys::Forbid => {
@@ -1440,12 +1624,81 @@ fn check_is_key_duplicate<'s, C: IniConfig<'s>>(
             *skip_value = false;
             *is_key_unique = is_unique;
 
+            Ok(())
+        }
+        // Never skip keys when we accumulate them into arrays.
+        IniDuplicateKeys::Array => {
+            // Values may not be appended to sections - error.
+            if let Some(true) = contains_key {
+                return Err((DuplicateKey, true));
+            }
+
+            *skip_value = false;
+            *is_key_unique = is_unique;
+            *append_to_array = !is_unique;
+
             Ok(())
         }
     }
 }
 
-/// Parses a string `value` and adds it to the `config`'s current section at `key`.
+/// Adds the (trimmed, possibly empty) current comment text to the `config`.
+fn add_comment_to_config<'s, C, S>(config: &mut C, substr: S, state: &IniParserPersistentState<'s>)
+where
+    C: IniConfig<'s>,
+    S: Substr<'s>,
+{
+    let comment = if state.comment.is_empty() {
+        ""
+    } else {
+        substr(state.comment.start..=(state.comment.end - 1)).as_str()
+    };
+
+    config.add_comment(comment.trim());
+}
+
+/// Returns the new parser state after the inline comment delimiter `c` at `idx`.
+/// If we preserve comments and the current line contains a value which was added to the config,
+/// parses the rest of the line as the inline comment text (-> InlineComment);
+/// else skips the rest of the line (-> SkipLine).
+fn inline_comment(
+    c: char,
+    idx: usize,
+    state: &mut IniParserPersistentState<'_>,
+    options: &IniOptions,
+) -> IniParserFSMState {
+    if options.preserve_comments
+        && !(state.is_section_line || state.skip_section || state.skip_value)
+    {
+        let start = idx + c.len_utf8();
+        state.comment = start..start;
+
+        IniParserFSMState::InlineComment
+    } else {
+        IniParserFSMState::SkipLine
+    }
+}
+
+/// Adds the (trimmed, possibly empty) current inline comment text to the `config`.
+fn add_inline_comment_to_config<'s, C, S>(
+    config: &mut C,
+    substr: S,
+    state: &IniParserPersistentState<'s>,
+) where
+    C: IniConfig<'s>,
+    S: Substr<'s>,
+{
+    let comment = if state.comment.is_empty() {
+        ""
+    } else {
+        substr(state.comment.start..=(state.comment.end - 1)).as_str()
+    };
+
+    config.add_inline_comment(comment.trim());
+}
+
+/// Parses a string `value` and adds it to the `config`'s current section at `key`
+/// (or appends it to the array at `key` if `append_to_array` is `true`).
 /// If `quoted` is `true`, `value` is always treated as a string,
 /// else it is first interpreted as a bool / integer / float.
 /// Empty `value`'s are treated as strings.
@@ -1456,74 +1709,213 @@ fn add_value_to_config<'s, C: IniConfig<'s>>(
     quoted: bool,
     skip: bool,
     is_key_unique: bool,
-    unquoted_strings: bool,
+    append_to_array: bool,
+    options: &IniOptions,
 ) -> Result<(), IniErrorKind> {
     if !skip {
-        config.add_value(
+        let value = parse_value_string(value, quoted, options)?;
+
+        if append_to_array {
+            if !config.append_value(key, value) {
+                return Err(IniErrorKind::MixedArray);
+            }
+        } else {
+            config.add_value(key, value, !is_key_unique);
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds a boolean `true` value at the current bare key (i.e. a key not followed by a key-value separator and a value)
+/// to the `config`'s current section (or appends it to the array at the key if `append_to_array` is `true`).
+/// If `finish_key` is `true`, the key is first pushed onto the path and checked for duplicates.
+/// Clears the key and pops it off the path.
+fn add_bare_key_to_config<'s, C, S>(
+    config: &mut C,
+    substr: S,
+    state: &mut IniParserPersistentState<'s>,
+    options: &IniOptions,
+    finish_key: bool,
+) -> Result<(), (IniErrorKind, bool)>
+where
+    C: IniConfig<'s>,
+    S: Substr<'s>,
+{
+    // Must succeed.
+    let key = unwrap_unchecked(state.key.key(&substr), "empty key");
+
+    if finish_key {
+        state.path.push(key);
+
+        check_is_key_duplicate(
+            config,
             key,
-            parse_value_string(value, quoted, unquoted_strings)?,
-            !is_key_unique,
-        );
+            state.skip_section,
+            &mut state.skip_value,
+            &mut state.is_key_unique,
+            &mut state.append_to_array,
+            options.duplicate_keys,
+        )?;
     }
 
+    if !(state.skip_section | state.skip_value) {
+        let value = IniValue::Bool(true);
+
+        if state.append_to_array {
+            if !config.append_value(key, value) {
+                return Err((IniErrorKind::MixedArray, true));
+            }
+        } else {
+            config.add_value(key, value, !state.is_key_unique);
+        }
+    }
+
+    state.key.clear();
+    state.path.pop();
+
     Ok(())
 }
 
-/// Adds an empty array to the `config`'s current section at `key`.
+/// Adds an empty array to the `config`'s current section at `key`,
+/// unless the array values are appended to the array at the (duplicate) `key`.
 fn add_array_to_config<'s, C: IniConfig<'s>>(
     config: &mut C,
     key: NonEmptyIniStr<'s, '_>,
     skip: bool,
     is_key_unique: bool,
+    append_to_array: bool,
 ) {
-    if !skip {
+    if !(skip || append_to_array) {
         config.start_array(key, !is_key_unique);
     }
 }
 
-/// Parses a string `value` and adds it to the `config`'s current array.
+/// Finishes the `config`'s current array at `key`,
+/// unless it was skipped or the array values were appended to the array at the (duplicate) `key`.
+/// Adds the buffered `array_values`, if any, to the array first,
+/// converting their types according to the `mixed_arrays` policy.
+fn end_array_in_config<'s, C: IniConfig<'s>>(
+    config: &mut C,
+    key: NonEmptyIniStr<'s, '_>,
+    skip: bool,
+    append_to_array: bool,
+    array_values: &mut Vec<BufferedIniValue>,
+    mixed_arrays: IniMixedArrays,
+) {
+    if !(skip || append_to_array) {
+        add_buffered_values_to_array(config, array_values, mixed_arrays);
+        config.end_array(key);
+    }
+
+    array_values.clear();
+}
+
+/// Adds the buffered `array_values` to the `config`'s current array,
+/// converting their types according to the `mixed_arrays` policy.
+fn add_buffered_values_to_array<'s, C: IniConfig<'s>>(
+    config: &mut C,
+    array_values: &mut Vec<BufferedIniValue>,
+    mixed_arrays: IniMixedArrays,
+) {
+    let mut value_types = array_values.iter().map(BufferedIniValue::get_ini_type);
+
+    let mixed = if let Some(array_type) = value_types.next() {
+        value_types.any(|value_type| !array_type.is_compatible(value_type))
+    } else {
+        false
+    };
+    let has_floats = array_values
+        .iter()
+        .any(|value| value.get_ini_type() == IniValueType::F64);
+
+    let to_string = mixed && (mixed_arrays == IniMixedArrays::CoerceToString);
+    let to_float = has_floats && (mixed_arrays == IniMixedArrays::PromoteToFloat);
+
+    for value in array_values.drain(..) {
+        let value = match value {
+            BufferedIniValue::Bool(value) if to_string => {
+                BufferedIniValue::String(value.to_string())
+            }
+            BufferedIniValue::I64(value) if to_string => {
+                BufferedIniValue::String(value.to_string())
+            }
+            BufferedIniValue::F64(value) if to_string => {
+                BufferedIniValue::String(value.to_string())
+            }
+            BufferedIniValue::I64(value) if to_float => BufferedIniValue::F64(value as f64),
+            value => value,
+        };
+
+        config.add_array_value(match &value {
+            BufferedIniValue::Bool(value) => IniValue::Bool(*value),
+            BufferedIniValue::I64(value) => IniValue::I64(*value),
+            BufferedIniValue::F64(value) => IniValue::F64(*value),
+            BufferedIniValue::String(value) => {
+                IniValue::String(NonEmptyStr::new(value).map_or(IniStr::Empty, IniStr::Owned))
+            }
+        });
+    }
+}
+
+/// Parses a string `value` and adds it to the `config`'s current array
+/// (or appends it to the array at `key` if `append_to_array` is `true`).
 /// If `quoted` is `true`, `value` is always treated as a string,
 /// else it is first interpreted as a bool / integer / float.
 /// Empty `value`'s are treated as strings.
 /// Updates the `array_type`.
+/// If mixed arrays are not forbidden, the value is buffered in `array_values` until the end of the array.
 fn add_value_to_array<'s, C: IniConfig<'s>>(
     config: &mut C,
+    key: NonEmptyIniStr<'s, '_>,
     value: IniStr<'s, '_>,
     quoted: bool,
     skip: bool,
     array_type: &mut Option<IniValueType>,
-    unquoted_strings: bool,
+    append_to_array: bool,
+    array_values: &mut Vec<BufferedIniValue>,
+    options: &IniOptions,
 ) -> Result<(), (IniErrorKind, bool)> {
     if skip {
         return Ok(());
     }
 
-    let value = parse_value_string(value, quoted, unquoted_strings)
-        .map_err(|error_kind| (error_kind, false))?;
+    let value =
+        parse_value_string(value, quoted, options).map_err(|error_kind| (error_kind, false))?;
     let value_type = value.get_ini_type();
 
-    // Make sure the array is not mixed.
+    // Make sure the array is not mixed, unless mixed arrays are coerced to strings.
     if let Some(array_type) = array_type {
-        if !array_type.is_compatible(value_type) {
+        if !array_type.is_compatible(value_type)
+            && options.mixed_arrays != IniMixedArrays::CoerceToString
+        {
             return Err((IniErrorKind::MixedArray, true));
         }
     } else {
         array_type.replace(value_type);
     }
 
-    config.add_array_value(value);
+    if append_to_array {
+        if !config.append_value(key, value) {
+            return Err((IniErrorKind::MixedArray, true));
+        }
+    } else if options.mixed_arrays == IniMixedArrays::Forbid {
+        config.add_array_value(value);
+    } else {
+        array_values.push(value.into());
+    }
 
     Ok(())
 }
 
 /// Parses a string `value`.
 /// If `quoted` is `true`, `value` is always treated as a string,
-/// else it is first interpreted as a bool / integer / float.
+/// else it is first interpreted as a bool (using the tokens allowed by `options`) / integer / float.
 /// Empty `value`'s are treated as strings.
 fn parse_value_string<'s, 'a>(
     value: IniStr<'s, 'a>,
     quoted: bool,
-    unquoted_strings: bool,
+    options: &IniOptions,
 ) -> Result<IniValue<'s, 'a>, IniErrorKind> {
     use IniErrorKind::*;
     use IniValue::*;
@@ -1533,10 +1925,8 @@ fn parse_value_string<'s, 'a>(
         String(value)
 
     // Check if it's a bool.
-    } else if value.as_str() == "true" {
-        Bool(true)
-    } else if value.as_str() == "false" {
-        Bool(false)
+    } else if let Some(value) = options.bool_tokens.parse(value.as_str()) {
+        Bool(value)
 
     // Check if it's an integer.
     } else if let Some(value) = try_parse_integer(value.as_str()) {
@@ -1549,7 +1939,7 @@ fn parse_value_string<'s, 'a>(
     // Else we assume it's an unquoted string.
     } else {
         // Unless we don't allow unquoted strings.
-        if !unquoted_strings {
+        if !options.unquoted_strings {
             return Err(UnquotedString);
         }
 
@@ -1559,39 +1949,6 @@ fn parse_value_string<'s, 'a>(
     Ok(value)
 }
 
-fn try_parse_integer(value: &str) -> Option<i64> {
-    if value.is_empty() {
-        None
-    } else {
-        // Explicit sign.
-        let (sign, value) = {
-            if let Some(value) = value.strip_prefix("+") {
-                (1, value)
-            } else if let Some(value) = value.strip_prefix("-") {
-                (-1, value)
-            } else {
-                (1, value)
-            }
-        };
-
-        // Radix.
-        let (radix, value) = {
-            // Hexadecimal.
-            if let Some(value) = value.strip_prefix("0x") {
-                (16, value)
-            // Octal.
-            } else if let Some(value) = value.strip_prefix("0o") {
-                (8, value)
-            // Else assume decimal.
-            } else {
-                (10, value)
-            }
-        };
-
-        i64::from_str_radix(value, radix).ok().map(|int| sign * int)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1601,6 +1958,8 @@ mod tests {
         assert_eq!(try_parse_integer("7").unwrap(), 7);
         assert_eq!(try_parse_integer("+7").unwrap(), 7);
         assert_eq!(try_parse_integer("-7").unwrap(), -7);
+        assert_eq!(try_parse_integer("+0").unwrap(), 0);
+        assert_eq!(try_parse_integer("-0").unwrap(), 0);
 
         assert_eq!(try_parse_integer("0x17").unwrap(), 23);
         assert_eq!(try_parse_integer("+0x17").unwrap(), 23);
@@ -1619,6 +1978,19 @@ mod tests {
         assert!(try_parse_integer("+0o").is_none());
         assert!(try_parse_integer("-0o").is_none());
 
+        assert_eq!(try_parse_integer("-9223372036854775808").unwrap(), i64::MIN);
+        assert_eq!(try_parse_integer("9223372036854775807").unwrap(), i64::MAX);
+        assert_eq!(try_parse_integer("-0x8000000000000000").unwrap(), i64::MIN);
+        assert!(try_parse_integer("9223372036854775808").is_none());
+        assert!(try_parse_integer("-9223372036854775809").is_none());
+
+        assert!(try_parse_integer("++7").is_none());
+        assert!(try_parse_integer("+-7").is_none());
+        assert!(try_parse_integer("-+7").is_none());
+        assert!(try_parse_integer("--7").is_none());
+        assert!(try_parse_integer("+0x+17").is_none());
+        assert!(try_parse_integer("-0o-17").is_none());
+
         assert!(try_parse_integer("+7.").is_none());
         assert!(try_parse_integer("-7.").is_none());
         assert!(try_parse_integer("7.").is_none());
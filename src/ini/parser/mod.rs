@@ -1,12 +1,20 @@
+mod events;
 mod fsm_state;
 
 use {
     super::*,
     crate::*,
     fsm_state::*,
-    std::{iter::Iterator, ops::RangeInclusive, str::CharIndices},
+    std::{
+        collections::HashMap,
+        iter::Iterator,
+        ops::{Range, RangeInclusive},
+        str::CharIndices,
+    },
 };
 
+pub use events::*;
+
 //////////////////////////////////////////////////////////
 /// Trait alias for a closure which, given an inclusive byte range in the `.ini` source string,
 /// returns the non-empty substring corresponding to the byte range.
@@ -32,6 +40,9 @@ pub(super) struct IniParserPersistentState<'s> {
     // Current nested section path, if any.
     // Contains at most one section name if nested sections are not supported.
     pub path: IniPath<'s>,
+    // First encountered casing of the section names, keyed by their lowercase section paths,
+    // if section names are case-insensitive.
+    pub section_names: HashMap<Vec<String>, NonEmptyString>,
     // Whether the key is unique in its table (root or section).
     pub is_key_unique: bool,
     // Whether we need to skip all key/value pairs in the current section
@@ -40,6 +51,15 @@ pub(super) struct IniParserPersistentState<'s> {
     // Whether we need to skip the current value
     // (i.e., when we encountered a duplicate key and we use the `First` duplicate key policy).
     pub skip_value: bool,
+    // Whether we need to append the current value(s) to the array at the current key
+    // (i.e., when we encountered a duplicate key and we use the `Array` duplicate key policy).
+    pub append_to_array: bool,
+    // Values of the current array, buffered until the end of the array if mixed arrays are not forbidden.
+    pub array_values: Vec<BufferedIniValue>,
+    // Source byte range of the current comment line / inline comment text (after the comment delimiter), if we preserve comments.
+    pub comment: Range<usize>,
+    // Whether the current line is a section line (as opposed to a key / value line).
+    pub is_section_line: bool,
 }
 
 impl<'s> IniParserPersistentState<'s> {
@@ -48,9 +68,14 @@ impl<'s> IniParserPersistentState<'s> {
             key: ParsedIniKey::new(),
             value: ParsedIniValue::new(),
             path: IniPath::new(),
+            section_names: HashMap::new(),
             is_key_unique: true,
             skip_section: false,
             skip_value: false,
+            append_to_array: false,
+            array_values: Vec::new(),
+            comment: 0..0,
+            is_section_line: false,
         }
     }
 
@@ -67,6 +92,37 @@ impl<'s> IniParserPersistentState<'s> {
     }
 }
 
+/// An owned `.ini` array value, buffered until the end of the array
+/// so that the array value types may be converted according to the mixed array policy.
+pub(super) enum BufferedIniValue {
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    String(String),
+}
+
+impl BufferedIniValue {
+    fn get_ini_type(&self) -> IniValueType {
+        match self {
+            BufferedIniValue::Bool(_) => IniValueType::Bool,
+            BufferedIniValue::I64(_) => IniValueType::I64,
+            BufferedIniValue::F64(_) => IniValueType::F64,
+            BufferedIniValue::String(_) => IniValueType::String,
+        }
+    }
+}
+
+impl<'s, 'a> From<IniValue<'s, 'a>> for BufferedIniValue {
+    fn from(value: IniValue<'s, 'a>) -> Self {
+        match value {
+            IniValue::Bool(value) => BufferedIniValue::Bool(value),
+            IniValue::I64(value) => BufferedIniValue::I64(value),
+            IniValue::F64(value) => BufferedIniValue::F64(value),
+            IniValue::String(value) => BufferedIniValue::String(value.as_str().to_owned()),
+        }
+    }
+}
+
 /// Current position in the source string.
 /// Used for error reporting.
 struct IniParserSrcPositionState {
@@ -128,6 +184,21 @@ impl<'s> IniParser<'s> {
         self
     }
 
+    /// Sets whether comment lines (i.e. those which begin with a comment delimiter) are reported to the config
+    /// via [`add_comment`](trait.IniConfig.html#method.add_comment), e.g. to be written back by [`to_ini_string`].
+    /// Inline comments (if [`supported`](#method.inline_comments)) following the values are reported
+    /// via [`add_inline_comment`](trait.IniConfig.html#method.add_inline_comment);
+    /// inline comments following the section names are not reported.
+    /// If [`comments`](#method.comments) is [`None`](struct.IniCommentDelimiter.html#associatedconstant.None), this value is ignored.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`to_ini_string`]: struct.DynConfig.html#method.to_ini_string
+    pub fn preserve_comments(mut self, preserve_comments: bool) -> Self {
+        self.options.preserve_comments = preserve_comments;
+        self
+    }
+
     /// Sets the valid key-value separator character(s).
     /// If no flag is set, [`Equals`](struct.IniKeyValueSeparator.html#associatedconstant.Equals) is assumed.
     ///
@@ -148,6 +219,17 @@ impl<'s> IniParser<'s> {
         self
     }
 
+    /// Sets the valid unquoted boolean value token(s), e.g. `yes` / `no`.
+    /// Tokens are case-sensitive.
+    /// If [`None`](struct.IniBoolTokens.html#associatedconstant.None), boolean values are not supported,
+    /// and unquoted `true` / `false` are parsed as strings (if [`unquoted_strings`](#method.unquoted_strings) are supported).
+    ///
+    /// Default: [`TrueFalse`](struct.IniBoolTokens.html#associatedconstant.TrueFalse).
+    pub fn bool_tokens(mut self, bool_tokens: IniBoolTokens) -> Self {
+        self.options.bool_tokens = bool_tokens;
+        self
+    }
+
     /// Sets whether unquoted string values are supported.
     /// If `false`, an unquoted value must parse as a boolean / integer / float, or an error will be raised.
     /// If [`string_quotes`](#method.string_quotes) is [`None`](struct.IniStringQuote.html#associatedconstant.None), this value is ignored.
@@ -189,11 +271,22 @@ impl<'s> IniParser<'s> {
         self
     }
 
-    /// Sets whether line ontinuation esacpe sequences (a backslash `'\'` followed by a newline `'\n'` / `'\r'`)
-    /// are supported in keys, section names and string values.
+    /// Sets whether line continuation escape sequences (a backslash `'\'` followed by a newline `'\n'` / `'\r'` / `"\r\n"`)
+    /// are supported in unquoted keys, section names and string values.
+    /// The escape sequence is skipped, joining the line with the next one.
+    /// Quoted keys, section names and string values may not be continued;
+    /// a backslash followed by a newline before the closing quotes results in an
+    /// [`UnexpectedNewLineInSectionName`] / [`UnexpectedNewLineInKey`] / [`UnexpectedNewLineInQuotedValue`] error.
+    /// A backslash at the very end of the source is not a line continuation
+    /// and results in an [`UnexpectedEndOfFileInEscapeSequence`] error.
     /// If [`escape`](#method.escape) is `false`, this value is ignored.
     ///
     /// Default: `false`.
+    ///
+    /// [`UnexpectedEndOfFileInEscapeSequence`]: enum.IniErrorKind.html#variant.UnexpectedEndOfFileInEscapeSequence
+    /// [`UnexpectedNewLineInSectionName`]: enum.IniErrorKind.html#variant.UnexpectedNewLineInSectionName
+    /// [`UnexpectedNewLineInKey`]: enum.IniErrorKind.html#variant.UnexpectedNewLineInKey
+    /// [`UnexpectedNewLineInQuotedValue`]: enum.IniErrorKind.html#variant.UnexpectedNewLineInQuotedValue
     pub fn line_continuation(mut self, line_continuation: bool) -> Self {
         self.options.line_continuation = line_continuation;
         self
@@ -207,8 +300,28 @@ impl<'s> IniParser<'s> {
         self
     }
 
+    /// Sets whether section names are case-insensitive.
+    /// If `true`, section names which only differ in case (e.g. `[Server]` and `[server]`)
+    /// refer to the same section, which keeps the casing of its first encountered instance,
+    /// and the [`duplicate section`](#method.duplicate_sections) handling policy applies to all following instances
+    /// (e.g. with the default [`Merge`](enum.IniDuplicateSections.html#variant.Merge) policy,
+    /// the keys of all instances are added to the same section,
+    /// and the [`duplicate key`](#method.duplicate_keys) handling policy applies to keys duplicated across them).
+    /// Keys are always case-sensitive.
+    ///
+    /// Default: `false`.
+    pub fn case_insensitive_sections(mut self, case_insensitive_sections: bool) -> Self {
+        self.options.case_insensitive_sections = case_insensitive_sections;
+        self
+    }
+
     /// Sets the duplicate key handling policy.
     ///
+    /// Use [`Array`](enum.IniDuplicateKeys.html#variant.Array) for `.ini` dialects which represent arrays by repeating a key,
+    /// e.g. `tag = a` / `tag = b` is parsed as `tag = [a, b]`.
+    /// Values of explicit arrays (e.g. `tag = [c, d]`, if [`arrays`](#method.arrays) are supported)
+    /// are appended to / appended with the values of the other instances of the key individually.
+    ///
     /// Default: [`Forbid`](enum.IniDuplicateKeys.html#variant.Forbid).
     pub fn duplicate_keys(mut self, duplicate_keys: IniDuplicateKeys) -> Self {
         self.options.duplicate_keys = duplicate_keys;
@@ -218,18 +331,37 @@ impl<'s> IniParser<'s> {
     /// Sets whether arrays are supported.
     /// If `true`, values enclosed in brackets `'['` \ `']'` are parsed as
     /// comma (`','`) delimited arrays of booleans / integers / floats / strings.
-    /// Types may not be mixed in the array, except integers / floats.
+    /// Types may not be mixed in the array, except integers / floats, unless allowed by [`mixed_arrays`].
     ///
     /// Default: `false`.
+    ///
+    /// [`mixed_arrays`]: #method.mixed_arrays
     pub fn arrays(mut self, arrays: bool) -> Self {
         self.options.arrays = arrays;
         self
     }
 
+    /// Sets the mixed array handling policy.
+    /// Does not apply to values appended to arrays at duplicate keys
+    /// (see [`IniDuplicateKeys::Array`](enum.IniDuplicateKeys.html#variant.Array)).
+    /// If [`arrays`](#method.arrays) is `false`, this value is ignored.
+    ///
+    /// Default: [`Forbid`](enum.IniMixedArrays.html#variant.Forbid).
+    pub fn mixed_arrays(mut self, mixed_arrays: IniMixedArrays) -> Self {
+        self.options.mixed_arrays = mixed_arrays;
+        self
+    }
+
     /// Maximum supported depth of nested sections.
-    /// If `0`, sections are not supported at all.
-    /// If `1`, one level of sections is supported; forward slashes (`'/'`) are treated as normal section name character.
-    /// If `>1`, nested sections are supported; section names which contain forward slashes (`'/'`) are treated as paths.
+    /// If `0`, sections are not supported at all; any section (e.g. `[a]`) results in a
+    /// [`NestedSectionDepthExceeded`](enum.IniErrorKind.html#variant.NestedSectionDepthExceeded) error.
+    /// If `1`, one level of sections is supported; forward slashes (`'/'`) are treated as normal section name character,
+    /// i.e. `[a/b]` is a single section named `a/b`.
+    /// If `>1`, nested sections are supported; section names which contain forward slashes (`'/'`) are treated as paths,
+    /// i.e. `[a/b]` is a section `b` nested in the section `a`.
+    /// Section paths longer than `nested_section_depth` (e.g. `[a/b/c]` for `2`) result in a
+    /// [`NestedSectionDepthExceeded`](enum.IniErrorKind.html#variant.NestedSectionDepthExceeded) error.
+    /// Periods (`'.'`) are never treated as nested section separators.
     ///
     /// Default: `1`.
     pub fn nested_section_depth(mut self, nested_section_depth: u32) -> Self {
@@ -249,6 +381,36 @@ impl<'s> IniParser<'s> {
         self
     }
 
+    /// Sets whether bare keys (i.e. keys not followed by a key-value separator and a value) are supported.
+    /// If `true`, bare keys (e.g. `key` on its own line) are parsed as boolean `true` values.
+    /// Otherwise bare keys are treated as an error.
+    ///
+    /// NOTE - keys followed by a key-value separator but no value (e.g. `key =`) are always parsed as empty strings.
+    ///
+    /// Default: `false`.
+    pub fn bare_key_as_true(mut self, bare_key_as_true: bool) -> Self {
+        self.options.bare_key_as_true = bare_key_as_true;
+        self
+    }
+
+    /// Returns the [`options`] for serializing a config to an `.ini` string
+    /// which match the parsing options of this parser (where applicable),
+    /// so that a parsed config may be written back in the same format.
+    ///
+    /// [`options`]: struct.ToIniStringOptions.html
+    pub fn to_ini_string_options(&self) -> ToIniStringOptions {
+        ToIniStringOptions {
+            escape: self.options.escape,
+            arrays: self.options.arrays,
+            nested_section_depth: self.options.nested_section_depth,
+            implicit_parent_sections: self.options.implicit_parent_sections,
+            key_value_separator: self.options.key_value_separator,
+            string_quotes: self.options.string_quotes,
+            comments: self.options.comments,
+            bool_tokens: self.options.bool_tokens,
+        }
+    }
+
     /// Consumes the parser and tries to parse the `.ini` config string, calling the methods on the passed `config` event handler.
     pub fn parse<C: IniConfig<'s>>(mut self, config: &mut C) -> Result<(), IniError> {
         self.validate_options();
@@ -302,6 +464,32 @@ impl<'s> IniParser<'s> {
         Ok(())
     }
 
+    /// Consumes the parser and returns an [`iterator`](struct.IniEvents.html) which parses the `.ini` config string on demand,
+    /// returning the generated [`events`](enum.IniEvent.html) one by one,
+    /// as an alternative to implementing the [`IniConfig`](trait.IniConfig.html) event handler and calling [`parse`](#method.parse).
+    pub fn events(mut self) -> IniEvents<'s> {
+        self.validate_options();
+
+        IniEvents::new(self)
+    }
+
+    /// Creates a new [`parser`](struct.IniParser.html) from the `.ini` config `string`
+    /// using the provided parsing `options`.
+    #[cfg(feature = "dyn")]
+    pub(crate) fn with_options(string: &'s str, options: IniOptions) -> Self {
+        Self {
+            source: string,
+            reader: string.char_indices(),
+            options,
+        }
+    }
+
+    /// Consumes the parser and returns the `.ini` config string and the parsing options.
+    #[cfg(feature = "dyn")]
+    pub(crate) fn into_parts(self) -> (&'s str, IniOptions) {
+        (self.source, self.options)
+    }
+
     fn validate_options(&mut self) {
         // Must have some key-value separator if none provided by the user - use `Equals`.
         if self.options.key_value_separator.is_empty() {
@@ -320,7 +508,16 @@ impl<'s> IniParser<'s> {
         reader: &mut CharIndices<'s>,
         state: &mut IniParserSrcPositionState,
     ) -> Option<(char, usize)> {
-        let next = reader.next();
+        let mut next = reader.next();
+
+        // Eat a line feed if the previous char was a carriage return.
+        if state.cr {
+            state.cr = false;
+
+            if let Some((_, '\n')) = next {
+                next = reader.next();
+            }
+        }
 
         if state.new_line {
             state.line += 1;
@@ -332,10 +529,6 @@ impl<'s> IniParser<'s> {
         match next {
             Some((idx, c)) => {
                 match c {
-                    // Eat a line feed if the previous char was a carriage return.
-                    '\n' if state.cr => {
-                        state.cr = false;
-                    }
                     '\r' => {
                         state.column += 1;
                         state.new_line = true;
@@ -333,6 +333,18 @@ fn UnexpectedNewLineInSectionName() {
             path: ConfigPath::new(),
         }
     );
+    // Line continuation in quoted section name (not supported in quoted strings).
+    assert_eq!(
+        DynConfig::from_ini(IniParser::new("[\"a\\\nb\"]").line_continuation(true))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 1,
+            column: 4,
+            error: IniErrorKind::UnexpectedNewLineInSectionName,
+            path: ConfigPath::new(),
+        }
+    );
 
     // But this succeeds.
 
@@ -749,6 +761,118 @@ fn DuplicateSection() {
     assert_eq!(ini.root().get_table("b").unwrap().get_i64("b").unwrap(), 43);
 }
 
+#[test]
+fn case_insensitive_sections() {
+    // Section names are case-sensitive by default.
+    let ini = dyn_config("[Server]\na=7\n[server]\nb=9");
+    assert_eq!(ini.root().len(), 2);
+
+    // Merged into the first encountered instance of the section.
+    let ini = DynConfig::from_ini(
+        IniParser::new("[Server]\na=7\n[b]\na=42\n[server]\nb=9\n[SERVER]\nc=11")
+            .case_insensitive_sections(true),
+    )
+    .unwrap();
+    assert_eq!(ini.root().len(), 2);
+    assert!(ini.root().get_table("server").is_err());
+    assert_eq!(ini.root().get_table("Server").unwrap().len(), 3);
+    assert_eq!(
+        ini.root()
+            .get_table("Server")
+            .unwrap()
+            .get_i64("b")
+            .unwrap(),
+        9
+    );
+    assert_eq!(
+        ini.root()
+            .get_table("Server")
+            .unwrap()
+            .get_i64("c")
+            .unwrap(),
+        11
+    );
+
+    let string = ini.to_ini_string().unwrap();
+    assert!(string.contains("[Server]"));
+    assert!(!string.contains("[server]"));
+
+    // Keys are still case-sensitive, duplicate key policy applies across the instances.
+    assert_eq!(
+        DynConfig::from_ini(
+            IniParser::new("[Server]\na=7\n[server]\nA=8\na=9").case_insensitive_sections(true),
+        )
+        .err()
+        .unwrap(),
+        IniError {
+            line: 5,
+            column: 1,
+            error: IniErrorKind::DuplicateKey,
+            path: vec![nestr!("Server").into(), nestr!("a").into()].into(),
+        }
+    );
+
+    let ini = DynConfig::from_ini(
+        IniParser::new("[Server]\na=7\n[server]\nA=8\na=9")
+            .case_insensitive_sections(true)
+            .duplicate_keys(IniDuplicateKeys::Last),
+    )
+    .unwrap();
+    assert_eq!(ini.root().get_table("Server").unwrap().len(), 2);
+    assert_eq!(
+        ini.root()
+            .get_table("Server")
+            .unwrap()
+            .get_i64("a")
+            .unwrap(),
+        9
+    );
+
+    // Duplicate section policy applies to the instances.
+    assert_eq!(
+        DynConfig::from_ini(
+            IniParser::new("[Server]\na=7\n[server]\nb=9")
+                .case_insensitive_sections(true)
+                .duplicate_sections(IniDuplicateSections::Forbid),
+        )
+        .err()
+        .unwrap(),
+        IniError {
+            line: 3,
+            column: 8,
+            error: IniErrorKind::DuplicateSection,
+            path: vec![nestr!("Server").into()].into(),
+        }
+    );
+
+    // Nested sections.
+    let ini = DynConfig::from_ini(
+        IniParser::new("[A]\n[a/B]\nx=1\n[A/b]\ny=2")
+            .nested_section_depth(2)
+            .case_insensitive_sections(true),
+    )
+    .unwrap();
+    assert_eq!(ini.root().len(), 1);
+    assert_eq!(ini.root().get_table("A").unwrap().len(), 1);
+    assert_eq!(
+        ini.root()
+            .get_i64_path(&["A".into(), "B".into(), "y".into()])
+            .unwrap(),
+        2
+    );
+
+    // Events report the first encountered casing.
+    let sections: Vec<_> = IniParser::new("[Server]\na=7\n[server]\nb=9")
+        .case_insensitive_sections(true)
+        .events()
+        .filter_map(|event| match event.unwrap() {
+            IniEvent::SectionStart { section, .. } => Some(section.as_str().to_owned()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(sections, vec!["Server", "Server"]);
+}
+
 #[test]
 fn InvalidCharacterAtLineEnd() {
     // After section.
@@ -818,12 +942,30 @@ fn InvalidCharacterInKey() {
             path: ConfigPath::new(),
         }
     );
+    // Columns count characters, not bytes.
+    let error = dyn_config_error("ключ = 7\nзначение[");
+    assert_eq!(
+        error,
+        IniError {
+            line: 2,
+            column: 9,
+            error: IniErrorKind::InvalidCharacterInKey('['),
+            path: ConfigPath::new(),
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "`.ini` parse error at line 2, column 9: invalid character ('[') in the key name (path: <root>)"
+    );
 
     // But this succeeds.
 
     let ini = dyn_config("a=true"); // Normal key.
     assert_eq!(ini.root().get_bool("a").unwrap(), true);
 
+    let ini = dyn_config("ключ = значение"); // Multi-byte key and value.
+    assert_eq!(ini.root().get_string("ключ").unwrap(), "значение");
+
     let ini = dyn_config("\"a\"=true"); // Quoted key.
     assert_eq!(ini.root().get_bool("a").unwrap(), true);
 
@@ -869,6 +1011,18 @@ fn UnexpectedNewLineInKey() {
             path: vec![nestr!("b").into()].into(),
         }
     );
+    // Line continuation in quoted key (not supported in quoted strings).
+    assert_eq!(
+        DynConfig::from_ini(IniParser::new("\"a\\\nb\" = 7").line_continuation(true))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 1,
+            column: 3,
+            error: IniErrorKind::UnexpectedNewLineInKey,
+            path: ConfigPath::new(),
+        }
+    );
 
     // But this succeeds.
 
@@ -1119,6 +1273,92 @@ fn DuplicateKey() {
     );
 }
 
+#[test]
+fn DuplicateKeys_Array() {
+    let dyn_config_array = |string| {
+        DynConfig::from_ini(
+            IniParser::new(string)
+                .duplicate_keys(IniDuplicateKeys::Array)
+                .arrays(true),
+        )
+    };
+
+    // In the root.
+    let ini = dyn_config_array("a=7\nb=8\na=9\nc=10\na=11").unwrap();
+    let a = ini.root().get_array("a").unwrap();
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.get_i64(0).unwrap(), 7);
+    assert_eq!(a.get_i64(1).unwrap(), 9);
+    assert_eq!(a.get_i64(2).unwrap(), 11);
+    assert_eq!(ini.root().get_i64("b").unwrap(), 8);
+    assert_eq!(ini.root().get_i64("c").unwrap(), 10);
+
+    // In the section, unique keys are unaffected.
+    let ini = dyn_config_array("[a]\na=\"foo\"\nb=8\na=bar").unwrap();
+    let a = ini.root().get_table("a").unwrap().get_array("a").unwrap();
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.get_string(0).unwrap(), "foo");
+    assert_eq!(a.get_string(1).unwrap(), "bar");
+    assert_eq!(ini.root().get_table("a").unwrap().get_i64("b").unwrap(), 8);
+
+    // In the merged section.
+    let ini = dyn_config_array("[a]\na=true\n[a]\na=false").unwrap();
+    let a = ini.root().get_table("a").unwrap().get_array("a").unwrap();
+    assert_eq!(a.len(), 2);
+    assert!(a.get_bool(0).unwrap());
+    assert!(!a.get_bool(1).unwrap());
+
+    // Explicit arrays are concatenated.
+    let ini = dyn_config_array("a=[1, 2]\na=3\na=[4, 5.5]").unwrap();
+    let a = ini.root().get_array("a").unwrap();
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.get_i64(0).unwrap(), 1);
+    assert_eq!(a.get_i64(2).unwrap(), 3);
+    assert!(cmp_f64(a.get_f64(4).unwrap(), 5.5));
+
+    let ini = dyn_config_array("a=\"foo\"\na=[\"bar\", baz]").unwrap();
+    let a = ini.root().get_array("a").unwrap();
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.get_string(2).unwrap(), "baz");
+
+    // Mixed values.
+    let error = dyn_config_array("a=7\na=foo").err().unwrap();
+    assert_eq!(error.error, IniErrorKind::MixedArray);
+    assert_eq!(error.line, 2);
+
+    let error = dyn_config_array("a=[7, 8]\na=true").err().unwrap();
+    assert_eq!(error.error, IniErrorKind::MixedArray);
+
+    let error = dyn_config_array("a=7\na=[8, \"foo\"]").err().unwrap();
+    assert_eq!(error.error, IniErrorKind::MixedArray);
+
+    let error = dyn_config_array("a=\"foo\"\na=[bar, 8]").err().unwrap();
+    assert_eq!(error.error, IniErrorKind::MixedArray);
+
+    // Key and section.
+    let error = dyn_config_array("a=7\n[a]\nb=8").err().unwrap();
+    assert_eq!(error.error, IniErrorKind::DuplicateKey);
+
+    // Section and key.
+    let error = DynConfig::from_ini(
+        IniParser::new("[a]\nb=8\n[a/b]")
+            .duplicate_keys(IniDuplicateKeys::Array)
+            .nested_section_depth(2),
+    )
+    .err()
+    .unwrap();
+    assert_eq!(error.error, IniErrorKind::DuplicateKey);
+
+    let error = DynConfig::from_ini(
+        IniParser::new("[a]\n[a/b]\n[a]\nb=8")
+            .duplicate_keys(IniDuplicateKeys::Array)
+            .nested_section_depth(2),
+    )
+    .err()
+    .unwrap();
+    assert_eq!(error.error, IniErrorKind::DuplicateKey);
+}
+
 #[test]
 fn UnexpectedEndOfFileBeforeKeyValueSeparator() {
     // Unquoted key.
@@ -1157,6 +1397,84 @@ fn UnexpectedEndOfFileBeforeKeyValueSeparator() {
     );
 }
 
+#[test]
+fn bare_key_as_true() {
+    fn dyn_config_bare_keys(string: &str) -> Result<DynConfig, IniError> {
+        DynConfig::from_ini(IniParser::new(string).bare_key_as_true(true))
+    }
+
+    // Bare keys are not supported by default.
+    assert_eq!(
+        dyn_config_error("a\n").error,
+        IniErrorKind::UnexpectedNewLineInKey
+    );
+    assert_eq!(
+        dyn_config_error("a").error,
+        IniErrorKind::UnexpectedEndOfFileBeforeKeyValueSeparator
+    );
+
+    // Bare keys before new lines / EOF, with trailing whitespace, quoted, in sections.
+    let ini = dyn_config_bare_keys("a\nb \n\"c d\"\n[s]\ne").unwrap();
+    assert!(ini.root().get_bool("a").unwrap());
+    assert!(ini.root().get_bool("b").unwrap());
+    assert!(ini.root().get_bool("c d").unwrap());
+    assert!(ini.root().get_table("s").unwrap().get_bool("e").unwrap());
+
+    // Bare keys followed by inline comments.
+    let ini = DynConfig::from_ini(
+        IniParser::new("a ;comment\nb;comment")
+            .bare_key_as_true(true)
+            .inline_comments(true),
+    )
+    .unwrap();
+    assert!(ini.root().get_bool("a").unwrap());
+    assert!(ini.root().get_bool("b").unwrap());
+
+    // Empty values are still empty strings.
+    let ini = dyn_config_bare_keys("a =\nb").unwrap();
+    assert_eq!(ini.root().get_string("a").unwrap(), "");
+    assert!(ini.root().get_bool("b").unwrap());
+
+    // Empty keys are still an error.
+    assert_eq!(
+        dyn_config_bare_keys("a\n=").err().unwrap(),
+        IniError {
+            line: 2,
+            column: 0,
+            error: IniErrorKind::EmptyKey,
+            path: ConfigPath::new(),
+        }
+    );
+
+    // Unterminated quoted keys are still an error.
+    assert_eq!(
+        dyn_config_bare_keys("\"a").err().unwrap().error,
+        IniErrorKind::UnexpectedEndOfFileBeforeKeyValueSeparator
+    );
+
+    // Duplicate bare keys follow the duplicate key policy.
+    assert_eq!(
+        dyn_config_bare_keys("a\na").err().unwrap(),
+        IniError {
+            line: 2,
+            column: 1,
+            error: IniErrorKind::DuplicateKey,
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
+
+    let ini = DynConfig::from_ini(
+        IniParser::new("a\na\n")
+            .bare_key_as_true(true)
+            .duplicate_keys(IniDuplicateKeys::Array),
+    )
+    .unwrap();
+    let array = ini.root().get_array("a").unwrap();
+    assert_eq!(array.len(), 2);
+    assert!(array.get_bool(0).unwrap());
+    assert!(array.get_bool(1).unwrap());
+}
+
 #[test]
 fn InvalidKeyValueSeparator() {
     assert_eq!(
@@ -1379,6 +1697,18 @@ fn UnexpectedEndOfFileInEscapeSequence() {
             path: vec![nestr!("a").into()].into(),
         }
     );
+    // In value, on the last line, with line continuation supported.
+    assert_eq!(
+        DynConfig::from_ini(IniParser::new("a = foo\\\nbar\\").line_continuation(true))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 2,
+            column: 4,
+            error: IniErrorKind::UnexpectedEndOfFileInEscapeSequence,
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
 
     // But this succeeds.
 
@@ -1448,10 +1778,17 @@ fn UnexpectedNewLineInEscapeSequence() {
     let ini = DynConfig::from_ini(IniParser::new("a = 7\\\n9").line_continuation(true)).unwrap();
     assert_eq!(ini.root().get_i64("a").unwrap(), 79);
 
-    // Line continuation in quoted string.
+    // Line continuation across several lines.
+    let ini = DynConfig::from_ini(IniParser::new("a = foo\\\nbar\\\nbaz").line_continuation(true))
+        .unwrap();
+    assert_eq!(ini.root().get_string("a").unwrap(), "foobarbaz");
+
+    // Line continuation with `"\r\n"` line breaks.
     let ini =
-        DynConfig::from_ini(IniParser::new("a = \"foo\\\nbar\"").line_continuation(true)).unwrap();
+        DynConfig::from_ini(IniParser::new("a = foo\\\r\nbar\r\nb = 7").line_continuation(true))
+            .unwrap();
     assert_eq!(ini.root().get_string("a").unwrap(), "foobar");
+    assert_eq!(ini.root().get_i64("b").unwrap(), 7);
 }
 
 #[test]
@@ -1721,6 +2058,44 @@ fn UnexpectedNewLineInQuotedValue() {
             path: vec![nestr!("a").into()].into(),
         }
     );
+    // Line continuation (not supported in quoted strings).
+    assert_eq!(
+        DynConfig::from_ini(IniParser::new("a = \"foo\\\nbar\"").line_continuation(true))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 1,
+            column: 9,
+            error: IniErrorKind::UnexpectedNewLineInQuotedValue,
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
+    assert_eq!(
+        DynConfig::from_ini(IniParser::new("a=\"\\\r\n\"").line_continuation(true))
+            .err()
+            .unwrap(),
+        IniError {
+            line: 1,
+            column: 4,
+            error: IniErrorKind::UnexpectedNewLineInQuotedValue,
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
+    assert_eq!(
+        DynConfig::from_ini(
+            IniParser::new("a=[\"fo\\\no\"]")
+                .arrays(true)
+                .line_continuation(true)
+        )
+        .err()
+        .unwrap(),
+        IniError {
+            line: 1,
+            column: 7,
+            error: IniErrorKind::UnexpectedNewLineInQuotedValue,
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
 
     // But this succeeds.
 
@@ -1735,10 +2110,6 @@ fn UnexpectedNewLineInQuotedValue() {
     // Line continuation.
     let ini = DynConfig::from_ini(IniParser::new("a=\\\n").line_continuation(true)).unwrap();
     assert_eq!(ini.root().get_string("a").unwrap(), "");
-
-    // Line continuation in quoted string.
-    let ini = DynConfig::from_ini(IniParser::new("a=\"\\\n\"").line_continuation(true)).unwrap();
-    assert_eq!(ini.root().get_string("a").unwrap(), "");
 }
 
 #[test]
@@ -1883,6 +2254,77 @@ fn MixedArray() {
     );
 }
 
+#[test]
+fn MixedArray_policy() {
+    // Forbidden by default.
+    assert_eq!(
+        DynConfig::from_ini(IniParser::new("a=[7, 3.14, \"foo\"]").arrays(true))
+            .err()
+            .unwrap()
+            .error,
+        IniErrorKind::MixedArray
+    );
+
+    // Coerce all values of a mixed array to strings.
+    let ini = DynConfig::from_ini(
+        IniParser::new("a=[7, 3.14, \"foo\", true]")
+            .arrays(true)
+            .mixed_arrays(IniMixedArrays::CoerceToString),
+    )
+    .unwrap();
+    let array = ini.root().get_array("a").unwrap();
+    assert_eq!(array.len(), 4);
+    assert_eq!(array.get_string(0).unwrap(), "7");
+    assert_eq!(array.get_string(1).unwrap(), "3.14");
+    assert_eq!(array.get_string(2).unwrap(), "foo");
+    assert_eq!(array.get_string(3).unwrap(), "true");
+
+    // Compatible values are not coerced.
+    let ini = DynConfig::from_ini(
+        IniParser::new("a=[7, 9]")
+            .arrays(true)
+            .mixed_arrays(IniMixedArrays::CoerceToString),
+    )
+    .unwrap();
+    let array = ini.root().get_array("a").unwrap();
+    assert_eq!(array.get_val(0).unwrap().get_type(), ValueType::I64);
+    assert_eq!(array.get_val(1).unwrap().get_type(), ValueType::I64);
+
+    // Promote integers to floats.
+    let ini = DynConfig::from_ini(
+        IniParser::new("a=[7, 3.14]")
+            .arrays(true)
+            .mixed_arrays(IniMixedArrays::PromoteToFloat),
+    )
+    .unwrap();
+    let array = ini.root().get_array("a").unwrap();
+    assert_eq!(array.get_val(0).unwrap().get_type(), ValueType::F64);
+    assert_eq!(array.get_val(1).unwrap().get_type(), ValueType::F64);
+
+    // Integers are not promoted if there are no floats.
+    let ini = DynConfig::from_ini(
+        IniParser::new("a=[7, 9]")
+            .arrays(true)
+            .mixed_arrays(IniMixedArrays::PromoteToFloat),
+    )
+    .unwrap();
+    let array = ini.root().get_array("a").unwrap();
+    assert_eq!(array.get_val(0).unwrap().get_type(), ValueType::I64);
+
+    // Other types still may not be mixed.
+    assert_eq!(
+        DynConfig::from_ini(
+            IniParser::new("a=[7, \"foo\"]")
+                .arrays(true)
+                .mixed_arrays(IniMixedArrays::PromoteToFloat)
+        )
+        .err()
+        .unwrap()
+        .error,
+        IniErrorKind::MixedArray
+    );
+}
+
 #[test]
 fn InvalidCharacterInArray() {
     // Unescaped special character.
@@ -2034,10 +2476,10 @@ fn UnexpectedEndOfFileInQuotedArrayValue() {
         }
     );
 
-    // But this works (line continuations enabled).
+    // But this works (line continuations enabled, unquoted array value).
 
     let ini = DynConfig::from_ini(
-        IniParser::new("a=[\"fo\\\no\"]")
+        IniParser::new("a=[fo\\\no]")
             .arrays(true)
             .line_continuation(true),
     )
@@ -2105,6 +2547,41 @@ string = "bar""#;
     assert_eq!(other_section.get_string("other_string").unwrap(), "foo");
 }
 
+#[test]
+fn explicit_sign() {
+    let ini = r#"int = +42
+float = +3.14
+zero = +0
+negative_int = -42
+negative_float = -3.14
+array = [+1, +2.5, -3]
+double_sign = +-42"#;
+
+    let config = DynConfig::from_ini(IniParser::new(ini).arrays(true)).unwrap();
+
+    assert_eq!(config.root().get_i64("int").unwrap(), 42);
+    assert!(cmp_f64(config.root().get_f64("float").unwrap(), 3.14));
+    assert_eq!(
+        config.root().get_val("zero").unwrap().get_type(),
+        ValueType::I64
+    );
+    assert_eq!(config.root().get_i64("zero").unwrap(), 0);
+    assert_eq!(config.root().get_i64("negative_int").unwrap(), -42);
+    assert!(cmp_f64(
+        config.root().get_f64("negative_float").unwrap(),
+        -3.14
+    ));
+
+    let array = config.root().get_array("array").unwrap();
+
+    assert_eq!(array.get_i64(0).unwrap(), 1);
+    assert!(cmp_f64(array.get_f64(1).unwrap(), 2.5));
+    assert_eq!(array.get_i64(2).unwrap(), -3);
+
+    // Only one explicit sign is allowed - parsed as a string.
+    assert_eq!(config.root().get_string("double_sign").unwrap(), "+-42");
+}
+
 #[test]
 fn ArraysNotAllowed() {
     let mut config = DynConfig::new();
@@ -2270,6 +2747,62 @@ fn NestedSectionsNotAllowed() {
     );
 }
 
+#[test]
+fn nested_section_separators() {
+    let mut config = DynConfig::new();
+    assert!(!config.root_mut().set(nestr!("a/b"), DynTable::new()));
+    let table = config.root_mut().get_table_mut("a/b").unwrap();
+    assert!(!table.set(nestr!("x"), 1));
+    assert!(!table.set(nestr!("c"), DynTable::new()));
+    let table = table.get_table_mut("c").unwrap();
+    assert!(!table.set(nestr!("d"), 7));
+
+    let options = ToIniStringOptions {
+        nested_section_depth: 2,
+        string_quotes: IniStringQuote::None,
+        ..Default::default()
+    };
+
+    // Nested section separators in section names are escaped if string quotes are not allowed ...
+    let string = config.to_ini_string_opts(options).unwrap();
+    assert_eq!(string, "[a\\/b]\nx = 1\n\n[a\\/b/c]\nd = 7");
+
+    // ... and round-trip.
+    let parsed = DynConfig::from_ini(
+        IniParser::new(&string)
+            .nested_section_depth(2)
+            .string_quotes(IniStringQuote::None),
+    )
+    .unwrap();
+    let table = parsed.root().get_table("a/b").unwrap();
+    assert_eq!(table.get_i64("x").unwrap(), 1);
+    assert_eq!(table.get_table("c").unwrap().get_i64("d").unwrap(), 7);
+    assert_eq!(parsed.to_ini_string_opts(options).unwrap(), string);
+
+    // Quoted instead if string quotes are allowed.
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                nested_section_depth: 2,
+                ..Default::default()
+            })
+            .unwrap(),
+        "[\"a/b\"]\nx = 1\n\n[\"a/b\"/c]\nd = 7"
+    );
+
+    // Error if neither string quotes nor escape sequences are allowed.
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                escape: false,
+                ..options
+            })
+            .err()
+            .unwrap(),
+        ToIniStringError::InvalidSectionName('/')
+    );
+}
+
 #[test]
 fn from_string_and_back() {
     let ini = r#"array = ["foo", "bar", "baz"]
@@ -2314,6 +2847,240 @@ string = "baz""#;
     assert_eq!(ini, string);
 }
 
+#[test]
+fn to_ini_string_options() {
+    let ini = r#"array : ['a "b"', 'c\'d']
+int : 7
+string : 'foo bar'
+
+['other section']
+key : 'value'"#;
+
+    let parser = IniParser::new(ini)
+        .key_value_separator(IniKeyValueSeparator::Colon)
+        .string_quotes(IniStringQuote::Single)
+        .arrays(true);
+    let options = parser.to_ini_string_options();
+
+    let config = DynConfig::from_ini(parser).unwrap();
+
+    assert_eq!(config.to_ini_string_opts(options).unwrap(), ini);
+
+    // Unquoted strings.
+    let string = config
+        .to_ini_string_opts(ToIniStringOptions {
+            string_quotes: IniStringQuote::None,
+            arrays: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert_eq!(
+        string,
+        r#"array = [a\ \"b\", c\'d]
+int = 7
+string = foo\ bar
+
+[other\ section]
+key = value"#
+    );
+
+    assert!(
+        DynConfig::from_ini(
+            IniParser::new(&string)
+                .string_quotes(IniStringQuote::None)
+                .arrays(true)
+        )
+        .unwrap()
+            == config
+    );
+}
+
+#[test]
+fn preserve_comments() {
+    let ini = r#"; Root key comment.
+;
+; Second line.
+a = 7
+
+; Section comment.
+[section]
+; Key comment.
+b = true
+
+; Nested section comment.
+[section/nested]
+c = "foo""#;
+
+    let parser = IniParser::new(ini)
+        .nested_section_depth(2)
+        .preserve_comments(true);
+    let options = parser.to_ini_string_options();
+
+    let config = DynConfig::from_ini(parser).unwrap();
+
+    let root = config.root();
+    assert_eq!(
+        root.comment("a").unwrap(),
+        "Root key comment.\n\nSecond line."
+    );
+    assert_eq!(root.comment("section").unwrap(), "Section comment.");
+    let section = root.get_table("section").unwrap();
+    assert_eq!(section.comment("b").unwrap(), "Key comment.");
+    assert_eq!(
+        section.comment("nested").unwrap(),
+        "Nested section comment."
+    );
+    assert!(section.get_table("nested").unwrap().comment("c").is_none());
+
+    assert_eq!(config.to_ini_string_opts(options).unwrap(), ini);
+
+    // Comments are not written if the comment delimiter is `None`.
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                nested_section_depth: 2,
+                comments: IniCommentDelimiter::None,
+                ..Default::default()
+            })
+            .unwrap(),
+        "a = 7\n\n[section]\nb = true\n\n[section/nested]\nc = \"foo\""
+    );
+
+    // Comments are not preserved by default, and do not affect equality.
+    let no_comments = DynConfig::from_ini(IniParser::new(ini).nested_section_depth(2)).unwrap();
+    assert!(no_comments.root().comment("a").is_none());
+    assert!(no_comments == config);
+
+    // Comments are removed with their keys.
+    let mut config = config;
+    let root = config.root_mut();
+    assert!(root.remove("a").is_some());
+    assert!(root.comment("a").is_none());
+    assert!(!root.set(nestr!("a"), 9));
+    assert!(root.set_comment(nestr!("a"), "New comment.").is_none());
+    assert_eq!(root.remove_comment("a").unwrap(), "New comment.");
+}
+
+#[test]
+fn preserve_inline_comments() {
+    let ini = r#"; Line comment.
+a = 7 ; Inline comment.
+b = [1, 2] ; Array comment.
+
+[section] ; Section comment.
+c = "foo" ; Quoted value comment.
+d = true"#;
+
+    let parser = IniParser::new(ini)
+        .arrays(true)
+        .inline_comments(true)
+        .preserve_comments(true);
+    let options = parser.to_ini_string_options();
+
+    let config = DynConfig::from_ini(parser).unwrap();
+
+    let root = config.root();
+    assert_eq!(root.comment("a").unwrap(), "Line comment.");
+    assert_eq!(root.inline_comment("a").unwrap(), "Inline comment.");
+    assert_eq!(root.inline_comment("b").unwrap(), "Array comment.");
+    // Inline comments after section names are not preserved.
+    assert!(root.inline_comment("section").is_none());
+    let section = root.get_table("section").unwrap();
+    assert_eq!(
+        section.inline_comment("c").unwrap(),
+        "Quoted value comment."
+    );
+    assert!(section.inline_comment("d").is_none());
+
+    assert_eq!(
+        config.to_ini_string_opts(options).unwrap(),
+        r#"; Line comment.
+a = 7 ; Inline comment.
+b = [1, 2] ; Array comment.
+
+[section]
+c = "foo" ; Quoted value comment.
+d = true"#
+    );
+
+    // Inline comments are reported by the event iterator.
+    let events = IniParser::new("a = 7 ; Inline comment.")
+        .inline_comments(true)
+        .preserve_comments(true)
+        .events()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(events[1], IniEvent::InlineComment("Inline comment."));
+
+    // Inline comments are not preserved by default.
+    let no_comments =
+        DynConfig::from_ini(IniParser::new(ini).arrays(true).inline_comments(true)).unwrap();
+    assert!(no_comments.root().inline_comment("a").is_none());
+    assert!(no_comments == config);
+
+    // Inline comments are set with the values, using the configured comment delimiter;
+    // line breaks are written as spaces.
+    let mut config = DynConfig::new();
+    let root = config.root_mut();
+    assert!(!root.set_with_inline_comment(nestr!("a"), 7, Some("Inline\ncomment.")));
+    assert!(root.set_with_inline_comment(nestr!("a"), 9, Some("Inline\ncomment.")));
+    assert!(!root.set_with_inline_comment(nestr!("b"), true, None));
+
+    assert_eq!(
+        config
+            .to_ini_string_opts(ToIniStringOptions {
+                comments: IniCommentDelimiter::NumberSign,
+                ..Default::default()
+            })
+            .unwrap(),
+        "a = 9 # Inline comment.\nb = true"
+    );
+
+    // Inline comments are removed with their keys, or by setting the value without the comment.
+    let root = config.root_mut();
+    assert!(root.set_with_inline_comment(nestr!("a"), 7, None));
+    assert!(root.inline_comment("a").is_none());
+    assert!(root.set_inline_comment(nestr!("b"), "Comment.").is_none());
+    assert!(root.remove("b").is_some());
+    assert!(root.inline_comment("b").is_none());
+}
+
+#[test]
+fn numeric_edge_values() {
+    let mut config = DynConfig::new();
+    let root = config.root_mut();
+
+    assert!(!root.set(nestr!("i64_min"), i64::MIN));
+    assert!(!root.set(nestr!("i64_max"), i64::MAX));
+    assert!(!root.set(nestr!("integral_float"), 3.0));
+    assert!(!root.set(nestr!("negative_zero"), -0.0));
+    assert!(!root.set(nestr!("min_positive"), f64::MIN_POSITIVE));
+    assert!(!root.set(nestr!("max"), f64::MAX));
+    assert!(!root.set(nestr!("min"), f64::MIN));
+
+    let config = dyn_config(&config.to_ini_string().unwrap());
+    let root = config.root();
+
+    assert_eq!(root.get_i64("i64_min").unwrap(), i64::MIN);
+    assert_eq!(root.get_i64("i64_max").unwrap(), i64::MAX);
+
+    for (key, value) in [
+        ("integral_float", 3.0),
+        ("negative_zero", -0.0),
+        ("min_positive", f64::MIN_POSITIVE),
+        ("max", f64::MAX),
+        ("min", f64::MIN),
+    ]
+    .iter()
+    {
+        assert_eq!(root.get_val(key).unwrap().get_type(), ValueType::F64);
+        // Compare the bits to check the sign of zero.
+        assert_eq!(root.get_f64(key).unwrap().to_bits(), value.to_bits());
+    }
+}
+
 #[test]
 fn escape() {
     // With escape sequences supported.
@@ -2341,6 +3108,18 @@ fn escape() {
     // Attempt to serialize an escaped character with support for escaped characters disabled.
     let ini = dyn_config("a\\t = 7");
 
+    assert_eq!(
+        ini.to_ini_string_opts(ToIniStringOptions {
+            escape: false,
+            ..Default::default()
+        })
+        .err()
+        .unwrap(),
+        ToIniStringError::InvalidKey('\t')
+    );
+
+    let ini = dyn_config("a = \"b\\tc\"");
+
     assert_eq!(
         ini.to_ini_string_opts(ToIniStringOptions {
             escape: false,
@@ -2351,6 +3130,44 @@ fn escape() {
         ToIniStringError::EscapedCharacterNotAllowed('\t')
     );
 
+    // Unquoted `.ini` special characters in keys / section names must be escaped.
+    let ini = dyn_config("[a\\=b]\nc\\;d = 7");
+
+    assert_eq!(
+        ini.to_ini_string_opts(ToIniStringOptions {
+            escape: false,
+            string_quotes: IniStringQuote::None,
+            ..Default::default()
+        })
+        .err()
+        .unwrap(),
+        ToIniStringError::InvalidSectionName('=')
+    );
+
+    let ini = dyn_config("c\\;d = 7");
+
+    assert_eq!(
+        ini.to_ini_string_opts(ToIniStringOptions {
+            escape: false,
+            string_quotes: IniStringQuote::None,
+            ..Default::default()
+        })
+        .err()
+        .unwrap(),
+        ToIniStringError::InvalidKey(';')
+    );
+
+    // Quoted instead if quotes are allowed.
+    assert_eq!(
+        ini.to_ini_string_opts(ToIniStringOptions {
+            escape: false,
+            string_quotes: IniStringQuote::Double,
+            ..Default::default()
+        })
+        .unwrap(),
+        "\"c;d\" = 7"
+    );
+
     // With escape sequences unsupported.
     assert_eq!(
         DynConfig::from_ini(
@@ -2402,3 +3219,192 @@ fn escape() {
         string
     );
 }
+
+#[test]
+fn bool_tokens() {
+    let ini = r#"a = yes
+b = off
+c = true
+d = "yes"
+e = Yes"#;
+
+    // Default - only `true` / `false`.
+    let config = dyn_config(ini);
+    assert_eq!(config.root().get_string("a").unwrap(), "yes");
+    assert_eq!(config.root().get_string("b").unwrap(), "off");
+    assert!(config.root().get_bool("c").unwrap());
+
+    let config = DynConfig::from_ini(
+        IniParser::new(ini).bool_tokens(IniBoolTokens::YesNo | IniBoolTokens::OnOff),
+    )
+    .unwrap();
+    assert!(config.root().get_bool("a").unwrap());
+    assert!(!config.root().get_bool("b").unwrap());
+    // `true` / `false` not allowed.
+    assert_eq!(config.root().get_string("c").unwrap(), "true");
+    // Quoted values are always strings.
+    assert_eq!(config.root().get_string("d").unwrap(), "yes");
+    // Tokens are case-sensitive.
+    assert_eq!(config.root().get_string("e").unwrap(), "Yes");
+
+    // Unrecognized tokens follow the unquoted string rules.
+    assert_eq!(
+        DynConfig::from_ini(
+            IniParser::new("a = true")
+                .bool_tokens(IniBoolTokens::YesNo)
+                .unquoted_strings(false)
+        )
+        .err()
+        .unwrap(),
+        IniError {
+            line: 1,
+            column: 8,
+            error: IniErrorKind::UnquotedString,
+            path: vec![nestr!("a").into()].into(),
+        }
+    );
+
+    // Booleans are written using the configured tokens.
+    let parser = IniParser::new("a = on\nb = off").bool_tokens(IniBoolTokens::OnOff);
+    let options = parser.to_ini_string_options();
+    let config = DynConfig::from_ini(parser).unwrap();
+    assert_eq!(
+        config.to_ini_string_opts(options).unwrap(),
+        "a = on\nb = off"
+    );
+    assert_eq!(config.to_ini_string().unwrap(), "a = true\nb = false");
+}
+
+#[test]
+fn ini_str_and_value_accessors() {
+    let key = NonEmptyIniStr::new("foo").unwrap();
+    assert_eq!(key.as_str(), "foo");
+    assert_eq!(key.to_string(), "foo");
+    assert!(NonEmptyIniStr::new("").is_none());
+
+    let string = IniStr::new("bar");
+    assert_eq!(string.as_str(), "bar");
+    assert_eq!(string.to_string(), "bar");
+    assert_eq!(IniStr::new(""), IniStr::Empty);
+
+    let value = IniValue::Bool(true);
+    assert_eq!(value.as_bool(), Some(true));
+    assert_eq!(value.as_i64(), None);
+    assert_eq!(value.as_f64(), None);
+    assert_eq!(value.as_str(), None);
+
+    let value = IniValue::I64(7);
+    assert_eq!(value.as_bool(), None);
+    assert_eq!(value.as_i64(), Some(7));
+    assert_eq!(value.as_f64(), Some(7.0));
+    assert_eq!(value.as_str(), None);
+
+    let value = IniValue::F64(7.5);
+    assert_eq!(value.as_i64(), Some(7));
+    assert_eq!(value.as_f64(), Some(7.5));
+
+    let value = IniValue::String(string);
+    assert_eq!(value.as_bool(), None);
+    assert_eq!(value.as_i64(), None);
+    assert_eq!(value.as_str(), Some("bar"));
+}
+
+#[test]
+fn events() {
+    let events = IniParser::new(
+        "a = 7
+[b]
+c = \"d\\te\"
+f = [true, false]
+[b]
+g = 3.5",
+    )
+    .arrays(true)
+    .events()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+
+    assert_eq!(
+        events,
+        vec![
+            IniEvent::KeyValue {
+                key: NonEmptyIniString::Borrowed(nestr!("a")),
+                value: IniEventValue::I64(7),
+                overwrite: false,
+            },
+            IniEvent::SectionStart {
+                section: NonEmptyIniString::Borrowed(nestr!("b")),
+                overwrite: false,
+            },
+            IniEvent::KeyValue {
+                key: NonEmptyIniString::Borrowed(nestr!("c")),
+                value: IniEventValue::String(IniString::Owned(nestr!("d\te").into())),
+                overwrite: false,
+            },
+            IniEvent::ArrayStart {
+                array: NonEmptyIniString::Borrowed(nestr!("f")),
+                overwrite: false,
+            },
+            IniEvent::ArrayValue(IniEventValue::Bool(true)),
+            IniEvent::ArrayValue(IniEventValue::Bool(false)),
+            IniEvent::ArrayEnd(NonEmptyIniString::Borrowed(nestr!("f"))),
+            IniEvent::SectionEnd(NonEmptyIniString::Borrowed(nestr!("b"))),
+            IniEvent::SectionStart {
+                section: NonEmptyIniString::Borrowed(nestr!("b")),
+                overwrite: false,
+            },
+            IniEvent::KeyValue {
+                key: NonEmptyIniString::Borrowed(nestr!("g")),
+                value: IniEventValue::F64(3.5),
+                overwrite: false,
+            },
+            IniEvent::SectionEnd(NonEmptyIniString::Borrowed(nestr!("b"))),
+        ]
+    );
+
+    // Duplicate keys are detected across section instances.
+    let mut events = IniParser::new(
+        "[a]
+b = 7
+[a]
+b = 9",
+    )
+    .events();
+
+    assert_eq!(
+        events.next().unwrap().unwrap(),
+        IniEvent::SectionStart {
+            section: NonEmptyIniString::Borrowed(nestr!("a")),
+            overwrite: false,
+        }
+    );
+    assert!(events.next().unwrap().is_ok());
+    assert!(events.next().unwrap().is_ok());
+    assert!(events.next().unwrap().is_ok());
+    let error = events.next().unwrap().err().unwrap();
+    assert_eq!(error.line, 4);
+    assert_eq!(error.error, IniErrorKind::DuplicateKey);
+    assert_eq!(
+        error.path,
+        ConfigPath(vec![nestr!("a").into(), nestr!("b").into()])
+    );
+    assert!(events.next().is_none());
+
+    // Duplicate keys are appended to arrays.
+    let events = IniParser::new(
+        "a = 7
+a = 9",
+    )
+    .duplicate_keys(IniDuplicateKeys::Array)
+    .events()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+
+    assert_eq!(
+        events[1],
+        IniEvent::AppendValue {
+            key: NonEmptyIniString::Borrowed(nestr!("a")),
+            value: IniEventValue::I64(9),
+        }
+    );
+}
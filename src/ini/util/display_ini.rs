@@ -1,5 +1,6 @@
 use {
     crate::{
+        ini::StringQuote,
         util::{write_char, WriteCharError},
         *,
     },
@@ -38,14 +39,20 @@ where
 
         Ok(match self {
             Value::Bool(value) => {
-                write!(writer, "{}", if *value { "true" } else { "false" })?;
+                write!(writer, "{}", options.bool_tokens.token(*value))?;
             }
             Value::I64(value) => write!(writer, "{}", value)?,
-            Value::F64(value) => write!(writer, "{}", value)?,
+            Value::U64(value) => write!(writer, "{}", value)?,
+            // `Debug` always writes the decimal point / exponent, unlike `Display`,
+            // so that integral floats (and `-0.0`) are not parsed back as integers.
+            Value::F64(value) => write!(writer, "{:?}", value)?,
             Value::String(value) => {
-                write!(writer, "\"")?;
-                write_ini_string(writer, value.as_ref(), true, options.escape)?;
-                write!(writer, "\"")?;
+                write_ini_quoted_string(
+                    writer,
+                    value.as_ref(),
+                    options.string_quote(),
+                    options.escape,
+                )?;
             }
             Value::Table(value) => {
                 if array {
@@ -69,22 +76,57 @@ where
 /// Writes the `string` to the writer `w`.
 /// If `escape` is `true`, escapes special characters
 /// ('\\', '\0', '\a', '\b', '\t', '\n', '\r', '\v', '\f'),
-/// double quotes ('"'),
-/// and, if `quoted` is `false`, single quotes ('\'') and spaces (' ');
+/// double quotes ('"') (unless the string is single-quoted),
+/// and, if `quote` is `None`, single quotes ('\'') and spaces (' ');
 /// and `.ini` special characters ('[', ']', ';', '#', '=', ':').
-/// If `quoted` is `true`, single quotes ('\'') are not escaped.
+/// If `quote` is double quotes, single quotes ('\'') are not escaped.
 /// If `escape` is `false` and and the `string` contains a character which must be escaped, returns an error.
 pub(crate) fn write_ini_string<W: Write>(
     w: &mut W,
     string: &str,
-    quoted: bool,
+    quote: Option<StringQuote>,
     escape: bool,
 ) -> Result<(), ToIniStringError> {
     for c in string.chars() {
-        write_char(w, c, true, quoted, escape).map_err(|err| match err {
-            WriteCharError::WriteError => ToIniStringError::WriteError,
-            WriteCharError::EscapedCharacter(c) => ToIniStringError::EscapedCharacterNotAllowed(c),
-        })?;
+        match (c, quote) {
+            // Double quotes don't need to be escaped in single-quoted strings.
+            ('"', Some(StringQuote::Single)) => w.write_char(c)?,
+            // Single quotes must be escaped in single-quoted strings.
+            ('\'', Some(StringQuote::Single)) => {
+                if escape {
+                    w.write_str(r#"\'"#)?;
+                } else {
+                    return Err(ToIniStringError::EscapedCharacterNotAllowed(c));
+                }
+            }
+            _ => write_char(w, c, true, quote.is_some(), escape).map_err(|err| match err {
+                WriteCharError::WriteError => ToIniStringError::WriteError,
+                WriteCharError::EscapedCharacter(c) => {
+                    ToIniStringError::EscapedCharacterNotAllowed(c)
+                }
+            })?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the `string` to the writer `w`, enclosing it in `quote`'s, if any.
+/// See [`write_ini_string`].
+fn write_ini_quoted_string<W: Write>(
+    w: &mut W,
+    string: &str,
+    quote: Option<StringQuote>,
+    escape: bool,
+) -> Result<(), ToIniStringError> {
+    if let Some(quote) = quote {
+        w.write_char(quote.as_char())?;
+    }
+
+    write_ini_string(w, string, quote, escape)?;
+
+    if let Some(quote) = quote {
+        w.write_char(quote.as_char())?;
     }
 
     Ok(())
@@ -122,13 +164,16 @@ fn string_needs_quotes(string: &str, escape_nested_section_separators: bool) ->
 /// `.ini` special characters ('[', ']', ';', '#', '=', ':'),
 /// spaces (' '),
 /// or if `nested_sections` is `true`, nested section separators ('/'),
-/// they are additionally enclosed in double quotes ('"').
+/// they are additionally enclosed in `quote`'s, if any, or escaped otherwise.
+/// Returns an [`InvalidSectionName`](enum.ToIniStringError.html#variant.InvalidSectionName) error
+/// if the sections in `path` cannot be written unambiguously.
 #[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
 fn write_ini_sections<W: Write>(
     w: &mut W,
     path: &IniPath,
     escape: bool,
     nested_sections: bool,
+    quote: Option<StringQuote>,
 ) -> Result<(), ToIniStringError> {
     debug_assert!(!path.is_empty());
     let num_sections = path.len();
@@ -139,16 +184,34 @@ fn write_ini_sections<W: Write>(
     for (index, section) in path.iter().enumerate() {
         let last = (index as u32) == (num_sections - 1);
 
-        let needs_quotes = string_needs_quotes(section.as_ne_str(), nested_sections);
+        let quote = if string_needs_quotes(section.as_ne_str(), nested_sections) {
+            quote
+        } else {
+            None
+        };
 
-        if needs_quotes {
-            write!(w, "\"")?;
-        }
-
-        write_ini_string(w, section.as_ne_str(), needs_quotes, escape)?;
+        let map_err = |err| match err {
+            ToIniStringError::EscapedCharacterNotAllowed(c) => {
+                ToIniStringError::InvalidSectionName(c)
+            }
+            err => err,
+        };
+
+        if nested_sections && quote.is_none() {
+            // Nested section separators must be escaped in unquoted section names.
+            for (part_index, part) in section.as_ne_str().split('/').enumerate() {
+                if part_index > 0 {
+                    if escape {
+                        w.write_str(r#"\/"#)?;
+                    } else {
+                        return Err(ToIniStringError::InvalidSectionName('/'));
+                    }
+                }
 
-        if needs_quotes {
-            write!(w, "\"")?;
+                write_ini_string(w, part, None, escape).map_err(map_err)?;
+            }
+        } else {
+            write_ini_quoted_string(w, section.as_ne_str(), quote, escape).map_err(map_err)?;
         }
 
         if !last {
@@ -166,6 +229,8 @@ fn write_ini_sections<W: Write>(
 pub(crate) fn write_ini_array<W: Write, A: Iterator<Item = I>, I: Borrow<V>, V: DisplayIni>(
     w: &mut W,
     key: &NonEmptyStr,
+    comment: Option<&str>,
+    inline_comment: Option<&str>,
     array: A,
     array_len: usize,
     last: bool,
@@ -176,9 +241,11 @@ pub(crate) fn write_ini_array<W: Write, A: Iterator<Item = I>, I: Borrow<V>, V:
     use ToIniStringError::*;
 
     if options.arrays {
-        write_ini_key(w, key, options.escape)?;
+        write_ini_comment(w, comment, options)?;
+
+        write_ini_key(w, key, options)?;
 
-        write!(w, " = [")?;
+        write!(w, " {} [", options.key_value_separator_char())?;
 
         for (array_index, array_value) in array.enumerate() {
             let last = array_index == array_len - 1;
@@ -194,6 +261,8 @@ pub(crate) fn write_ini_array<W: Write, A: Iterator<Item = I>, I: Borrow<V>, V:
 
         write!(w, "]")?;
 
+        write_ini_inline_comment(w, inline_comment, options)?;
+
         if !last {
             writeln!(w)?;
         }
@@ -208,6 +277,7 @@ pub(crate) fn write_ini_array<W: Write, A: Iterator<Item = I>, I: Borrow<V>, V:
 pub(crate) fn write_ini_table<W: Write, V: DisplayIni>(
     w: &mut W,
     key: &NonEmptyStr,
+    comment: Option<&str>,
     key_index: u32,
     table: &V,
     table_len: u32,
@@ -227,10 +297,18 @@ pub(crate) fn write_ini_table<W: Write, V: DisplayIni>(
         writeln!(w)?;
     }
 
+    write_ini_comment(w, comment, options)?;
+
     path.push(NonEmptyIniStr::Owned(key));
 
     if has_non_tables || !options.implicit_parent_sections || (table_len == 0) {
-        write_ini_sections(w, path, options.escape, options.nested_sections())?;
+        write_ini_sections(
+            w,
+            path,
+            options.escape,
+            options.nested_sections(),
+            options.string_quote(),
+        )?;
 
         if table_len > 0 {
             writeln!(w)?;
@@ -258,6 +336,8 @@ pub(crate) fn write_ini_table<W: Write, V: DisplayIni>(
 pub(crate) fn write_ini_value<W: Write, V: DisplayIni>(
     w: &mut W,
     key: &NonEmptyStr,
+    comment: Option<&str>,
+    inline_comment: Option<&str>,
     value: &V,
     last: bool,
     level: u32,
@@ -265,12 +345,16 @@ pub(crate) fn write_ini_value<W: Write, V: DisplayIni>(
     path: &mut IniPath,
     options: ToIniStringOptions,
 ) -> Result<(), ToIniStringError> {
-    write_ini_key(w, key, options.escape)?;
+    write_ini_comment(w, comment, options)?;
+
+    write_ini_key(w, key, options)?;
 
-    write!(w, " = ")?;
+    write!(w, " {} ", options.key_value_separator_char())?;
 
     value.fmt_ini(w, level + 1, array, path, options)?;
 
+    write_ini_inline_comment(w, inline_comment, options)?;
+
     if !last {
         writeln!(w)?;
     }
@@ -283,23 +367,64 @@ pub(crate) fn write_ini_value<W: Write, V: DisplayIni>(
 /// ('\\', '\0', '\a', '\b', '\t', '\n', '\v', '\f', '\r'),
 /// string quotes ('\'', '"'),
 /// `.ini` special characters ('[', ']', ';', '#', '=', ':') or spaces (' '),
-/// it is additionally enclosed in double quotes ('"').
+/// it is additionally enclosed in the string quotes specified by the `options`, if any, or escaped otherwise.
+/// Returns an [`InvalidKey`](enum.ToIniStringError.html#variant.InvalidKey) error
+/// if the `key` cannot be written unambiguously under the `options`.
 #[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
 fn write_ini_key<W: Write>(
     w: &mut W,
     key: &NonEmptyStr,
-    escape: bool,
+    options: ToIniStringOptions,
 ) -> Result<(), ToIniStringError> {
-    let needs_quotes = string_needs_quotes(key.as_ref(), false);
+    let quote = if string_needs_quotes(key.as_ref(), false) {
+        options.string_quote()
+    } else {
+        None
+    };
+
+    write_ini_quoted_string(w, key.as_ref(), quote, options.escape).map_err(|err| match err {
+        ToIniStringError::EscapedCharacterNotAllowed(c) => ToIniStringError::InvalidKey(c),
+        err => err,
+    })
+}
 
-    if needs_quotes {
-        write!(w, "\"")?;
+/// Writes the (possibly multi-line) `comment`, if any, to the writer `w`,
+/// one `.ini` comment line per line, using the comment delimiter specified by the `options`.
+/// Does nothing if the `options` do not specify a comment delimiter.
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+fn write_ini_comment<W: Write>(
+    w: &mut W,
+    comment: Option<&str>,
+    options: ToIniStringOptions,
+) -> Result<(), ToIniStringError> {
+    if let (Some(comment), Some(delimiter)) = (comment, options.comment_delimiter_char()) {
+        for line in comment.lines() {
+            if line.is_empty() {
+                writeln!(w, "{}", delimiter)?;
+            } else {
+                writeln!(w, "{} {}", delimiter, line)?;
+            }
+        }
     }
 
-    write_ini_string(w, key.as_ref(), needs_quotes, escape)?;
+    Ok(())
+}
 
-    if needs_quotes {
-        write!(w, "\"")?;
+/// Writes the inline `comment`, if any, to the writer `w` after the value on the same line,
+/// using the comment delimiter specified by the `options`; line breaks in the `comment` are written as spaces.
+/// Does nothing if the `options` do not specify a comment delimiter.
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+fn write_ini_inline_comment<W: Write>(
+    w: &mut W,
+    comment: Option<&str>,
+    options: ToIniStringOptions,
+) -> Result<(), ToIniStringError> {
+    if let (Some(comment), Some(delimiter)) = (comment, options.comment_delimiter_char()) {
+        write!(w, " {}", delimiter)?;
+
+        for line in comment.lines() {
+            write!(w, " {}", line)?;
+        }
     }
 
     Ok(())
@@ -1,4 +1,7 @@
-use ministr::{NonEmptyStr, NonEmptyString};
+use {
+    ministr::{NonEmptyStr, NonEmptyString},
+    std::fmt::{Display, Formatter},
+};
 
 /// Type for (maybe empty) string values returned by the [`.ini parser`](struct.IniParser.html).
 /// If not empty, either borrowed directly from the `.ini` source, if possible,
@@ -15,6 +18,14 @@ pub enum IniStr<'s, 'a> {
 }
 
 impl<'s, 'a> IniStr<'s, 'a> {
+    /// Creates a new [`IniStr`](enum.IniStr.html) borrowed from the `string`.
+    pub fn new(string: &'s str) -> Self {
+        NonEmptyStr::new(string)
+            .map(IniStr::Borrowed)
+            .unwrap_or(IniStr::Empty)
+    }
+
+    /// Returns the (maybe empty) string slice.
     pub fn as_str(&self) -> &str {
         match self {
             IniStr::Borrowed(_str) => _str.as_str(),
@@ -36,6 +47,12 @@ impl<'s, 'a> Into<String> for IniStr<'s, 'a> {
     }
 }
 
+impl<'s, 'a> Display for IniStr<'s, 'a> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
 /// Type for non-empty string keys / section names returned by the `.ini` parser.
 /// Either borrowed directly from the `.ini` source, if possible,
 /// or otherwise contained in a temporary helper buffer in the parser.
@@ -49,6 +66,13 @@ pub enum NonEmptyIniStr<'s, 'a> {
 }
 
 impl<'s, 'a> NonEmptyIniStr<'s, 'a> {
+    /// Creates a new [`NonEmptyIniStr`](enum.NonEmptyIniStr.html) borrowed from the `string`.
+    /// Returns `None` if the `string` is empty.
+    pub fn new(string: &'s str) -> Option<Self> {
+        NonEmptyStr::new(string).map(NonEmptyIniStr::Borrowed)
+    }
+
+    /// Returns the non-empty string slice.
     pub fn as_ne_str(&self) -> &NonEmptyStr {
         match self {
             NonEmptyIniStr::Borrowed(_str) => _str,
@@ -56,6 +80,7 @@ impl<'s, 'a> NonEmptyIniStr<'s, 'a> {
         }
     }
 
+    /// Returns the (non-empty) string slice.
     pub fn as_str(&self) -> &str {
         self.as_ne_str().as_str()
     }
@@ -84,3 +109,116 @@ impl<'s, 'a> Into<String> for NonEmptyIniStr<'s, 'a> {
         self.as_ne_str().into()
     }
 }
+
+impl<'s, 'a> Display for NonEmptyIniStr<'s, 'a> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+/// Type for (maybe empty) string values which outlive the [`.ini parser`](struct.IniParser.html)'s temporary helper buffers.
+/// If not empty, either borrowed directly from the `.ini` source, if possible,
+/// or owned.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum IniString<'s> {
+    /// The (non-empty) string is borrowed directly from the `.ini` source.
+    Borrowed(&'s NonEmptyStr),
+    /// The (non-empty) string is owned
+    /// (i.e. the parsed string contained at least one escape sequence and thus could not be borrowed directly).
+    Owned(NonEmptyString),
+    /// The string is empty.
+    Empty,
+}
+
+impl<'s> IniString<'s> {
+    pub fn as_str(&self) -> &str {
+        match self {
+            IniString::Borrowed(_str) => _str.as_str(),
+            IniString::Owned(_str) => _str.as_str(),
+            IniString::Empty => "",
+        }
+    }
+}
+
+impl<'s> AsRef<str> for IniString<'s> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'s> From<IniString<'s>> for String {
+    fn from(string: IniString<'s>) -> Self {
+        string.as_str().into()
+    }
+}
+
+impl<'s, 'a> From<IniStr<'s, 'a>> for IniString<'s> {
+    fn from(string: IniStr<'s, 'a>) -> Self {
+        match string {
+            IniStr::Borrowed(_str) => IniString::Borrowed(_str),
+            IniStr::Owned(_str) => IniString::Owned(_str.into()),
+            IniStr::Empty => IniString::Empty,
+        }
+    }
+}
+
+/// Type for non-empty string keys / section names which outlive the `.ini` parser's temporary helper buffers.
+/// Either borrowed directly from the `.ini` source, if possible,
+/// or owned.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum NonEmptyIniString<'s> {
+    /// The (non-empty) string is borrowed directly from the `.ini` source.
+    Borrowed(&'s NonEmptyStr),
+    /// The (non-empty) string is owned
+    /// (i.e. the parsed string contained at least one escape sequence and thus could not be borrowed directly).
+    Owned(NonEmptyString),
+}
+
+impl<'s> NonEmptyIniString<'s> {
+    pub fn as_ne_str(&self) -> &NonEmptyStr {
+        match self {
+            NonEmptyIniString::Borrowed(_str) => _str,
+            NonEmptyIniString::Owned(_str) => _str.as_ne_str(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.as_ne_str().as_str()
+    }
+}
+
+impl<'s> AsRef<NonEmptyStr> for NonEmptyIniString<'s> {
+    fn as_ref(&self) -> &NonEmptyStr {
+        self.as_ne_str()
+    }
+}
+
+impl<'s> AsRef<str> for NonEmptyIniString<'s> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'s> From<NonEmptyIniString<'s>> for NonEmptyString {
+    fn from(string: NonEmptyIniString<'s>) -> Self {
+        match string {
+            NonEmptyIniString::Borrowed(_str) => _str.into(),
+            NonEmptyIniString::Owned(_str) => _str,
+        }
+    }
+}
+
+impl<'s> From<NonEmptyIniString<'s>> for String {
+    fn from(string: NonEmptyIniString<'s>) -> Self {
+        string.as_ne_str().into()
+    }
+}
+
+impl<'s, 'a> From<NonEmptyIniStr<'s, 'a>> for NonEmptyIniString<'s> {
+    fn from(string: NonEmptyIniStr<'s, 'a>) -> Self {
+        match string {
+            NonEmptyIniStr::Borrowed(_str) => NonEmptyIniString::Borrowed(_str),
+            NonEmptyIniStr::Owned(_str) => NonEmptyIniString::Owned(_str.into()),
+        }
+    }
+}
@@ -35,14 +35,17 @@ impl ParsedIniString {
 
     /// Pushes the char `c` at byte index `i` in the source string to this string.
     /// Copies `c` to the inner buffer if it's an onwed string,
-    /// or updates (increments) the byte range with `i` if it's a cleared or borrowed string.
+    /// or extends the byte range to the last byte of `c` if it's a cleared or borrowed string.
     fn push(&mut self, c: char, i: usize) {
         use ParsedIniStringKind::*;
 
+        // (Inclusive) index of the last byte of the (possibly multi-byte) `c`.
+        let last = i + c.len_utf8() - 1;
+
         match &mut self.kind {
             Cleared => {
                 debug_assert!(self.buffer.is_empty());
-                self.kind = Borrowed((i, i));
+                self.kind = Borrowed((i, last));
             }
             Borrowed(range) => {
                 debug_assert!(self.buffer.is_empty());
@@ -54,7 +57,7 @@ impl ParsedIniString {
                     i == range.1 + 1,
                     "byte ranges for borrowed strings must be contiguous"
                 );
-                range.1 = i;
+                range.1 = last;
             }
             Owned => {
                 debug_assert!(!self.buffer.is_empty());
@@ -11,6 +11,54 @@ pub enum IniValue<'s, 'a> {
 }
 
 impl<'s, 'a> IniValue<'s, 'a> {
+    /// Extracts the [`bool`] value from the `.ini` value.
+    /// Returns `None` if the value is not a [`bool`].
+    ///
+    /// [`bool`]: #variant.Bool
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            IniValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Extracts the [`i64`] value from the `.ini` value.
+    /// Returns `None` if the value is not an [`i64`] / [`f64`].
+    ///
+    /// [`i64`]: #variant.I64
+    /// [`f64`]: #variant.F64
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            IniValue::I64(value) => Some(*value),
+            IniValue::F64(value) => Some(*value as i64),
+            _ => None,
+        }
+    }
+
+    /// Extracts the [`f64`] value from the `.ini` value.
+    /// Returns `None` if the value is not an [`f64`] / [`i64`].
+    ///
+    /// [`i64`]: #variant.I64
+    /// [`f64`]: #variant.F64
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            IniValue::I64(value) => Some(*value as f64),
+            IniValue::F64(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Extracts the [`string`] value from the `.ini` value.
+    /// Returns `None` if the value is not a [`string`].
+    ///
+    /// [`string`]: #variant.String
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            IniValue::String(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
     pub(crate) fn get_ini_type(&self) -> IniValueType {
         match self {
             IniValue::Bool(_) => IniValueType::Bool,
@@ -0,0 +1,174 @@
+use {
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// An actual concrete error kind returned by the JSON config [`parser`].
+///
+/// [`parser`]: struct.DynConfig.html#method.from_json
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum JSONErrorKind {
+    /// Unexpected end of file.
+    UnexpectedEndOfFile,
+    /// Unexpected character encountered.
+    /// Contains the unexpected character.
+    UnexpectedCharacter(char),
+    /// The root JSON value must be an object.
+    RootNotAnObject,
+    /// Empty object keys are invalid.
+    EmptyKey,
+    /// Duplicate object key encountered.
+    DuplicateKey,
+    /// Invalid (malformed) number.
+    InvalidNumber,
+    /// Invalid (unescaped control) character in a string.
+    /// Contains the invalid character.
+    InvalidCharacterInString(char),
+    /// Invalid character in an escape sequence.
+    /// Contains the invalid character.
+    InvalidEscapeCharacter(char),
+    /// Invalid Unicode escape sequence (`\uXXXX`) or surrogate pair.
+    InvalidUnicodeEscapeSequence,
+    /// JSON `null` values are not supported by the config.
+    NullValue,
+    /// Mixed value types in an array - all array elements must have the same type.
+    MixedArray,
+    /// Nested objects / arrays exceed the maximum supported depth.
+    ///
+    /// See [`max_depth`](struct.JSONOptions.html#structfield.max_depth).
+    DepthExceeded,
+}
+
+impl Error for JSONErrorKind {}
+
+impl Display for JSONErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use JSONErrorKind::*;
+
+        match self {
+            UnexpectedEndOfFile => "unexpected end of file".fmt(f),
+            UnexpectedCharacter(c) => {
+                write!(f, "unexpected character ('{}')", c.escape_debug())
+            }
+            RootNotAnObject => "root JSON value must be an object".fmt(f),
+            EmptyKey => "empty object keys are invalid".fmt(f),
+            DuplicateKey => "duplicate object key".fmt(f),
+            InvalidNumber => "invalid number".fmt(f),
+            InvalidCharacterInString(c) => {
+                write!(f, "invalid character ('{}') in string", c.escape_debug())
+            }
+            InvalidEscapeCharacter(c) => {
+                write!(f, "invalid escape character ('{}')", c.escape_debug())
+            }
+            InvalidUnicodeEscapeSequence => "invalid Unicode escape sequence".fmt(f),
+            NullValue => "`null` values are not supported".fmt(f),
+            MixedArray => "mixed value types in an array".fmt(f),
+            DepthExceeded => "nested objects / arrays exceed the maximum supported depth".fmt(f),
+        }
+    }
+}
+
+/// An error returned by the JSON config [`parser`].
+///
+/// [`parser`]: struct.DynConfig.html#method.from_json
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct JSONError {
+    /// Line in the source string where the error occured.
+    pub line: u32,
+    /// Column in the source string where the error occured.
+    pub column: u32,
+    /// Path to the key / value in which the error happened, or an empty path for the root object.
+    pub path: ConfigPath,
+    /// Actual error.
+    pub error: JSONErrorKind,
+}
+
+impl Error for JSONError {}
+
+impl Display for JSONError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "JSON parse error; line: {}, column: {}, path: {}, error: {}",
+            self.line, self.column, self.path, self.error
+        )
+    }
+}
+
+/// An error returned by `to_json_string` / `fmt_json` methods on [`dyn`] configs.
+///
+/// [`dyn`]: struct.DynConfig.html#method.to_json_string
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ToJSONStringError {
+    /// Encountered a non-finite (NaN / infinite) float value not allowed by [`options`].
+    /// Contains the path to the value.
+    ///
+    /// [`options`]: enum.JSONNonFiniteFloats.html
+    NonFiniteFloat(ConfigPath),
+    /// Encountered a nested object / array deeper than allowed by [`options`].
+    /// Contains the path to the value.
+    ///
+    /// [`options`]: struct.ToJSONStringOptions.html#structfield.max_depth
+    DepthExceeded(ConfigPath),
+    /// General write error.
+    WriteError,
+}
+
+impl ToJSONStringError {
+    /// Pushes the table key / array index to the back of the path if the error has one.
+    pub(crate) fn push_key<K: Into<OwnedConfigKey>>(mut self, key: K) -> Self {
+        use ToJSONStringError::*;
+
+        match &mut self {
+            NonFiniteFloat(path) | DepthExceeded(path) => path.0.push(key.into()),
+            WriteError => {}
+        };
+
+        self
+    }
+
+    /// Reverses the path if the error has one.
+    /// Must do this because path elements were pushed to the back of the `Vec`
+    /// when unwinding the stack on error.
+    pub(crate) fn reverse(mut self) -> Self {
+        use ToJSONStringError::*;
+
+        match &mut self {
+            NonFiniteFloat(path) | DepthExceeded(path) => path.0.reverse(),
+            WriteError => {}
+        };
+
+        self
+    }
+}
+
+impl From<std::fmt::Error> for ToJSONStringError {
+    fn from(_: std::fmt::Error) -> Self {
+        Self::WriteError
+    }
+}
+
+impl Error for ToJSONStringError {}
+
+impl Display for ToJSONStringError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use ToJSONStringError::*;
+
+        match self {
+            NonFiniteFloat(path) => write!(
+                f,
+                "encountered a non-finite float value not allowed by options at {}",
+                path
+            ),
+            DepthExceeded(path) => write!(
+                f,
+                "encountered a nested object / array deeper than allowed by options at {}",
+                path
+            ),
+            WriteError => "general write error".fmt(f),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+/// Controls how non-finite (NaN / infinite) float values, if any, are serialized to a JSON string.
+///
+/// JSON has no representation for non-finite floats.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum JSONNonFiniteFloats {
+    /// Do not allow non-finite floats (strict JSON).
+    /// Serialization fails with a [`NonFiniteFloat`](enum.ToJSONStringError.html#variant.NonFiniteFloat) error.
+    Forbid,
+    /// Serialize non-finite floats as `null`.
+    /// NOTE - the value type is lost, and `null` is not a valid config value.
+    Null,
+    /// Serialize non-finite floats as non-standard `NaN` / `Infinity` / `-Infinity` literals (as in JSON5).
+    /// NOTE - not all JSON parsers accept these.
+    Literal,
+}
+
+/// Configuration options for serializing a config to a JSON string.
+#[derive(Clone, Copy, Debug)]
+pub struct ToJSONStringOptions {
+    /// Non-finite float value handling policy.
+    ///
+    /// Default: [`Forbid`](enum.JSONNonFiniteFloats.html#variant.Forbid).
+    pub non_finite_floats: JSONNonFiniteFloats,
+    /// Maximum supported depth of nested objects / arrays.
+    /// Values of the root object are at depth `1`.
+    /// Deeper nested objects / arrays cause a [`DepthExceeded`](enum.ToJSONStringError.html#variant.DepthExceeded) error.
+    ///
+    /// Default: `128`.
+    pub max_depth: u32,
+}
+
+impl Default for ToJSONStringOptions {
+    fn default() -> Self {
+        Self {
+            non_finite_floats: JSONNonFiniteFloats::Forbid,
+            max_depth: 128,
+        }
+    }
+}
+
+/// Configuration options for parsing a JSON string to a [`config`](struct.DynConfig.html).
+#[derive(Clone, Copy, Debug)]
+pub struct JSONOptions {
+    /// Maximum supported depth of nested objects / arrays.
+    /// Values of the root object are at depth `1`.
+    /// Deeper nested objects / arrays cause a [`DepthExceeded`](enum.JSONErrorKind.html#variant.DepthExceeded) error.
+    ///
+    /// Protects against stack overflow when parsing JSON strings from untrusted sources.
+    ///
+    /// Default: `128`.
+    pub max_depth: u32,
+    /// If `true`, object members with `null` values are skipped, as if the key was not present.
+    /// Otherwise (and always for `null` array elements) `null` values cause a [`NullValue`](enum.JSONErrorKind.html#variant.NullValue) error,
+    /// as there is no `null` config value.
+    ///
+    /// Default: `false`.
+    pub skip_null_members: bool,
+}
+
+impl Default for JSONOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            skip_null_members: false,
+        }
+    }
+}
@@ -0,0 +1,493 @@
+use {
+    crate::*,
+    std::{iter::Peekable, str::Chars},
+};
+
+/// Parses the JSON config string to a [`dynamic config`].
+///
+/// [`dynamic config`]: struct.DynConfig.html
+pub(crate) struct JSONParser<'s> {
+    /// Source string reader.
+    reader: Chars<'s>,
+    /// Current line in the source string.
+    line: u32,
+    /// Current column in the source string.
+    column: u32,
+    /// Current depth of nested objects / arrays.
+    depth: u32,
+    /// Parsing options as provided by the user.
+    options: JSONOptions,
+}
+
+/// Internal parse result - error kind and the (reversed) path to the element where the error occured.
+type JSONParseResult<T> = Result<T, (JSONErrorKind, ConfigPath)>;
+
+impl<'s> JSONParser<'s> {
+    pub(crate) fn new(string: &'s str, options: JSONOptions) -> Self {
+        Self {
+            reader: string.chars(),
+            line: 1,
+            column: 0,
+            depth: 0,
+            options,
+        }
+    }
+
+    /// Consumes the parser and tries to parse the JSON config string.
+    pub(crate) fn parse(mut self) -> Result<DynConfig, JSONError> {
+        let result = self.parse_root();
+
+        result.map_err(|(error, mut path)| {
+            path.0.reverse();
+
+            JSONError {
+                line: self.line,
+                column: self.column,
+                path,
+                error,
+            }
+        })
+    }
+
+    fn parse_root(&mut self) -> JSONParseResult<DynConfig> {
+        use JSONErrorKind::*;
+
+        self.skip_whitespace();
+
+        let mut config = DynConfig::new();
+
+        match self.next() {
+            Some('{') => {
+                *config.root_mut() = self.parse_object()?;
+            }
+            Some(_) => return Err(Self::error(RootNotAnObject)),
+            None => return Err(Self::error(UnexpectedEndOfFile)),
+        }
+
+        self.skip_whitespace();
+
+        // Only whitespace is allowed after the root object.
+        if let Some(c) = self.next() {
+            return Err(Self::error(UnexpectedCharacter(c)));
+        }
+
+        Ok(config)
+    }
+
+    /// Parses the object after the opening brace (`'{'`).
+    fn parse_object(&mut self) -> JSONParseResult<DynTable> {
+        use JSONErrorKind::*;
+
+        let mut table = DynTable::new();
+
+        self.skip_whitespace();
+
+        if self.peek() == Some('}') {
+            self.next();
+            return Ok(table);
+        }
+
+        loop {
+            self.skip_whitespace();
+
+            match self.next() {
+                Some('"') => {}
+                Some(c) => return Err(Self::error(UnexpectedCharacter(c))),
+                None => return Err(Self::error(UnexpectedEndOfFile)),
+            }
+
+            let key = self.parse_string()?;
+            let key = NonEmptyStr::new(&key).ok_or_else(|| Self::error(EmptyKey))?;
+
+            if table.contains(key.as_str()) {
+                return Err(Self::error(DuplicateKey).push_key(key));
+            }
+
+            self.skip_whitespace();
+            self.expect(':')?;
+            self.skip_whitespace();
+
+            // Skip the `null` member if allowed.
+            if self.options.skip_null_members && self.peek() == Some('n') {
+                self.next();
+                self.expect_literal("ull")
+                    .map_err(|err| err.push_key(key))?;
+            } else {
+                let value = self.parse_value().map_err(|err| err.push_key(key))?;
+
+                table.set(key, value);
+            }
+
+            self.skip_whitespace();
+
+            match self.next() {
+                Some(',') => {}
+                Some('}') => break,
+                Some(c) => return Err(Self::error(UnexpectedCharacter(c))),
+                None => return Err(Self::error(UnexpectedEndOfFile)),
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// Parses the array after the opening bracket (`'['`).
+    fn parse_array(&mut self) -> JSONParseResult<DynArray> {
+        use JSONErrorKind::*;
+
+        let mut array = DynArray::new();
+
+        self.skip_whitespace();
+
+        if self.peek() == Some(']') {
+            self.next();
+            return Ok(array);
+        }
+
+        loop {
+            let index = array.len();
+
+            let value = self.parse_value().map_err(|err| err.push_key(index))?;
+
+            array
+                .push(value)
+                .map_err(|_| Self::error(MixedArray).push_key(index))?;
+
+            self.skip_whitespace();
+
+            match self.next() {
+                Some(',') => {}
+                Some(']') => break,
+                Some(c) => return Err(Self::error(UnexpectedCharacter(c))),
+                None => return Err(Self::error(UnexpectedEndOfFile)),
+            }
+        }
+
+        Ok(array)
+    }
+
+    fn parse_value(&mut self) -> JSONParseResult<DynConfigValue> {
+        use JSONErrorKind::*;
+
+        self.skip_whitespace();
+
+        Ok(match self.next() {
+            Some('{') => Value::Table(self.parse_nested(Self::parse_object)?),
+            Some('[') => Value::Array(self.parse_nested(Self::parse_array)?),
+            Some('"') => Value::String(self.parse_string()?),
+            Some('t') => {
+                self.expect_literal("rue")?;
+                Value::Bool(true)
+            }
+            Some('f') => {
+                self.expect_literal("alse")?;
+                Value::Bool(false)
+            }
+            Some('n') => {
+                self.expect_literal("ull")?;
+                return Err(Self::error(NullValue));
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(c)?,
+            Some(c) => return Err(Self::error(UnexpectedCharacter(c))),
+            None => return Err(Self::error(UnexpectedEndOfFile)),
+        })
+    }
+
+    /// Parses the nested object / array via `parse`,
+    /// unless it exceeds the maximum supported depth.
+    fn parse_nested<T>(
+        &mut self,
+        parse: fn(&mut Self) -> JSONParseResult<T>,
+    ) -> JSONParseResult<T> {
+        if self.depth >= self.options.max_depth {
+            return Err(Self::error(JSONErrorKind::DepthExceeded));
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+
+        result
+    }
+
+    /// Parses the number starting with the `first` character.
+    /// Numbers without a fractional part and an exponent are parsed as integers
+    /// (`i64`, or `u64` if they don't fit into an `i64`), unless they don't fit into a `u64`.
+    fn parse_number(&mut self, first: char) -> JSONParseResult<DynConfigValue> {
+        let mut number = String::new();
+        number.push(first);
+
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E') {
+                self.next();
+                number.push(c);
+            } else {
+                break;
+            }
+        }
+
+        let is_float =
+            validate_number(&number).ok_or_else(|| Self::error(JSONErrorKind::InvalidNumber))?;
+
+        if !is_float {
+            if let Ok(value) = number.parse::<i64>() {
+                return Ok(Value::I64(value));
+            }
+
+            if let Ok(value) = number.parse::<u64>() {
+                return Ok(Value::U64(value));
+            }
+        }
+
+        number
+            .parse::<f64>()
+            .map(Value::F64)
+            .map_err(|_| Self::error(JSONErrorKind::InvalidNumber))
+    }
+
+    /// Parses the string after the opening double quotes (`'"'`).
+    fn parse_string(&mut self) -> JSONParseResult<String> {
+        use JSONErrorKind::*;
+
+        let mut string = String::new();
+
+        loop {
+            match self.next() {
+                Some('"') => break,
+                Some('\\') => string.push(self.parse_escape_sequence()?),
+                Some(c) if (c as u32) < 0x20 => {
+                    return Err(Self::error(InvalidCharacterInString(c)))
+                }
+                Some(c) => string.push(c),
+                None => return Err(Self::error(UnexpectedEndOfFile)),
+            }
+        }
+
+        Ok(string)
+    }
+
+    /// Parses the escape sequence after the backslash (`'\'`).
+    fn parse_escape_sequence(&mut self) -> JSONParseResult<char> {
+        use JSONErrorKind::*;
+
+        Ok(match self.next() {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\x08',
+            Some('f') => '\x0c',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+                let high = self.parse_unicode_escape_hex_digits()?;
+
+                // Surrogate pair - must be followed by the low surrogate.
+                if (0xd800..0xdc00).contains(&high) {
+                    self.expect_literal("\\u")
+                        .map_err(|_| Self::error(InvalidUnicodeEscapeSequence))?;
+
+                    let low = self.parse_unicode_escape_hex_digits()?;
+
+                    if !(0xdc00..0xe000).contains(&low) {
+                        return Err(Self::error(InvalidUnicodeEscapeSequence));
+                    }
+
+                    std::char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))
+                        .ok_or_else(|| Self::error(InvalidUnicodeEscapeSequence))?
+                } else {
+                    std::char::from_u32(high)
+                        .ok_or_else(|| Self::error(InvalidUnicodeEscapeSequence))?
+                }
+            }
+            Some(c) => return Err(Self::error(InvalidEscapeCharacter(c))),
+            None => return Err(Self::error(UnexpectedEndOfFile)),
+        })
+    }
+
+    /// Parses exactly 4 hexadecimal digits of a Unicode escape sequence.
+    fn parse_unicode_escape_hex_digits(&mut self) -> JSONParseResult<u32> {
+        use JSONErrorKind::*;
+
+        let mut result = 0;
+
+        for _ in 0..4 {
+            match self.next() {
+                Some(c) => {
+                    let digit = c
+                        .to_digit(16)
+                        .ok_or_else(|| Self::error(InvalidUnicodeEscapeSequence))?;
+                    result = result * 16 + digit;
+                }
+                None => return Err(Self::error(UnexpectedEndOfFile)),
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn expect(&mut self, expected: char) -> JSONParseResult<()> {
+        use JSONErrorKind::*;
+
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(Self::error(UnexpectedCharacter(c))),
+            None => Err(Self::error(UnexpectedEndOfFile)),
+        }
+    }
+
+    fn expect_literal(&mut self, expected: &str) -> JSONParseResult<()> {
+        for c in expected.chars() {
+            self.expect(c)?;
+        }
+
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if matches!(c, ' ' | '\t' | '\n' | '\r') {
+                self.next();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.reader.clone().next()
+    }
+
+    /// Reads the next character from the source string reader.
+    /// Increments the line/column counters.
+    fn next(&mut self) -> Option<char> {
+        let next = self.reader.next();
+
+        if let Some(c) = next {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 0;
+            } else {
+                self.column += 1;
+            }
+        }
+
+        next
+    }
+
+    /// Error helper method.
+    fn error(error: JSONErrorKind) -> (JSONErrorKind, ConfigPath) {
+        (error, ConfigPath::new())
+    }
+}
+
+trait PushKey {
+    fn push_key<K: Into<OwnedConfigKey>>(self, key: K) -> Self;
+}
+
+impl PushKey for (JSONErrorKind, ConfigPath) {
+    /// Pushes the table key / array index to the back of the path.
+    fn push_key<K: Into<OwnedConfigKey>>(mut self, key: K) -> Self {
+        self.1 .0.push(key.into());
+        self
+    }
+}
+
+/// Validates the `number` string according to the JSON number grammar
+/// (`-? (0 | [1-9][0-9]*) (.[0-9]+)? ([eE][+-]?[0-9]+)?`).
+/// Returns `Some(true)` if the number has a fractional part and / or an exponent,
+/// `Some(false)` if it's an integer, or `None` if the number is invalid.
+fn validate_number(number: &str) -> Option<bool> {
+    fn digits(chars: &mut Peekable<Chars<'_>>) -> usize {
+        let mut num_digits = 0;
+
+        while let Some(c) = chars.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+
+            chars.next();
+            num_digits += 1;
+        }
+
+        num_digits
+    }
+
+    let mut chars = number.chars().peekable();
+    let mut is_float = false;
+
+    // Sign.
+    if chars.peek() == Some(&'-') {
+        chars.next();
+    }
+
+    // Integer part.
+    if chars.peek() == Some(&'0') {
+        chars.next();
+    } else if digits(&mut chars) == 0 {
+        return None;
+    }
+
+    // Fractional part.
+    if chars.peek() == Some(&'.') {
+        chars.next();
+        is_float = true;
+
+        if digits(&mut chars) == 0 {
+            return None;
+        }
+    }
+
+    // Exponent.
+    if matches!(chars.peek(), Some(&'e') | Some(&'E')) {
+        chars.next();
+        is_float = true;
+
+        if matches!(chars.peek(), Some(&'+') | Some(&'-')) {
+            chars.next();
+        }
+
+        if digits(&mut chars) == 0 {
+            return None;
+        }
+    }
+
+    if chars.next().is_some() {
+        None
+    } else {
+        Some(is_float)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_number_test() {
+        assert_eq!(validate_number("0"), Some(false));
+        assert_eq!(validate_number("-0"), Some(false));
+        assert_eq!(validate_number("7"), Some(false));
+        assert_eq!(validate_number("-7"), Some(false));
+        assert_eq!(validate_number("1234"), Some(false));
+
+        assert_eq!(validate_number("0.5"), Some(true));
+        assert_eq!(validate_number("-0.5"), Some(true));
+        assert_eq!(validate_number("3.0"), Some(true));
+        assert_eq!(validate_number("1e5"), Some(true));
+        assert_eq!(validate_number("1E+5"), Some(true));
+        assert_eq!(validate_number("1.5e-5"), Some(true));
+
+        assert!(validate_number("").is_none());
+        assert!(validate_number("-").is_none());
+        assert!(validate_number("+7").is_none());
+        assert!(validate_number("07").is_none());
+        assert!(validate_number("7.").is_none());
+        assert!(validate_number(".7").is_none());
+        assert!(validate_number("7e").is_none());
+        assert!(validate_number("7e+").is_none());
+        assert!(validate_number("7-").is_none());
+        assert!(validate_number("--7").is_none());
+        assert!(validate_number("7.0.0").is_none());
+    }
+}
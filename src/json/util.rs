@@ -0,0 +1,154 @@
+use {crate::*, std::fmt::Write};
+
+/// A trait implemented by configs serializable to a JSON string.
+pub(crate) trait DisplayJSON {
+    fn fmt_json<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToJSONStringOptions,
+    ) -> Result<(), ToJSONStringError>;
+
+    fn do_indent<W: Write>(w: &mut W, indent: u32) -> std::fmt::Result {
+        for _ in 0..indent {
+            w.write_char('\t')?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S, A, T> DisplayJSON for Value<S, A, T>
+where
+    S: AsRef<str>,
+    A: DisplayJSON,
+    T: DisplayJSON,
+{
+    fn fmt_json<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToJSONStringOptions,
+    ) -> Result<(), ToJSONStringError> {
+        match self {
+            Value::Bool(value) => write!(w, "{}", if *value { "true" } else { "false" })?,
+            Value::I64(value) => write!(w, "{}", value)?,
+            Value::U64(value) => write!(w, "{}", value)?,
+            Value::F64(value) => write_json_f64(w, *value, options)?,
+            Value::String(value) => write_json_string(w, value.as_ref())?,
+            Value::Array(_) | Value::Table(_) if indent > options.max_depth => {
+                return Err(ToJSONStringError::DepthExceeded(ConfigPath::new()))
+            }
+            Value::Array(value) => value.fmt_json(w, indent, options)?,
+            Value::Table(value) => value.fmt_json(w, indent, options)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the float `value` to the writer `w`.
+/// Finite values always contain a decimal point or an exponent,
+/// so that they are not confused with integers when parsed back.
+/// Non-finite values are handled according to `options`.
+fn write_json_f64<W: Write>(
+    w: &mut W,
+    value: f64,
+    options: ToJSONStringOptions,
+) -> Result<(), ToJSONStringError> {
+    if value.is_finite() {
+        // `Debug` always writes the decimal point / exponent, unlike `Display`.
+        write!(w, "{:?}", value)?;
+    } else {
+        match options.non_finite_floats {
+            JSONNonFiniteFloats::Forbid => {
+                return Err(ToJSONStringError::NonFiniteFloat(ConfigPath::new()))
+            }
+            JSONNonFiniteFloats::Null => w.write_str("null")?,
+            JSONNonFiniteFloats::Literal => w.write_str(if value.is_nan() {
+                "NaN"
+            } else if value.is_sign_positive() {
+                "Infinity"
+            } else {
+                "-Infinity"
+            })?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the `string` to the writer `w`, enclosing it in double quotes and escaping
+/// double quotes ('"'), backslashes ('\\') and control characters.
+pub(crate) fn write_json_string<W: Write>(w: &mut W, string: &str) -> std::fmt::Result {
+    w.write_char('"')?;
+
+    for c in string.chars() {
+        match c {
+            '"' => w.write_str(r#"\""#)?,
+            '\\' => w.write_str(r#"\\"#)?,
+            '\x08' => w.write_str(r#"\b"#)?,
+            '\x0c' => w.write_str(r#"\f"#)?,
+            '\n' => w.write_str(r#"\n"#)?,
+            '\r' => w.write_str(r#"\r"#)?,
+            '\t' => w.write_str(r#"\t"#)?,
+            c if (c as u32) < 0x20 => write!(w, "\\u{:04x}", c as u32)?,
+            c => w.write_char(c)?,
+        }
+    }
+
+    w.write_char('"')
+}
+
+/// Writes the table `key` / `value` pair to the writer `w`, preceded by indentation.
+/// Pushes the `key` to the error path on error.
+pub(crate) fn write_json_key_value<W: Write, V: DisplayJSON>(
+    w: &mut W,
+    key: &NonEmptyStr,
+    value: &V,
+    last: bool,
+    indent: u32,
+    options: ToJSONStringOptions,
+) -> Result<(), ToJSONStringError> {
+    <V as DisplayJSON>::do_indent(w, indent)?;
+
+    write_json_string(w, key.as_str())?;
+    write!(w, ": ")?;
+
+    value
+        .fmt_json(w, indent, options)
+        .map_err(|err| err.push_key(key))?;
+
+    if !last {
+        write!(w, ",")?;
+    }
+
+    writeln!(w)?;
+
+    Ok(())
+}
+
+/// Writes the array `value` at `index` to the writer `w`, preceded by indentation.
+/// Pushes the `index` to the error path on error.
+pub(crate) fn write_json_array_value<W: Write, V: DisplayJSON>(
+    w: &mut W,
+    index: u32,
+    value: &V,
+    last: bool,
+    indent: u32,
+    options: ToJSONStringOptions,
+) -> Result<(), ToJSONStringError> {
+    <V as DisplayJSON>::do_indent(w, indent)?;
+
+    value
+        .fmt_json(w, indent, options)
+        .map_err(|err| err.push_key(index))?;
+
+    if !last {
+        write!(w, ",")?;
+    }
+
+    writeln!(w)?;
+
+    Ok(())
+}
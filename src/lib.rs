@@ -10,6 +10,21 @@ mod lua_config;
 #[cfg(feature = "ini")]
 mod ini;
 
+#[cfg(feature = "json")]
+mod json;
+
+#[cfg(feature = "yaml")]
+mod yaml;
+
+#[cfg(feature = "toml")]
+mod toml;
+
+#[cfg(feature = "ron")]
+mod ron;
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+
 #[cfg(any(
     feature = "bin",
     feature = "dyn",
@@ -51,6 +66,21 @@ pub use util::*;
 #[cfg(feature = "ini")]
 pub use ini::*;
 
+#[cfg(feature = "json")]
+pub use json::*;
+
+#[cfg(feature = "yaml")]
+pub use yaml::*;
+
+#[cfg(feature = "toml")]
+pub use toml::*;
+
+#[cfg(feature = "ron")]
+pub use ron::*;
+
+#[cfg(feature = "serde")]
+pub use serde_impl::*;
+
 #[cfg(all(feature = "bin", feature = "str_hash"))]
 pub use util::StringAndHash;
 
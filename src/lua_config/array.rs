@@ -36,9 +36,24 @@ impl<'lua> LuaArray<'lua> {
         self.len() == 0
     }
 
+    /// Returns the [`type`] of the [`values`] in the [`array`] (as determined by its first [`value`]),
+    /// or `None` if the [`array`] is empty.
+    ///
+    /// NOTE: numeric values (`i64`, `u64` and `f64`) are compatible,
+    /// so a numeric [`array`] may contain values of other numeric [`types`].
+    ///
+    /// [`type`]: enum.ValueType.html
+    /// [`types`]: enum.ValueType.html
+    /// [`values`]: type.LuaConfigValue.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    pub fn element_type(&self) -> Option<ValueType> {
+        get_array_value_type(&self.0)
+    }
+
     /// Clears the [`array`].
     ///
-    /// [`array`]: struct.DynArray.html
+    /// [`array`]: struct.LuaArray.html
     pub fn clear(&mut self) {
         clear_array(&self.0);
 
@@ -46,6 +61,32 @@ impl<'lua> LuaArray<'lua> {
         set_table_len(&self.0, 0);
     }
 
+    /// Shortens the [`array`], keeping the first `len` [`values`] and removing the rest.
+    ///
+    /// Has no effect if `len` is greater than or equal to the [`array`]'s current length.
+    ///
+    /// [`array`]: struct.LuaArray.html
+    /// [`values`]: type.LuaConfigValue.html
+    pub fn truncate(&mut self, len: u32) {
+        let old_len = self.len();
+
+        if len >= old_len {
+            return;
+        }
+
+        // `+ 1` because of Lua array indexing.
+        for index in (len + 1)..=old_len {
+            let _ = self.0.raw_set(index, rlua::Value::Nil);
+        }
+
+        // If the array is now empty, reset its value type.
+        if len == 0 {
+            set_array_value_type(&self.0, None);
+        }
+
+        set_table_len(&self.0, len);
+    }
+
     /// Tries to get a reference to a [`value`] in the [`array`] at `0`-based `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds.
@@ -194,6 +235,44 @@ impl<'lua> LuaArray<'lua> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`u64`] [`value`] in the [`array`] at `0`-based `index`.
+    ///
+    /// Returns an [`error`] if `index` is out of bounds or if value is not a [`u64`] / [`i64`] / [`f64`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`error`]: enum.ArrayError.html
+    pub fn get_u64(&self, index: u32) -> Result<u64, ArrayError> {
+        self.get(index)
+    }
+
+    /// Tries to get a [`u64`] [`value`] in the [`array`] at `path`.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`] or an [`array`](enum.Value.html#variant.Array) value.
+    /// The last key must correspond to a [`u64`] / [`i64`] / [`f64`] [`value`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`table`]: enum.Value.html#variant.Table
+    pub fn get_u64_path<'k, K, P>(&self, path: P) -> Result<u64, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_path(path)
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`array`] at `0`-based `index`.
     ///
     /// Returns an [`error`] if `index` is out of bounds or if value is not an [`f64`] / [`i64`].
@@ -333,8 +412,75 @@ impl<'lua> LuaArray<'lua> {
     ///
     /// [`values`]: enum.Value.html
     /// [`array`]: struct.LuaArray.html
-    pub fn iter(&self) -> impl Iterator<Item = LuaConfigValue<'lua>> {
-        LuaArrayIter(self.0.clone().sequence_values())
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = LuaConfigValue<'lua>> {
+        LuaArrayIter(self.0.clone().sequence_values(), self.len())
+    }
+
+    /// Tries to convert all [`values`] in the [`array`] to the user-requested type
+    /// [`convertible`](TryFromValue) from a [`value`], and collect them to a `Vec`.
+    ///
+    /// Returns an [`error`] if the [`array`]'s value type is incorrect and incompatible.
+    ///
+    /// [`values`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+    pub fn to_vec<V: TryFromValue<LuaString<'lua>, LuaArray<'lua>, LuaTable<'lua>>>(
+        &self,
+    ) -> Result<Vec<V>, ArrayError> {
+        self.iter()
+            .map(|value| V::try_from(value).map_err(ArrayError::IncorrectValueType))
+            .collect()
+    }
+
+    /// Tries to collect all [`bool`] [`values`] in the [`array`] to a `Vec`.
+    ///
+    /// Returns an [`error`] if the [`array`]'s value type is not [`bool`].
+    ///
+    /// [`bool`]: enum.Value.html#variant.Bool
+    /// [`values`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+    pub fn as_bool_vec(&self) -> Result<Vec<bool>, ArrayError> {
+        self.to_vec()
+    }
+
+    /// Tries to collect all [`i64`] [`values`] in the [`array`] to a `Vec`.
+    ///
+    /// Returns an [`error`] if the [`array`]'s value type is not [`i64`] / [`f64`].
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`values`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn as_i64_vec(&self) -> Result<Vec<i64>, ArrayError> {
+        self.to_vec()
+    }
+
+    /// Tries to collect all [`f64`] [`values`] in the [`array`] to a `Vec`.
+    ///
+    /// Returns an [`error`] if the [`array`]'s value type is not [`f64`] / [`i64`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`values`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+    /// [`i64`]: enum.Value.html#variant.I64
+    pub fn as_f64_vec(&self) -> Result<Vec<f64>, ArrayError> {
+        self.to_vec()
+    }
+
+    /// Tries to collect all [`string`] [`values`] in the [`array`] to a `Vec` of owned `String`'s.
+    ///
+    /// Returns an [`error`] if the [`array`]'s value type is not [`string`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`values`]: type.LuaConfigValue.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`error`]: enum.ArrayError.html#variant.IncorrectValueType
+    pub fn as_string_vec(&self) -> Result<Vec<String>, ArrayError> {
+        self.to_vec()
     }
 
     /// Changes the [`value`] in the [`array`] at `index` to `value`.
@@ -377,6 +523,21 @@ impl<'lua> LuaArray<'lua> {
         self.pop_impl()
     }
 
+    /// Recursively deep-copies this [`array`] to a new owned [`dynamic array`],
+    /// which may outlive the [`Lua context`].
+    ///
+    /// [`array`]: struct.LuaArray.html
+    /// [`dynamic array`]: struct.DynArray.html
+    /// [`Lua context`]: https://docs.rs/rlua/*/rlua/struct.Context.html
+    #[cfg(feature = "dyn")]
+    pub fn to_dyn_array(&self) -> DynArray {
+        let mut result = DynArray::new();
+
+        LuaConfig::array_to_dyn_array(self.clone(), &mut result);
+
+        result
+    }
+
     pub(super) fn from_valid_table(table: rlua::Table<'lua>) -> Self {
         Self(table)
     }
@@ -448,6 +609,7 @@ impl<'lua> LuaArray<'lua> {
                 Value::Bool(value) => array.raw_set(index, value),
                 Value::F64(value) => array.raw_set(index, value),
                 Value::I64(value) => array.raw_set(index, value),
+                Value::U64(value) => array.raw_set(index, lua_value_from_u64(value)),
                 Value::String(value) => array.raw_set(index, value),
                 Value::Array(value) => array.raw_set(index, value.0),
                 Value::Table(value) => array.raw_set(index, value.0),
@@ -538,28 +700,36 @@ impl<'lua> LuaArray<'lua> {
         }
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        writeln!(w, "{{")?;
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        <Self as DisplayLua>::write_lua_open(w, options)?;
+
+        let len = self.len();
 
         // Iterate the array.
         for (index, value) in self.iter().enumerate() {
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+            <Self as DisplayLua>::write_lua_element_start(w, indent, options)?;
 
-            value.fmt_lua(w, indent + 1)?;
+            value
+                .fmt_lua(w, indent + 1, options)
+                .map_err(|err| err.push_key(index as u32))?;
 
-            write!(w, ",")?;
+            <Self as DisplayLua>::write_lua_comma(w, index as u32 + 1 == len, options)?;
 
             let is_array_or_table = matches!(value.get_type(), ValueType::Array | ValueType::Table);
 
-            if is_array_or_table {
+            if is_array_or_table && options.write_key_comments() {
                 write!(w, " -- [{}]", index)?;
             }
 
-            writeln!(w)?;
+            <Self as DisplayLua>::write_lua_element_end(w, options)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
-        write!(w, "}}")?;
+        <Self as DisplayLua>::write_lua_close(w, indent, len == 0, options)?;
 
         Ok(())
     }
@@ -569,13 +739,19 @@ impl<'lua> LuaArray<'lua> {
 ///
 /// [`values`]: type.LuaConfigValue.html
 /// [`array`]: struct.LuaArray.html
-struct LuaArrayIter<'lua>(rlua::TableSequence<'lua, rlua::Value<'lua>>);
+struct LuaArrayIter<'lua>(
+    rlua::TableSequence<'lua, rlua::Value<'lua>>,
+    // Number of remaining values.
+    u32,
+);
 
 impl<'lua> std::iter::Iterator for LuaArrayIter<'lua> {
     type Item = LuaConfigValue<'lua>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(value) = self.0.next() {
+            self.1 = self.1.saturating_sub(1);
+
             if let Ok(value) = value {
                 // Must succeed - the array only contains valid values.
                 Some(unwrap_unchecked(
@@ -590,17 +766,29 @@ impl<'lua> std::iter::Iterator for LuaArrayIter<'lua> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.1 as usize, Some(self.1 as usize))
+    }
 }
 
+impl<'lua> ExactSizeIterator for LuaArrayIter<'lua> {}
+
 impl<'lua> DisplayLua for LuaArray<'lua> {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
 impl<'lua> Display for LuaArray<'lua> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
     }
 }
 
@@ -738,6 +926,66 @@ mod tests {
         });
     }
 
+    #[test]
+    fn truncate() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let mut array = LuaArray::new(lua);
+
+            for value in 1..=3 {
+                array.push(Value::I64(value)).unwrap();
+            }
+
+            array.truncate(7);
+            assert_eq!(array.len(), 3);
+
+            array.truncate(1);
+            assert_eq!(array.as_i64_vec().unwrap(), vec![1]);
+
+            array.truncate(0);
+            assert!(array.is_empty());
+            assert_eq!(array.element_type(), None);
+
+            // The value type was reset.
+            array.push(Value::Bool(true)).unwrap();
+            assert_eq!(array.element_type(), Some(ValueType::Bool));
+        });
+    }
+
+    #[test]
+    fn to_vec() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let mut array = LuaArray::new(lua);
+
+            for value in 1..=3 {
+                array.push(Value::I64(value)).unwrap();
+            }
+
+            assert_eq!(array.as_i64_vec().unwrap(), vec![1, 2, 3]);
+            assert_eq!(array.as_f64_vec().unwrap(), vec![1.0, 2.0, 3.0]);
+            assert_eq!(array.to_vec::<u64>().unwrap(), vec![1, 2, 3]);
+            assert_eq!(
+                array.as_bool_vec().err().unwrap(),
+                ArrayError::IncorrectValueType(ValueType::I64)
+            );
+
+            let mut array = LuaArray::new(lua);
+            array.push(Value::String("foo")).unwrap();
+            array.push(Value::String("bar")).unwrap();
+
+            assert_eq!(array.as_string_vec().unwrap(), vec!["foo", "bar"]);
+            assert_eq!(
+                array.as_f64_vec().err().unwrap(),
+                ArrayError::IncorrectValueType(ValueType::String)
+            );
+
+            assert!(LuaArray::new(lua).as_bool_vec().unwrap().is_empty());
+        });
+    }
+
     #[test]
     fn basic() {
         let lua = rlua::Lua::new();
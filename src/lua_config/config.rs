@@ -5,6 +5,12 @@ use {
     std::{
         fmt::{Display, Formatter, Write},
         num::NonZeroU32,
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        time::Instant,
     },
 };
 
@@ -13,6 +19,11 @@ use crate::util::unwrap_unchecked;
 
 /// Represents a mutable config with a root [`Lua table`] within the [`Lua context`].
 ///
+/// Lua has no unsigned integers, so [`U64`] values are stored as Lua integers if they fit into an `i64`,
+/// otherwise as Lua numbers, rounded to the nearest `f64` (which is exact only for values up to `2^53`).
+/// They are read back as `I64` / `F64` values, which may still be fetched as `u64`'s.
+///
+/// [`U64`]: enum.Value.html#variant.U64
 /// [`Lua table`]: struct.LuaTable.html
 /// [`Lua context`]: https://docs.rs/rlua/*/rlua/struct.Context.html
 #[derive(Clone)]
@@ -27,10 +38,31 @@ impl<'lua> LuaConfig<'lua> {
         LuaConfig(LuaTable::from_valid_table(new_table(lua)))
     }
 
-    /// Creates a new [`Lua config`] from the Lua `script`.
+    /// Creates a new [`Lua config`] from the Lua `script` using default [`options`].
+    ///
+    /// The `script` is executed with an empty environment - it has no access to any globals,
+    /// including the Lua standard library (`os`, `io`, `require` etc.).
+    /// Use [`LuaConfigKey::from_script_limits`] to also limit the script's execution time.
     ///
     /// [`Lua config`]: struct.LuaConfig.html
+    /// [`options`]: struct.LuaConfigOptions.html
+    /// [`LuaConfigKey::from_script_limits`]: struct.LuaConfigKey.html#method.from_script_limits
     pub fn from_script(lua: Context<'lua>, script: &str) -> Result<Self, LuaConfigError> {
+        Self::from_script_opts(lua, script, Default::default())
+    }
+
+    /// Creates a new [`Lua config`] from the Lua `script` using provided [`options`].
+    ///
+    /// See [`from_script`].
+    ///
+    /// [`Lua config`]: struct.LuaConfig.html
+    /// [`options`]: struct.LuaConfigOptions.html
+    /// [`from_script`]: #method.from_script
+    pub fn from_script_opts(
+        lua: Context<'lua>,
+        script: &str,
+        options: LuaConfigOptions,
+    ) -> Result<Self, LuaConfigError> {
         use LuaConfigError::*;
 
         let root = lua.create_table().map_err(LuaScriptError)?;
@@ -47,22 +79,57 @@ impl<'lua> LuaConfig<'lua> {
             .map_err(LuaScriptError)?;
 
         // Must succeed.
-        let root = unwrap_unchecked(
+        let root: rlua::Value<'_> = unwrap_unchecked(
             root.raw_get("root"),
-            "failed to get the Lua config root table from the environment",
+            "failed to get the Lua config root value from the environment",
         );
 
-        Self::from_table(lua, root)
+        // The script must evaluate to a table.
+        match root {
+            rlua::Value::Table(root) => Self::from_table_opts(lua, root, options),
+            root => Err(InvalidRootType(rlua_ext::value_type(&root))),
+        }
     }
 
-    /// Creates a new [`config`] from the Lua `table`.
+    /// Creates a new [`Lua config`] from the Lua script file at `path`.
+    ///
+    /// Reads the file and executes the script as per [`from_script`].
+    /// The returned [`error`] contains the `path`.
+    ///
+    /// [`Lua config`]: struct.LuaConfig.html
+    /// [`from_script`]: #method.from_script
+    /// [`error`]: struct.FileError.html
+    pub fn from_file<P: AsRef<Path>>(
+        lua: Context<'lua>,
+        path: P,
+    ) -> Result<Self, FileError<LuaConfigError>> {
+        let path = path.as_ref();
+        let script = std::fs::read_to_string(path).map_err(|err| FileError::io(path, err))?;
+
+        Self::from_script(lua, &script).map_err(|err| FileError::config(path, err))
+    }
+
+    /// Creates a new [`config`] from the Lua `table` using default [`options`].
     ///
     /// [`config`]: struct.LuaConfig.html
+    /// [`options`]: struct.LuaConfigOptions.html
     pub fn from_table(
         lua: Context<'lua>,
         table: rlua::Table<'lua>,
     ) -> Result<Self, LuaConfigError> {
-        validate_lua_config_table(lua, &table)?;
+        Self::from_table_opts(lua, table, Default::default())
+    }
+
+    /// Creates a new [`config`] from the Lua `table` using provided [`options`].
+    ///
+    /// [`config`]: struct.LuaConfig.html
+    /// [`options`]: struct.LuaConfigOptions.html
+    pub fn from_table_opts(
+        lua: Context<'lua>,
+        table: rlua::Table<'lua>,
+        options: LuaConfigOptions,
+    ) -> Result<Self, LuaConfigError> {
+        validate_lua_config_table(lua, &table, options)?;
 
         Ok(LuaConfig(LuaTable::from_valid_table(table)))
     }
@@ -97,7 +164,7 @@ impl<'lua> LuaConfig<'lua> {
     /// NOTE: you may also call `to_string` via the [`config`]'s `Display` implementation.
     ///
     /// [`config`]: struct.LuaConfig.html
-    pub fn to_lua_string(&self) -> Result<String, std::fmt::Error> {
+    pub fn to_lua_string(&self) -> Result<String, ToLuaStringError> {
         let mut result = String::new();
 
         self.fmt_lua(&mut result)?;
@@ -112,8 +179,10 @@ impl<'lua> LuaConfig<'lua> {
     /// NOTE: you may also use the [`config`]'s `Display` implementation.
     ///
     /// [`config`]: struct.LuaConfig.html
-    pub fn fmt_lua<W: Write>(&self, w: &mut W) -> Result<(), std::fmt::Error> {
-        self.root().fmt_lua(w, 0)
+    pub fn fmt_lua<W: Write>(&self, w: &mut W) -> Result<(), ToLuaStringError> {
+        self.root()
+            .fmt_lua(w, 0, Default::default())
+            .map_err(ToLuaStringError::reverse)
     }
 
     /// Serializes this [`config`] to a [`dynamic config`].
@@ -131,12 +200,17 @@ impl<'lua> LuaConfig<'lua> {
 
     /// Tries to serialize this [`config`] to a [`binary config`].
     ///
+    /// Serialization is deterministic - table keys are written in alphabetical order,
+    /// so equal [`config`]s are serialized to identical data blobs.
+    /// See also [`BinConfig::structurally_eq`](struct.BinConfig.html#method.structurally_eq).
+    ///
+    /// An empty [`config`] is serialized to a minimal [`binary config`] data blob
+    /// which is read back as an empty [`config`].
+    ///
     /// [`config`]: struct.LuaConfig.html
     /// [`binary config`]: struct.BinConfig.html
     #[cfg(feature = "bin")]
     pub fn to_bin_config(&self) -> Result<Box<[u8]>, BinConfigWriterError> {
-        use BinConfigWriterError::*;
-
         let root = self.root();
 
         if let Some(root_len) = NonZeroU32::new(root.len()) {
@@ -146,9 +220,9 @@ impl<'lua> LuaConfig<'lua> {
 
             writer.finish()
 
-        // The root table is empty - nothing to do.
+        // The root table is empty - write just the header.
         } else {
-            Err(EmptyRootTable)
+            BinConfigWriter::empty()
         }
     }
 
@@ -203,7 +277,7 @@ impl<'lua> LuaConfig<'lua> {
     }
 
     #[cfg(feature = "dyn")]
-    fn table_to_dyn_table(table: LuaTable<'_>, dyn_table: &mut DynTable) {
+    pub(super) fn table_to_dyn_table(table: LuaTable<'_>, dyn_table: &mut DynTable) {
         for (key, value) in table.iter() {
             // Must succeed - we don't allow empty keys.
             let key = unwrap_unchecked(
@@ -215,7 +289,7 @@ impl<'lua> LuaConfig<'lua> {
     }
 
     #[cfg(feature = "dyn")]
-    fn array_to_dyn_array(array: LuaArray<'_>, dyn_array: &mut DynArray) {
+    pub(super) fn array_to_dyn_array(array: LuaArray<'_>, dyn_array: &mut DynArray) {
         for value in array.iter() {
             Self::value_to_dyn_array(value, dyn_array);
         }
@@ -232,6 +306,7 @@ impl<'lua> LuaConfig<'lua> {
         let already_existed = match value {
             Bool(value) => dyn_table.set(key, value),
             I64(value) => dyn_table.set(key, value),
+            U64(value) => dyn_table.set(key, DynConfigValue::U64(value)),
             F64(value) => dyn_table.set(key, value),
             String(value) => dyn_table.set(key, value.as_ref()),
             Array(value) => {
@@ -260,10 +335,12 @@ impl<'lua> LuaConfig<'lua> {
     ) {
         use Value::*;
 
-        // Must succeed - we are adding values of the same type to the dyn array.
-        if let Err(_) = match value {
+        // Must succeed - we are adding values of the same type to the dyn array
+        // (array value types are validated when the Lua config is loaded).
+        let result = match value {
             Bool(value) => dyn_array.push(Value::Bool(value)),
             I64(value) => dyn_array.push(Value::I64(value)),
+            U64(value) => dyn_array.push(Value::U64(value)),
             F64(value) => dyn_array.push(Value::F64(value)),
             String(value) => dyn_array.push(Value::String(value.as_ref().to_owned())),
             Array(value) => {
@@ -276,9 +353,9 @@ impl<'lua> LuaConfig<'lua> {
                 Self::table_to_dyn_table(value, &mut table);
                 dyn_array.push(Value::Table(table))
             }
-        } {
-            debug_unreachable!("pushing a value to the array failed")
-        }
+        };
+
+        debug_assert!(result.is_ok(), "pushing a value to the array failed");
     }
 }
 
@@ -328,13 +405,111 @@ impl LuaConfigKey {
         LuaConfig::new(lua).key(lua)
     }
 
-    /// Creates a new [`Lua config`] from the Lua `script`.
+    /// Creates a new [`Lua config`] from the Lua `script` using default [`options`].
     ///
     /// [`Lua config`]: struct.LuaConfigKey.html
+    /// [`options`]: struct.LuaConfigOptions.html
     pub fn from_script(lua: Context<'_>, script: &str) -> Result<Self, LuaConfigError> {
         LuaConfig::from_script(lua, script).map(|config| config.key(lua))
     }
 
+    /// Creates a new [`Lua config`] from the Lua `script` using provided [`options`].
+    ///
+    /// [`Lua config`]: struct.LuaConfigKey.html
+    /// [`options`]: struct.LuaConfigOptions.html
+    pub fn from_script_opts(
+        lua: Context<'_>,
+        script: &str,
+        options: LuaConfigOptions,
+    ) -> Result<Self, LuaConfigError> {
+        LuaConfig::from_script_opts(lua, script, options).map(|config| config.key(lua))
+    }
+
+    /// Creates a new [`Lua config`] from the Lua `script`, executed within the [`Lua state`] `lua`
+    /// subject to the execution `limits`.
+    ///
+    /// Returns a [`Timeout`] error if the `script` exceeds the `limits`.
+    ///
+    /// Allows loading Lua configs from semi-trusted sources.
+    /// Like [`from_script`], the `script` has no access to any globals, including the Lua standard library.
+    ///
+    /// NOTE - replaces any hook previously set on the [`Lua state`] and removes it when finished.
+    ///
+    /// [`Lua config`]: struct.LuaConfigKey.html
+    /// [`Lua state`]: https://docs.rs/rlua/*/rlua/struct.Lua.html
+    /// [`Timeout`]: enum.LuaConfigError.html#variant.Timeout
+    /// [`from_script`]: #method.from_script
+    pub fn from_script_limits(
+        lua: &rlua::Lua,
+        script: &str,
+        limits: LuaConfigLimits,
+    ) -> Result<Self, LuaConfigError> {
+        if limits.is_empty() {
+            return lua.context(|lua| Self::from_script(lua, script));
+        }
+
+        let batch = limits.max_instructions.map_or(
+            LuaConfigLimits::INSTRUCTION_BATCH,
+            |max_instructions| {
+                max_instructions
+                    .get()
+                    .min(LuaConfigLimits::INSTRUCTION_BATCH)
+            },
+        );
+
+        // Set by the hook when the limits are exceeded.
+        // The script might (in theory) swallow the hook error, so we check the flag instead of the error returned by the script.
+        let exceeded = Arc::new(AtomicBool::new(false));
+
+        {
+            let exceeded = exceeded.clone();
+            let start = Instant::now();
+            let mut num_instructions = 0u64;
+
+            lua.set_hook(
+                rlua::HookTriggers {
+                    every_nth_instruction: Some(batch),
+                    ..Default::default()
+                },
+                move |_, _| {
+                    num_instructions += batch as u64;
+
+                    let instructions_exceeded = limits
+                        .max_instructions
+                        .map(|max_instructions| num_instructions >= max_instructions.get() as u64)
+                        .unwrap_or(false);
+                    let timeout_exceeded = limits
+                        .timeout
+                        .map(|timeout| start.elapsed() >= timeout)
+                        .unwrap_or(false);
+
+                    if instructions_exceeded || timeout_exceeded {
+                        exceeded.store(true, Ordering::SeqCst);
+                        Err(rlua::Error::RuntimeError(
+                            "Lua config script execution limits exceeded".to_owned(),
+                        ))
+                    } else {
+                        Ok(())
+                    }
+                },
+            );
+        }
+
+        let result = lua.context(|lua| Self::from_script(lua, script));
+
+        lua.remove_hook();
+
+        if exceeded.load(Ordering::SeqCst) {
+            if let Ok(config) = result {
+                let _ = lua.context(|lua| config.destroy(lua));
+            }
+
+            Err(LuaConfigError::Timeout)
+        } else {
+            result
+        }
+    }
+
     /// Returns the root [`Lua table`] of the config.
     ///
     /// [`Lua table`]: struct.LuaTable.html
@@ -348,24 +523,12 @@ fn table_to_bin_config(
     table: LuaTable<'_>,
     writer: &mut BinConfigWriter,
 ) -> Result<(), BinConfigWriterError> {
-    // Gather the keys.
-    let mut keys: Vec<_> = table.iter().map(|(key, _)| key).collect();
-
-    // Sort the keys in alphabetical order.
-    keys.sort_by(|l, r| l.as_ref().cmp(r.as_ref()));
-
-    // Iterate the table using the sorted keys.
-    for key in keys.into_iter() {
+    // Iterate the table in alphabetical key order.
+    for (key, value) in table.iter_sorted() {
         // Must succeed - we don't allow empty table keys.
         let key_str =
             unwrap_unchecked(NonEmptyStr::new(key.as_ref()), "empty Lua config table key");
 
-        // Must succeed - all keys are valid.
-        let value = unwrap_unchecked(
-            table.get_val(key_str),
-            "failed to get a value from a Lua config table with a valid key",
-        );
-
         value_to_bin_config(Some(key_str), value, writer)?;
     }
 
@@ -400,6 +563,9 @@ fn value_to_bin_config(
         I64(value) => {
             writer.i64(key, value)?;
         }
+        U64(value) => {
+            writer.u64(key, value)?;
+        }
         F64(value) => {
             writer.f64(key, value)?;
         }
@@ -423,7 +589,9 @@ fn value_to_bin_config(
 
 impl<'lua> Display for LuaConfig<'lua> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.root().fmt_lua(f, 0)
+        self.root()
+            .fmt_lua(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
     }
 }
 
@@ -453,6 +621,26 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn LuaConfigError_InvalidRootType() {
+        assert!(matches!(
+            lua_config_error("7"),
+            LuaConfigError::InvalidRootType(LuaValueType::Integer)
+        ));
+        assert!(matches!(
+            lua_config_error(r#""foo""#),
+            LuaConfigError::InvalidRootType(LuaValueType::String)
+        ));
+        assert!(matches!(
+            lua_config_error("nil"),
+            LuaConfigError::InvalidRootType(LuaValueType::Nil)
+        ));
+
+        // But this works.
+
+        lua_config("{}").unwrap();
+    }
+
     #[test]
     fn LuaConfigError_MixedKeys() {
         assert!(matches!(
@@ -556,6 +744,35 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn LuaConfigError_MixedArrayTables() {
+        assert!(matches!(
+            lua_config_error("{ array = { { 1, 2 }, { a = 1 } } }"),
+            LuaConfigError::MixedArrayTables(path) if path == vec![nestr!("array").into(), 1.into()].into()
+        ));
+
+        assert!(matches!(
+            lua_config_error("{ array = { {}, { a = 1 }, { 1, 2 } } }"),
+            LuaConfigError::MixedArrayTables(path) if path == vec![nestr!("array").into(), 2.into()].into()
+        ));
+
+        // But this should work - empty tables are empty arrays in arrays of arrays.
+
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let config = LuaConfig::from_script(lua, "{ array = { {}, { 1, 2 }, {} } }").unwrap();
+            let array = config.root().get_array("array").unwrap();
+
+            assert_eq!(array.len(), 3);
+            assert_eq!(array.get_array(0).unwrap().len(), 0);
+            assert_eq!(array.get_array(1).unwrap().len(), 2);
+            assert_eq!(array.get_array(2).unwrap().len(), 0);
+        });
+
+        lua_config("{ array = { {}, { a = 1 }, {} } }").unwrap();
+    }
+
     #[test]
     fn LuaConfigError_InvalidKeyType() {
         assert!(matches!(
@@ -702,6 +919,245 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn LuaConfigError_Timeout() {
+        let lua = rlua::Lua::new();
+
+        let infinite_loop = r#"(function()
+                while true do end
+                return {}
+            end)()"#;
+
+        let limits = LuaConfigLimits {
+            max_instructions: Some(std::num::NonZeroU32::new(100_000).unwrap()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            LuaConfigKey::from_script_limits(&lua, infinite_loop, limits),
+            Err(LuaConfigError::Timeout)
+        ));
+
+        let limits = LuaConfigLimits {
+            timeout: Some(std::time::Duration::from_millis(10)),
+            ..Default::default()
+        };
+        assert!(matches!(
+            LuaConfigKey::from_script_limits(&lua, infinite_loop, limits),
+            Err(LuaConfigError::Timeout)
+        ));
+
+        // No access to the standard library.
+        assert!(matches!(
+            LuaConfigKey::from_script_limits(&lua, "os.exit()", limits),
+            Err(LuaConfigError::LuaScriptError(_))
+        ));
+
+        // But this works.
+
+        let config = LuaConfigKey::from_script_limits(
+            &lua,
+            r#"(function()
+                local array = {}
+                for i = 1, 10 do
+                    array[i] = i
+                end
+                return { array = array }
+            end)()"#,
+            limits,
+        )
+        .unwrap();
+
+        lua.context(|lua| {
+            let root = config.root(lua).unwrap();
+            assert_eq!(root.get_array("array").unwrap().len(), 10);
+        });
+    }
+
+    #[test]
+    fn LuaConfigError_DepthExceeded() {
+        assert!(matches!(
+            lua_config_error(
+                r#"(function()
+                    local root = {}
+                    local table = root
+                    for i = 1, 10000 do
+                        table.a = {}
+                        table = table.a
+                    end
+                    return root
+                end)()"#,
+            ),
+            LuaConfigError::DepthExceeded(path) if path.0.len() == 129
+        ));
+
+        // Self-referencing table.
+        assert!(matches!(
+            lua_config_error(
+                r#"(function()
+                    local root = {}
+                    root.a = { root }
+                    return root
+                end)()"#,
+            ),
+            LuaConfigError::DepthExceeded(path) if path.0.len() == 129
+        ));
+
+        // But this works.
+
+        assert!(lua_config(
+            r#"(function()
+                local root = {}
+                local table = root
+                for i = 1, 128 do
+                    table.a = {}
+                    table = table.a
+                end
+                return root
+            end)()"#,
+        )
+        .is_ok());
+
+        // The limit is configurable.
+
+        let script = r#"(function()
+            local root = {}
+            local table = root
+            for i = 1, 200 do
+                table.a = {}
+                table = table.a
+            end
+            return root
+        end)()"#;
+
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            assert!(matches!(
+                LuaConfig::from_script(lua, script).err().unwrap(),
+                LuaConfigError::DepthExceeded(path) if path.0.len() == 129
+            ));
+            assert!(matches!(
+                LuaConfig::from_script_opts(lua, script, LuaConfigOptions { max_depth: 16 })
+                    .err()
+                    .unwrap(),
+                LuaConfigError::DepthExceeded(path) if path.0.len() == 17
+            ));
+
+            let config =
+                LuaConfig::from_script_opts(lua, script, LuaConfigOptions { max_depth: 200 })
+                    .unwrap();
+
+            // Serialization is limited separately.
+            assert!(matches!(
+                config.to_lua_string().err().unwrap(),
+                ToLuaStringError::DepthExceeded(path) if path.0.len() == 129
+            ));
+        });
+    }
+
+    #[test]
+    fn from_file() {
+        let path = std::env::temp_dir().join("miniconfig_from_file.lua");
+
+        std::fs::write(&path, "{ foo = 7 }").unwrap();
+
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let config = LuaConfig::from_file(lua, &path).unwrap();
+            assert_eq!(config.root().get_i64("foo").unwrap(), 7);
+
+            std::fs::write(&path, "7").unwrap();
+
+            let error = LuaConfig::from_file(lua, &path).err().unwrap();
+            assert_eq!(error.path, path);
+            assert!(matches!(
+                error.error,
+                FileErrorKind::ConfigError(LuaConfigError::InvalidRootType(LuaValueType::Integer))
+            ));
+
+            std::fs::remove_file(&path).unwrap();
+
+            let error = LuaConfig::from_file(lua, &path).err().unwrap();
+            assert_eq!(error.path, path);
+            assert!(matches!(error.error, FileErrorKind::IOError(_)));
+        });
+    }
+
+    #[test]
+    fn Value_from_rlua() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let from_rlua = |value| LuaConfigValue::from_rlua(lua, value);
+
+            assert!(from_rlua(rlua::Value::Boolean(true))
+                .unwrap()
+                .bool()
+                .unwrap());
+            assert_eq!(
+                from_rlua(rlua::Value::Integer(7)).unwrap().i64().unwrap(),
+                7
+            );
+            assert!(cmp_f64(
+                from_rlua(rlua::Value::Number(2.5)).unwrap().f64().unwrap(),
+                2.5
+            ));
+            assert_eq!(
+                from_rlua(rlua::Value::String(lua.create_string("foo").unwrap()))
+                    .unwrap()
+                    .string()
+                    .unwrap()
+                    .as_str(),
+                "foo"
+            );
+
+            let table = lua.create_table().unwrap();
+            table.set("foo", 7).unwrap();
+
+            let array = lua.create_table().unwrap();
+            array.set(1, "bar").unwrap();
+            array.set(2, "baz").unwrap();
+            table.set("array", array).unwrap();
+
+            let table = from_rlua(rlua::Value::Table(table))
+                .unwrap()
+                .table()
+                .unwrap();
+            assert_eq!(table.len(), 2);
+            assert_eq!(table.get_i64("foo").unwrap(), 7);
+            let array = table.get_array("array").unwrap();
+            assert_eq!(array.len(), 2);
+            assert_eq!(array.get_string(1).unwrap().as_str(), "baz");
+
+            // Unsupported values.
+            assert!(matches!(
+                from_rlua(rlua::Value::Nil).err().unwrap(),
+                ValueFromLuaValueError::KeyDoesNotExist
+            ));
+
+            let function = lua.create_function(|_, ()| Ok(())).unwrap();
+            assert!(matches!(
+                from_rlua(rlua::Value::Function(function)).err().unwrap(),
+                ValueFromLuaValueError::InvalidValueType(LuaValueType::Function)
+            ));
+
+            let string = lua.create_string(&[0xc0u8][..]).unwrap();
+            assert!(matches!(
+                from_rlua(rlua::Value::String(string)).err().unwrap(),
+                ValueFromLuaValueError::InvalidValueUTF8(_)
+            ));
+
+            let table = lua.create_table().unwrap();
+            table.set("foo", 7).unwrap();
+            table.set(1, 9).unwrap();
+            assert!(matches!(
+                from_rlua(rlua::Value::Table(table)).err().unwrap(),
+                ValueFromLuaValueError::InvalidTable(LuaConfigError::MixedKeys(_))
+            ));
+        });
+    }
+
     const SCRIPT: &str = "{
 \tarray_of_tables_value = {
 \t\t{
@@ -746,6 +1202,140 @@ mod tests {
         });
     }
 
+    #[test]
+    fn numeric_edge_values() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let config = LuaConfig::new(lua);
+            let mut root = config.root();
+
+            assert!(!root.set(nestr!("i64_min"), i64::MIN));
+            assert!(!root.set(nestr!("i64_max"), i64::MAX));
+            assert!(!root.set(nestr!("integral_float"), 3.0));
+            assert!(!root.set(nestr!("negative_zero"), -0.0));
+            assert!(!root.set(nestr!("min_positive"), f64::MIN_POSITIVE));
+            assert!(!root.set(nestr!("max"), f64::MAX));
+            assert!(!root.set(nestr!("infinity"), f64::INFINITY));
+            assert!(!root.set(nestr!("nan"), f64::NAN));
+
+            let config = LuaConfig::from_script(lua, &config.to_lua_string().unwrap()).unwrap();
+            let root = config.root();
+
+            assert_eq!(root.get_i64("i64_min").unwrap(), i64::MIN);
+            assert_eq!(root.get_val("i64_min").unwrap().get_type(), ValueType::I64);
+            assert_eq!(root.get_i64("i64_max").unwrap(), i64::MAX);
+
+            for (key, value) in [
+                ("integral_float", 3.0),
+                ("negative_zero", -0.0),
+                ("min_positive", f64::MIN_POSITIVE),
+                ("max", f64::MAX),
+                ("infinity", f64::INFINITY),
+            ]
+            .iter()
+            {
+                assert_eq!(root.get_val(key).unwrap().get_type(), ValueType::F64);
+                // Compare the bits to check the sign of zero.
+                assert_eq!(root.get_f64(key).unwrap().to_bits(), value.to_bits());
+            }
+
+            assert!(root.get_f64("nan").unwrap().is_nan());
+        });
+    }
+
+    #[test]
+    fn integer_boundaries() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let config = LuaConfig::from_script(
+                lua,
+                "{
+                    max = 9223372036854775807,
+                    min = -9223372036854775807 - 1,
+                    wrapped = 9223372036854775807 + 1,
+                    big = 9223372036854775808,
+                    negative_big = -9300000000000000000,
+                    huge = 1e300,
+                }",
+            )
+            .unwrap();
+            let root = config.root();
+
+            // Lua integers are always in `i64` range.
+            assert_eq!(root.get_val("max").unwrap().get_type(), ValueType::I64);
+            assert_eq!(root.get_i64("max").unwrap(), i64::MAX);
+            assert_eq!(root.get_val("min").unwrap().get_type(), ValueType::I64);
+            assert_eq!(root.get_i64("min").unwrap(), i64::MIN);
+
+            // Lua integer arithmetic wraps around.
+            assert_eq!(root.get_val("wrapped").unwrap().get_type(), ValueType::I64);
+            assert_eq!(root.get_i64("wrapped").unwrap(), i64::MIN);
+
+            // Integer literals outside of `i64` range are Lua numbers.
+            for key in ["big", "negative_big", "huge"].iter() {
+                assert_eq!(root.get_val(key).unwrap().get_type(), ValueType::F64);
+                assert_eq!(
+                    root.get_i64_coerced(key).err().unwrap(),
+                    TableError::LossyConversion(ValueType::F64)
+                );
+            }
+
+            assert!(cmp_f64(
+                root.get_f64("big").unwrap(),
+                9_223_372_036_854_775_808.0
+            ));
+            assert_eq!(root.get_u64("big").unwrap(), 1 << 63);
+            assert!(cmp_f64(root.get_f64("negative_big").unwrap(), -9.3e18));
+            assert!(cmp_f64(root.get_f64("huge").unwrap(), 1e300));
+        });
+    }
+
+    #[test]
+    fn string_escapes() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let config = LuaConfig::new(lua);
+            let mut root = config.root();
+
+            let strings = [
+                "\"quoted\"",
+                "back\\slash",
+                "new\nline",
+                "\ttab",
+                "\0\x07\x08\r\x0b\x0c",
+                "\x01\x1b\x7f",
+                "\u{80}\u{9f}",
+                "\\x0c",
+                "]]",
+                "\u{1F600}",
+            ];
+
+            let key = |idx: usize| format!("key_{}", (b'a' + idx as u8) as char);
+
+            for (idx, string) in strings.iter().enumerate() {
+                assert!(!root.set(NonEmptyStr::new(&key(idx)).unwrap(), *string));
+            }
+
+            let script = config.to_lua_string().unwrap();
+
+            // Control characters in strings are always escaped.
+            assert!(!script.contains(|c: char| c.is_control() && c != '\n' && c != '\t'));
+            assert!(script.contains(r#"key_a = "\"quoted\"","#));
+            assert!(script.contains(r#"key_f = "\x01\x1b\x7f","#));
+            assert!(script.contains(r#"key_g = "\u{80}\u{9f}","#));
+
+            let config = LuaConfig::from_script(lua, &script).unwrap();
+            let root = config.root();
+
+            for (idx, string) in strings.iter().enumerate() {
+                assert_eq!(root.get_string(&key(idx)).unwrap().as_ref(), *string);
+            }
+        });
+    }
+
     #[cfg(feature = "bin")]
     #[test]
     fn to_bin_config() {
@@ -803,6 +1393,12 @@ mod tests {
             assert!(cmp_f64(table_value.get_f64("bar".into()).unwrap(), 2020.0));
             assert_eq!(table_value.get_string("baz".into()).unwrap(), "hello");
             assert_eq!(table_value.get_bool("foo".into()).unwrap(), false);
+
+            // Empty config.
+            let bin_data = LuaConfig::new(lua).to_bin_config().unwrap();
+
+            let bin_config = BinConfig::new(bin_data).unwrap();
+            assert!(bin_config.root().is_empty());
         });
     }
 
@@ -7,15 +7,21 @@ use {
     },
 };
 
-/// An error returned by [`LuaConfig::from_script`], [`LuaConfigKey::from_script`].
+/// An error returned by [`LuaConfig::from_script`], [`LuaConfigKey::from_script`], [`LuaConfigKey::from_script_limits`].
 ///
 /// [`LuaConfig::from_script`]: struct.LuaConfig.html#method.from_script
 /// [`LuaConfigKey::from_script`]: struct.LuaConfigKey.html#method.from_script
+/// [`LuaConfigKey::from_script_limits`]: struct.LuaConfigKey.html#method.from_script_limits
 #[derive(Clone, Debug)]
 pub enum LuaConfigError {
     /// Error loading the Lua config script.
     /// Contains the actual Lua error.
     LuaScriptError(rlua::Error),
+    /// The Lua config script must evaluate to a [`table`].
+    /// Contains the actual Lua value type the script evaluated to.
+    ///
+    /// [`table`]: struct.LuaTable.html
+    InvalidRootType(rlua_ext::ValueType),
     /// Mixed string and integer keys are not allowed in Lua config [`tables`].
     /// Contains the path to the [`table`], or an empty path if the mixed keys are in the root [`table`].
     ///
@@ -37,6 +43,12 @@ pub enum LuaConfigError {
         /// [`type`]: enum.ValueType.html
         found: rlua_ext::ValueType,
     },
+    /// Mixed nested [`arrays`] and (non-empty) [`tables`] are not allowed in Lua config [`arrays`].
+    /// Contains the path to the invalid array element.
+    ///
+    /// [`arrays`]: struct.LuaArray.html
+    /// [`tables`]: struct.LuaTable.html
+    MixedArrayTables(ConfigPath),
     /// Only string and number keys are allowed in Lua config [`tables`].
     ///
     /// [`tables`]: struct.LuaTable.html
@@ -92,6 +104,22 @@ pub enum LuaConfigError {
         /// UTF-8 parse error.
         error: rlua::Error,
     },
+    /// The Lua config script exceeded the [`execution limits`]
+    /// passed to [`LuaConfigKey::from_script_limits`] and was aborted.
+    ///
+    /// [`execution limits`]: struct.LuaConfigLimits.html
+    /// [`LuaConfigKey::from_script_limits`]: struct.LuaConfigKey.html#method.from_script_limits
+    Timeout,
+    /// Lua config [`tables`] / [`arrays`] are nested deeper than allowed by [`options`]
+    /// (or a [`table`] contains itself).
+    /// Contains the path to the first [`table`] / [`array`] exceeding the depth.
+    ///
+    /// [`tables`]: struct.LuaTable.html
+    /// [`arrays`]: struct.LuaArray.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`array`]: struct.LuaArray.html
+    /// [`options`]: struct.LuaConfigOptions.html#structfield.max_depth
+    DepthExceeded(ConfigPath),
 }
 
 impl LuaConfigError {
@@ -102,14 +130,16 @@ impl LuaConfigError {
         match &mut self {
             MixedKeys(path) => path.0.push(key),
             MixedArray { path, .. } => path.0.push(key),
+            MixedArrayTables(path) => path.0.push(key),
             InvalidKeyType { path, .. } => path.0.push(key),
             InvalidKeyUTF8 { path, .. } => path.0.push(key),
             EmptyKey(path) => path.0.push(key),
             InvalidArrayIndex(path) => path.0.push(key),
             InvalidValueType { path, .. } => path.0.push(key),
             InvalidValueUTF8 { path, .. } => path.0.push(key),
+            DepthExceeded(path) => path.0.push(key),
 
-            LuaScriptError(_) => {}
+            LuaScriptError(_) | InvalidRootType(_) | Timeout => {}
         };
 
         self
@@ -125,21 +155,33 @@ impl LuaConfigError {
         match &mut self {
             MixedKeys(path) => path.0.reverse(),
             MixedArray { path, .. } => path.0.reverse(),
+            MixedArrayTables(path) => path.0.reverse(),
             InvalidKeyType { path, .. } => path.0.reverse(),
             InvalidKeyUTF8 { path, .. } => path.0.reverse(),
             EmptyKey(path) => path.0.reverse(),
             InvalidArrayIndex(path) => path.0.reverse(),
             InvalidValueType { path, .. } => path.0.reverse(),
             InvalidValueUTF8 { path, .. } => path.0.reverse(),
+            DepthExceeded(path) => path.0.reverse(),
 
-            LuaScriptError(_) => {}
+            LuaScriptError(_) | InvalidRootType(_) | Timeout => {}
         };
 
         self
     }
 }
 
-impl Error for LuaConfigError {}
+impl Error for LuaConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use LuaConfigError::*;
+
+        match self {
+            LuaScriptError(err) => Some(err),
+            InvalidValueUTF8 { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
 
 impl Display for LuaConfigError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
@@ -147,6 +189,7 @@ impl Display for LuaConfigError {
 
         match self {
             LuaScriptError(err) => write!(f, "error loading the Lua config script: {}", err),
+            InvalidRootType(invalid_type) => write!(f, "the Lua config script must evaluate to a table; found: \"{}\"", invalid_type),
             MixedKeys(path) => write!(f, "mixed string and integer keys are not allowed in Lua config table {}", path),
             MixedArray { path, expected, found } =>
                 write!(
@@ -156,16 +199,74 @@ impl Display for LuaConfigError {
                     expected,
                     found,
                 ),
+            MixedArrayTables(path) => write!(f, "mixed arrays and tables are not allowed in Lua config array {}", path),
             InvalidKeyType{ path, invalid_type } => write!(f, "only string and number keys are allowed in Lua config table {}; found: \"{}\"", path, invalid_type),
             InvalidKeyUTF8{ path, error } => write!(f, "invalid string key UTF-8 in Lua config table {}: {}", path, error),
             EmptyKey(path) => write!(f, "empty key strings are not allowed in Lua config table {}", path),
             InvalidArrayIndex(path) => write!(f, "invalid index in Lua config array {}", path),
             InvalidValueType{ path, invalid_type } => write!(f, "invalid Lua value type (\"{}\") for a Lua config value at {}", invalid_type, path),
             InvalidValueUTF8{ path, error } => write!(f, "invalid string value UTF-8 at {}: {}", path, error),
+            Timeout => "the Lua config script exceeded the execution limits".fmt(f),
+            DepthExceeded(path) => write!(f, "Lua config tables / arrays nested deeper than allowed by options at {}", path),
+        }
+    }
+}
+
+/// An error returned by [`Value::from_rlua`].
+///
+/// [`Value::from_rlua`]: enum.Value.html#method.from_rlua
+#[derive(Clone, Debug)]
+pub enum ValueFromLuaValueError {
+    /// The Lua value is `nil` (e.g. the Lua table key does not exist).
+    KeyDoesNotExist,
+    /// Lua value type is not supported by the config -
+    /// only booleans, integers, numbers, strings and tables are supported
+    /// (i.e. not functions, userdata, light userdata, threads or errors).
+    /// Contains the actual Lua value type.
+    InvalidValueType(rlua_ext::ValueType),
+    /// Lua string value is not valid UTF-8.
+    /// Contains the actual UTF-8 error.
+    InvalidValueUTF8(rlua::Error),
+    /// Lua table value is not a valid Lua config [`table`] / [`array`].
+    /// Contains the actual Lua config validation error.
+    ///
+    /// [`table`]: struct.LuaTable.html
+    /// [`array`]: struct.LuaArray.html
+    InvalidTable(LuaConfigError),
+}
+
+impl Error for ValueFromLuaValueError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use ValueFromLuaValueError::*;
+
+        match self {
+            InvalidValueUTF8(error) => Some(error),
+            InvalidTable(error) => Some(error),
+            KeyDoesNotExist | InvalidValueType(_) => None,
         }
     }
 }
 
+impl Display for ValueFromLuaValueError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use ValueFromLuaValueError::*;
+
+        match self {
+            KeyDoesNotExist => "the Lua value is nil".fmt(f),
+            InvalidValueType(invalid_type) => write!(
+                f,
+                "invalid Lua value type (\"{}\") for a Lua config value",
+                invalid_type
+            ),
+            InvalidValueUTF8(error) => write!(f, "invalid string value UTF-8: {}", error),
+            InvalidTable(error) => write!(f, "invalid Lua config table: {}", error),
+        }
+    }
+}
+
+/// An error returned by [`LuaConfigKey`] accessors.
+///
+/// [`LuaConfigKey`]: struct.LuaConfigKey.html
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum LuaConfigKeyError {
     /// Lua state mismatch - tried to call [`config()`] / [`root()`] with the [`Lua context`]
@@ -1,8 +1,9 @@
 mod array;
 mod config;
 mod error;
+mod options;
 mod table;
 mod util;
 mod value;
 
-pub use {array::*, config::*, error::*, table::*, value::*};
+pub use {array::*, config::*, error::*, options::*, table::*, value::*};
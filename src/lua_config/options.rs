@@ -0,0 +1,51 @@
+use std::{num::NonZeroU32, time::Duration};
+
+/// Configuration options for loading a [`Lua config`](struct.LuaConfig.html) from a Lua script / table.
+#[derive(Clone, Copy, Debug)]
+pub struct LuaConfigOptions {
+    /// Maximum supported nesting depth of Lua config tables / arrays (the root table is at depth `0`).
+    /// Deeper nested (or self-referencing) Lua tables cause a [`DepthExceeded`](enum.LuaConfigError.html#variant.DepthExceeded) error.
+    ///
+    /// Also bounds the recursion when serializing / converting the loaded configs.
+    ///
+    /// Default: `128`.
+    pub max_depth: u32,
+}
+
+impl Default for LuaConfigOptions {
+    fn default() -> Self {
+        Self { max_depth: 128 }
+    }
+}
+
+/// Execution limits for loading a [`Lua config`](struct.LuaConfig.html) script
+/// via [`LuaConfigKey::from_script_limits`](struct.LuaConfigKey.html#method.from_script_limits).
+///
+/// Script execution is aborted with a [`Timeout`](enum.LuaConfigError.html#variant.Timeout) error
+/// if any of the limits is exceeded.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct LuaConfigLimits {
+    /// Maximum number of Lua VM instructions the script may execute.
+    ///
+    /// NOTE - instructions are counted in batches of at most [`INSTRUCTION_BATCH`](#associatedconstant.INSTRUCTION_BATCH),
+    /// so the script may execute slightly more instructions before it is aborted.
+    ///
+    /// Default: `None` (no limit).
+    pub max_instructions: Option<NonZeroU32>,
+    /// Maximum (wall-clock) time the script may run for.
+    ///
+    /// NOTE - elapsed time is checked every [`INSTRUCTION_BATCH`](#associatedconstant.INSTRUCTION_BATCH) instructions,
+    /// so time spent in a single long-running Lua standard library call (e.g. `string.rep`) is not interrupted.
+    ///
+    /// Default: `None` (no limit).
+    pub timeout: Option<Duration>,
+}
+
+impl LuaConfigLimits {
+    /// Number of Lua VM instructions executed between the checks of the limits.
+    pub const INSTRUCTION_BATCH: u32 = 1024;
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.max_instructions.is_none() && self.timeout.is_none()
+    }
+}
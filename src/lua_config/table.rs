@@ -81,6 +81,92 @@ impl<'lua> LuaTable<'lua> {
         V::try_from(self.get_val(key).ok_or_else(|| KeyDoesNotExist)?).map_err(IncorrectValueType)
     }
 
+    /// Like [`get`], but returns the `default` value if the [`table`] does not contain the `key`.
+    ///
+    /// Still returns an [`error`] if the [`value`] is of incorrect and incompatible type.
+    ///
+    /// [`get`]: #method.get
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`value`]: type.LuaConfigValue.html
+    pub fn get_or<
+        K: AsRef<str>,
+        V: TryFromValue<LuaString<'lua>, LuaArray<'lua>, LuaTable<'lua>>,
+    >(
+        &self,
+        key: K,
+        default: V,
+    ) -> Result<V, TableError> {
+        match self.get(key) {
+            Err(TableError::KeyDoesNotExist) => Ok(default),
+            result => result,
+        }
+    }
+
+    /// Tries to get a [`bool`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// or returns the `default` value if the [`table`] does not contain the `key`.
+    ///
+    /// Returns an [`error`] if the value is not a [`bool`].
+    ///
+    /// [`bool`]: enum.Value.html#variant.Bool
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.TableError.html
+    pub fn get_bool_or<K: AsRef<str>>(&self, key: K, default: bool) -> Result<bool, TableError> {
+        self.get_or(key, default)
+    }
+
+    /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// or returns the `default` value if the [`table`] does not contain the `key`.
+    ///
+    /// Returns an [`error`] if the value is not an [`i64`] / [`f64`].
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn get_i64_or<K: AsRef<str>>(&self, key: K, default: i64) -> Result<i64, TableError> {
+        self.get_or(key, default)
+    }
+
+    /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// or returns the `default` value if the [`table`] does not contain the `key`.
+    ///
+    /// Returns an [`error`] if the value is not an [`f64`] / [`i64`].
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    pub fn get_f64_or<K: AsRef<str>>(&self, key: K, default: f64) -> Result<f64, TableError> {
+        self.get_or(key, default)
+    }
+
+    /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// or returns the `default` string if the [`table`] does not contain the `key`.
+    ///
+    /// As the `default` string is not interned in the Lua state, the result is an owned `String`.
+    ///
+    /// Returns an [`error`] if the value is not a [`string`].
+    ///
+    /// [`string`]: enum.Value.html#variant.String
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.TableError.html
+    pub fn get_string_or<K: AsRef<str>>(
+        &self,
+        key: K,
+        default: &str,
+    ) -> Result<String, TableError> {
+        match self.get_string(key) {
+            Ok(string) => Ok(string.as_ref().to_owned()),
+            Err(TableError::KeyDoesNotExist) => Ok(default.to_owned()),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Tries to get a reference to a [`value`] in the [`table`] at `path`.
     ///
     /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
@@ -203,6 +289,44 @@ impl<'lua> LuaTable<'lua> {
         self.get_path(path)
     }
 
+    /// Tries to get a [`u64`] [`value`] in the [`table`] with the (non-empty) string `key`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`u64`] / [`i64`] / [`f64`].
+    ///
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`error`]: enum.TableError.html
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn get_u64<K: AsRef<str>>(&self, key: K) -> Result<u64, TableError> {
+        self.get(key)
+    }
+
+    /// Tries to get a [`u64`] [`value`] in the [`table`] at `path`.
+    ///
+    /// `path` is an iterator over consecutively nested [`config keys`] - either (non-empty) string [`table keys`],
+    /// or (`0`-based) [`array indices`].
+    /// All keys except the last one must correspond to a [`table`](enum.Value.html#variant.Table) or an [`array`] value.
+    /// The last key must correspond to a [`u64`] / [`i64`] / [`f64`] [`value`].
+    ///
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`config keys`]: enum.ConfigKey.html
+    /// [`table keys`]: enum.ConfigKey.html#variant.Table
+    /// [`array indices`]: enum.ConfigKey.html#variant.Array
+    /// [`array`]: enum.Value.html#variant.Array
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn get_u64_path<'k, K, P>(&self, path: P) -> Result<u64, GetPathError>
+    where
+        K: Borrow<ConfigKey<'k>>,
+        P: IntoIterator<Item = K>,
+    {
+        self.get_path(path)
+    }
+
     /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not an [`f64`] / [`i64`].
@@ -239,6 +363,48 @@ impl<'lua> LuaTable<'lua> {
         self.get_path(path)
     }
 
+    /// Tries to get an [`i64`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// converting a [`u64`] / [`f64`] [`value`] only if it is exactly representable as an [`i64`].
+    ///
+    /// Unlike [`get_i64`], does not truncate [`f64`] values with a fractional part.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`i64`] / [`u64`] / [`f64`],
+    /// or if the conversion is lossy.
+    ///
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`get_i64`]: #method.get_i64
+    /// [`error`]: enum.TableError.html
+    pub fn get_i64_coerced<K: AsRef<str>>(&self, key: K) -> Result<i64, TableError> {
+        self.get_val(key)
+            .ok_or(TableError::KeyDoesNotExist)?
+            .i64_coerced()
+    }
+
+    /// Tries to get an [`f64`] [`value`] in the [`table`] with the (non-empty) string `key`,
+    /// converting an [`i64`] / [`u64`] [`value`] only if it is exactly representable as an [`f64`].
+    ///
+    /// Unlike [`get_f64`], does not round integers with a magnitude greater than `2^53`.
+    ///
+    /// Returns an [`error`] if the [`table`] does not contain the `key`, if value is not an [`f64`] / [`i64`] / [`u64`],
+    /// or if the conversion is lossy.
+    ///
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    /// [`get_f64`]: #method.get_f64
+    /// [`error`]: enum.TableError.html
+    pub fn get_f64_coerced<K: AsRef<str>>(&self, key: K) -> Result<f64, TableError> {
+        self.get_val(key)
+            .ok_or(TableError::KeyDoesNotExist)?
+            .f64_coerced()
+    }
+
     /// Tries to get a [`string`] [`value`] in the [`table`] with the (non-empty) string `key`.
     ///
     /// Returns an [`error`] if the [`table`] does not contain the `key` or if value is not a [`string`].
@@ -343,8 +509,41 @@ impl<'lua> LuaTable<'lua> {
     /// [`key`]: struct.LuaString.html
     /// [`value`]: type.LuaConfigValue.html
     /// [`table`]: struct.LuaTable.html
-    pub fn iter(&self) -> impl Iterator<Item = (LuaString<'lua>, LuaConfigValue<'lua>)> {
-        LuaTableIter(self.0.clone().pairs())
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (LuaString<'lua>, LuaConfigValue<'lua>)> {
+        LuaTableIter(self.0.clone().pairs(), self.len())
+    }
+
+    /// Returns an iterator over the [`keys`] of the [`table`], in unspecified order.
+    ///
+    /// [`keys`]: struct.LuaString.html
+    /// [`table`]: struct.LuaTable.html
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = LuaString<'lua>> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the [`values`] of the [`table`], in unspecified order.
+    ///
+    /// [`values`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    pub fn values(&self) -> impl ExactSizeIterator<Item = LuaConfigValue<'lua>> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over ([`key`], [`value`]) pairs of the [`table`], in ascending (alphabetical) key order.
+    ///
+    /// [`key`]: struct.LuaString.html
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`table`]: struct.LuaTable.html
+    pub fn iter_sorted(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (LuaString<'lua>, LuaConfigValue<'lua>)> {
+        // Gather the key / value pairs.
+        let mut pairs: Vec<_> = self.iter().collect();
+
+        // Sort the pairs in canonical key order.
+        sort_by_table_key(&mut pairs, |(key, _)| key.as_ref());
+
+        pairs.into_iter()
     }
 
     /// Inserts or changes the [`value`] at (non-empty) string `key`.
@@ -369,6 +568,21 @@ impl<'lua> LuaTable<'lua> {
         self.remove_impl(key.as_ref().try_into().ok()?)
     }
 
+    /// Recursively deep-copies this [`table`] to a new owned [`dynamic table`],
+    /// which may outlive the [`Lua context`].
+    ///
+    /// [`table`]: struct.LuaTable.html
+    /// [`dynamic table`]: struct.DynTable.html
+    /// [`Lua context`]: https://docs.rs/rlua/*/rlua/struct.Context.html
+    #[cfg(feature = "dyn")]
+    pub fn to_dyn_table(&self) -> DynTable {
+        let mut result = DynTable::new();
+
+        LuaConfig::table_to_dyn_table(self.clone(), &mut result);
+
+        result
+    }
+
     pub(super) fn from_valid_table(table: rlua::Table<'lua>) -> Self {
         Self(table)
     }
@@ -383,7 +597,9 @@ impl<'lua> LuaTable<'lua> {
         match value_from_lua_value(value) {
             Err(err) => match err {
                 ValueFromLuaValueError::KeyDoesNotExist => None,
-                ValueFromLuaValueError::InvalidValueType(_) => {
+                ValueFromLuaValueError::InvalidValueType(_)
+                | ValueFromLuaValueError::InvalidValueUTF8(_)
+                | ValueFromLuaValueError::InvalidTable(_) => {
                     debug_unreachable!(
                         "invalid type values may not exist in a valid Lua config table"
                     )
@@ -405,6 +621,7 @@ impl<'lua> LuaTable<'lua> {
                 Value::Bool(value) => table.raw_set(key, value),
                 Value::F64(value) => table.raw_set(key, value),
                 Value::I64(value) => table.raw_set(key, value),
+                Value::U64(value) => table.raw_set(key, lua_value_from_u64(value)),
                 Value::String(value) => table.raw_set(key, value),
                 Value::Array(value) => table.raw_set(key, value.0),
                 Value::Table(value) => table.raw_set(key, value.0),
@@ -464,45 +681,48 @@ impl<'lua> LuaTable<'lua> {
         }
     }
 
-    fn fmt_lua_impl<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        writeln!(w, "{{")?;
+    fn fmt_lua_impl<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        <Self as DisplayLua>::write_lua_open(w, options)?;
 
-        // Gather the keys.
-        let mut keys: Vec<_> = self.iter().map(|(key, _)| key).collect();
+        // Gather the key / value pairs.
+        let mut pairs: Vec<_> = self.iter().collect();
 
-        // Sort the keys in alphabetical order.
-        keys.sort_by(|l, r| l.as_ref().cmp(r.as_ref()));
+        // Sort the pairs in canonical key order, if required.
+        if options.sort_keys {
+            sort_by_table_key(&mut pairs, |(key, _)| key.as_ref());
+        }
 
-        // Iterate the table using the sorted keys.
-        for key in keys.into_iter() {
+        let len = pairs.len();
+
+        for (index, (key, value)) in pairs.into_iter().enumerate() {
             let key = unwrap_unchecked(NonEmptyStr::new(key.as_ref()), "empty key");
 
-            <Self as DisplayLua>::do_indent(w, indent + 1)?;
+            <Self as DisplayLua>::write_lua_element_start(w, indent, options)?;
 
             write_lua_key(w, key)?;
             write!(w, " = ")?;
 
-            // Must succeed - all keys are valid.
-            let value = unwrap_unchecked(
-                self.get_val(key),
-                "failed to get a value from a Lua config table with a valid key",
-            );
-
             let is_array_or_table = matches!(value.get_type(), ValueType::Array | ValueType::Table);
 
-            value.fmt_lua(w, indent + 1)?;
+            value
+                .fmt_lua(w, indent + 1, options)
+                .map_err(|err| err.push_key(key))?;
 
-            write!(w, ",")?;
+            <Self as DisplayLua>::write_lua_comma(w, index + 1 == len, options)?;
 
-            if is_array_or_table {
+            if is_array_or_table && options.write_key_comments() {
                 write!(w, " -- {}", key)?;
             }
 
-            writeln!(w)?;
+            <Self as DisplayLua>::write_lua_element_end(w, options)?;
         }
 
-        <Self as DisplayLua>::do_indent(w, indent)?;
-        write!(w, "}}")?;
+        <Self as DisplayLua>::write_lua_close(w, indent, len == 0, options)?;
 
         Ok(())
     }
@@ -519,9 +739,9 @@ impl<'lua> LuaTable<'lua> {
         debug_assert!(options.nested_sections() || level < 2);
 
         // Gather the keys.
-        let mut keys: Vec<_> = self.iter().map(|(key, _)| key).collect();
+        let mut keys: Vec<_> = self.keys().collect();
 
-        // Sort the keys in alphabetical order, non-tables first.
+        // Sort the keys in canonical order, non-tables first.
         keys.sort_by(|l, r| {
             // Must succeed - all keys are valid.
             let l_val = unwrap_unchecked(
@@ -533,16 +753,12 @@ impl<'lua> LuaTable<'lua> {
                 "failed to get a value from a Lua config table with a valid key",
             );
 
-            let l_is_a_table = l_val.get_type() == ValueType::Table;
-            let r_is_a_table = r_val.get_type() == ValueType::Table;
-
-            if !l_is_a_table && r_is_a_table {
-                std::cmp::Ordering::Less
-            } else if l_is_a_table && !r_is_a_table {
-                std::cmp::Ordering::Greater
-            } else {
-                l.as_ref().cmp(r.as_ref())
-            }
+            cmp_ini_keys(
+                l.as_ref(),
+                l_val.get_type() == ValueType::Table,
+                r.as_ref(),
+                r_val.get_type() == ValueType::Table,
+            )
         });
 
         let len = self.len() as usize;
@@ -564,6 +780,8 @@ impl<'lua> LuaTable<'lua> {
                     write_ini_array(
                         w,
                         key,
+                        None,
+                        None,
                         value.iter(),
                         value.len() as usize,
                         last,
@@ -580,6 +798,7 @@ impl<'lua> LuaTable<'lua> {
                     write_ini_table(
                         w,
                         key,
+                        None,
                         key_index as u32,
                         &value,
                         value.len(),
@@ -591,7 +810,9 @@ impl<'lua> LuaTable<'lua> {
                     )?;
                 }
                 value => {
-                    write_ini_value(w, key, &value, last, level, array, path, options)?;
+                    write_ini_value(
+                        w, key, None, None, &value, last, level, array, path, options,
+                    )?;
                 }
             }
         }
@@ -605,13 +826,19 @@ impl<'lua> LuaTable<'lua> {
 /// [`key`]: struct.LuaString.html
 /// [`value`]: type.LuaConfigValue.html
 /// [`table`]: struct.LuaTable.html
-struct LuaTableIter<'lua>(rlua::TablePairs<'lua, rlua::Value<'lua>, rlua::Value<'lua>>);
+struct LuaTableIter<'lua>(
+    rlua::TablePairs<'lua, rlua::Value<'lua>, rlua::Value<'lua>>,
+    // Number of remaining pairs, as determined by the table length.
+    u32,
+);
 
 impl<'lua> std::iter::Iterator for LuaTableIter<'lua> {
     type Item = (LuaString<'lua>, LuaConfigValue<'lua>);
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(pair) = self.0.next() {
+            self.1 = self.1.saturating_sub(1);
+
             if let Ok((key, value)) = pair {
                 // Must succeed - all table keys are valid UTF-8 strings.
                 let key = if let rlua::Value::String(key) = key {
@@ -635,11 +862,22 @@ impl<'lua> std::iter::Iterator for LuaTableIter<'lua> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.1 as usize, Some(self.1 as usize))
+    }
 }
 
+impl<'lua> ExactSizeIterator for LuaTableIter<'lua> {}
+
 impl<'lua> DisplayLua for LuaTable<'lua> {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
-        self.fmt_lua_impl(w, indent)
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
+        self.fmt_lua_impl(w, indent, options)
     }
 }
 
@@ -659,7 +897,8 @@ impl<'lua> DisplayIni for LuaTable<'lua> {
 
 impl<'lua> Display for LuaTable<'lua> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua_impl(f, 0)
+        self.fmt_lua_impl(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
     }
 }
 
@@ -715,6 +954,133 @@ mod tests {
         });
     }
 
+    #[test]
+    fn iter_sorted() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let mut table = LuaTable::new(lua);
+
+            table.set(nestr!("foo"), 1);
+            table.set(nestr!("bar"), 2);
+            table.set(nestr!("baz"), 3);
+            table.set(nestr!("Qux"), 4);
+
+            let iter = table.iter_sorted();
+            assert_eq!(iter.len(), 4);
+
+            let pairs: Vec<_> = iter
+                .map(|(key, value)| (key.as_ref().to_owned(), value.i64().unwrap()))
+                .collect();
+
+            assert_eq!(
+                pairs,
+                vec![
+                    ("Qux".to_owned(), 4),
+                    ("bar".to_owned(), 2),
+                    ("baz".to_owned(), 3),
+                    ("foo".to_owned(), 1)
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn u64() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let mut table = LuaTable::new(lua);
+
+            // `U64` values which fit into an `i64` are stored as Lua integers.
+            assert!(!table.set(
+                nestr!("small_u64"),
+                Value::<&str, LuaArray, LuaTable>::U64(7)
+            ));
+            assert_eq!(
+                table.get_val("small_u64").unwrap().get_type(),
+                ValueType::I64
+            );
+            assert_eq!(table.get_u64("small_u64").unwrap(), 7);
+
+            // Larger `U64` values are stored as Lua numbers.
+            assert!(!table.set(
+                nestr!("u64"),
+                Value::<&str, LuaArray, LuaTable>::U64(1 << 63)
+            ));
+            assert_eq!(table.get_val("u64").unwrap().get_type(), ValueType::F64);
+            assert_eq!(table.get_u64("u64").unwrap(), 1 << 63);
+        });
+    }
+
+    #[test]
+    fn get_or() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let mut table = LuaTable::new(lua);
+
+            assert!(!table.set(nestr!("bool"), true));
+            assert!(!table.set(nestr!("i64"), 7));
+            assert!(!table.set(nestr!("f64"), 3.5));
+            assert!(!table.set(nestr!("string"), "foo"));
+
+            // Existing values are returned.
+            assert_eq!(table.get_bool_or("bool", false).unwrap(), true);
+            assert_eq!(table.get_i64_or("i64", 9).unwrap(), 7);
+            assert!(cmp_f64(table.get_f64_or("f64", 9.0).unwrap(), 3.5));
+            assert_eq!(table.get_string_or("string", "bar").unwrap(), "foo");
+
+            // Missing (or empty) keys return the default.
+            assert_eq!(table.get_bool_or("missing", false).unwrap(), false);
+            assert_eq!(table.get_i64_or("missing", 9).unwrap(), 9);
+            assert!(cmp_f64(table.get_f64_or("", 9.0).unwrap(), 9.0));
+            assert_eq!(table.get_string_or("missing", "bar").unwrap(), "bar");
+            assert_eq!(table.get_or("missing", 9u64).unwrap(), 9);
+
+            // Values of incorrect type are still an error.
+            assert_eq!(
+                table.get_i64_or("string", 9).err().unwrap(),
+                TableError::IncorrectValueType(ValueType::String)
+            );
+            assert_eq!(
+                table.get_string_or("bool", "bar").err().unwrap(),
+                TableError::IncorrectValueType(ValueType::Bool)
+            );
+        });
+    }
+
+    #[test]
+    fn coerced() {
+        let lua = rlua::Lua::new();
+
+        lua.context(|lua| {
+            let mut table = LuaTable::new(lua);
+
+            assert!(!table.set(nestr!("i64"), 7));
+            assert!(!table.set(nestr!("big_i64"), i64::MAX));
+            assert!(!table.set(nestr!("f64"), 3.0));
+            assert!(!table.set(nestr!("fractional_f64"), 3.5));
+            assert!(!table.set(nestr!("bool"), true));
+
+            assert!(cmp_f64(table.get_f64_coerced("i64").unwrap(), 7.0));
+            assert_eq!(table.get_i64_coerced("f64").unwrap(), 3);
+
+            assert_eq!(
+                table.get_f64_coerced("big_i64").err().unwrap(),
+                TableError::LossyConversion(ValueType::I64)
+            );
+            assert_eq!(
+                table.get_i64_coerced("fractional_f64").err().unwrap(),
+                TableError::LossyConversion(ValueType::F64)
+            );
+            assert_eq!(
+                table.get_i64_coerced("bool").err().unwrap(),
+                TableError::IncorrectValueType(ValueType::Bool)
+            );
+        });
+    }
+
     #[test]
     fn LuaTableError_KeyDoesNotExist() {
         let lua = rlua::Lua::new();
@@ -997,6 +1363,12 @@ mod tests {
                 -17.235
             ));
 
+            // Iterators report their exact length.
+            let mut iter = table.iter();
+            assert_eq!(iter.len(), table.len() as usize);
+            iter.next().unwrap();
+            assert_eq!(iter.len(), table.len() as usize - 1);
+
             // Iterate the table.
             for (key, value) in table.iter() {
                 match key.as_ref() {
@@ -1032,4 +1404,61 @@ mod tests {
             }
         });
     }
+
+    #[cfg(feature = "dyn")]
+    #[test]
+    fn to_dyn_table() {
+        let lua = rlua::Lua::new();
+
+        // Extract the subtrees and keep them past the Lua context scope.
+        let (table, array) = lua.context(|lua| {
+            let config = LuaConfig::from_script(
+                lua,
+                "{
+                    foo = {
+                        bool = true,
+                        int = 7,
+                        float = 3.5,
+                        string = \"bar\",
+                        table = { baz = { 1, 2 } },
+                    },
+                    array = { { 1, 2 }, { 3.5 } },
+                }",
+            )
+            .unwrap();
+
+            let root = config.root();
+
+            (
+                root.get_table("foo").unwrap().to_dyn_table(),
+                root.get_array("array").unwrap().to_dyn_array(),
+            )
+        });
+
+        assert_eq!(table.len(), 5);
+        assert_eq!(table.get_bool("bool").unwrap(), true);
+        assert_eq!(table.get_i64("int").unwrap(), 7);
+        assert!(cmp_f64(table.get_f64("float").unwrap(), 3.5));
+        assert_eq!(table.get_string("string").unwrap(), "bar");
+        assert_eq!(table.get_table("table").unwrap().len(), 1);
+        assert_eq!(
+            table
+                .get_i64_path(&["table".into(), "baz".into(), 1.into()])
+                .unwrap(),
+            2
+        );
+
+        // Array element types are preserved.
+        assert_eq!(array.len(), 2);
+        assert!(array
+            .get_array(0)
+            .unwrap()
+            .iter()
+            .all(|value| value.get_type() == ValueType::I64));
+        assert!(array
+            .get_array(1)
+            .unwrap()
+            .iter()
+            .all(|value| value.get_type() == ValueType::F64));
+    }
 }
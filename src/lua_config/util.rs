@@ -2,6 +2,7 @@ use {
     crate::{util::unwrap_unchecked, value::*, *},
     rlua::Value as LuaValue,
     rlua_ext::value_type,
+    std::convert::TryFrom,
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -60,18 +61,28 @@ pub(super) fn are_lua_types_compatible(l: rlua_ext::ValueType, r: rlua_ext::Valu
 pub(super) fn validate_lua_config_table<'lua>(
     lua: rlua::Context<'lua>,
     table: &rlua::Table<'lua>,
+    options: LuaConfigOptions,
 ) -> Result<(), LuaConfigError> {
-    validate_lua_config_table_impl(lua, table)
+    validate_lua_config_table_impl(lua, table, 0, options)
         .map(|_| ())
         .map_err(LuaConfigError::reverse)
 }
 
+/// `depth` is the nesting depth of the `table` (`0` for the root table).
+/// Returns the type and the length of the `table`.
 fn validate_lua_config_table_impl<'lua>(
     lua: rlua::Context<'lua>,
     table: &rlua::Table<'lua>,
-) -> Result<LuaTableType, LuaConfigError> {
+    depth: u32,
+    options: LuaConfigOptions,
+) -> Result<(LuaTableType, u32), LuaConfigError> {
     use LuaConfigError::*;
 
+    // Also guards against (infinitely nested) self-referencing tables.
+    if depth > options.max_depth {
+        return Err(DepthExceeded(ConfigPath::new()));
+    }
+
     // Needed to ensure all keys are the same type.
     let mut key_type = None;
 
@@ -79,6 +90,10 @@ fn validate_lua_config_table_impl<'lua>(
     let mut array_lua_value_type = None;
     let mut array_value_type = None;
 
+    // For arrays, needed to ensure nested arrays and tables are not mixed.
+    // Empty Lua tables (`{}`) are compatible with both.
+    let mut array_tables_empty = true;
+
     // Keep track of actual table length.
     let mut len = 0;
 
@@ -90,6 +105,7 @@ fn validate_lua_config_table_impl<'lua>(
         // Must succeed - no conversion from `LuaValue` is performed.
         let (key, value) = unwrap_unchecked(pair, "failed to iterate the Lua config table");
 
+        #[derive(Clone, Copy)]
         enum Key<'a> {
             String(&'a NonEmptyStr),
             Integer(u32),
@@ -189,6 +205,8 @@ fn validate_lua_config_table_impl<'lua>(
         }
 
         // Validate the value and get its config value type.
+        let mut empty_table = false;
+
         let value_type = match value {
             LuaValue::Boolean(_) => ValueType::Bool,
             LuaValue::Integer(_) => ValueType::I64,
@@ -204,14 +222,20 @@ fn validate_lua_config_table_impl<'lua>(
 
                 ValueType::String
             }
-            LuaValue::Table(value) => validate_lua_config_table_impl(lua, &value)
-                .map(|table_type| match table_type {
-                    LuaTableType::Array => ValueType::Array,
-                    LuaTableType::Table => ValueType::Table,
-                })
-                // Push the current table / array key to the end of the path on error.
-                // The path will be reversed at the end.
-                .map_err(|err| err.push_key(key.into()))?,
+            LuaValue::Table(value) => {
+                validate_lua_config_table_impl(lua, &value, depth + 1, options)
+                    .map(|(table_type, len)| {
+                        empty_table = len == 0;
+
+                        match table_type {
+                            LuaTableType::Array => ValueType::Array,
+                            LuaTableType::Table => ValueType::Table,
+                        }
+                    })
+                    // Push the current table / array key to the end of the path on error.
+                    // The path will be reversed at the end.
+                    .map_err(|err| err.push_key(key.into()))?
+            }
             // Only valid Lua value types allowed.
             invalid_value => {
                 return Err(InvalidValueType {
@@ -221,6 +245,27 @@ fn validate_lua_config_table_impl<'lua>(
             }
         };
 
+        // For arrays, ensure nested arrays and tables are not mixed.
+        if is_array {
+            match (array_value_type, value_type) {
+                // Only empty tables so far - the array value type is determined by the first nested array.
+                (Some(ValueType::Table), ValueType::Array) if array_tables_empty => {
+                    array_value_type.replace(ValueType::Array);
+                }
+                // Empty tables are treated as empty arrays in arrays of arrays.
+                (Some(ValueType::Array), ValueType::Table) if empty_table => {}
+                (Some(ValueType::Table), ValueType::Array)
+                | (Some(ValueType::Array), ValueType::Table) => {
+                    return Err(MixedArrayTables(vec![key.into()].into()));
+                }
+                _ => {}
+            }
+
+            if (value_type == ValueType::Table) && !empty_table {
+                array_tables_empty = false;
+            }
+        }
+
         // For arrays first value type will determine the array value type.
         if array_value_type.is_none() {
             array_value_type.replace(value_type);
@@ -241,9 +286,24 @@ fn validate_lua_config_table_impl<'lua>(
         }
     }?;
 
+    // Empty tables in arrays of arrays are empty arrays.
+    if (table_type == LuaTableType::Array) && (array_value_type == Some(ValueType::Array)) {
+        for value in table.clone().sequence_values::<LuaValue>() {
+            // Must succeed - no conversion from `LuaValue` is performed.
+            let value = unwrap_unchecked(value, "failed to iterate the Lua config array");
+
+            if let LuaValue::Table(value) = value {
+                if get_table_type(&value) == LuaTableType::Table {
+                    debug_assert_eq!(get_table_len(&value), 0);
+                    set_lua_config_table_metatable(lua, &value, LuaTableType::Array, None, 0);
+                }
+            }
+        }
+    }
+
     set_lua_config_table_metatable(lua, &table, table_type, array_value_type, len);
 
-    Ok(table_type)
+    Ok((table_type, len))
 }
 
 const TABLE_TYPE_METATABLE_KEY: &str = "table_type";
@@ -365,12 +425,25 @@ pub(super) fn set_table_len(table: &rlua::Table<'_>, len: u32) {
     )
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub(super) enum ValueFromLuaValueError {
-    KeyDoesNotExist,
-    InvalidValueType(rlua_ext::ValueType),
+/// Converts the `u64` config `value` to a Lua value.
+/// Lua has no unsigned integers, so `value`s which fit into an `i64` are converted to Lua integers,
+/// and all other `value`s are converted to Lua numbers (`f64`) and are rounded to the nearest representable `f64`
+/// (integers above `2^53` are not all exactly representable).
+/// In both cases the value is read back as an `I64` / `F64` value, which may be fetched as a `u64`.
+pub(super) fn lua_value_from_u64<'lua>(value: u64) -> LuaValue<'lua> {
+    match <i64 as TryFrom<u64>>::try_from(value) {
+        Ok(value) => LuaValue::Integer(value),
+        Err(_) => LuaValue::Number(value as f64),
+    }
 }
 
+/// Converts the Lua `value` to a Lua config value.
+/// Lua integers are converted to `I64` values, Lua numbers - to `F64` values;
+/// `U64` values are never returned, see `lua_value_from_u64()`.
+/// Lua integers are always in `i64` range (Lua integer arithmetic wraps around),
+/// and Lua numbers are never converted to integers, even if they are integral -
+/// e.g. integer literals outside of `i64` range are Lua numbers and are returned as (possibly rounded) `F64` values.
+/// NOTE - the caller guarantees Lua string `value`s are valid UTF-8 and Lua table `value`s are valid Lua config tables.
 pub(super) fn value_from_lua_value(
     value: LuaValue<'_>,
 ) -> Result<LuaConfigValue<'_>, ValueFromLuaValueError> {
@@ -1,4 +1,5 @@
 use {
+    super::util::*,
     crate::*,
     std::{
         borrow::Borrow,
@@ -40,6 +41,12 @@ impl<'s, 'lua> From<&'s str> for Value<&'s str, LuaArray<'lua>, LuaTable<'lua>>
     }
 }
 
+impl<'s, 'lua> From<&'s String> for Value<&'s str, LuaArray<'lua>, LuaTable<'lua>> {
+    fn from(val: &'s String) -> Self {
+        Value::String(val.as_str())
+    }
+}
+
 impl<'lua, S> From<LuaTable<'lua>> for Value<S, LuaArray<'lua>, LuaTable<'lua>> {
     fn from(val: LuaTable<'lua>) -> Self {
         Value::Table(val)
@@ -53,6 +60,42 @@ impl<'lua, S> From<LuaArray<'lua>> for Value<S, LuaArray<'lua>, LuaTable<'lua>>
 }
 
 impl<'lua> LuaConfigValue<'lua> {
+    /// Tries to convert the raw Lua `value` to a Lua config [`value`].
+    ///
+    /// Booleans, integers, numbers, (valid UTF-8) strings and (valid Lua config) tables are supported.
+    /// Lua integers are converted to [`i64`] values, Lua numbers - to [`f64`] values, even if they are integral
+    /// (e.g. integer literals outside of [`i64`] range, which Lua parses as numbers).
+    /// `nil`, functions, userdata, light userdata, threads and errors are not supported.
+    ///
+    /// Lua tables are validated (recursively) the same way as by [`LuaConfig::from_table`] (using default [`options`])
+    /// and are modified (their metatables are set) to become valid Lua config [`tables`] / [`arrays`].
+    ///
+    /// [`value`]: type.LuaConfigValue.html
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    /// [`LuaConfig::from_table`]: struct.LuaConfig.html#method.from_table
+    /// [`options`]: struct.LuaConfigOptions.html
+    /// [`tables`]: struct.LuaTable.html
+    /// [`arrays`]: struct.LuaArray.html
+    pub fn from_rlua(
+        lua: rlua::Context<'lua>,
+        value: rlua::Value<'lua>,
+    ) -> Result<Self, ValueFromLuaValueError> {
+        use ValueFromLuaValueError::*;
+
+        match &value {
+            rlua::Value::String(string) => {
+                string.to_str().map_err(InvalidValueUTF8)?;
+            }
+            rlua::Value::Table(table) => {
+                validate_lua_config_table(lua, table, Default::default()).map_err(InvalidTable)?;
+            }
+            _ => {}
+        }
+
+        value_from_lua_value(value)
+    }
+
     pub(crate) fn get_path<'k, K, P>(self, mut path: P) -> Result<Self, GetPathError>
     where
         K: Borrow<ConfigKey<'k>>,
@@ -103,7 +146,8 @@ impl<'lua> LuaConfigValue<'lua> {
 
 impl<'lua> Display for LuaConfigValue<'lua> {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        self.fmt_lua(f, 0)
+        self.fmt_lua(f, 0, LuaDisplayOptions::unbounded())
+            .map_err(|_| std::fmt::Error)
     }
 }
 
@@ -0,0 +1,156 @@
+use {
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// An actual concrete error kind returned by the RON config [`parser`].
+///
+/// [`parser`]: struct.DynConfig.html#method.from_ron
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RONErrorKind {
+    /// The RON string is malformed.
+    /// Contains the RON parser error message, including the position in the source string, if known.
+    InvalidRON(String),
+    /// The root RON value is not a map.
+    RootNotAMap,
+    /// Map keys must be strings.
+    NonStringKey,
+    /// Empty map keys are invalid.
+    EmptyKey,
+    /// RON unit (`()`) and `None` values are not supported.
+    UnitValue,
+    /// Mixed value types in an array - all array elements must have the same type.
+    MixedArray,
+    /// Nested maps / lists exceed the maximum supported depth.
+    /// The error path is empty if the input is nested too deeply to be parsed by the `ron` crate at all.
+    ///
+    /// See [`max_depth`](struct.RONOptions.html#structfield.max_depth).
+    DepthExceeded,
+}
+
+impl Error for RONErrorKind {}
+
+impl Display for RONErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use RONErrorKind::*;
+
+        match self {
+            InvalidRON(message) => write!(f, "invalid RON: {}", message),
+            RootNotAMap => "the root value is not a map".fmt(f),
+            NonStringKey => "map keys must be strings".fmt(f),
+            EmptyKey => "empty map keys are invalid".fmt(f),
+            UnitValue => "unit / `None` values are not supported".fmt(f),
+            MixedArray => "mixed value types in an array".fmt(f),
+            DepthExceeded => "nested maps / lists exceed the maximum supported depth".fmt(f),
+        }
+    }
+}
+
+/// An error returned by the RON config [`parser`].
+///
+/// [`parser`]: struct.DynConfig.html#method.from_ron
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RONError {
+    /// Path to the key / value in which the error happened, or an empty path for the root table.
+    pub path: ConfigPath,
+    /// Actual error.
+    pub error: RONErrorKind,
+}
+
+impl Error for RONError {}
+
+impl Display for RONError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "RON parse error; path: {}, error: {}",
+            self.path, self.error
+        )
+    }
+}
+
+/// An error returned by `to_ron_string` / `fmt_ron` methods on [`dyn`] configs.
+///
+/// [`dyn`]: struct.DynConfig.html#method.to_ron_string
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ToRONStringError {
+    /// Encountered a `U64` value which does not fit into a RON (64-bit signed) integer.
+    /// Contains the path to the value.
+    IntegerOutOfRange(ConfigPath),
+    /// Encountered a non-finite (NaN / infinite) float value.
+    /// Contains the path to the value.
+    NonFiniteFloat(ConfigPath),
+    /// Encountered a nested map / list deeper than allowed by [`options`].
+    /// Contains the path to the value.
+    ///
+    /// [`options`]: struct.ToRONStringOptions.html#structfield.max_depth
+    DepthExceeded(ConfigPath),
+    /// General write error.
+    WriteError,
+}
+
+impl ToRONStringError {
+    /// Pushes the table key / array index to the back of the path if the error has one.
+    pub(crate) fn push_key<K: Into<OwnedConfigKey>>(mut self, key: K) -> Self {
+        use ToRONStringError::*;
+
+        match &mut self {
+            IntegerOutOfRange(path) | NonFiniteFloat(path) | DepthExceeded(path) => {
+                path.0.push(key.into())
+            }
+            WriteError => {}
+        };
+
+        self
+    }
+
+    /// Reverses the path if the error has one.
+    /// Must do this because path elements were pushed to the back of the `Vec`
+    /// when unwinding the stack on error.
+    pub(crate) fn reverse(mut self) -> Self {
+        use ToRONStringError::*;
+
+        match &mut self {
+            IntegerOutOfRange(path) | NonFiniteFloat(path) | DepthExceeded(path) => {
+                path.0.reverse()
+            }
+            WriteError => {}
+        };
+
+        self
+    }
+}
+
+impl From<std::fmt::Error> for ToRONStringError {
+    fn from(_: std::fmt::Error) -> Self {
+        Self::WriteError
+    }
+}
+
+impl Error for ToRONStringError {}
+
+impl Display for ToRONStringError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use ToRONStringError::*;
+
+        match self {
+            IntegerOutOfRange(path) => write!(
+                f,
+                "encountered an unsigned integer value out of RON integer range at {}",
+                path
+            ),
+            NonFiniteFloat(path) => {
+                write!(f, "encountered a non-finite float value at {}", path)
+            }
+            DepthExceeded(path) => write!(
+                f,
+                "encountered a nested map / list deeper than allowed by options at {}",
+                path
+            ),
+            WriteError => "general write error".fmt(f),
+        }
+    }
+}
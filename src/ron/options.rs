@@ -0,0 +1,35 @@
+/// Configuration options for parsing a RON string to a [`config`](struct.DynConfig.html).
+#[derive(Clone, Copy, Debug)]
+pub struct RONOptions {
+    /// Maximum supported depth of nested maps / lists.
+    /// Values of the root map are at depth `1`.
+    /// Deeper nested maps / lists cause a [`DepthExceeded`](enum.RONErrorKind.html#variant.DepthExceeded) error.
+    ///
+    /// Protects against stack overflow when parsing RON strings from untrusted sources.
+    ///
+    /// Default: `128`.
+    pub max_depth: u32,
+}
+
+impl Default for RONOptions {
+    fn default() -> Self {
+        Self { max_depth: 128 }
+    }
+}
+
+/// Configuration options for serializing a config to a RON string.
+#[derive(Clone, Copy, Debug)]
+pub struct ToRONStringOptions {
+    /// Maximum supported depth of nested maps / lists.
+    /// Values of the root map are at depth `1`.
+    /// Deeper nested maps / lists cause a [`DepthExceeded`](enum.ToRONStringError.html#variant.DepthExceeded) error.
+    ///
+    /// Default: `128`.
+    pub max_depth: u32,
+}
+
+impl Default for ToRONStringOptions {
+    fn default() -> Self {
+        Self { max_depth: 128 }
+    }
+}
@@ -0,0 +1,148 @@
+use {
+    crate::*,
+    ron_rs::{value::Map as RONMap, Number as RONNumber, Value as RONValue},
+};
+
+/// Internal parse result - error kind and the (reversed) path to the element where the error occured.
+type RONParseResult<T> = Result<T, (RONErrorKind, ConfigPath)>;
+
+/// Parses the RON config `string` to a [`dynamic config`].
+///
+/// The actual RON parsing is done by the `ron` crate;
+/// this only converts the resulting RON value to the [`dynamic config`].
+///
+/// [`dynamic config`]: struct.DynConfig.html
+pub(crate) struct RONParser<'s> {
+    /// Source string.
+    string: &'s str,
+    /// Parsing options as provided by the user.
+    options: RONOptions,
+}
+
+impl<'s> RONParser<'s> {
+    pub(crate) fn new(string: &'s str, options: RONOptions) -> Self {
+        Self { string, options }
+    }
+
+    /// Consumes the parser and tries to parse the RON config string.
+    pub(crate) fn parse(self) -> Result<DynConfig, RONError> {
+        self.parse_root().map_err(|(error, mut path)| {
+            path.0.reverse();
+
+            RONError { path, error }
+        })
+    }
+
+    fn parse_root(&self) -> RONParseResult<DynConfig> {
+        use RONErrorKind::*;
+
+        // The `ron` crate deserializes recursively - limit its recursion depth too, to avoid stack overflow
+        // on deeply nested input. It uses two recursion levels per nesting level (map / list, then its element),
+        // the root map included. Leave one extra nesting level of headroom, so that `parse_value` (below)
+        // reports the path to the offending value for input nested one level too deep.
+        let recursion_limit = (self.options.max_depth as usize)
+            .saturating_add(2)
+            .saturating_mul(2);
+
+        let value = ron_rs::Options::default()
+            .with_recursion_limit(recursion_limit)
+            .from_str::<RONValue>(self.string)
+            .map_err(|err| match err.code {
+                ron_rs::Error::ExceededRecursionLimit => Self::error(DepthExceeded),
+                _ => Self::error(InvalidRON(err.to_string())),
+            })?;
+
+        let map = match value {
+            RONValue::Map(map) => map,
+            _ => return Err(Self::error(RootNotAMap)),
+        };
+
+        let mut config = DynConfig::new();
+
+        *config.root_mut() = self.parse_table(&map, 1)?;
+
+        Ok(config)
+    }
+
+    /// Parses the RON `map` at `depth` (values of the root map are at depth `1`).
+    fn parse_table(&self, map: &RONMap, depth: u32) -> RONParseResult<DynTable> {
+        use RONErrorKind::*;
+
+        let mut result = DynTable::with_capacity(map.len() as u32);
+
+        for (key, value) in map.iter() {
+            let key = match key {
+                RONValue::String(key) => key,
+                _ => return Err(Self::error(NonStringKey)),
+            };
+
+            let key = NonEmptyStr::new(key).ok_or_else(|| Self::error(EmptyKey))?;
+
+            let value = self
+                .parse_value(value, depth)
+                .map_err(|err| err.push_key(key))?;
+
+            result.set(key, value);
+        }
+
+        Ok(result)
+    }
+
+    /// Parses the RON `array` at `depth`.
+    fn parse_array(&self, array: &[RONValue], depth: u32) -> RONParseResult<DynArray> {
+        use RONErrorKind::*;
+
+        let mut result = DynArray::with_capacity(array.len() as u32);
+
+        for value in array {
+            let index = result.len();
+
+            let value = self
+                .parse_value(value, depth)
+                .map_err(|err| err.push_key(index))?;
+
+            result
+                .push(value)
+                .map_err(|_| Self::error(MixedArray).push_key(index))?;
+        }
+
+        Ok(result)
+    }
+
+    /// Parses the RON `value` at `depth`.
+    fn parse_value(&self, value: &RONValue, depth: u32) -> RONParseResult<DynConfigValue> {
+        use RONErrorKind::*;
+
+        Ok(match value {
+            RONValue::Bool(value) => Value::Bool(*value),
+            RONValue::Number(RONNumber::Integer(value)) => Value::I64(*value),
+            RONValue::Number(RONNumber::Float(value)) => Value::F64(value.get()),
+            RONValue::String(value) => Value::String(value.clone()),
+            RONValue::Char(value) => Value::String(value.to_string()),
+            RONValue::Option(Some(value)) => self.parse_value(value, depth)?,
+            RONValue::Option(None) | RONValue::Unit => return Err(Self::error(UnitValue)),
+            RONValue::Seq(_) | RONValue::Map(_) if depth > self.options.max_depth => {
+                return Err(Self::error(DepthExceeded))
+            }
+            RONValue::Seq(value) => Value::Array(self.parse_array(value, depth + 1)?),
+            RONValue::Map(value) => Value::Table(self.parse_table(value, depth + 1)?),
+        })
+    }
+
+    /// Error helper method.
+    fn error(error: RONErrorKind) -> (RONErrorKind, ConfigPath) {
+        (error, ConfigPath::new())
+    }
+}
+
+trait PushKey {
+    fn push_key<K: Into<OwnedConfigKey>>(self, key: K) -> Self;
+}
+
+impl PushKey for (RONErrorKind, ConfigPath) {
+    /// Pushes the table key / array index to the back of the path.
+    fn push_key<K: Into<OwnedConfigKey>>(mut self, key: K) -> Self {
+        self.1 .0.push(key.into());
+        self
+    }
+}
@@ -0,0 +1,126 @@
+use {
+    crate::*,
+    std::{convert::TryFrom, fmt::Write},
+};
+
+/// A trait implemented by configs serializable to a RON string.
+pub(crate) trait DisplayRON {
+    fn fmt_ron<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToRONStringOptions,
+    ) -> Result<(), ToRONStringError>;
+
+    fn do_indent<W: Write>(w: &mut W, indent: u32) -> std::fmt::Result {
+        for _ in 0..indent {
+            w.write_char('\t')?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S, A, T> DisplayRON for Value<S, A, T>
+where
+    S: AsRef<str>,
+    A: DisplayRON,
+    T: DisplayRON,
+{
+    fn fmt_ron<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: ToRONStringOptions,
+    ) -> Result<(), ToRONStringError> {
+        match self {
+            Value::Bool(value) => write!(w, "{}", if *value { "true" } else { "false" })?,
+            Value::I64(value) => write!(w, "{}", value)?,
+            Value::U64(value) => match <i64 as TryFrom<u64>>::try_from(*value) {
+                Ok(value) => write!(w, "{}", value)?,
+                Err(_) => return Err(ToRONStringError::IntegerOutOfRange(ConfigPath::new())),
+            },
+            Value::F64(value) => {
+                if !value.is_finite() {
+                    return Err(ToRONStringError::NonFiniteFloat(ConfigPath::new()));
+                }
+
+                // `Debug` always writes the decimal point / exponent, unlike `Display`,
+                // so that floats are not confused with integers when parsed back.
+                write!(w, "{:?}", value)?
+            }
+            Value::String(value) => write_ron_string(w, value.as_ref())?,
+            Value::Array(_) | Value::Table(_) if indent > options.max_depth => {
+                return Err(ToRONStringError::DepthExceeded(ConfigPath::new()))
+            }
+            Value::Array(value) => value.fmt_ron(w, indent, options)?,
+            Value::Table(value) => value.fmt_ron(w, indent, options)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the `string` to the writer `w`, enclosing it in double quotes and escaping
+/// double quotes ('"'), backslashes ('\\'), new lines, carriage returns and tabs.
+fn write_ron_string<W: Write>(w: &mut W, string: &str) -> std::fmt::Result {
+    w.write_char('"')?;
+
+    for c in string.chars() {
+        match c {
+            '"' => w.write_str(r#"\""#)?,
+            '\\' => w.write_str(r#"\\"#)?,
+            '\n' => w.write_str(r#"\n"#)?,
+            '\r' => w.write_str(r#"\r"#)?,
+            '\t' => w.write_str(r#"\t"#)?,
+            c => w.write_char(c)?,
+        }
+    }
+
+    w.write_char('"')
+}
+
+/// Writes the table `key` / `value` pair to the writer `w`, preceded by indentation
+/// and followed by a (trailing) comma.
+/// Pushes the `key` to the error path on error.
+pub(crate) fn write_ron_key_value<W: Write, V: DisplayRON>(
+    w: &mut W,
+    key: &NonEmptyStr,
+    value: &V,
+    indent: u32,
+    options: ToRONStringOptions,
+) -> Result<(), ToRONStringError> {
+    <V as DisplayRON>::do_indent(w, indent)?;
+
+    write_ron_string(w, key.as_str())?;
+    write!(w, ": ")?;
+
+    value
+        .fmt_ron(w, indent, options)
+        .map_err(|err| err.push_key(key))?;
+
+    writeln!(w, ",")?;
+
+    Ok(())
+}
+
+/// Writes the array `value` at `index` to the writer `w`, preceded by indentation
+/// and followed by a (trailing) comma.
+/// Pushes the `index` to the error path on error.
+pub(crate) fn write_ron_array_value<W: Write, V: DisplayRON>(
+    w: &mut W,
+    index: u32,
+    value: &V,
+    indent: u32,
+    options: ToRONStringOptions,
+) -> Result<(), ToRONStringError> {
+    <V as DisplayRON>::do_indent(w, indent)?;
+
+    value
+        .fmt_ron(w, indent, options)
+        .map_err(|err| err.push_key(index))?;
+
+    writeln!(w, ",")?;
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use {
+    crate::*,
+    serde::de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor},
+    std::fmt::{Display, Formatter},
+};
+
+/// Booleans, strings, sequences and maps are deserialized as booleans, strings, [`arrays`] and [`tables`] respectively.
+/// Integer inputs are deserialized as [`I64`] values
+/// (unsigned integers which do not fit into `i64` - as [`U64`] values), float inputs - as [`F64`] values.
+/// Other inputs (e.g. `null`) are not supported.
+///
+/// [`arrays`]: struct.DynArray.html
+/// [`tables`]: struct.DynTable.html
+/// [`I64`]: enum.Value.html#variant.I64
+/// [`U64`]: enum.Value.html#variant.U64
+/// [`F64`]: enum.Value.html#variant.F64
+impl<'de> Deserialize<'de> for DynConfigValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(DynConfigValueVisitor)
+    }
+}
+
+/// Deserialized from a sequence of values of the same (or compatible) type.
+impl<'de> Deserialize<'de> for DynArray {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_seq(DynConfigValueVisitor)?
+            .array()
+            .ok_or_else(|| D::Error::custom("expected an array"))
+    }
+}
+
+/// Deserialized from a map with unique non-empty string keys.
+impl<'de> Deserialize<'de> for DynTable {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_map(DynConfigValueVisitor)?
+            .table()
+            .ok_or_else(|| D::Error::custom("expected a table"))
+    }
+}
+
+/// Deserialized from a map (the root [`table`]).
+///
+/// [`table`]: struct.DynTable.html
+impl<'de> Deserialize<'de> for DynConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut config = DynConfig::new();
+
+        *config.root_mut() = DynTable::deserialize(deserializer)?;
+
+        Ok(config)
+    }
+}
+
+struct DynConfigValueVisitor;
+
+impl<'de> Visitor<'de> for DynConfigValueVisitor {
+    type Value = DynConfigValue;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        "a boolean, a number, a string, a sequence or a map".fmt(f)
+    }
+
+    fn visit_bool<E: Error>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E: Error>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(Value::I64(value))
+    }
+
+    fn visit_u64<E: Error>(self, value: u64) -> Result<Self::Value, E> {
+        // Only use `U64` if the value does not fit into an `i64`.
+        Ok(if value > i64::MAX as u64 {
+            Value::U64(value)
+        } else {
+            Value::I64(value as i64)
+        })
+    }
+
+    fn visit_f64<E: Error>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(Value::F64(value))
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(Value::String(value.into()))
+    }
+
+    fn visit_string<E: Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut array = DynArray::new();
+
+        while let Some(value) = seq.next_element::<DynConfigValue>()? {
+            array.push(value).map_err(|err| match err {
+                ArrayError::IncorrectValueType(value_type) => {
+                    A::Error::custom(format!("mixed array (expected a \"{}\" value)", value_type))
+                }
+                ArrayError::IndexOutOfBounds(_) | ArrayError::ArrayEmpty => {
+                    debug_unreachable!("`push()` only returns `IncorrectValueType(_)`")
+                }
+            })?;
+        }
+
+        Ok(Value::Array(array))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut table = DynTable::new();
+
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value::<DynConfigValue>()?;
+
+            let key = NonEmptyStr::new(key.as_str())
+                .ok_or_else(|| A::Error::custom("empty table key"))?;
+
+            if table.set(key, value) {
+                return Err(A::Error::custom(format!("duplicate table key \"{}\"", key)));
+            }
+        }
+
+        Ok(Value::Table(table))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn deserialize() {
+        let config: DynConfig = serde_json::from_str(
+            r#"{"array":[7,7.5],"bool":true,"float":7.0,"int":7,"table":{"empty_array":[],"string":"foo"}}"#,
+        )
+        .unwrap();
+
+        let root = config.root();
+
+        assert_eq!(root.len(), 5);
+
+        let array = root.get_array("array").unwrap();
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.get_val(0).unwrap().get_type(), ValueType::I64);
+        assert_eq!(array.get_val(1).unwrap().get_type(), ValueType::F64);
+
+        assert!(root.get_bool("bool").unwrap());
+        assert_eq!(root.get_val("float").unwrap().get_type(), ValueType::F64);
+        assert_eq!(root.get_val("int").unwrap().get_type(), ValueType::I64);
+        assert_eq!(root.get_i64("int").unwrap(), 7);
+
+        let table = root.get_table("table").unwrap();
+        assert!(table.get_array("empty_array").unwrap().is_empty());
+        assert_eq!(table.get_string("string").unwrap(), "foo");
+
+        // Round trip.
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"array":[7,7.5],"bool":true,"float":7.0,"int":7,"table":{"empty_array":[],"string":"foo"}}"#
+        );
+
+        // Large unsigned integers are deserialized as `U64` values.
+        let config: DynConfig = serde_json::from_str(r#"{"u64":18446744073709551615}"#).unwrap();
+        assert_eq!(config.root().get_u64("u64").unwrap(), u64::MAX);
+
+        // Errors.
+        assert!(serde_json::from_str::<DynConfig>("[7]").is_err()); // Root not a table.
+        assert!(serde_json::from_str::<DynConfig>(r#"{"a":null}"#).is_err()); // Null value.
+        assert!(serde_json::from_str::<DynConfig>(r#"{"":7}"#).is_err()); // Empty key.
+        assert!(serde_json::from_str::<DynConfig>(r#"{"a":7,"a":9}"#).is_err()); // Duplicate key.
+        assert!(serde_json::from_str::<DynConfig>(r#"{"a":[7,true]}"#).is_err());
+        // Mixed array.
+    }
+}
@@ -0,0 +1,557 @@
+use {
+    crate::*,
+    serde::{
+        de::{
+            value::BorrowedStrDeserializer, DeserializeSeed, Deserializer, EnumAccess, Error as _,
+            IntoDeserializer, MapAccess, SeqAccess, Unexpected, VariantAccess, Visitor,
+        },
+        forward_to_deserialize_any,
+    },
+};
+
+/// Implements `Deserializer` and `IntoDeserializer` for a config value type
+/// with an array type `A` and a table type `T`, where `A::iter()` / `T::iter()`
+/// return the values / (key, value) pairs borrowed for `'de`.
+macro_rules! impl_value_deserializer {
+    ($(#[$attr:meta])* $value:ty) => {
+        $(#[$attr])*
+        impl<'de> Deserializer<'de> for $value {
+            type Error = DeserializeError;
+
+            fn deserialize_any<V: Visitor<'de>>(
+                self,
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                match self {
+                    Value::Bool(value) => visitor.visit_bool(value),
+                    Value::I64(value) => visitor.visit_i64(value),
+                    Value::U64(value) => visitor.visit_u64(value),
+                    Value::F64(value) => visitor.visit_f64(value),
+                    Value::String(value) => visitor.visit_borrowed_str(value),
+                    Value::Array(value) => visit_array(value.iter(), visitor),
+                    Value::Table(value) => visit_table(value.iter(), visitor),
+                }
+            }
+
+            fn deserialize_option<V: Visitor<'de>>(
+                self,
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                visitor.visit_some(self)
+            }
+
+            fn deserialize_newtype_struct<V: Visitor<'de>>(
+                self,
+                _name: &'static str,
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                visitor.visit_newtype_struct(self)
+            }
+
+            fn deserialize_enum<V: Visitor<'de>>(
+                self,
+                _name: &'static str,
+                _variants: &'static [&'static str],
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                match self {
+                    // Unit variant.
+                    Value::String(variant) => {
+                        visitor.visit_enum(BorrowedStrDeserializer::new(variant))
+                    }
+                    // Newtype / tuple / struct variant.
+                    Value::Table(table) if table.len() == 1 => {
+                        // Must succeed - the table has one entry.
+                        let (variant, value) =
+                            unwrap_unchecked(table.iter().next(), "empty single-entry table");
+
+                        visitor.visit_enum(ValueEnumAccess {
+                            variant: variant.as_str(),
+                            value,
+                        })
+                    }
+                    value => Err(DeserializeError::invalid_type(
+                        unexpected(&value),
+                        &"a string or a single-key table",
+                    )),
+                }
+            }
+
+            forward_to_deserialize_any! {
+                bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf
+                unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+            }
+        }
+
+        impl<'de> IntoDeserializer<'de, DeserializeError> for $value {
+            type Deserializer = Self;
+
+            fn into_deserializer(self) -> Self::Deserializer {
+                self
+            }
+        }
+    };
+}
+
+#[cfg(feature = "dyn")]
+impl_value_deserializer! {
+    /// Booleans, integers, floats and strings are deserialized as `bool`'s, `i64`'s / `u64`'s, `f64`'s and borrowed `str`'s respectively.
+    /// [`Arrays`] and [`tables`] are deserialized as sequences and maps respectively.
+    ///
+    /// Options are always deserialized as `Some` (missing [`table`] keys deserialize optional struct fields as `None`).
+    /// Enums are deserialized from strings (unit variants) or from single-key [`tables`]
+    /// (newtype / tuple / struct variants, keyed by the variant name).
+    ///
+    /// [`Arrays`]: struct.DynArray.html
+    /// [`tables`]: struct.DynTable.html
+    /// [`table`]: struct.DynTable.html
+    DynConfigValueRef<'de>
+}
+
+#[cfg(feature = "bin")]
+impl_value_deserializer! {
+    /// Reads the values directly from the binary config data blob, without deserializing it to a [`dyn config`];
+    /// strings are borrowed from the data blob.
+    ///
+    /// The type mapping is the same as for [`DynConfigValueRef`].
+    ///
+    /// [`dyn config`]: struct.DynConfig.html
+    /// [`DynConfigValueRef`]: type.DynConfigValueRef.html
+    BinConfigValue<'de>
+}
+
+/// Deserializes the array `values` as a sequence, making sure all of them were consumed by the `visitor`.
+fn visit_array<'de, V, I>(values: I, visitor: V) -> Result<V::Value, DeserializeError>
+where
+    V: Visitor<'de>,
+    I: ExactSizeIterator,
+    I::Item: Deserializer<'de, Error = DeserializeError>,
+{
+    let len = values.len();
+    let mut access = ValueSeqAccess(values);
+
+    let value = visitor.visit_seq(&mut access)?;
+
+    if access.0.next().is_none() {
+        Ok(value)
+    } else {
+        Err(DeserializeError::invalid_length(
+            len,
+            &"fewer elements in the array",
+        ))
+    }
+}
+
+/// Deserializes the table `entries` as a map, making sure all of them were consumed by the `visitor`.
+fn visit_table<'de, V, I, T>(entries: I, visitor: V) -> Result<V::Value, DeserializeError>
+where
+    V: Visitor<'de>,
+    I: ExactSizeIterator<Item = (&'de NonEmptyStr, T)>,
+    T: Deserializer<'de, Error = DeserializeError>,
+{
+    let len = entries.len();
+    let mut access = ValueMapAccess {
+        entries,
+        value: None,
+    };
+
+    let value = visitor.visit_map(&mut access)?;
+
+    if access.entries.next().is_none() {
+        Ok(value)
+    } else {
+        Err(DeserializeError::invalid_length(
+            len,
+            &"fewer entries in the table",
+        ))
+    }
+}
+
+/// Returns the `value` description used in deserialization errors.
+fn unexpected<'a, A, T>(value: &Value<&'a str, A, T>) -> Unexpected<'a> {
+    match *value {
+        Value::Bool(value) => Unexpected::Bool(value),
+        Value::I64(value) => Unexpected::Signed(value),
+        Value::U64(value) => Unexpected::Unsigned(value),
+        Value::F64(value) => Unexpected::Float(value),
+        Value::String(value) => Unexpected::Str(value),
+        Value::Array(_) => Unexpected::Seq,
+        Value::Table(_) => Unexpected::Map,
+    }
+}
+
+struct ValueSeqAccess<I>(I);
+
+impl<'de, I> SeqAccess<'de> for ValueSeqAccess<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Deserializer<'de, Error = DeserializeError>,
+{
+    type Error = DeserializeError;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Self::Error> {
+        self.0
+            .next()
+            .map(|value| seed.deserialize(value))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct ValueMapAccess<I, T> {
+    entries: I,
+    // Value of the last key returned by `next_key_seed`.
+    value: Option<T>,
+}
+
+impl<'de, I, T> MapAccess<'de> for ValueMapAccess<I, T>
+where
+    I: ExactSizeIterator<Item = (&'de NonEmptyStr, T)>,
+    T: Deserializer<'de, Error = DeserializeError>,
+{
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        if let Some((key, value)) = self.entries.next() {
+            self.value.replace(value);
+
+            seed.deserialize(BorrowedStrDeserializer::new(key.as_str()))
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, Self::Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| DeserializeError::custom("table value requested before its key"))?;
+
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct ValueEnumAccess<'de, A, T> {
+    variant: &'de str,
+    value: Value<&'de str, A, T>,
+}
+
+impl<'de, A, T> EnumAccess<'de> for ValueEnumAccess<'de, A, T>
+where
+    Value<&'de str, A, T>: Deserializer<'de, Error = DeserializeError>,
+{
+    type Error = DeserializeError;
+    type Variant = ValueVariantAccess<'de, A, T>;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(BorrowedStrDeserializer::<DeserializeError>::new(
+            self.variant,
+        ))?;
+
+        Ok((variant, ValueVariantAccess(self.value)))
+    }
+}
+
+struct ValueVariantAccess<'de, A, T>(Value<&'de str, A, T>);
+
+impl<'de, A, T> VariantAccess<'de> for ValueVariantAccess<'de, A, T>
+where
+    Value<&'de str, A, T>: Deserializer<'de, Error = DeserializeError>,
+{
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Err(DeserializeError::invalid_type(
+            unexpected(&self.0),
+            &"a unit variant",
+        ))
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<S::Value, Self::Error> {
+        seed.deserialize(self.0)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_map(visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::*,
+        ministr_macro::nestr,
+        serde::Deserialize,
+        std::{
+            collections::{BTreeMap, HashMap},
+            num::NonZeroU32,
+        },
+    };
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum Mode {
+        Fast,
+        Limited(u32),
+        Custom { level: i8, name: String },
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Server<'a> {
+        host: &'a str,
+        port: u16,
+        ratio: f32,
+        enabled: bool,
+        tags: Vec<String>,
+        timeout: Option<u64>,
+        retries: Option<u8>,
+        mode: Mode,
+        fallback_modes: Vec<Mode>,
+        limits: HashMap<String, i64>,
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Config<'a> {
+        #[serde(borrow)]
+        server: Server<'a>,
+        version: (u8, u8),
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn dyn_deserialize_into() {
+        let config = DynConfig::from_json(
+            r#"{
+                "server": {
+                    "host": "localhost",
+                    "port": 8080,
+                    "ratio": 0.5,
+                    "enabled": true,
+                    "tags": ["foo", "bar"],
+                    "timeout": 30,
+                    "mode": "Fast",
+                    "fallback_modes": [{ "Limited": 7 }, { "Custom": { "level": -3, "name": "baz" } }],
+                    "limits": { "memory": 1024, "cpu": 4 }
+                },
+                "version": [1, 2]
+            }"#,
+        )
+        .unwrap();
+
+        let deserialized: Config<'_> = config.deserialize_into().unwrap();
+
+        assert_eq!(
+            deserialized,
+            Config {
+                server: Server {
+                    host: "localhost",
+                    port: 8080,
+                    ratio: 0.5,
+                    enabled: true,
+                    tags: vec!["foo".into(), "bar".into()],
+                    timeout: Some(30),
+                    retries: None,
+                    mode: Mode::Fast,
+                    fallback_modes: vec![
+                        Mode::Limited(7),
+                        Mode::Custom {
+                            level: -3,
+                            name: "baz".into()
+                        }
+                    ],
+                    limits: vec![("memory".into(), 1024), ("cpu".into(), 4)]
+                        .into_iter()
+                        .collect(),
+                },
+                version: (1, 2),
+            }
+        );
+
+        // Untyped maps.
+        let map: BTreeMap<String, Vec<i64>> = DynConfig::from_json(r#"{ "a": [1, 2], "b": [] }"#)
+            .unwrap()
+            .deserialize_into()
+            .unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], vec![1, 2]);
+        assert!(map["b"].is_empty());
+
+        // Errors.
+        #[derive(Deserialize, Debug)]
+        struct Port {
+            #[allow(dead_code)]
+            port: u16,
+        }
+
+        let error = |json: &str| {
+            DynConfig::from_json(json)
+                .unwrap()
+                .deserialize_into::<Port>()
+                .err()
+                .unwrap()
+        };
+
+        // Missing field.
+        assert_eq!(error("{}").message(), "missing field `port`");
+        // Out of range integer.
+        assert!(error(r#"{ "port": 65536 }"#).message().contains("65536"));
+        // Incorrect value type.
+        assert!(error(r#"{ "port": "8080" }"#)
+            .message()
+            .starts_with("invalid type: string \"8080\""));
+
+        // Too many tuple elements.
+        assert!(DynConfig::from_json(r#"{ "version": [1, 2, 3] }"#)
+            .unwrap()
+            .deserialize_into::<BTreeMap<String, (u8, u8)>>()
+            .is_err());
+        // Unknown enum variant.
+        assert!(DynConfig::from_json(r#"{ "mode": "Slow" }"#)
+            .unwrap()
+            .deserialize_into::<BTreeMap<String, Mode>>()
+            .is_err());
+        // Multi-key enum table.
+        assert!(
+            DynConfig::from_json(r#"{ "mode": { "Limited": 7, "Fast": 9 } }"#)
+                .unwrap()
+                .deserialize_into::<BTreeMap<String, Mode>>()
+                .is_err()
+        );
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum Quality {
+        Low,
+        Custom { scale: f64 },
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Graphics<'a> {
+        name: &'a str,
+        fullscreen: bool,
+        resolution: (u32, u32),
+        quality: Quality,
+        presets: Vec<Quality>,
+        vsync: Option<bool>,
+        seed: u64,
+    }
+
+    #[cfg(feature = "bin")]
+    #[test]
+    fn bin_deserialize_into() {
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(7).unwrap()).unwrap();
+        writer.bool(nestr!("fullscreen"), true).unwrap();
+        writer.string(nestr!("name"), "ultra").unwrap();
+        writer.array(nestr!("presets"), 2).unwrap();
+        writer.table(None, 1).unwrap();
+        writer.table(nestr!("Custom"), 1).unwrap();
+        writer.f64(nestr!("scale"), 0.5).unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+        writer.table(None, 1).unwrap();
+        writer.table(nestr!("Custom"), 1).unwrap();
+        writer.f64(nestr!("scale"), 2.0).unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+        writer.end().unwrap();
+        writer.string(nestr!("quality"), "Low").unwrap();
+        writer.array(nestr!("resolution"), 2).unwrap();
+        writer.i64(None, 1920).unwrap();
+        writer.i64(None, 1080).unwrap();
+        writer.end().unwrap();
+        writer.u64(nestr!("seed"), u64::MAX).unwrap();
+        writer.i64(nestr!("unused"), 7).unwrap();
+        let data = writer.finish().unwrap();
+
+        let expected = Graphics {
+            name: "ultra",
+            fullscreen: true,
+            resolution: (1920, 1080),
+            quality: Quality::Low,
+            presets: vec![
+                Quality::Custom { scale: 0.5 },
+                Quality::Custom { scale: 2.0 },
+            ],
+            vsync: None,
+            seed: u64::MAX,
+        };
+
+        let config = BinConfig::new(data.clone()).unwrap();
+        assert_eq!(config.deserialize_into::<Graphics<'_>>().unwrap(), expected);
+
+        // Strings borrow the data blob and outlive the config.
+        let graphics: Graphics<'_> = BinConfigRef::new(&data)
+            .unwrap()
+            .deserialize_into()
+            .unwrap();
+        assert_eq!(graphics, expected);
+        assert!(data.as_ptr_range().contains(&graphics.name.as_ptr()));
+
+        // Same as the dyn config.
+        #[cfg(feature = "dyn")]
+        {
+            let dyn_config = config.to_dyn_config();
+            assert_eq!(
+                dyn_config.deserialize_into::<Graphics<'_>>().unwrap(),
+                expected
+            );
+        }
+
+        // Untyped maps.
+        let mut writer = BinConfigWriter::new(NonZeroU32::new(2).unwrap()).unwrap();
+        writer.i64(nestr!("a"), 1).unwrap();
+        writer.i64(nestr!("b"), 2).unwrap();
+        let data = writer.finish().unwrap();
+
+        let map: BTreeMap<String, u8> = BinConfig::new(data).unwrap().deserialize_into().unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], 1);
+        assert_eq!(map["b"], 2);
+
+        // Errors.
+        #[derive(Deserialize, Debug)]
+        struct Seed {
+            #[allow(dead_code)]
+            seed: i64,
+        }
+
+        // `u64::MAX` does not fit into an `i64`.
+        assert!(config.deserialize_into::<Seed>().is_err());
+        // Incorrect value type.
+        assert!(config.deserialize_into::<BTreeMap<String, bool>>().is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+/// An error returned when deserializing a type from a [`config`] or a [`value`],
+/// e.g. by [`DynConfig::deserialize_into`].
+///
+/// Contains the error message, e.g. reported by the deserialized type for a missing field
+/// or a value of unexpected type.
+///
+/// [`config`]: struct.DynConfig.html
+/// [`value`]: type.DynConfigValueRef.html
+/// [`DynConfig::deserialize_into`]: struct.DynConfig.html#method.deserialize_into
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DeserializeError(String);
+
+impl DeserializeError {
+    /// Returns the error message.
+    pub fn message(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Error for DeserializeError {}
+
+impl Display for DeserializeError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl serde::de::Error for DeserializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        DeserializeError(msg.to_string())
+    }
+}
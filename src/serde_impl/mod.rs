@@ -0,0 +1,13 @@
+mod ser;
+
+#[cfg(feature = "dyn")]
+mod de;
+
+#[cfg(any(feature = "bin", feature = "dyn"))]
+mod deserializer;
+
+#[cfg(any(feature = "bin", feature = "dyn"))]
+mod error;
+
+#[cfg(any(feature = "bin", feature = "dyn"))]
+pub use error::*;
@@ -0,0 +1,115 @@
+use {
+    crate::*,
+    serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer},
+};
+
+/// Booleans, integers, floats and strings are serialized as `bool`'s, `i64`'s, `f64`'s and `str`'s respectively.
+/// Arrays and tables are serialized as sequences and maps respectively.
+impl<S, A, T> Serialize for Value<S, A, T>
+where
+    S: AsRef<str>,
+    A: Serialize,
+    T: Serialize,
+{
+    fn serialize<SE: Serializer>(&self, serializer: SE) -> Result<SE::Ok, SE::Error> {
+        match self {
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::I64(value) => serializer.serialize_i64(*value),
+            Value::U64(value) => serializer.serialize_u64(*value),
+            Value::F64(value) => serializer.serialize_f64(*value),
+            Value::String(value) => serializer.serialize_str(value.as_ref()),
+            Value::Array(value) => value.serialize(serializer),
+            Value::Table(value) => value.serialize(serializer),
+        }
+    }
+}
+
+/// Serialized as a sequence of [`values`].
+///
+/// [`values`]: type.DynConfigValueRef.html
+#[cfg(feature = "dyn")]
+impl Serialize for DynArray {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len() as usize))?;
+
+        for value in self.iter() {
+            seq.serialize_element(&value)?;
+        }
+
+        seq.end()
+    }
+}
+
+/// Serialized as a map of string keys to [`values`], in alphabetical key order.
+///
+/// [`values`]: type.DynConfigValueRef.html
+#[cfg(feature = "dyn")]
+impl Serialize for DynTable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len() as usize))?;
+
+        // Sort the keys in canonical order.
+        let mut entries: Vec<_> = self.iter().collect();
+        sort_by_table_key(&mut entries, |(key, _)| key.as_str());
+
+        for (key, value) in entries {
+            map.serialize_entry(key.as_str(), &value)?;
+        }
+
+        map.end()
+    }
+}
+
+/// Serialized as a map (the root [`table`]).
+///
+/// [`table`]: struct.DynTable.html
+#[cfg(feature = "dyn")]
+impl Serialize for DynConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.root().serialize(serializer)
+    }
+}
+
+#[cfg(all(test, feature = "dyn"))]
+mod tests {
+    use {crate::*, ministr_macro::nestr};
+
+    #[test]
+    fn serialize() {
+        let mut config = DynConfig::new();
+        let root = config.root_mut();
+
+        let mut array = DynArray::new();
+        array.push(Value::I64(7)).unwrap();
+        array.push(Value::F64(7.5)).unwrap();
+        assert!(!root.set(nestr!("array"), array));
+
+        assert!(!root.set(nestr!("bool"), true));
+        assert!(!root.set(nestr!("float"), 7.0));
+        assert!(!root.set(nestr!("int"), 7));
+
+        let mut table = DynTable::new();
+        assert!(!table.set(nestr!("string"), "foo"));
+        assert!(!table.set(nestr!("empty_array"), DynArray::new()));
+        assert!(!root.set(nestr!("table"), table));
+
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"array":[7,7.5],"bool":true,"float":7.0,"int":7,"table":{"empty_array":[],"string":"foo"}}"#
+        );
+
+        // Values.
+        assert_eq!(
+            serde_json::to_string(&config.root().get_val("int").unwrap()).unwrap(),
+            "7"
+        );
+        assert_eq!(
+            serde_json::to_string(&DynConfigValue::F64(-0.5)).unwrap(),
+            "-0.5"
+        );
+        assert_eq!(
+            serde_json::to_string(&DynConfigValue::String("bar".into())).unwrap(),
+            r#""bar""#
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use {
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// An actual concrete error kind returned by the TOML config [`parser`].
+///
+/// [`parser`]: struct.DynConfig.html#method.from_toml
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TOMLErrorKind {
+    /// The TOML string is malformed.
+    /// Contains the TOML parser error message, including the position in the source string, if known.
+    InvalidTOML(String),
+    /// Empty table keys are invalid.
+    EmptyKey,
+    /// Datetime value encountered, but not allowed by [`options`].
+    ///
+    /// [`options`]: enum.TOMLDatetimes.html
+    Datetime,
+    /// Mixed value types in an array - all array elements must have the same type.
+    MixedArray,
+    /// Nested tables / arrays exceed the maximum supported depth.
+    /// The error path is empty, as the input is checked before it is parsed by the `toml` crate,
+    /// unless the nesting depth could only be determined when parsing it.
+    ///
+    /// See [`max_depth`](struct.TOMLOptions.html#structfield.max_depth).
+    DepthExceeded,
+}
+
+impl Error for TOMLErrorKind {}
+
+impl Display for TOMLErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use TOMLErrorKind::*;
+
+        match self {
+            InvalidTOML(message) => write!(f, "invalid TOML: {}", message),
+            EmptyKey => "empty table keys are invalid".fmt(f),
+            Datetime => "datetime values are not allowed by options".fmt(f),
+            MixedArray => "mixed value types in an array".fmt(f),
+            DepthExceeded => "nested tables / arrays exceed the maximum supported depth".fmt(f),
+        }
+    }
+}
+
+/// An error returned by the TOML config [`parser`].
+///
+/// [`parser`]: struct.DynConfig.html#method.from_toml
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TOMLError {
+    /// Path to the key / value in which the error happened, or an empty path for the root table.
+    pub path: ConfigPath,
+    /// Actual error.
+    pub error: TOMLErrorKind,
+}
+
+impl Error for TOMLError {}
+
+impl Display for TOMLError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "TOML parse error; path: {}, error: {}",
+            self.path, self.error
+        )
+    }
+}
+
+/// An error returned by `to_toml_string` / `fmt_toml` methods on [`dyn`] configs.
+///
+/// [`dyn`]: struct.DynConfig.html#method.to_toml_string
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ToTOMLStringError {
+    /// Encountered a `U64` value which does not fit into a TOML (64-bit signed) integer.
+    /// Contains the path to the value.
+    IntegerOutOfRange(ConfigPath),
+    /// The TOML serializer failed.
+    /// Contains the TOML serializer error message.
+    SerializationError(String),
+    /// General write error.
+    WriteError,
+}
+
+impl ToTOMLStringError {
+    /// Pushes the table key / array index to the back of the path if the error has one.
+    pub(crate) fn push_key<K: Into<OwnedConfigKey>>(mut self, key: K) -> Self {
+        use ToTOMLStringError::*;
+
+        match &mut self {
+            IntegerOutOfRange(path) => path.0.push(key.into()),
+            SerializationError(_) | WriteError => {}
+        };
+
+        self
+    }
+
+    /// Reverses the path if the error has one.
+    /// Must do this because path elements were pushed to the back of the `Vec`
+    /// when unwinding the stack on error.
+    pub(crate) fn reverse(mut self) -> Self {
+        use ToTOMLStringError::*;
+
+        match &mut self {
+            IntegerOutOfRange(path) => path.0.reverse(),
+            SerializationError(_) | WriteError => {}
+        };
+
+        self
+    }
+}
+
+impl From<std::fmt::Error> for ToTOMLStringError {
+    fn from(_: std::fmt::Error) -> Self {
+        Self::WriteError
+    }
+}
+
+impl Error for ToTOMLStringError {}
+
+impl Display for ToTOMLStringError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use ToTOMLStringError::*;
+
+        match self {
+            IntegerOutOfRange(path) => write!(
+                f,
+                "encountered an unsigned integer value out of TOML integer range at {}",
+                path
+            ),
+            SerializationError(message) => write!(f, "TOML serialization error: {}", message),
+            WriteError => "general write error".fmt(f),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+/// Controls how TOML datetime values (offset / local datetimes, local dates and local times),
+/// if any, are parsed to a [`config`](struct.DynConfig.html).
+///
+/// The config has no datetime value type.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TOMLDatetimes {
+    /// Parse datetime values as strings in RFC 3339 format (e.g. `"1979-05-27T07:32:00Z"`).
+    /// NOTE - the value type is lost, and the value is serialized back to TOML as a string.
+    String,
+    /// Do not allow datetime values (strict mode).
+    /// Parsing fails with a [`Datetime`](enum.TOMLErrorKind.html#variant.Datetime) error.
+    Forbid,
+}
+
+/// Configuration options for parsing a TOML string to a [`config`](struct.DynConfig.html).
+#[derive(Clone, Copy, Debug)]
+pub struct TOMLOptions {
+    /// Datetime value handling policy.
+    ///
+    /// Default: [`String`](enum.TOMLDatetimes.html#variant.String).
+    pub datetimes: TOMLDatetimes,
+    /// Maximum supported depth of nested tables / arrays.
+    /// Values of the root table are at depth `1`.
+    /// Deeper nested tables / arrays cause a [`DepthExceeded`](enum.TOMLErrorKind.html#variant.DepthExceeded) error.
+    ///
+    /// Protects against stack overflow when parsing TOML strings from untrusted sources.
+    ///
+    /// Default: `128`.
+    pub max_depth: u32,
+}
+
+impl Default for TOMLOptions {
+    fn default() -> Self {
+        Self {
+            datetimes: TOMLDatetimes::String,
+            max_depth: 128,
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use {crate::*, toml_rs::Value as TOMLValue};
+
+/// Internal parse result - error kind and the (reversed) path to the element where the error occured.
+type TOMLParseResult<T> = Result<T, (TOMLErrorKind, ConfigPath)>;
+
+/// Parses the TOML config `string` to a [`dynamic config`].
+///
+/// The actual TOML parsing is done by the `toml` crate;
+/// this only converts the resulting TOML document to the [`dynamic config`].
+///
+/// [`dynamic config`]: struct.DynConfig.html
+pub(crate) struct TOMLParser<'s> {
+    /// Source string.
+    string: &'s str,
+    /// Parsing options as provided by the user.
+    options: TOMLOptions,
+}
+
+impl<'s> TOMLParser<'s> {
+    pub(crate) fn new(string: &'s str, options: TOMLOptions) -> Self {
+        Self { string, options }
+    }
+
+    /// Consumes the parser and tries to parse the TOML config string.
+    pub(crate) fn parse(self) -> Result<DynConfig, TOMLError> {
+        self.parse_root().map_err(|(error, mut path)| {
+            path.0.reverse();
+
+            TOMLError { path, error }
+        })
+    }
+
+    fn parse_root(&self) -> TOMLParseResult<DynConfig> {
+        use TOMLErrorKind::*;
+
+        // The `toml` crate parses nested arrays / inline tables recursively with no depth limit,
+        // so make sure we don't overflow the stack on deeply nested input.
+        if self.exceeds_max_depth() {
+            return Err(Self::error(DepthExceeded));
+        }
+
+        let table = toml_rs::from_str::<toml_rs::value::Table>(self.string)
+            .map_err(|err| Self::error(InvalidTOML(err.to_string())))?;
+
+        let mut config = DynConfig::new();
+
+        *config.root_mut() = self.parse_table(table, 1)?;
+
+        Ok(config)
+    }
+
+    /// `depth` is the nesting depth of the `table`'s values (`1` for the root table).
+    fn parse_table(&self, table: toml_rs::value::Table, depth: u32) -> TOMLParseResult<DynTable> {
+        use TOMLErrorKind::*;
+
+        let mut result = DynTable::with_capacity(table.len() as u32);
+
+        for (key, value) in table {
+            let key = NonEmptyStr::new(&key).ok_or_else(|| Self::error(EmptyKey))?;
+
+            let value = self
+                .parse_value(value, depth)
+                .map_err(|err| err.push_key(key))?;
+
+            result.set(key, value);
+        }
+
+        Ok(result)
+    }
+
+    /// `depth` is the nesting depth of the `array`'s values.
+    fn parse_array(&self, array: toml_rs::value::Array, depth: u32) -> TOMLParseResult<DynArray> {
+        use TOMLErrorKind::*;
+
+        let mut result = DynArray::with_capacity(array.len() as u32);
+
+        for value in array {
+            let index = result.len();
+
+            let value = self
+                .parse_value(value, depth)
+                .map_err(|err| err.push_key(index))?;
+
+            result
+                .push(value)
+                .map_err(|_| Self::error(MixedArray).push_key(index))?;
+        }
+
+        Ok(result)
+    }
+
+    /// `depth` is the nesting depth of the `value`.
+    fn parse_value(&self, value: TOMLValue, depth: u32) -> TOMLParseResult<DynConfigValue> {
+        use TOMLErrorKind::*;
+
+        Ok(match value {
+            TOMLValue::Array(_) | TOMLValue::Table(_) if depth > self.options.max_depth => {
+                return Err(Self::error(DepthExceeded))
+            }
+            TOMLValue::Boolean(value) => Value::Bool(value),
+            TOMLValue::Integer(value) => Value::I64(value),
+            TOMLValue::Float(value) => Value::F64(value),
+            TOMLValue::String(value) => Value::String(value),
+            TOMLValue::Datetime(value) => match self.options.datetimes {
+                TOMLDatetimes::String => Value::String(value.to_string()),
+                TOMLDatetimes::Forbid => return Err(Self::error(Datetime)),
+            },
+            TOMLValue::Array(value) => Value::Array(self.parse_array(value, depth + 1)?),
+            TOMLValue::Table(value) => Value::Table(self.parse_table(value, depth + 1)?),
+        })
+    }
+
+    /// Scans the source string for nested tables / arrays (table headers, dotted keys, arrays and inline tables)
+    /// and returns `true` if any of them is nested deeper than allowed by `options`.
+    ///
+    /// Only tracks the syntax needed to determine the nesting depth (skipping strings and comments),
+    /// leaving the validation to the `toml` crate -
+    /// malformed input is either rejected here or by the `toml` crate later.
+    fn exceeds_max_depth(&self) -> bool {
+        let max_depth = self.options.max_depth;
+        let string = self.string.as_bytes();
+
+        // Nesting depth of the table the current top-level keys belong to (`0` for the root table).
+        let mut header_depth = 0;
+        // Nesting depths of the enclosing arrays / inline tables and whether they are (inline) tables.
+        let mut containers: Vec<(u32, bool)> = Vec::new();
+        // Whether we are parsing a key (as opposed to a value).
+        let mut in_key = true;
+        // Number of the dotted key segments.
+        let mut key_segments = 1;
+        // Nesting depth of the value being parsed, if it is not an array element.
+        let mut value_depth = 0;
+
+        let mut i = 0;
+
+        while let Some(&c) = string.get(i) {
+            match c {
+                b'#' => {
+                    while !matches!(string.get(i), None | Some(b'\n')) {
+                        i += 1;
+                    }
+                    continue;
+                }
+                b'"' | b'\'' => {
+                    i = Self::skip_string(string, i);
+                    continue;
+                }
+                b'\n' if containers.is_empty() => {
+                    in_key = true;
+                    key_segments = 1;
+                }
+                // Table header.
+                b'[' if in_key && containers.is_empty() => {
+                    let array = string.get(i + 1) == Some(&b'[');
+
+                    let mut segments = 1;
+
+                    while let Some(&c) = string.get(i) {
+                        match c {
+                            b'"' | b'\'' => {
+                                i = Self::skip_string(string, i);
+                                continue;
+                            }
+                            b'.' => segments += 1,
+                            b']' | b'\n' => break,
+                            _ => {}
+                        }
+                        i += 1;
+                    }
+
+                    // Elements of arrays of tables are nested one level deeper than the array.
+                    header_depth = if array { segments + 1 } else { segments };
+
+                    if header_depth > max_depth {
+                        return true;
+                    }
+                }
+                b'.' if in_key => key_segments += 1,
+                b'=' if in_key => {
+                    let base_depth = containers.last().map_or(header_depth, |&(depth, _)| depth);
+
+                    value_depth = base_depth + key_segments;
+
+                    // Dotted keys define nested tables.
+                    if value_depth - 1 > max_depth {
+                        return true;
+                    }
+
+                    in_key = false;
+                    key_segments = 1;
+                }
+                b'[' | b'{' => {
+                    let depth = match containers.last() {
+                        Some(&(depth, false)) => depth + 1,
+                        _ => value_depth,
+                    };
+
+                    if depth > max_depth {
+                        return true;
+                    }
+
+                    let table = c == b'{';
+
+                    containers.push((depth, table));
+                    in_key = table;
+                }
+                b']' | b'}' => {
+                    containers.pop();
+                    in_key = false;
+                }
+                b',' => {
+                    in_key = matches!(containers.last(), Some((_, true)));
+                }
+                _ => {}
+            }
+
+            i += 1;
+        }
+
+        false
+    }
+
+    /// Returns the index of the byte following the (possibly unterminated) string
+    /// starting with a quote at index `i` in the source `string`.
+    fn skip_string(string: &[u8], mut i: usize) -> usize {
+        let quote = string[i];
+        // Only basic strings support escape sequences.
+        let escape = quote == b'"';
+
+        let count_quotes = |i: usize| string[i..].iter().take_while(|&&c| c == quote).count();
+
+        // Multi-line strings.
+        if count_quotes(i) >= 3 {
+            i += 3;
+
+            while let Some(&c) = string.get(i) {
+                if escape && c == b'\\' {
+                    i += 2;
+                } else if c == quote && count_quotes(i) >= 3 {
+                    // Up to two quotes may precede the closing delimiter.
+                    return i + count_quotes(i).min(5);
+                } else {
+                    i += 1;
+                }
+            }
+
+            return i;
+        }
+
+        i += 1;
+
+        while let Some(&c) = string.get(i) {
+            if escape && c == b'\\' {
+                i += 2;
+            } else if c == quote {
+                return i + 1;
+            } else if c == b'\n' {
+                return i;
+            } else {
+                i += 1;
+            }
+        }
+
+        i
+    }
+
+    /// Error helper method.
+    fn error(error: TOMLErrorKind) -> (TOMLErrorKind, ConfigPath) {
+        (error, ConfigPath::new())
+    }
+}
+
+trait PushKey {
+    fn push_key<K: Into<OwnedConfigKey>>(self, key: K) -> Self;
+}
+
+impl PushKey for (TOMLErrorKind, ConfigPath) {
+    /// Pushes the table key / array index to the back of the path.
+    fn push_key<K: Into<OwnedConfigKey>>(mut self, key: K) -> Self {
+        self.1 .0.push(key.into());
+        self
+    }
+}
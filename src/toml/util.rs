@@ -0,0 +1,32 @@
+use {crate::*, std::convert::TryFrom, toml_rs::Value as TOMLValue};
+
+/// A trait implemented by configs serializable to a TOML string.
+///
+/// The config is first converted to a TOML document, which is then serialized by the `toml` crate.
+pub(crate) trait DisplayTOML {
+    /// Converts the value to a TOML value.
+    /// Pushes the table keys / array indices to the error path on error.
+    fn to_toml_value(&self) -> Result<TOMLValue, ToTOMLStringError>;
+}
+
+impl<S, A, T> DisplayTOML for Value<S, A, T>
+where
+    S: AsRef<str>,
+    A: DisplayTOML,
+    T: DisplayTOML,
+{
+    fn to_toml_value(&self) -> Result<TOMLValue, ToTOMLStringError> {
+        Ok(match self {
+            Value::Bool(value) => TOMLValue::Boolean(*value),
+            Value::I64(value) => TOMLValue::Integer(*value),
+            Value::U64(value) => TOMLValue::Integer(
+                <i64 as TryFrom<u64>>::try_from(*value)
+                    .map_err(|_| ToTOMLStringError::IntegerOutOfRange(ConfigPath::new()))?,
+            ),
+            Value::F64(value) => TOMLValue::Float(*value),
+            Value::String(value) => TOMLValue::String(value.as_ref().to_owned()),
+            Value::Array(value) => value.to_toml_value()?,
+            Value::Table(value) => value.to_toml_value()?,
+        })
+    }
+}
@@ -211,7 +211,7 @@ impl<'a> Display for ConfigKey<'a> {
 ///
 /// [`table`]: enum.Value.html#variant.Table
 /// [`array`]: enum.Value.html#variant.Array
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum OwnedConfigKey {
     /// A string [`table`] key.
     ///
@@ -253,7 +253,7 @@ impl Display for OwnedConfigKey {
 /// Describes the full path to a config element.
 /// Empty path means the root table.
 /// Used in error reporting by config accessors and parsers.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct ConfigPath(pub Vec<OwnedConfigKey>);
 
 impl ConfigPath {
@@ -276,7 +276,7 @@ impl Display for ConfigPath {
             for (key_index, key) in self.0.iter().enumerate() {
                 key.fmt(f)?;
 
-                if !key_index == (self.0.len() - 1) {
+                if key_index != (self.0.len() - 1) {
                     '/'.fmt(f)?;
                 }
             }
@@ -39,8 +39,8 @@ pub(crate) fn write_char<W: Write>(
             '\t' => r#"\t"#,
             '\n' => r#"\n"#,
             '\r' => r#"\r"#,
-            '\x0b' => r#"\x0b"#,  // \v
-            '\x0c' => r#"\\x0c"#, // \f
+            '\x0b' => r#"\x0b"#, // \v
+            '\x0c' => r#"\x0c"#, // \f
 
             '"' => r#"\""#,
 
@@ -1,23 +1,256 @@
-use {crate::*, std::fmt::Write};
+use {
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter, Write},
+    },
+};
 
 pub(crate) trait DisplayLua {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result;
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError>;
 
-    fn do_indent<W: Write>(w: &mut W, indent: u32) -> std::fmt::Result {
+    fn do_indent<W: Write>(w: &mut W, indent: u32, options: LuaDisplayOptions) -> std::fmt::Result {
         for _ in 0..indent {
-            w.write_char('\t')?;
+            match options.indent {
+                LuaIndent::Tabs => w.write_char('\t')?,
+                LuaIndent::Spaces(num_spaces) => {
+                    for _ in 0..num_spaces {
+                        w.write_char(' ')?;
+                    }
+                }
+            }
         }
 
         Ok(())
     }
+
+    /// Writes the opening brace of a table / array, followed by a newline unless in compact mode.
+    fn write_lua_open<W: Write>(w: &mut W, options: LuaDisplayOptions) -> std::fmt::Result {
+        if options.compact {
+            w.write_char('{')
+        } else {
+            writeln!(w, "{{")
+        }
+    }
+
+    /// Writes the indentation (or, in compact mode, the space) preceding a table / array element.
+    fn write_lua_element_start<W: Write>(
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> std::fmt::Result {
+        if options.compact {
+            w.write_char(' ')
+        } else {
+            Self::do_indent(w, indent + 1, options)
+        }
+    }
+
+    /// Writes the comma following a table / array element,
+    /// unless it is the `last` element and trailing commas are disabled.
+    fn write_lua_comma<W: Write>(
+        w: &mut W,
+        last: bool,
+        options: LuaDisplayOptions,
+    ) -> std::fmt::Result {
+        if !last || options.trailing_comma {
+            w.write_char(',')?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the newline following a table / array element, unless in compact mode.
+    fn write_lua_element_end<W: Write>(w: &mut W, options: LuaDisplayOptions) -> std::fmt::Result {
+        if options.compact {
+            Ok(())
+        } else {
+            writeln!(w)
+        }
+    }
+
+    /// Writes the indentation (or, in compact mode, the space if the table / array is not `empty`)
+    /// and the closing brace of a table / array.
+    fn write_lua_close<W: Write>(
+        w: &mut W,
+        indent: u32,
+        empty: bool,
+        options: LuaDisplayOptions,
+    ) -> std::fmt::Result {
+        if options.compact {
+            if !empty {
+                w.write_char(' ')?;
+            }
+        } else {
+            Self::do_indent(w, indent, options)?;
+        }
+
+        w.write_char('}')
+    }
+}
+
+/// Indentation of nested values in the Lua script string serialized from a config.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LuaIndent {
+    /// Each nesting level is indented by a single tab character (`'\t'`).
+    Tabs,
+    /// Each nesting level is indented by the number of space characters (`' '`).
+    Spaces(u32),
+}
+
+/// Configuration options for serializing a config to a Lua script string.
+#[derive(Clone, Copy, Debug)]
+pub struct LuaDisplayOptions {
+    /// Indentation of nested values.
+    ///
+    /// Default: [`Tabs`](enum.LuaIndent.html#variant.Tabs).
+    pub indent: LuaIndent,
+    /// Whether nested tables / arrays are followed by a comment with their key / index
+    /// (e.g. `}, -- key`).
+    /// Ignored in [`compact`](#structfield.compact) mode.
+    ///
+    /// Default: `true`.
+    pub key_comments: bool,
+    /// Whether table keys are written in alphabetical order.
+    /// If `false`, the order is unspecified.
+    ///
+    /// Default: `true`.
+    pub sort_keys: bool,
+    /// Whether the tables / arrays are written on a single line (e.g. `{ a = 1, b = 2 }`),
+    /// without indentation and key comments.
+    /// If `false`, each table / array element is written on its own indented line.
+    ///
+    /// Default: `false`.
+    pub compact: bool,
+    /// Whether the last table / array element is followed by a comma.
+    ///
+    /// Default: `true`.
+    pub trailing_comma: bool,
+    /// Maximum nesting depth of tables / arrays (the root table is at depth `0`).
+    /// Deeper nested tables / arrays cause a [`DepthExceeded`](enum.ToLuaStringError.html#variant.DepthExceeded) error.
+    ///
+    /// NOTE: the configs' `Display` implementations ignore this limit.
+    ///
+    /// Default: `128`.
+    pub max_depth: u32,
+}
+
+impl LuaDisplayOptions {
+    /// Returns `true` if nested tables / arrays are followed by a comment with their key / index.
+    pub(crate) fn write_key_comments(&self) -> bool {
+        self.key_comments && !self.compact
+    }
+
+    /// Default options with no nesting depth limit, used by the configs' `Display` implementations.
+    pub(crate) fn unbounded() -> Self {
+        Self {
+            max_depth: u32::MAX,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for LuaDisplayOptions {
+    fn default() -> Self {
+        Self {
+            indent: LuaIndent::Tabs,
+            key_comments: true,
+            sort_keys: true,
+            compact: false,
+            trailing_comma: true,
+            max_depth: 128,
+        }
+    }
+}
+
+/// An error returned when serializing a config to a Lua script string.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ToLuaStringError {
+    /// Encountered a nested table / array deeper than allowed by [`options`].
+    /// Contains the path to the value.
+    ///
+    /// [`options`]: struct.LuaDisplayOptions.html#structfield.max_depth
+    DepthExceeded(ConfigPath),
+    /// General write error.
+    WriteError,
+}
+
+impl ToLuaStringError {
+    /// Pushes the table key / array index to the back of the path if the error has one.
+    pub(crate) fn push_key<K: Into<OwnedConfigKey>>(mut self, key: K) -> Self {
+        use ToLuaStringError::*;
+
+        match &mut self {
+            DepthExceeded(path) => path.0.push(key.into()),
+            WriteError => {}
+        };
+
+        self
+    }
+
+    /// Reverses the path if the error has one.
+    /// Must do this because path elements were pushed to the back of the `Vec`
+    /// when unwinding the stack on error.
+    pub(crate) fn reverse(mut self) -> Self {
+        use ToLuaStringError::*;
+
+        match &mut self {
+            DepthExceeded(path) => path.0.reverse(),
+            WriteError => {}
+        };
+
+        self
+    }
+}
+
+impl From<std::fmt::Error> for ToLuaStringError {
+    fn from(_: std::fmt::Error) -> Self {
+        Self::WriteError
+    }
+}
+
+impl Error for ToLuaStringError {}
+
+impl Display for ToLuaStringError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use ToLuaStringError::*;
+
+        match self {
+            DepthExceeded(path) => write!(
+                f,
+                "encountered a nested table / array deeper than allowed by options at {}",
+                path
+            ),
+            WriteError => "general write error".fmt(f),
+        }
+    }
 }
 
 /// Writes the `string` to the writer `w`, enclosing it in quotes and escaping special characters
-/// ('\\', '\0', '\a', '\b', '\t', '\n', '\r', '\v', '\f') and double quotes ('"').
+/// ('\\', '\0', '\a', '\b', '\t', '\n', '\r', '\v', '\f'), double quotes ('"')
+/// and other control characters (as hexadecimal / Unicode escape sequences),
+/// so that Lua reads it back as the identical string.
 pub(crate) fn write_lua_string<W: Write>(w: &mut W, string: &str) -> std::fmt::Result {
     w.write_char('"')?;
 
     for c in string.chars() {
+        // Special characters are handled below.
+        if c.is_control() && !matches!(c, '\0' | '\x07'..='\r') {
+            // NOTE - Lua `\x` escape sequences are bytes, not Unicode scalar values.
+            if c.is_ascii() {
+                write!(w, "\\x{:02x}", c as u32)?;
+            } else {
+                write!(w, "\\u{{{:x}}}", c as u32)?;
+            }
+
+            continue;
+        }
+
         write_char(w, c, false, true, true).map_err(|err| match err {
             WriteCharError::WriteError => std::fmt::Error,
             WriteCharError::EscapedCharacter(_) => debug_unreachable!(
@@ -29,8 +262,36 @@ pub(crate) fn write_lua_string<W: Write>(w: &mut W, string: &str) -> std::fmt::R
     w.write_char('"')
 }
 
+/// Writes the integer `value` to the writer `w`.
+/// `i64::MIN` is written as an expression, because its absolute value overflows a Lua integer literal,
+/// which would make Lua read it as a float.
+pub(crate) fn write_lua_i64<W: Write>(w: &mut W, value: i64) -> std::fmt::Result {
+    if value == i64::MIN {
+        write!(w, "({} - 1)", i64::MIN + 1)
+    } else {
+        write!(w, "{}", value)
+    }
+}
+
+/// Writes the float `value` to the writer `w`.
+/// Finite values always include the decimal point / exponent so that Lua reads them back as floats
+/// (which also preserves the sign of `-0.0`).
+/// Non-finite values are written as division expressions, as Lua has no literals for them.
+pub(crate) fn write_lua_f64<W: Write>(w: &mut W, value: f64) -> std::fmt::Result {
+    if value.is_finite() {
+        // `Debug` always writes the decimal point / exponent, unlike `Display`.
+        write!(w, "{:?}", value)
+    } else if value.is_nan() {
+        w.write_str("(0/0)")
+    } else if value.is_sign_positive() {
+        w.write_str("(1/0)")
+    } else {
+        w.write_str("(-1/0)")
+    }
+}
+
 /// Writes the Lua table `key` to the writer `w`.
-/// Writes the string as-is if it's a valid Lua identifier,
+/// Writes the string as-is if it's a valid Lua identifier (and not a reserved word),
 /// otherwise encloses it in brackets and quotes, and escapes special characters
 /// ('\\', '\0', '\a', '\b', '\t', '\n', '\r', '\v', '\f') and quotes ('"').
 pub(crate) fn write_lua_key<W: Write>(w: &mut W, key: &NonEmptyStr) -> std::fmt::Result {
@@ -44,8 +305,13 @@ pub(crate) fn write_lua_key<W: Write>(w: &mut W, key: &NonEmptyStr) -> std::fmt:
 }
 
 /// Returns `true` if the non-empty string `key` is a valid Lua identifier.
-/// Lua identifiers start with an ASCII letter and may contain ASCII letters, digits and underscores.
+/// Lua identifiers start with an ASCII letter or an underscore and may contain ASCII letters, digits and underscores,
+/// and may not be reserved words.
 fn is_lua_identifier_key(key: &NonEmptyStr) -> bool {
+    if LUA_RESERVED_WORDS.contains(&key.as_str()) {
+        return false;
+    }
+
     for (idx, key_char) in key.as_str().chars().enumerate() {
         if !is_lua_identifier_char(key_char, idx == 0) {
             return false;
@@ -55,8 +321,14 @@ fn is_lua_identifier_key(key: &NonEmptyStr) -> bool {
     true
 }
 
+/// Lua reserved words, which may not be used as identifiers.
+const LUA_RESERVED_WORDS: [&str; 22] = [
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if", "in",
+    "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
 /// Returns `true` if the char `c` is a valid Lua identifier character.
-/// Lua identifiers start with an ASCII letter and may contain ASCII letters, digits and underscores.
+/// Lua identifiers start with an ASCII letter or an underscore and may contain ASCII letters, digits and underscores.
 fn is_lua_identifier_char(c: char, first: bool) -> bool {
-    c.is_ascii_alphabetic() || (!first && ((c == '_') || c.is_ascii_digit()))
+    c.is_ascii_alphabetic() || (c == '_') || (!first && c.is_ascii_digit())
 }
@@ -0,0 +1,72 @@
+use std::{fmt, io};
+
+/// Adapts an [`io::Write`] sink (e.g. a file) to the [`fmt::Write`] trait
+/// accepted by the `fmt_*` config serialization methods,
+/// so that the serialized config is streamed to the sink instead of being buffered in a `String`.
+///
+/// `fmt::Error` carries no information, so the underlying I/O error, if any, is stored in the adapter -
+/// see [`error`](#method.error).
+///
+/// NOTE: serialization performs many small writes, so the sink should be buffered (e.g. with a [`io::BufWriter`]).
+///
+/// [`io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+/// [`fmt::Write`]: https://doc.rust-lang.org/std/fmt/trait.Write.html
+/// [`io::BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+pub struct IoWriter<W: io::Write> {
+    inner: W,
+    // First I/O error, if any.
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoWriter<W> {
+    /// Creates a new adapter for the `inner` sink.
+    pub fn new(inner: W) -> Self {
+        Self { inner, error: None }
+    }
+
+    /// Returns the I/O error which caused the last write to fail, if any.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Takes the I/O error which caused the last write to fail, if any.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Consumes the adapter and returns the underlying sink.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error.replace(err);
+            fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::fmt::Write};
+
+    #[test]
+    fn io_writer() {
+        let mut w = IoWriter::new(Vec::new());
+
+        write!(w, "foo {}", 7).unwrap();
+        assert!(w.error().is_none());
+        assert_eq!(w.into_inner(), b"foo 7");
+
+        // Writes to a full buffer fail.
+        let mut buffer = [0u8; 4];
+        let mut w = IoWriter::new(&mut buffer[..]);
+
+        assert!(write!(w, "foo {}", 7).is_err());
+        assert_eq!(w.take_error().unwrap().kind(), io::ErrorKind::WriteZero);
+        assert!(w.error().is_none());
+    }
+}
@@ -0,0 +1,29 @@
+use std::cmp::Ordering;
+
+/// Compares the table keys `l` and `r` in the canonical key order
+/// used by all (text and binary) config serializers -
+/// ascending by Unicode code point, which is the same as the byte order of the UTF-8 encoded keys.
+pub(crate) fn cmp_keys(l: &str, r: &str) -> Ordering {
+    l.cmp(r)
+}
+
+/// Sorts the table `entries` (keys or key / value pairs) in the canonical key order (see [`cmp_keys`]),
+/// using `key` to get the key of each entry.
+///
+/// [`cmp_keys`]: fn.cmp_keys.html
+pub(crate) fn sort_by_table_key<T, F>(entries: &mut [T], key: F)
+where
+    F: Fn(&T) -> &str,
+{
+    entries.sort_by(|l, r| cmp_keys(key(l), key(r)));
+}
+
+/// Compares the table keys `l` and `r` in the `.ini` key order -
+/// keys of non-table values first (as tables are serialized as sections, which follow the keys),
+/// then in the canonical key order (see [`cmp_keys`]).
+///
+/// [`cmp_keys`]: fn.cmp_keys.html
+#[cfg(feature = "ini")]
+pub(crate) fn cmp_ini_keys(l: &str, l_is_a_table: bool, r: &str, r_is_a_table: bool) -> Ordering {
+    l_is_a_table.cmp(&r_is_a_table).then_with(|| cmp_keys(l, r))
+}
@@ -7,15 +7,30 @@ mod display_lua;
 #[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
 mod config_path;
 
+#[cfg(feature = "dyn")]
+mod io_writer;
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+mod key_order;
+
 #[cfg(any(feature = "bin", feature = "dyn", feature = "ini", feature = "lua"))]
 pub(crate) use display::*;
 
 #[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
 pub(crate) use display_lua::*;
 
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+pub use display_lua::{LuaDisplayOptions, LuaIndent, ToLuaStringError};
+
+#[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
+pub(crate) use key_order::*;
+
 #[cfg(any(feature = "bin", feature = "dyn", feature = "lua", feature = "ini"))]
 pub use config_path::*;
 
+#[cfg(feature = "dyn")]
+pub use io_writer::IoWriter;
+
 #[cfg(all(test, any(feature = "bin", feature = "dyn", feature = "lua")))]
 pub(crate) fn cmp_f64(l: f64, r: f64) -> bool {
     (l - r).abs() < 0.000_001
@@ -81,3 +96,50 @@ pub(crate) fn unwrap_unchecked<U: UnwrapUnchecked<T>, T>(
 ) -> T {
     option_or_result.unwrap_unchecked(msg)
 }
+
+/// Parses the (unquoted) `value` as a decimal, hexadecimal (`0x`) or octal (`0o`) integer
+/// with an optional explicit sign (`'+'` / `'-'`), as in `.ini` values.
+/// Returns `None` if the `value` is not a valid integer or does not fit in an `i64`.
+#[cfg(any(feature = "dyn", feature = "ini"))]
+pub(crate) fn try_parse_integer(value: &str) -> Option<i64> {
+    use std::convert::TryFrom;
+
+    if value.is_empty() {
+        None
+    } else {
+        // Explicit sign.
+        let (sign, value) = {
+            if let Some(value) = value.strip_prefix("+") {
+                (1i128, value)
+            } else if let Some(value) = value.strip_prefix("-") {
+                (-1i128, value)
+            } else {
+                (1i128, value)
+            }
+        };
+
+        // Radix.
+        let (radix, value) = {
+            // Hexadecimal.
+            if let Some(value) = value.strip_prefix("0x") {
+                (16, value)
+            // Octal.
+            } else if let Some(value) = value.strip_prefix("0o") {
+                (8, value)
+            // Else assume decimal.
+            } else {
+                (10, value)
+            }
+        };
+
+        // `from_str_radix` accepts an explicit sign - make sure we don't accept two.
+        if value.starts_with('+') || value.starts_with('-') {
+            return None;
+        }
+
+        // Parse the absolute value as an unsigned integer - `i64::MIN`'s absolute value does not fit in an `i64`.
+        let int = u64::from_str_radix(value, radix).ok()?;
+
+        i64::try_from(sign * i128::from(int)).ok()
+    }
+}
@@ -1,11 +1,17 @@
 use std::{
-    convert::From,
+    convert::{From, TryFrom},
+    error::Error,
     fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    str::FromStr,
 };
 
 #[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
 use {crate::util::*, std::fmt::Write};
 
+#[cfg(any(feature = "dyn", feature = "lua"))]
+use crate::TableError;
+
 /// Represents a config value.
 ///
 /// Different config implementations may represent strings, arrays and tables differently.
@@ -13,6 +19,7 @@ use {crate::util::*, std::fmt::Write};
 pub enum Value<S, A, T> {
     Bool(bool),
     I64(i64),
+    U64(u64),
     F64(f64),
     String(S),
     Array(A),
@@ -44,28 +51,53 @@ where
     A: DisplayLua,
     T: DisplayLua,
 {
-    fn fmt_lua<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+    fn fmt_lua<W: Write>(
+        &self,
+        w: &mut W,
+        indent: u32,
+        options: LuaDisplayOptions,
+    ) -> Result<(), ToLuaStringError> {
         match self {
-            Value::Bool(value) => write!(w, "{}", if *value { "true" } else { "false" }),
-            Value::I64(value) => write!(w, "{}", value),
-            Value::F64(value) => write!(w, "{}", value),
-            Value::String(value) => write_lua_string(w, value.as_ref()),
-            Value::Array(value) => value.fmt_lua(w, indent),
-            Value::Table(value) => value.fmt_lua(w, indent),
+            Value::Bool(value) => write!(w, "{}", if *value { "true" } else { "false" })?,
+            Value::I64(value) => write_lua_i64(w, *value)?,
+            Value::U64(value) => write!(w, "{}", value)?,
+            Value::F64(value) => write_lua_f64(w, *value)?,
+            Value::String(value) => write_lua_string(w, value.as_ref())?,
+            Value::Array(_) | Value::Table(_) if indent > options.max_depth => {
+                return Err(ToLuaStringError::DepthExceeded(ConfigPath::new()))
+            }
+            Value::Array(value) => value.fmt_lua(w, indent, options)?,
+            Value::Table(value) => value.fmt_lua(w, indent, options)?,
         }
+
+        Ok(())
     }
 }
 
 /// Represents the type of the [`config value`].
 ///
+/// Returned by [`get_type`] and contained in `IncorrectValueType` errors.
+///
+/// `Display` writes a human-readable type name (`"boolean"`, `"integer"`, `"unsigned integer"`, `"float"`,
+/// `"string"`, `"array"`, `"table"`), which is parsed back by `FromStr`.
+///
 /// [`config value`]: enum.Value.html
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// [`get_type`]: enum.Value.html#method.get_type
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum ValueType {
+    /// [`Boolean`](enum.Value.html#variant.Bool) value.
     Bool,
+    /// Signed 64-bit [`integer`](enum.Value.html#variant.I64) value.
     I64,
+    /// Unsigned 64-bit [`integer`](enum.Value.html#variant.U64) value.
+    U64,
+    /// 64-bit [`floating point`](enum.Value.html#variant.F64) value.
     F64,
+    /// [`String`](enum.Value.html#variant.String) value.
     String,
+    /// [`Array`](enum.Value.html#variant.Array) value.
     Array,
+    /// [`Table`](enum.Value.html#variant.Table) value.
     Table,
 }
 
@@ -77,6 +109,7 @@ impl<S, A, T> Value<S, A, T> {
         match self {
             Value::Bool(_) => Bool,
             Value::I64(_) => I64,
+            Value::U64(_) => U64,
             Value::F64(_) => F64,
             Value::String(_) => String,
             Value::Array(_) => Array,
@@ -96,26 +129,47 @@ impl<S, A, T> Value<S, A, T> {
     }
 
     /// Extracts the [`i64`] value from the config value.
-    /// Returns `None` if the value is not an [`i64`] / [`f64`].
+    /// Returns `None` if the value is not an [`i64`] / [`u64`] / [`f64`],
+    /// or if it is a [`u64`] greater than `i64::MAX`.
     ///
     /// [`i64`]: enum.Value.html#variant.I64
+    /// [`u64`]: enum.Value.html#variant.U64
     /// [`f64`]: enum.Value.html#variant.F64
     pub fn i64(&self) -> Option<i64> {
         match self {
             Value::I64(val) => Some(*val),
+            Value::U64(val) => <i64 as TryFrom<u64>>::try_from(*val).ok(),
             Value::F64(val) => Some(*val as i64),
             _ => None,
         }
     }
 
+    /// Extracts the [`u64`] value from the config value.
+    /// Returns `None` if the value is not a [`u64`] / [`i64`] / [`f64`],
+    /// or if it is a negative [`i64`] / [`f64`].
+    ///
+    /// [`u64`]: enum.Value.html#variant.U64
+    /// [`i64`]: enum.Value.html#variant.I64
+    /// [`f64`]: enum.Value.html#variant.F64
+    pub fn u64(&self) -> Option<u64> {
+        match self {
+            Value::I64(val) => <u64 as TryFrom<i64>>::try_from(*val).ok(),
+            Value::U64(val) => Some(*val),
+            Value::F64(val) if *val >= 0.0 => Some(*val as u64),
+            _ => None,
+        }
+    }
+
     /// Extracts the [`f64`] value from the config value.
-    /// Returns `None` if the value is not an [`f64`] / [`i64`].
+    /// Returns `None` if the value is not an [`f64`] / [`i64`] / [`u64`].
     ///
     /// [`i64`]: enum.Value.html#variant.I64
+    /// [`u64`]: enum.Value.html#variant.U64
     /// [`f64`]: enum.Value.html#variant.F64
     pub fn f64(&self) -> Option<f64> {
         match self {
             Value::I64(val) => Some(*val as f64),
+            Value::U64(val) => Some(*val as f64),
             Value::F64(val) => Some(*val),
             _ => None,
         }
@@ -155,6 +209,56 @@ impl<S, A, T> Value<S, A, T> {
     }
 }
 
+#[cfg(any(feature = "dyn", feature = "lua"))]
+impl<S, A, T> Value<S, A, T> {
+    /// Extracts the [`i64`] value from the config value,
+    /// converting a [`u64`] / [`f64`] value only if it is exactly representable as an [`i64`].
+    pub(crate) fn i64_coerced(&self) -> Result<i64, TableError> {
+        match self {
+            Value::I64(val) => Ok(*val),
+            Value::U64(val) => <i64 as TryFrom<u64>>::try_from(*val)
+                .map_err(|_| TableError::LossyConversion(ValueType::U64)),
+            // `-(2^63)` is exactly representable as an `f64`, `2^63 - 1` is not.
+            Value::F64(val)
+                if val.fract() == 0.0
+                    && *val >= -9_223_372_036_854_775_808.0
+                    && *val < 9_223_372_036_854_775_808.0 =>
+            {
+                Ok(*val as i64)
+            }
+            Value::F64(_) => Err(TableError::LossyConversion(ValueType::F64)),
+            _ => Err(TableError::IncorrectValueType(self.get_type())),
+        }
+    }
+
+    /// Extracts the [`f64`] value from the config value,
+    /// converting an [`i64`] / [`u64`] value only if it is exactly representable as an [`f64`].
+    pub(crate) fn f64_coerced(&self) -> Result<f64, TableError> {
+        match self {
+            Value::I64(val) => {
+                let f64_val = *val as f64;
+
+                if f64_val as i128 == *val as i128 {
+                    Ok(f64_val)
+                } else {
+                    Err(TableError::LossyConversion(ValueType::I64))
+                }
+            }
+            Value::U64(val) => {
+                let f64_val = *val as f64;
+
+                if f64_val as u128 == *val as u128 {
+                    Ok(f64_val)
+                } else {
+                    Err(TableError::LossyConversion(ValueType::U64))
+                }
+            }
+            Value::F64(val) => Ok(*val),
+            _ => Err(TableError::IncorrectValueType(self.get_type())),
+        }
+    }
+}
+
 impl<S: AsRef<str>, A, T> Value<S, A, T> {
     pub fn as_str(&self) -> Option<&str> {
         match self {
@@ -164,6 +268,53 @@ impl<S: AsRef<str>, A, T> Value<S, A, T> {
     }
 }
 
+/// Config values are equal if they are of the same [`type`] and their contents are equal.
+///
+/// `I64`, `U64` and `F64` values are never equal to each other, even if they represent the same number.
+///
+/// `F64` values are compared bit-exactly (by their `f64::to_bits()` bit pattern), unlike `f64` equality:
+/// a `NaN` value is equal to itself (but not to `NaN`'s with a different bit pattern),
+/// and `0.0` is not equal to `-0.0`.
+///
+/// [`type`]: enum.ValueType.html
+impl<S: PartialEq, A: PartialEq, T: PartialEq> PartialEq for Value<S, A, T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Bool(l), Value::Bool(r)) => l == r,
+            (Value::I64(l), Value::I64(r)) => l == r,
+            (Value::U64(l), Value::U64(r)) => l == r,
+            (Value::F64(l), Value::F64(r)) => l.to_bits() == r.to_bits(),
+            (Value::String(l), Value::String(r)) => l == r,
+            (Value::Array(l), Value::Array(r)) => l == r,
+            (Value::Table(l), Value::Table(r)) => l == r,
+            _ => false,
+        }
+    }
+}
+
+/// `F64` values are compared bit-exactly, so equality is reflexive (including for `NaN`'s).
+impl<S: Eq, A: Eq, T: Eq> Eq for Value<S, A, T> {}
+
+/// Config values are hashed consistently with their equality.
+///
+/// `F64` values are hashed by their bit pattern, as they are compared bit-exactly
+/// (so `0.0` and `-0.0` are different keys, and a `NaN` is the same key as itself).
+impl<S: Hash, A: Hash, T: Hash> Hash for Value<S, A, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+
+        match self {
+            Value::Bool(val) => val.hash(state),
+            Value::I64(val) => val.hash(state),
+            Value::U64(val) => val.hash(state),
+            Value::F64(val) => val.to_bits().hash(state),
+            Value::String(val) => val.hash(state),
+            Value::Array(val) => val.hash(state),
+            Value::Table(val) => val.hash(state),
+        }
+    }
+}
+
 impl ValueType {
     #[cfg(any(feature = "bin", feature = "dyn", feature = "lua"))]
     pub(crate) fn is_compatible(self, other: ValueType) -> bool {
@@ -171,8 +322,7 @@ impl ValueType {
 
         match self {
             Bool => other == Bool,
-            I64 => (other == I64) || (other == F64),
-            F64 => (other == I64) || (other == F64),
+            I64 | U64 | F64 => (other == I64) || (other == U64) || (other == F64),
             String => other == String,
             Array => other == Array,
             Table => other == Table,
@@ -180,21 +330,63 @@ impl ValueType {
     }
 }
 
-impl Display for ValueType {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+impl ValueType {
+    /// Returns the human-readable name of the value type, as written by `Display`.
+    pub fn name(self) -> &'static str {
         use ValueType::*;
 
         match self {
-            Bool => "Bool".fmt(f),
-            I64 => "I64".fmt(f),
-            F64 => "F64".fmt(f),
-            String => "String".fmt(f),
-            Array => "Array".fmt(f),
-            Table => "Table".fmt(f),
+            Bool => "boolean",
+            I64 => "integer",
+            U64 => "unsigned integer",
+            F64 => "float",
+            String => "string",
+            Array => "array",
+            Table => "table",
         }
     }
 }
 
+impl Display for ValueType {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// An error returned when parsing a [`value type`] from a string fails.
+///
+/// [`value type`]: enum.ValueType.html
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParseValueTypeError;
+
+impl Error for ParseValueTypeError {}
+
+impl Display for ParseValueTypeError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        "unknown value type".fmt(f)
+    }
+}
+
+/// Parses the human-readable type names written by `Display`
+/// (`"boolean"`, `"integer"`, `"unsigned integer"`, `"float"`, `"string"`, `"array"`, `"table"`)
+/// and the variant names (`"Bool"`, `"I64"`, `"U64"`, `"F64"`, `"String"`, `"Array"`, `"Table"`), ignoring ASCII case.
+impl FromStr for ValueType {
+    type Err = ParseValueTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ValueType::*;
+
+        [Bool, I64, U64, F64, String, Array, Table]
+            .iter()
+            .copied()
+            .find(|value_type| {
+                value_type.name().eq_ignore_ascii_case(s)
+                    || format!("{:?}", value_type).eq_ignore_ascii_case(s)
+            })
+            .ok_or(ParseValueTypeError)
+    }
+}
+
 #[cfg(any(feature = "bin", feature = "lua"))]
 pub(crate) fn value_type_to_u32<V: Into<Option<ValueType>>>(val: V) -> u32 {
     use ValueType::*;
@@ -209,6 +401,7 @@ pub(crate) fn value_type_to_u32<V: Into<Option<ValueType>>>(val: V) -> u32 {
             String => 4,
             Array => 5,
             Table => 6,
+            U64 => 7,
         }
     } else {
         0
@@ -227,6 +420,7 @@ pub(crate) fn value_type_from_u32(val: u32) -> Option<ValueType> {
         4 => Some(String),
         5 => Some(Array),
         6 => Some(Table),
+        7 => Some(U64),
         _ => None,
     }
 }
@@ -249,6 +443,12 @@ impl<S, A, T> TryFromValue<S, A, T> for i64 {
     }
 }
 
+impl<S, A, T> TryFromValue<S, A, T> for u64 {
+    fn try_from(val: Value<S, A, T>) -> Result<Self, ValueType> {
+        val.u64().ok_or_else(|| val.get_type())
+    }
+}
+
 impl<S, A, T> TryFromValue<S, A, T> for f64 {
     fn try_from(val: Value<S, A, T>) -> Result<Self, ValueType> {
         val.f64().ok_or_else(|| val.get_type())
@@ -257,9 +457,29 @@ impl<S, A, T> TryFromValue<S, A, T> for f64 {
 
 #[cfg(test)]
 mod tests {
-    #[cfg(any(feature = "bin", feature = "lua"))]
     use super::*;
 
+    #[test]
+    fn value_type_display_and_from_str() {
+        use ValueType::*;
+
+        for value_type in [Bool, I64, U64, F64, String, Array, Table].iter() {
+            assert_eq!(
+                value_type.to_string().parse::<ValueType>().unwrap(),
+                *value_type
+            );
+            assert_eq!(
+                format!("{:?}", value_type).parse::<ValueType>().unwrap(),
+                *value_type
+            );
+        }
+
+        assert_eq!(I64.to_string(), "integer");
+        assert_eq!("Boolean".parse::<ValueType>().unwrap(), Bool);
+        assert_eq!("f64".parse::<ValueType>().unwrap(), F64);
+        assert_eq!("foo".parse::<ValueType>(), Err(ParseValueTypeError));
+    }
+
     #[cfg(any(feature = "bin", feature = "lua"))]
     #[test]
     fn value_type_to_u32_and_back() {
@@ -272,6 +492,10 @@ mod tests {
             value_type_from_u32(value_type_to_u32(ValueType::I64)),
             Some(ValueType::I64)
         );
+        assert_eq!(
+            value_type_from_u32(value_type_to_u32(ValueType::U64)),
+            Some(ValueType::U64)
+        );
         assert_eq!(
             value_type_from_u32(value_type_to_u32(ValueType::F64)),
             Some(ValueType::F64)
@@ -289,4 +513,67 @@ mod tests {
             Some(ValueType::Table)
         );
     }
+
+    #[cfg(feature = "dyn")]
+    #[test]
+    fn hash() {
+        use {
+            crate::*,
+            std::{
+                collections::{hash_map::DefaultHasher, HashSet},
+                hash::{Hash, Hasher},
+            },
+        };
+
+        fn hash_of<V: Hash>(value: &V) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        // Equal values hash equally.
+        assert_eq!(
+            hash_of(&DynConfigValue::F64(f64::NAN)),
+            hash_of(&DynConfigValue::F64(f64::NAN))
+        );
+        assert_eq!(
+            hash_of(&DynConfigValue::from("foo")),
+            hash_of(&DynConfigValue::from("foo"))
+        );
+
+        // Tables hash equally regardless of the order of the keys.
+        let mut left = DynTable::new();
+        let mut right = DynTable::new();
+
+        for idx in 0..16 {
+            let key = format!("key_{}", idx);
+            assert!(!left.set(NonEmptyStr::new(&key).unwrap(), idx as i64));
+        }
+
+        for idx in (0..16).rev() {
+            let key = format!("key_{}", idx);
+            assert!(!right.set(NonEmptyStr::new(&key).unwrap(), idx as i64));
+        }
+
+        assert!(left == right);
+        assert_eq!(hash_of(&left), hash_of(&right));
+
+        // Deduplicate values.
+        let mut set = HashSet::new();
+
+        assert!(set.insert(DynConfigValue::I64(7)));
+        assert!(set.insert(DynConfigValue::U64(7)));
+        assert!(!set.insert(DynConfigValue::I64(7)));
+        assert!(set.insert(DynConfigValue::Table(left)));
+        assert!(!set.insert(DynConfigValue::Table(right)));
+        assert!(set.insert(DynConfigValue::Table(DynTable::new())));
+        assert_eq!(set.len(), 4);
+
+        // `NaN`'s are deduplicated, signed zeros are not.
+        assert!(set.insert(DynConfigValue::F64(f64::NAN)));
+        assert!(!set.insert(DynConfigValue::F64(f64::NAN)));
+        assert!(set.insert(DynConfigValue::F64(0.0)));
+        assert!(set.insert(DynConfigValue::F64(-0.0)));
+        assert_eq!(set.len(), 7);
+    }
 }
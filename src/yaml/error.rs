@@ -0,0 +1,102 @@
+use {
+    crate::*,
+    std::{
+        error::Error,
+        fmt::{Display, Formatter},
+    },
+};
+
+/// An actual concrete error kind returned by the YAML config [`parser`].
+///
+/// [`parser`]: struct.DynConfig.html#method.from_yaml
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum YAMLErrorKind {
+    /// The YAML string is malformed.
+    InvalidYAML {
+        /// Line in the source string where the error occured.
+        line: u32,
+        /// Column in the source string where the error occured.
+        column: u32,
+        /// YAML scanner error message.
+        message: String,
+    },
+    /// The YAML string must contain exactly one document.
+    /// Contains the number of documents in the string.
+    InvalidDocumentCount(u32),
+    /// The root YAML node must be a mapping.
+    RootNotAMapping,
+    /// Mapping keys must be strings.
+    NonStringKey,
+    /// Empty mapping keys are invalid.
+    EmptyKey,
+    /// Invalid (malformed) number.
+    InvalidNumber,
+    /// YAML `null` values are not supported by the config.
+    NullValue,
+    /// YAML aliases are not supported by the config.
+    Alias,
+    /// Invalid (unresolvable) YAML value.
+    InvalidValue,
+    /// Mixed value types in a sequence - all sequence elements must have the same type.
+    MixedArray,
+    /// Nested mappings / sequences exceed the maximum supported depth.
+    /// See [`max_depth`](struct.YAMLOptions.html#structfield.max_depth).
+    DepthExceeded,
+}
+
+impl Error for YAMLErrorKind {}
+
+impl Display for YAMLErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        use YAMLErrorKind::*;
+
+        match self {
+            InvalidYAML {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "invalid YAML (line: {}, column: {}): {}",
+                line, column, message
+            ),
+            InvalidDocumentCount(count) => {
+                write!(f, "expected exactly one YAML document, found {}", count)
+            }
+            RootNotAMapping => "root YAML node must be a mapping".fmt(f),
+            NonStringKey => "mapping keys must be strings".fmt(f),
+            EmptyKey => "empty mapping keys are invalid".fmt(f),
+            InvalidNumber => "invalid number".fmt(f),
+            NullValue => "`null` values are not supported".fmt(f),
+            Alias => "aliases are not supported".fmt(f),
+            InvalidValue => "invalid value".fmt(f),
+            MixedArray => "mixed value types in a sequence".fmt(f),
+            DepthExceeded => {
+                "nested mappings / sequences exceed the maximum supported depth".fmt(f)
+            }
+        }
+    }
+}
+
+/// An error returned by the YAML config [`parser`].
+///
+/// [`parser`]: struct.DynConfig.html#method.from_yaml
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct YAMLError {
+    /// Path to the key / value in which the error happened, or an empty path for the root mapping.
+    pub path: ConfigPath,
+    /// Actual error.
+    pub error: YAMLErrorKind,
+}
+
+impl Error for YAMLError {}
+
+impl Display for YAMLError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "YAML parse error; path: {}, error: {}",
+            self.path, self.error
+        )
+    }
+}
@@ -0,0 +1,8 @@
+mod error;
+mod options;
+mod parser;
+mod util;
+
+pub use {error::*, options::*};
+
+pub(crate) use {parser::*, util::*};
@@ -0,0 +1,18 @@
+/// Configuration options for parsing a YAML string to a [`config`](struct.DynConfig.html).
+#[derive(Clone, Copy, Debug)]
+pub struct YAMLOptions {
+    /// Maximum supported depth of nested mappings / sequences.
+    /// Values of the root mapping are at depth `1`.
+    /// Deeper nested mappings / sequences cause a [`DepthExceeded`](enum.YAMLErrorKind.html#variant.DepthExceeded) error.
+    ///
+    /// Protects against stack overflow when parsing YAML strings from untrusted sources.
+    ///
+    /// Default: `128`.
+    pub max_depth: u32,
+}
+
+impl Default for YAMLOptions {
+    fn default() -> Self {
+        Self { max_depth: 128 }
+    }
+}
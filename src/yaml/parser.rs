@@ -0,0 +1,143 @@
+use {
+    crate::*,
+    yaml_rust2::{Yaml, YamlLoader},
+};
+
+/// Internal parse result - error kind and the (reversed) path to the element where the error occured.
+type YAMLParseResult<T> = Result<T, (YAMLErrorKind, ConfigPath)>;
+
+/// Parses the YAML config `string` to a [`dynamic config`].
+///
+/// The actual YAML parsing is done by the `yaml-rust2` crate;
+/// this only converts the resulting YAML document to the [`dynamic config`].
+///
+/// [`dynamic config`]: struct.DynConfig.html
+pub(crate) fn parse_yaml(string: &str, options: YAMLOptions) -> Result<DynConfig, YAMLError> {
+    parse_yaml_impl(string, options).map_err(|(error, mut path)| {
+        path.0.reverse();
+
+        YAMLError { path, error }
+    })
+}
+
+fn parse_yaml_impl(string: &str, options: YAMLOptions) -> YAMLParseResult<DynConfig> {
+    use YAMLErrorKind::*;
+
+    let documents = YamlLoader::load_from_str(string).map_err(|err| {
+        let marker = err.marker();
+
+        error(InvalidYAML {
+            line: marker.line() as u32,
+            column: marker.col() as u32 + 1,
+            message: err.info().to_owned(),
+        })
+    })?;
+
+    if documents.len() != 1 {
+        return Err(error(InvalidDocumentCount(documents.len() as u32)));
+    }
+
+    let mut config = DynConfig::new();
+
+    match documents.into_iter().next() {
+        Some(Yaml::Hash(hash)) => {
+            *config.root_mut() = parse_mapping(hash, 1, options)?;
+        }
+        _ => return Err(error(RootNotAMapping)),
+    }
+
+    Ok(config)
+}
+
+/// `depth` is the nesting depth of the mapping's values (`1` for the root mapping).
+fn parse_mapping(
+    hash: yaml_rust2::yaml::Hash,
+    depth: u32,
+    options: YAMLOptions,
+) -> YAMLParseResult<DynTable> {
+    use YAMLErrorKind::*;
+
+    let mut table = DynTable::with_capacity(hash.len() as u32);
+
+    for (key, value) in hash {
+        let key = match key {
+            Yaml::String(key) => key,
+            _ => return Err(error(NonStringKey)),
+        };
+        let key = NonEmptyStr::new(&key).ok_or_else(|| error(EmptyKey))?;
+
+        let value = parse_value(value, depth, options).map_err(|err| push_key(err, key))?;
+
+        table.set(key, value);
+    }
+
+    Ok(table)
+}
+
+/// `depth` is the nesting depth of the sequence's values.
+fn parse_sequence(
+    sequence: Vec<Yaml>,
+    depth: u32,
+    options: YAMLOptions,
+) -> YAMLParseResult<DynArray> {
+    use YAMLErrorKind::*;
+
+    let mut array = DynArray::with_capacity(sequence.len() as u32);
+
+    for value in sequence {
+        let index = array.len();
+
+        let value = parse_value(value, depth, options).map_err(|err| push_key(err, index))?;
+
+        array
+            .push(value)
+            .map_err(|_| push_key(error(MixedArray), index))?;
+    }
+
+    Ok(array)
+}
+
+/// `depth` is the nesting depth of the `value`.
+fn parse_value(value: Yaml, depth: u32, options: YAMLOptions) -> YAMLParseResult<DynConfigValue> {
+    use YAMLErrorKind::*;
+
+    Ok(match value {
+        Yaml::Array(_) | Yaml::Hash(_) if depth > options.max_depth => {
+            return Err(error(DepthExceeded))
+        }
+        Yaml::Boolean(value) => Value::Bool(value),
+        Yaml::Integer(value) => Value::I64(value),
+        // Integers which don't fit into an `i64` are reported as reals.
+        Yaml::Real(value) => {
+            if let Ok(value) = value.parse::<u64>() {
+                Value::U64(value)
+            } else {
+                Value::F64(
+                    Yaml::Real(value)
+                        .as_f64()
+                        .ok_or_else(|| error(InvalidNumber))?,
+                )
+            }
+        }
+        Yaml::String(value) => Value::String(value),
+        Yaml::Array(value) => Value::Array(parse_sequence(value, depth + 1, options)?),
+        Yaml::Hash(value) => Value::Table(parse_mapping(value, depth + 1, options)?),
+        Yaml::Null => return Err(error(NullValue)),
+        Yaml::Alias(_) => return Err(error(Alias)),
+        Yaml::BadValue => return Err(error(InvalidValue)),
+    })
+}
+
+/// Error helper function.
+fn error(error: YAMLErrorKind) -> (YAMLErrorKind, ConfigPath) {
+    (error, ConfigPath::new())
+}
+
+/// Pushes the table key / array index to the back of the error path.
+fn push_key<K: Into<OwnedConfigKey>>(
+    mut error: (YAMLErrorKind, ConfigPath),
+    key: K,
+) -> (YAMLErrorKind, ConfigPath) {
+    error.1 .0.push(key.into());
+    error
+}
@@ -0,0 +1,174 @@
+use {crate::*, std::fmt::Write};
+
+/// A trait implemented by configs serializable to a YAML string.
+///
+/// Tables are serialized as block mappings, arrays - as block sequences.
+/// Empty tables / arrays are serialized as flow `{}` / `[]`.
+pub(crate) trait DisplayYAML {
+    /// Writes the value to the writer `w`.
+    ///
+    /// The writer is assumed to be positioned at the `indent` level on the first line,
+    /// so only the following lines are preceded by indentation.
+    /// Block mappings / sequences end with a new line, other values do not.
+    fn fmt_yaml<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result;
+
+    /// Returns `true` if the value is serialized as a (multi-line) block mapping / sequence.
+    fn is_block(&self) -> bool;
+
+    fn do_indent<W: Write>(w: &mut W, indent: u32) -> std::fmt::Result {
+        // YAML does not allow tabs for indentation.
+        for _ in 0..indent {
+            w.write_str("  ")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S, A, T> DisplayYAML for Value<S, A, T>
+where
+    S: AsRef<str>,
+    A: DisplayYAML,
+    T: DisplayYAML,
+{
+    fn fmt_yaml<W: Write>(&self, w: &mut W, indent: u32) -> std::fmt::Result {
+        match self {
+            Value::Bool(value) => write!(w, "{}", if *value { "true" } else { "false" }),
+            Value::I64(value) => write!(w, "{}", value),
+            Value::U64(value) => write!(w, "{}", value),
+            Value::F64(value) => write_yaml_f64(w, *value),
+            Value::String(value) => write_yaml_string(w, value.as_ref()),
+            Value::Array(value) => value.fmt_yaml(w, indent),
+            Value::Table(value) => value.fmt_yaml(w, indent),
+        }
+    }
+
+    fn is_block(&self) -> bool {
+        match self {
+            Value::Array(value) => value.is_block(),
+            Value::Table(value) => value.is_block(),
+            _ => false,
+        }
+    }
+}
+
+/// Writes the float `value` to the writer `w`.
+/// Finite values always contain a decimal point or an exponent,
+/// so that they are not confused with integers when parsed back.
+/// Non-finite values are written as YAML `.nan` / `.inf` / `-.inf`.
+fn write_yaml_f64<W: Write>(w: &mut W, value: f64) -> std::fmt::Result {
+    if value.is_nan() {
+        w.write_str(".nan")
+    } else if value.is_infinite() {
+        w.write_str(if value.is_sign_positive() {
+            ".inf"
+        } else {
+            "-.inf"
+        })
+    } else {
+        // `Debug` always writes the decimal point / exponent, unlike `Display`.
+        write!(w, "{:?}", value)
+    }
+}
+
+/// Returns `true` if the `string` may be written as a plain (unquoted) YAML scalar
+/// and will be parsed back as a string.
+///
+/// Conservatively only allows identifier-like strings (ASCII alphanumerics, `'_'`, `'-'` and `'.'`,
+/// starting with a letter or an underscore) which do not look like YAML booleans, `null`s or numbers.
+fn is_plain_yaml_string(string: &str) -> bool {
+    const RESERVED: &[&str] = &["true", "false", "yes", "no", "on", "off", "y", "n", "null"];
+
+    let mut chars = string.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+        && !RESERVED
+            .iter()
+            .any(|reserved| reserved.eq_ignore_ascii_case(string))
+        // E.g. `inf`, `NaN`.
+        && string.parse::<f64>().is_err()
+}
+
+/// Writes the `string` to the writer `w` as a plain scalar if possible (see `is_plain_yaml_string`),
+/// otherwise encloses it in double quotes and escapes double quotes ('"'), backslashes ('\\') and control characters.
+pub(crate) fn write_yaml_string<W: Write>(w: &mut W, string: &str) -> std::fmt::Result {
+    if is_plain_yaml_string(string) {
+        return w.write_str(string);
+    }
+
+    w.write_char('"')?;
+
+    for c in string.chars() {
+        match c {
+            '"' => w.write_str(r#"\""#)?,
+            '\\' => w.write_str(r#"\\"#)?,
+            '\0' => w.write_str(r#"\0"#)?,
+            '\x08' => w.write_str(r#"\b"#)?,
+            '\x0c' => w.write_str(r#"\f"#)?,
+            '\n' => w.write_str(r#"\n"#)?,
+            '\r' => w.write_str(r#"\r"#)?,
+            '\t' => w.write_str(r#"\t"#)?,
+            c if (c as u32) < 0x20 || c == '\x7f' => write!(w, "\\x{:02x}", c as u32)?,
+            c => w.write_char(c)?,
+        }
+    }
+
+    w.write_char('"')
+}
+
+/// Writes the table `key` / `value` pair to the writer `w`.
+/// The first key of the block mapping is not preceded by indentation.
+pub(crate) fn write_yaml_key_value<W: Write, V: DisplayYAML>(
+    w: &mut W,
+    key: &NonEmptyStr,
+    value: &V,
+    first: bool,
+    indent: u32,
+) -> std::fmt::Result {
+    if !first {
+        <V as DisplayYAML>::do_indent(w, indent)?;
+    }
+
+    write_yaml_string(w, key.as_str())?;
+    w.write_char(':')?;
+
+    // Nested block mappings / sequences start on the next line.
+    if value.is_block() {
+        writeln!(w)?;
+        <V as DisplayYAML>::do_indent(w, indent + 1)?;
+        value.fmt_yaml(w, indent + 1)
+    } else {
+        w.write_char(' ')?;
+        value.fmt_yaml(w, indent)?;
+        writeln!(w)
+    }
+}
+
+/// Writes the array `value` to the writer `w`.
+/// The first value of the block sequence is not preceded by indentation.
+pub(crate) fn write_yaml_array_value<W: Write, V: DisplayYAML>(
+    w: &mut W,
+    value: &V,
+    first: bool,
+    indent: u32,
+) -> std::fmt::Result {
+    if !first {
+        <V as DisplayYAML>::do_indent(w, indent)?;
+    }
+
+    // Nested block mappings / sequences start on the same line, after the `"- "`,
+    // which has the same width as one indentation level.
+    w.write_str("- ")?;
+
+    if value.is_block() {
+        value.fmt_yaml(w, indent + 1)
+    } else {
+        value.fmt_yaml(w, indent + 1)?;
+        writeln!(w)
+    }
+}